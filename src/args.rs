@@ -0,0 +1,186 @@
+//! Transaction argument builder.
+//!
+//! Builds the JSON object passed to a contract method by `tx:` links and
+//! actions, escaping string values so user-provided text can't break out of
+//! the argument object.
+//!
+//! Integers beyond +/-(2^53 - 1) are written as quoted decimal strings,
+//! since JavaScript viewers parse JSON numbers as doubles and would
+//! otherwise round them.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use soroban_render_sdk::args::TxArgs;
+//!
+//! let args = TxArgs::new(&env)
+//!     .add_u32("id", 5)
+//!     .add_str("title", "Say \"hi\"")
+//!     .add_bool("pin", true);
+//! // args.to_bytes() contains {"id":5,"title":"Say \"hi\"","pin":true}
+//! ```
+
+use crate::bytes::{escape_json_bytes, escape_json_string, json_number_bytes, u32_to_bytes};
+use soroban_sdk::{Bytes, Env, String};
+
+/// A builder for JSON transaction arguments.
+pub struct TxArgs<'a> {
+    env: &'a Env,
+    body: Bytes,
+    count: u32,
+}
+
+impl<'a> TxArgs<'a> {
+    /// Create an empty argument set.
+    pub fn new(env: &'a Env) -> Self {
+        Self {
+            env,
+            body: Bytes::new(env),
+            count: 0,
+        }
+    }
+
+    /// Start a new `"key":` entry, adding a comma separator if needed.
+    fn key(&mut self, key: &str) {
+        if self.count > 0 {
+            self.body.push_back(b',');
+        }
+        self.count += 1;
+        self.body.push_back(b'"');
+        self.body
+            .append(&escape_json_bytes(self.env, key.as_bytes()));
+        self.body.extend_from_slice(b"\":");
+    }
+
+    /// Add a u32 argument.
+    pub fn add_u32(mut self, key: &str, value: u32) -> Self {
+        self.key(key);
+        self.body.append(&u32_to_bytes(self.env, value));
+        self
+    }
+
+    /// Add a u64 argument, quoted if above 2^53 - 1.
+    pub fn add_u64(mut self, key: &str, value: u64) -> Self {
+        self.key(key);
+        self.body
+            .append(&json_number_bytes(self.env, value as i128));
+        self
+    }
+
+    /// Add an i64 argument, quoted if beyond +/-(2^53 - 1).
+    pub fn add_i64(mut self, key: &str, value: i64) -> Self {
+        self.key(key);
+        self.body
+            .append(&json_number_bytes(self.env, value as i128));
+        self
+    }
+
+    /// Add an i128 argument, quoted if beyond +/-(2^53 - 1).
+    pub fn add_i128(mut self, key: &str, value: i128) -> Self {
+        self.key(key);
+        self.body.append(&json_number_bytes(self.env, value));
+        self
+    }
+
+    /// Add a boolean argument.
+    pub fn add_bool(mut self, key: &str, value: bool) -> Self {
+        self.key(key);
+        let literal: &[u8] = if value { b"true" } else { b"false" };
+        self.body.extend_from_slice(literal);
+        self
+    }
+
+    /// Add a string argument, escaped for JSON.
+    pub fn add_str(mut self, key: &str, value: &str) -> Self {
+        self.key(key);
+        self.body.push_back(b'"');
+        self.body
+            .append(&escape_json_bytes(self.env, value.as_bytes()));
+        self.body.push_back(b'"');
+        self
+    }
+
+    /// Add a string argument from a soroban_sdk::String, escaped for JSON.
+    pub fn add_string(mut self, key: &str, value: &String) -> Self {
+        self.key(key);
+        self.body.push_back(b'"');
+        self.body.append(&escape_json_string(self.env, value));
+        self.body.push_back(b'"');
+        self
+    }
+
+    /// Check whether no arguments have been added.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Get the arguments as a JSON object.
+    pub fn to_bytes(&self) -> Bytes {
+        let mut result = Bytes::from_slice(self.env, b"{");
+        result.append(&self.body);
+        result.push_back(b'}');
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mixed_types() {
+        let env = Env::default();
+        let args = TxArgs::new(&env)
+            .add_u32("id", 5)
+            .add_str("title", "Hello")
+            .add_bool("pin", true)
+            .add_bool("lock", false)
+            .add_u64("big", 5_000_000_000)
+            .add_i64("delta", -3)
+            .add_string("body", &String::from_str(&env, "Text"));
+        assert_eq!(
+            args.to_bytes(),
+            Bytes::from_slice(
+                &env,
+                br#"{"id":5,"title":"Hello","pin":true,"lock":false,"big":5000000000,"delta":-3,"body":"Text"}"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_unsafe_integers_are_quoted() {
+        let env = Env::default();
+        let args = TxArgs::new(&env)
+            .add_u64("max", u64::MAX)
+            .add_i64("min", -(1 << 53) + 1)
+            .add_i128("big", 1 << 53);
+        assert_eq!(
+            args.to_bytes(),
+            Bytes::from_slice(
+                &env,
+                br#"{"max":"18446744073709551615","min":-9007199254740991,"big":"9007199254740992"}"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_empty_args() {
+        let env = Env::default();
+        let args = TxArgs::new(&env);
+        assert!(args.is_empty());
+        assert_eq!(args.to_bytes(), Bytes::from_slice(&env, b"{}"));
+    }
+
+    #[test]
+    fn test_quotes_in_string_values() {
+        let env = Env::default();
+        let args = TxArgs::new(&env)
+            .add_str("title", "Say \"hi\"")
+            .add_string("body", &String::from_str(&env, "a\\b\nc"));
+        assert!(!args.is_empty());
+        assert_eq!(
+            args.to_bytes(),
+            Bytes::from_slice(&env, br#"{"title":"Say \"hi\"","body":"a\\b\nc"}"#)
+        );
+    }
+}
@@ -2,15 +2,30 @@
 //!
 //! These functions provide common operations for working with `Bytes` in a `no_std` environment.
 
-use soroban_sdk::{Address, Bytes, Env, I256, String, Symbol, U256, Vec};
+use soroban_sdk::unwrap::UnwrapInfallible;
+use soroban_sdk::{Address, Bytes, Env, EnvBase, I256, String, Symbol, U256, Vec};
 
-/// Maximum supported string length for conversion.
-/// Strings longer than this cannot be fully converted due to Soroban SDK
-/// limitations (copy_into_slice requires a buffer >= string length).
+/// Maximum supported Bytes length for `bytes_to_string`.
+/// Longer input cannot be converted because `String` can only be created
+/// from a single guest buffer, which is stack-allocated here.
 pub const MAX_STRING_SIZE: usize = 16384;
 
+/// Chunk size used when copying long strings out of the host.
+const STRING_CHUNK_SIZE: u32 = 1024;
+
+/// Parts shorter than this are merged into slabs by `concat_bytes`.
+const SMALL_PART_SIZE: u32 = 64;
+
+/// Size of the slab buffer used by `concat_bytes`.
+const CONCAT_SLAB_SIZE: usize = 1024;
+
 /// Concatenate a vector of Bytes into a single Bytes object.
 ///
+/// Builders produce many tiny parts (`**`, `\n`, single words), and appending
+/// each one to a growing host object is costly. Runs of parts under 64 bytes
+/// are copied into a 1KB stack slab first and appended as one piece, so the
+/// result only grows once per slab or large part.
+///
 /// # Example
 ///
 /// ```rust,ignore
@@ -22,18 +37,38 @@ pub const MAX_STRING_SIZE: usize = 16384;
 /// ```
 pub fn concat_bytes(env: &Env, parts: &Vec<Bytes>) -> Bytes {
     let mut result = Bytes::new(env);
+    let mut slab = [0u8; CONCAT_SLAB_SIZE];
+    let mut slab_len = 0usize;
+
     for part in parts.iter() {
-        result.append(&part);
+        let len = part.len() as usize;
+        if part.len() >= SMALL_PART_SIZE {
+            if slab_len > 0 {
+                result.extend_from_slice(&slab[..slab_len]);
+                slab_len = 0;
+            }
+            result.append(&part);
+            continue;
+        }
+        if slab_len + len > CONCAT_SLAB_SIZE {
+            result.extend_from_slice(&slab[..slab_len]);
+            slab_len = 0;
+        }
+        part.copy_into_slice(&mut slab[slab_len..slab_len + len]);
+        slab_len += len;
+    }
+
+    if slab_len > 0 {
+        result.extend_from_slice(&slab[..slab_len]);
     }
     result
 }
 
 /// Convert a soroban_sdk::String to Bytes.
 ///
-/// Uses tiered buffer sizes for efficiency: 256B, 1KB, 4KB, or 16KB based on
-/// string length. Strings up to 16KB are fully converted. Strings exceeding
-/// 16KB return a placeholder message since Soroban's `copy_into_slice` requires
-/// a buffer at least as large as the string.
+/// Strings up to 4KB are copied through a single stack buffer sized to fit
+/// (256B, 1KB, or 4KB). Longer strings are copied in 1KB chunks, so there is
+/// no length limit and stack use stays bounded.
 ///
 /// # Example
 ///
@@ -42,10 +77,24 @@ pub fn concat_bytes(env: &Env, parts: &Vec<Bytes>) -> Bytes {
 /// let bytes = string_to_bytes(&env, &s);
 /// ```
 pub fn string_to_bytes(env: &Env, s: &String) -> Bytes {
+    let mut result: Option<Bytes> = None;
+    for_each_string_chunk(env, s, |chunk| match &mut result {
+        None => result = Some(Bytes::from_slice(env, chunk)),
+        Some(bytes) => bytes.extend_from_slice(chunk),
+    });
+    result.unwrap_or_else(|| Bytes::new(env))
+}
+
+/// Copy a String out of the host and pass its contents to `f`.
+///
+/// Strings up to 4KB arrive in one call through a stack buffer sized to fit
+/// (256B, 1KB, or 4KB); longer strings arrive in 1KB chunks. `f` is not
+/// called for an empty string.
+pub(crate) fn for_each_string_chunk(env: &Env, s: &String, mut f: impl FnMut(&[u8])) {
     let len = s.len() as usize;
 
     if len == 0 {
-        return Bytes::new(env);
+        return;
     }
 
     // Tiered buffers to balance stack usage vs. capability.
@@ -53,30 +102,80 @@ pub fn string_to_bytes(env: &Env, s: &String) -> Bytes {
     if len <= 256 {
         let mut buf = [0u8; 256];
         s.copy_into_slice(&mut buf[..len]);
-        return Bytes::from_slice(env, &buf[..len]);
+        return f(&buf[..len]);
     }
 
     if len <= 1024 {
         let mut buf = [0u8; 1024];
         s.copy_into_slice(&mut buf[..len]);
-        return Bytes::from_slice(env, &buf[..len]);
+        return f(&buf[..len]);
     }
 
     if len <= 4096 {
         let mut buf = [0u8; 4096];
         s.copy_into_slice(&mut buf[..len]);
-        return Bytes::from_slice(env, &buf[..len]);
+        return f(&buf[..len]);
+    }
+
+    // `String::copy_into_slice` only copies whole strings, so go through
+    // the host call directly with an offset for each chunk.
+    let len = len as u32;
+    let obj = s.to_object();
+    let mut buf = [0u8; STRING_CHUNK_SIZE as usize];
+    let mut pos = 0u32;
+    while pos < len {
+        let end = (pos + STRING_CHUNK_SIZE).min(len);
+        let n = (end - pos) as usize;
+        env.string_copy_to_slice(obj, pos.into(), &mut buf[..n])
+            .unwrap_infallible();
+        f(&buf[..n]);
+        pos = end;
+    }
+}
+
+/// Convert Bytes to a soroban_sdk::String.
+///
+/// The inverse of `string_to_bytes`, using tiered stack buffers up to
+/// `MAX_STRING_SIZE` (16KB). Longer input returns a placeholder message.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let bytes = Bytes::from_slice(&env, b"Hello");
+/// let s = bytes_to_string(&env, &bytes);
+/// ```
+pub fn bytes_to_string(env: &Env, b: &Bytes) -> String {
+    let len = b.len() as usize;
+
+    if len == 0 {
+        return String::from_str(env, "");
+    }
+
+    if len <= 256 {
+        let mut buf = [0u8; 256];
+        b.copy_into_slice(&mut buf[..len]);
+        return String::from_bytes(env, &buf[..len]);
+    }
+
+    if len <= 1024 {
+        let mut buf = [0u8; 1024];
+        b.copy_into_slice(&mut buf[..len]);
+        return String::from_bytes(env, &buf[..len]);
+    }
+
+    if len <= 4096 {
+        let mut buf = [0u8; 4096];
+        b.copy_into_slice(&mut buf[..len]);
+        return String::from_bytes(env, &buf[..len]);
     }
 
     if len <= MAX_STRING_SIZE {
         let mut buf = [0u8; MAX_STRING_SIZE];
-        s.copy_into_slice(&mut buf[..len]);
-        return Bytes::from_slice(env, &buf[..len]);
+        b.copy_into_slice(&mut buf[..len]);
+        return String::from_bytes(env, &buf[..len]);
     }
 
-    // String exceeds maximum supported size.
-    // We cannot truncate because copy_into_slice requires a buffer >= string length.
-    Bytes::from_slice(env, b"[content exceeds 16KB limit]")
+    String::from_str(env, "[content exceeds 16KB limit]")
 }
 
 /// Convert an Address to its contract ID string as Bytes.
@@ -180,6 +279,108 @@ fn decode_symbol_char(code: u8) -> u8 {
     }
 }
 
+// =============================================================================
+// Buffered Writing
+// =============================================================================
+
+/// Size of the `BytesWriter` staging buffer.
+const WRITER_BUF_SIZE: usize = 128;
+
+/// Accumulates bytes in a guest buffer and hands them to the host in slabs.
+///
+/// Each `Bytes::push_back` is a host call, so building output one byte at a
+/// time costs one call per byte. A `BytesWriter` stages up to 128 bytes and
+/// moves them with a single `extend_from_slice`; a 40-digit number or a
+/// 64-character hex string becomes one host call instead of 40 or 64.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let mut out = BytesWriter::new(&env);
+/// out.write(b"0x");
+/// out.write_byte(b'f');
+/// let bytes = out.into_bytes();
+/// // bytes contains "0xf"
+/// ```
+pub struct BytesWriter<'a> {
+    env: &'a Env,
+    /// Slabs already handed to the host, created on first spill.
+    spilled: Option<Bytes>,
+    buf: [u8; WRITER_BUF_SIZE],
+    len: usize,
+}
+
+impl<'a> BytesWriter<'a> {
+    /// Create an empty writer.
+    pub fn new(env: &'a Env) -> Self {
+        Self {
+            env,
+            spilled: None,
+            buf: [0; WRITER_BUF_SIZE],
+            len: 0,
+        }
+    }
+
+    /// Append a byte slice.
+    pub fn write(&mut self, data: &[u8]) {
+        if self.len + data.len() > WRITER_BUF_SIZE {
+            self.spill();
+            if data.len() >= WRITER_BUF_SIZE {
+                self.spilled_mut().extend_from_slice(data);
+                return;
+            }
+        }
+        self.buf[self.len..self.len + data.len()].copy_from_slice(data);
+        self.len += data.len();
+    }
+
+    /// Append a single byte.
+    pub fn write_byte(&mut self, b: u8) {
+        if self.len == WRITER_BUF_SIZE {
+            self.spill();
+        }
+        self.buf[self.len] = b;
+        self.len += 1;
+    }
+
+    /// Append everything written so far to `target` and reset the writer.
+    pub fn flush_into(&mut self, target: &mut Bytes) {
+        if let Some(spilled) = self.spilled.take() {
+            target.append(&spilled);
+        }
+        if self.len > 0 {
+            target.extend_from_slice(&self.buf[..self.len]);
+            self.len = 0;
+        }
+    }
+
+    /// Finish writing and return the output.
+    pub fn into_bytes(mut self) -> Bytes {
+        match self.spilled.take() {
+            None => Bytes::from_slice(self.env, &self.buf[..self.len]),
+            Some(mut spilled) => {
+                spilled.extend_from_slice(&self.buf[..self.len]);
+                spilled
+            }
+        }
+    }
+
+    /// Move the staged bytes to the host.
+    fn spill(&mut self) {
+        if self.len > 0 {
+            let len = self.len;
+            self.len = 0;
+            let buf = self.buf;
+            self.spilled_mut().extend_from_slice(&buf[..len]);
+        }
+    }
+
+    fn spilled_mut(&mut self) -> &mut Bytes {
+        let env = self.env;
+        self.spilled.get_or_insert_with(|| Bytes::new(env))
+    }
+}
+
 // =============================================================================
 // Numeric Conversion Macros
 // =============================================================================
@@ -203,11 +404,11 @@ macro_rules! impl_unsigned_to_bytes {
                 i += 1;
             }
 
-            let mut result = Bytes::new(env);
+            let mut result = BytesWriter::new(env);
             for j in (0..i).rev() {
-                result.push_back(digits[j]);
+                result.write_byte(digits[j]);
             }
-            result
+            result.into_bytes()
         }
     };
 }
@@ -236,14 +437,14 @@ macro_rules! impl_signed_to_bytes {
                 i += 1;
             }
 
-            let mut result = Bytes::new(env);
+            let mut result = BytesWriter::new(env);
             if negative {
-                result.push_back(b'-');
+                result.write_byte(b'-');
             }
             for j in (0..i).rev() {
-                result.push_back(digits[j]);
+                result.write_byte(digits[j]);
             }
-            result
+            result.into_bytes()
         }
     };
 }
@@ -325,11 +526,12 @@ macro_rules! impl_unsigned_to_hex {
                 i += 1;
             }
 
-            let mut result = Bytes::from_slice(env, b"0x");
+            let mut result = BytesWriter::new(env);
+            result.write(b"0x");
             for j in (0..i).rev() {
-                result.push_back(digits[j]);
+                result.write_byte(digits[j]);
             }
-            result
+            result.into_bytes()
         }
     };
 }
@@ -358,15 +560,13 @@ macro_rules! impl_signed_to_hex {
                 i += 1;
             }
 
-            let mut result = if negative {
-                Bytes::from_slice(env, b"-0x")
-            } else {
-                Bytes::from_slice(env, b"0x")
-            };
+            let mut result = BytesWriter::new(env);
+            let prefix: &[u8] = if negative { b"-0x" } else { b"0x" };
+            result.write(prefix);
             for j in (0..i).rev() {
-                result.push_back(digits[j]);
+                result.write_byte(digits[j]);
             }
-            result
+            result.into_bytes()
         }
     };
 }
@@ -528,14 +728,67 @@ pub fn i128_to_bytes(env: &Env, n: i128) -> Bytes {
         i += 1;
     }
 
-    let mut result = Bytes::new(env);
+    let mut result = BytesWriter::new(env);
     if negative {
-        result.push_back(b'-');
+        result.write_byte(b'-');
     }
     for j in (0..i).rev() {
-        result.push_back(digits[j]);
+        result.write_byte(digits[j]);
     }
-    result
+    result.into_bytes()
+}
+
+/// Largest integer a JSON viewer can represent exactly (2^53 - 1).
+const MAX_SAFE_INTEGER: i128 = (1 << 53) - 1;
+
+/// Serialize an integer as a JSON value.
+///
+/// Values within +/-(2^53 - 1) are bare numbers; anything beyond is a
+/// quoted decimal string, since JavaScript viewers would lose precision.
+pub(crate) fn json_number_bytes(env: &Env, n: i128) -> Bytes {
+    let digits = i128_to_bytes(env, n);
+    if (-MAX_SAFE_INTEGER..=MAX_SAFE_INTEGER).contains(&n) {
+        return digits;
+    }
+    let mut quoted = Bytes::from_slice(env, b"\"");
+    quoted.append(&digits);
+    quoted.push_back(b'"');
+    quoted
+}
+
+/// Convert an i128 to decimal Bytes with `,` between groups of three digits.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let bytes = i128_to_bytes_grouped(&env, -1204);
+/// // bytes contains "-1,204"
+/// ```
+pub fn i128_to_bytes_grouped(env: &Env, n: i128) -> Bytes {
+    let mut num = n.unsigned_abs();
+    let mut digits: [u8; 39] = [0; 39];
+    let mut i = 0;
+
+    loop {
+        digits[i] = b'0' + (num % 10) as u8;
+        num /= 10;
+        i += 1;
+        if num == 0 {
+            break;
+        }
+    }
+
+    let mut result = BytesWriter::new(env);
+    if n < 0 {
+        result.write_byte(b'-');
+    }
+    for j in (0..i).rev() {
+        result.write_byte(digits[j]);
+        if j > 0 && j % 3 == 0 {
+            result.write_byte(b',');
+        }
+    }
+    result.into_bytes()
 }
 
 // Generate bytes to unsigned parsing functions
@@ -696,15 +949,13 @@ pub fn i128_to_hex(env: &Env, n: i128) -> Bytes {
         i += 1;
     }
 
-    let mut result = if negative {
-        Bytes::from_slice(env, b"-0x")
-    } else {
-        Bytes::from_slice(env, b"0x")
-    };
+    let mut result = BytesWriter::new(env);
+    let prefix: &[u8] = if negative { b"-0x" } else { b"0x" };
+    result.write(prefix);
     for j in (0..i).rev() {
-        result.push_back(digits[j]);
+        result.write_byte(digits[j]);
     }
-    result
+    result.into_bytes()
 }
 
 // Generate hex to unsigned parsing functions
@@ -917,11 +1168,11 @@ pub fn u256_to_bytes(env: &Env, n: &U256) -> Bytes {
         digit_count += 1;
     }
 
-    let mut result = Bytes::new(env);
+    let mut result = BytesWriter::new(env);
     for i in (0..digit_count).rev() {
-        result.push_back(digits[i]);
+        result.write_byte(digits[i]);
     }
-    result
+    result.into_bytes()
 }
 
 /// Helper: check if a 256-bit Bytes is zero
@@ -1002,11 +1253,12 @@ pub fn i256_to_bytes(env: &Env, n: &I256) -> Bytes {
             digit_count += 1;
         }
 
-        let mut result = Bytes::from_slice(env, b"-");
+        let mut result = BytesWriter::new(env);
+        result.write(b"-");
         for i in (0..digit_count).rev() {
-            result.push_back(digits[i]);
+            result.write_byte(digits[i]);
         }
-        result
+        result.into_bytes()
     } else {
         let mut num = bytes_to_array_256(&be_bytes);
 
@@ -1023,11 +1275,11 @@ pub fn i256_to_bytes(env: &Env, n: &I256) -> Bytes {
             digit_count += 1;
         }
 
-        let mut result = Bytes::new(env);
+        let mut result = BytesWriter::new(env);
         for i in (0..digit_count).rev() {
-            result.push_back(digits[i]);
+            result.write_byte(digits[i]);
         }
-        result
+        result.into_bytes()
     }
 }
 
@@ -1057,7 +1309,8 @@ pub fn u256_to_hex(env: &Env, n: &U256) -> Bytes {
         return Bytes::from_slice(env, b"0x0");
     }
 
-    let mut result = Bytes::from_slice(env, b"0x");
+    let mut result = BytesWriter::new(env);
+    result.write(b"0x");
     let mut started = false;
 
     for i in 0..32u32 {
@@ -1067,18 +1320,18 @@ pub fn u256_to_hex(env: &Env, n: &U256) -> Bytes {
                 // First non-zero byte: skip leading zero nibble if present
                 let high = byte >> 4;
                 if high != 0 {
-                    result.push_back(HEX_CHARS[high as usize]);
+                    result.write_byte(HEX_CHARS[high as usize]);
                 }
-                result.push_back(HEX_CHARS[(byte & 0xF) as usize]);
+                result.write_byte(HEX_CHARS[(byte & 0xF) as usize]);
             } else {
-                result.push_back(HEX_CHARS[(byte >> 4) as usize]);
-                result.push_back(HEX_CHARS[(byte & 0xF) as usize]);
+                result.write_byte(HEX_CHARS[(byte >> 4) as usize]);
+                result.write_byte(HEX_CHARS[(byte & 0xF) as usize]);
             }
             started = true;
         }
     }
 
-    result
+    result.into_bytes()
 }
 
 /// Convert an I256 to its hexadecimal Bytes representation with "0x" prefix.
@@ -1108,7 +1361,8 @@ pub fn i256_to_hex(env: &Env, n: &I256) -> Bytes {
             return Bytes::from_slice(env, b"0x0");
         }
 
-        let mut result = Bytes::from_slice(env, b"-0x");
+        let mut result = BytesWriter::new(env);
+        result.write(b"-0x");
         let mut started = false;
 
         for &byte in abs_num.iter() {
@@ -1116,24 +1370,25 @@ pub fn i256_to_hex(env: &Env, n: &I256) -> Bytes {
                 if !started {
                     let high = byte >> 4;
                     if high != 0 {
-                        result.push_back(HEX_CHARS[high as usize]);
+                        result.write_byte(HEX_CHARS[high as usize]);
                     }
-                    result.push_back(HEX_CHARS[(byte & 0xF) as usize]);
+                    result.write_byte(HEX_CHARS[(byte & 0xF) as usize]);
                 } else {
-                    result.push_back(HEX_CHARS[(byte >> 4) as usize]);
-                    result.push_back(HEX_CHARS[(byte & 0xF) as usize]);
+                    result.write_byte(HEX_CHARS[(byte >> 4) as usize]);
+                    result.write_byte(HEX_CHARS[(byte & 0xF) as usize]);
                 }
                 started = true;
             }
         }
 
-        result
+        result.into_bytes()
     } else {
         if is_zero_256_bytes(&be_bytes) {
             return Bytes::from_slice(env, b"0x0");
         }
 
-        let mut result = Bytes::from_slice(env, b"0x");
+        let mut result = BytesWriter::new(env);
+        result.write(b"0x");
         let mut started = false;
 
         for i in 0..32u32 {
@@ -1142,18 +1397,18 @@ pub fn i256_to_hex(env: &Env, n: &I256) -> Bytes {
                 if !started {
                     let high = byte >> 4;
                     if high != 0 {
-                        result.push_back(HEX_CHARS[high as usize]);
+                        result.write_byte(HEX_CHARS[high as usize]);
                     }
-                    result.push_back(HEX_CHARS[(byte & 0xF) as usize]);
+                    result.write_byte(HEX_CHARS[(byte & 0xF) as usize]);
                 } else {
-                    result.push_back(HEX_CHARS[(byte >> 4) as usize]);
-                    result.push_back(HEX_CHARS[(byte & 0xF) as usize]);
+                    result.write_byte(HEX_CHARS[(byte >> 4) as usize]);
+                    result.write_byte(HEX_CHARS[(byte & 0xF) as usize]);
                 }
                 started = true;
             }
         }
 
-        result
+        result.into_bytes()
     }
 }
 
@@ -1581,6 +1836,7 @@ pub fn str_to_i256(env: &Env, s: &str) -> Option<I256> {
 /// - newline -> `\n`
 /// - carriage return -> `\r`
 /// - tab -> `\t`
+/// - any other control character (below 0x20) -> `\u00XX`
 ///
 /// # Example
 ///
@@ -1590,1303 +1846,2762 @@ pub fn str_to_i256(env: &Env, s: &str) -> Option<I256> {
 /// // escaped contains: Hello \"World\"
 /// ```
 pub fn escape_json_string(env: &Env, s: &String) -> Bytes {
-    let input = string_to_bytes(env, s);
-    escape_json_bytes_internal(env, &input)
+    // The string is copied out of the host once and escaped from the guest
+    // buffer, rather than converted to Bytes and then read back byte by byte.
+    let mut result: Option<Bytes> = None;
+    for_each_string_chunk(env, s, |chunk| match &mut result {
+        None => result = Some(escape_json_bytes(env, chunk)),
+        Some(bytes) => push_escaped_slice(bytes, chunk),
+    });
+    result.unwrap_or_else(|| Bytes::new(env))
 }
 
 /// Escape a byte slice for safe inclusion in JSON.
 ///
-/// Like `escape_json_string` but works directly with byte slices.
+/// Like `escape_json_string` but works directly with byte slices. Input that
+/// needs no escaping is returned with a single `Bytes::from_slice`.
 pub fn escape_json_bytes(env: &Env, input: &[u8]) -> Bytes {
-    let mut result = Bytes::new(env);
-
-    for &b in input {
-        push_escaped_byte(&mut result, b);
+    if !input.iter().any(|&b| needs_json_escape(b)) {
+        return Bytes::from_slice(env, input);
     }
-
+    let mut result = Bytes::new(env);
+    push_escaped_slice(&mut result, input);
     result
 }
 
-/// Internal helper for JSON escaping from Bytes
-fn escape_json_bytes_internal(env: &Env, input: &Bytes) -> Bytes {
-    let mut result = Bytes::new(env);
+/// Check whether a byte must be escaped inside a JSON string.
+fn needs_json_escape(b: u8) -> bool {
+    b == b'"' || b == b'\\' || b < 0x20
+}
 
-    for i in 0..input.len() {
-        if let Some(b) = input.get(i) {
-            push_escaped_byte(&mut result, b);
+/// Append `input` to `result` with JSON escaping.
+///
+/// Unescaped runs are appended as whole slices between escape points.
+fn push_escaped_slice(result: &mut Bytes, input: &[u8]) {
+    let mut start = 0;
+    for (i, &b) in input.iter().enumerate() {
+        if !needs_json_escape(b) {
+            continue;
+        }
+        if i > start {
+            result.extend_from_slice(&input[start..i]);
         }
+        let (seq, len) = json_escape_sequence(b);
+        result.extend_from_slice(&seq[..len]);
+        start = i + 1;
+    }
+    if start < input.len() {
+        result.extend_from_slice(&input[start..]);
     }
-
-    result
 }
 
-/// Push an escaped byte to the result
-fn push_escaped_byte(result: &mut Bytes, b: u8) {
+/// The escape sequence for a byte that `needs_json_escape`.
+///
+/// Returns the sequence in a fixed buffer along with its length.
+fn json_escape_sequence(b: u8) -> ([u8; 6], usize) {
     match b {
-        b'"' => {
-            result.push_back(b'\\');
-            result.push_back(b'"');
-        }
-        b'\\' => {
-            result.push_back(b'\\');
-            result.push_back(b'\\');
-        }
-        b'\n' => {
-            result.push_back(b'\\');
-            result.push_back(b'n');
-        }
-        b'\r' => {
-            result.push_back(b'\\');
-            result.push_back(b'r');
-        }
-        b'\t' => {
-            result.push_back(b'\\');
-            result.push_back(b't');
-        }
-        _ => {
-            result.push_back(b);
-        }
+        b'"' => ([b'\\', b'"', 0, 0, 0, 0], 2),
+        b'\\' => ([b'\\', b'\\', 0, 0, 0, 0], 2),
+        b'\n' => ([b'\\', b'n', 0, 0, 0, 0], 2),
+        b'\r' => ([b'\\', b'r', 0, 0, 0, 0], 2),
+        b'\t' => ([b'\\', b't', 0, 0, 0, 0], 2),
+        _ => (
+            [
+                b'\\',
+                b'u',
+                b'0',
+                b'0',
+                HEX_CHARS[(b >> 4) as usize],
+                HEX_CHARS[(b & 0xF) as usize],
+            ],
+            6,
+        ),
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use soroban_sdk::Env;
-
-    #[test]
-    fn test_concat_bytes() {
-        let env = Env::default();
-        let mut parts: Vec<Bytes> = Vec::new(&env);
-        parts.push_back(Bytes::from_slice(&env, b"Hello, "));
-        parts.push_back(Bytes::from_slice(&env, b"World!"));
+// =============================================================================
+// URL Encoding
+// =============================================================================
 
-        let result = concat_bytes(&env, &parts);
-        assert_eq!(result.len(), 13);
+/// Percent-encode Bytes for use as a URL path segment.
+///
+/// Unreserved characters (`A-Z a-z 0-9 - _ . ~`) pass through unchanged;
+/// every other byte becomes `%XX` with uppercase hex digits.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let segment = Bytes::from_slice(&env, b"hello world/1");
+/// let encoded = percent_encode(&env, &segment);
+/// // encoded contains "hello%20world%2F1"
+/// ```
+pub fn percent_encode(env: &Env, input: &Bytes) -> Bytes {
+    const UPPER_HEX: &[u8] = b"0123456789ABCDEF";
+    let mut result = BytesWriter::new(env);
+
+    for b in input.iter() {
+        if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.' | b'~') {
+            result.write_byte(b);
+        } else {
+            result.write(&[
+                b'%',
+                UPPER_HEX[(b >> 4) as usize],
+                UPPER_HEX[(b & 0xF) as usize],
+            ]);
+        }
     }
 
-    #[test]
-    fn test_concat_bytes_empty() {
-        let env = Env::default();
-        let parts: Vec<Bytes> = Vec::new(&env);
-        let result = concat_bytes(&env, &parts);
-        assert_eq!(result.len(), 0);
-    }
+    result.into_bytes()
+}
 
-    #[test]
-    fn test_string_to_bytes() {
-        let env = Env::default();
-        let s = String::from_str(&env, "Hello");
-        let bytes = string_to_bytes(&env, &s);
-        assert_eq!(bytes.len(), 5);
-    }
+// =============================================================================
+// Hashing and Comparison
+// =============================================================================
 
-    #[test]
-    fn test_string_to_bytes_empty() {
-        let env = Env::default();
-        let s = String::from_str(&env, "");
-        let bytes = string_to_bytes(&env, &s);
-        assert_eq!(bytes.len(), 0);
-    }
+/// Chunk size used when reading `Bytes` for hashing.
+const HASH_CHUNK_SIZE: u32 = 64;
 
-    #[test]
-    fn test_string_to_bytes_256_boundary() {
-        let env = Env::default();
-        // Exactly 256 bytes - should use first tier
-        let content = "a".repeat(256);
-        let s = String::from_str(&env, &content);
-        let bytes = string_to_bytes(&env, &s);
-        assert_eq!(bytes.len(), 256);
-    }
+/// Chunk size used when comparing two `Bytes` values.
+const EQ_CHUNK_SIZE: u32 = 32;
 
-    #[test]
-    fn test_string_to_bytes_257_uses_1kb_tier() {
-        let env = Env::default();
-        // 257 bytes - should use second tier (1KB buffer)
-        let content = "a".repeat(257);
-        let s = String::from_str(&env, &content);
-        let bytes = string_to_bytes(&env, &s);
-        assert_eq!(bytes.len(), 257);
-    }
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
 
-    #[test]
-    fn test_string_to_bytes_1kb_boundary() {
-        let env = Env::default();
-        let content = "a".repeat(1024);
-        let s = String::from_str(&env, &content);
-        let bytes = string_to_bytes(&env, &s);
-        assert_eq!(bytes.len(), 1024);
+/// Compute a 64-bit FNV-1a hash of a Bytes value.
+///
+/// The input is copied out of the host in 64-byte chunks rather than read
+/// one byte at a time, so hashing large content costs a handful of host calls
+/// instead of one per byte. The result is stable across calls and contracts,
+/// making it suitable for cache keys and content fingerprints. It is not a
+/// cryptographic hash; use `env.crypto()` where collision resistance matters.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let content = Bytes::from_slice(&env, b"# Hello");
+/// let key = bytes_hash_64(&env, &content);
+/// ```
+pub fn bytes_hash_64(_env: &Env, data: &Bytes) -> u64 {
+    let len = data.len();
+    let mut hash = FNV_OFFSET_BASIS;
+    let mut buf = [0u8; HASH_CHUNK_SIZE as usize];
+    let mut start = 0u32;
+
+    while start < len {
+        let end = (start + HASH_CHUNK_SIZE).min(len);
+        let n = (end - start) as usize;
+        data.slice(start..end).copy_into_slice(&mut buf[..n]);
+        for &b in &buf[..n] {
+            hash ^= b as u64;
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+        start = end;
     }
 
-    #[test]
-    fn test_string_to_bytes_4kb() {
-        let env = Env::default();
-        let content = "a".repeat(4000);
-        let s = String::from_str(&env, &content);
-        let bytes = string_to_bytes(&env, &s);
-        assert_eq!(bytes.len(), 4000);
-    }
+    hash
+}
 
-    #[test]
-    fn test_string_to_bytes_large() {
-        let env = Env::default();
-        // 10KB string - should use 16KB tier
-        let content = "a".repeat(10000);
-        let s = String::from_str(&env, &content);
-        let bytes = string_to_bytes(&env, &s);
-        assert_eq!(bytes.len(), 10000);
+/// Compare two Bytes values for equality.
+///
+/// Returns early when the lengths differ, then compares 32-byte chunks so
+/// that long values are checked with few host calls.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// if bytes_eq(&request.path, &cached_path) {
+///     // reuse cached output
+/// }
+/// ```
+pub fn bytes_eq(a: &Bytes, b: &Bytes) -> bool {
+    let len = a.len();
+    if len != b.len() {
+        return false;
+    }
+
+    let mut buf_a = [0u8; EQ_CHUNK_SIZE as usize];
+    let mut buf_b = [0u8; EQ_CHUNK_SIZE as usize];
+    let mut start = 0u32;
+
+    while start < len {
+        let end = (start + EQ_CHUNK_SIZE).min(len);
+        let n = (end - start) as usize;
+        a.slice(start..end).copy_into_slice(&mut buf_a[..n]);
+        b.slice(start..end).copy_into_slice(&mut buf_b[..n]);
+        if buf_a[..n] != buf_b[..n] {
+            return false;
+        }
+        start = end;
     }
 
-    #[test]
-    fn test_string_to_bytes_max_size() {
-        let env = Env::default();
-        // Exactly at the 16KB limit
-        let content = "a".repeat(MAX_STRING_SIZE);
-        let s = String::from_str(&env, &content);
-        let bytes = string_to_bytes(&env, &s);
-        assert_eq!(bytes.len(), MAX_STRING_SIZE as u32);
-    }
+    true
+}
 
-    #[test]
-    fn test_u32_to_bytes_zero() {
-        let env = Env::default();
-        let bytes = u32_to_bytes(&env, 0);
-        assert_eq!(bytes.len(), 1);
-        assert_eq!(bytes.get(0), Some(b'0'));
-    }
+// =============================================================================
+// Time Formatting
+// =============================================================================
 
-    #[test]
+const SECS_PER_MINUTE: u64 = 60;
+const SECS_PER_HOUR: u64 = 60 * SECS_PER_MINUTE;
+const SECS_PER_DAY: u64 = 24 * SECS_PER_HOUR;
+const SECS_PER_YEAR: u64 = 365 * SECS_PER_DAY;
+
+/// Format the time between two unix timestamps as a relative phrase.
+///
+/// Produces "just now" (under a minute, or when `then` is in the future),
+/// then "N minutes ago", "N hours ago", "N days ago", and "N years ago"
+/// (365-day years), each in the singular for 1. Values are truncated, so
+/// 119 seconds is "1 minute ago".
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let now = env.ledger().timestamp();
+/// let ago = format_relative_time(&env, now, post.created_at);
+/// // ago contains "5 minutes ago"
+/// ```
+pub fn format_relative_time(env: &Env, now: u64, then: u64) -> Bytes {
+    let delta = now.saturating_sub(then);
+    if delta < SECS_PER_MINUTE {
+        return Bytes::from_slice(env, b"just now");
+    }
+
+    let (n, unit): (u64, &[u8]) = if delta < SECS_PER_HOUR {
+        (delta / SECS_PER_MINUTE, b" minute")
+    } else if delta < SECS_PER_DAY {
+        (delta / SECS_PER_HOUR, b" hour")
+    } else if delta < SECS_PER_YEAR {
+        (delta / SECS_PER_DAY, b" day")
+    } else {
+        (delta / SECS_PER_YEAR, b" year")
+    };
+
+    let mut result = u64_to_bytes(env, n);
+    result.extend_from_slice(unit);
+    if n != 1 {
+        result.push_back(b's');
+    }
+    result.extend_from_slice(b" ago");
+    result
+}
+
+/// Convert days since the unix epoch to a (year, month, day) civil date.
+///
+/// Howard Hinnant's `civil_from_days`, restricted to non-negative input.
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + (month <= 2) as u64;
+    (year, month, day)
+}
+
+/// Write `n` in decimal, zero-padded to at least `width` digits.
+fn write_padded(buf: &mut [u8], pos: &mut usize, n: u64, width: usize) {
+    let mut digits = [0u8; 20];
+    let mut len = 0;
+    let mut n = n;
+    loop {
+        digits[len] = b'0' + (n % 10) as u8;
+        len += 1;
+        n /= 10;
+        if n == 0 {
+            break;
+        }
+    }
+    while len < width {
+        digits[len] = b'0';
+        len += 1;
+    }
+    for i in (0..len).rev() {
+        buf[*pos] = digits[i];
+        *pos += 1;
+    }
+}
+
+/// Write `YYYY-MM-DD` for the given unix timestamp.
+fn write_date(buf: &mut [u8], pos: &mut usize, unix_secs: u64) {
+    let (year, month, day) = civil_from_days(unix_secs / SECS_PER_DAY);
+    write_padded(buf, pos, year, 4);
+    buf[*pos] = b'-';
+    *pos += 1;
+    write_padded(buf, pos, month, 2);
+    buf[*pos] = b'-';
+    *pos += 1;
+    write_padded(buf, pos, day, 2);
+}
+
+/// Format a unix timestamp as an ISO-8601 style UTC date and time.
+///
+/// Produces `YYYY-MM-DD HH:MM:SS UTC` using integer arithmetic only.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let when = format_timestamp(&env, 1_704_153_600);
+/// // when contains "2024-01-02 00:00:00 UTC"
+/// ```
+pub fn format_timestamp(env: &Env, unix_secs: u64) -> Bytes {
+    // u64::MAX seconds lands in a 12-digit year; 40 bytes leaves headroom.
+    let mut buf = [0u8; 40];
+    let mut pos = 0;
+    write_date(&mut buf, &mut pos, unix_secs);
+
+    let secs_of_day = unix_secs % SECS_PER_DAY;
+    buf[pos] = b' ';
+    pos += 1;
+    write_padded(&mut buf, &mut pos, secs_of_day / SECS_PER_HOUR, 2);
+    buf[pos] = b':';
+    pos += 1;
+    write_padded(
+        &mut buf,
+        &mut pos,
+        secs_of_day % SECS_PER_HOUR / SECS_PER_MINUTE,
+        2,
+    );
+    buf[pos] = b':';
+    pos += 1;
+    write_padded(&mut buf, &mut pos, secs_of_day % SECS_PER_MINUTE, 2);
+    buf[pos..pos + 4].copy_from_slice(b" UTC");
+    pos += 4;
+
+    Bytes::from_slice(env, &buf[..pos])
+}
+
+/// Format the date part of a unix timestamp as `YYYY-MM-DD` (UTC).
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let day = format_date(&env, 1_704_153_600);
+/// // day contains "2024-01-02"
+/// ```
+pub fn format_date(env: &Env, unix_secs: u64) -> Bytes {
+    let mut buf = [0u8; 26];
+    let mut pos = 0;
+    write_date(&mut buf, &mut pos, unix_secs);
+    Bytes::from_slice(env, &buf[..pos])
+}
+
+// =============================================================================
+// Truncation
+// =============================================================================
+
+/// The `…` character appended to truncated text.
+const ELLIPSIS: &[u8] = "\u{2026}".as_bytes();
+
+/// Shorten Bytes to at most `max_len` bytes, appending `…` if anything was cut.
+///
+/// The cut backs up to the previous UTF-8 character boundary so a multi-byte
+/// character is never split. The ellipsis is added on top of `max_len`, so a
+/// truncated result is up to `max_len + 3` bytes long. Input that already
+/// fits is returned unchanged.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let body = Bytes::from_slice(&env, b"A long post body");
+/// let short = truncate_bytes(&env, &body, 6);
+/// // short contains "A long…"
+/// ```
+pub fn truncate_bytes(env: &Env, input: &Bytes, max_len: u32) -> Bytes {
+    if input.len() <= max_len {
+        return input.clone();
+    }
+
+    // Back up while the first excluded byte is a UTF-8 continuation byte.
+    let mut cut = max_len;
+    while cut > 0 && input.get_unchecked(cut) & 0xC0 == 0x80 {
+        cut -= 1;
+    }
+
+    let mut result = Bytes::new(env);
+    result.append(&input.slice(0..cut));
+    result.extend_from_slice(ELLIPSIS);
+    result
+}
+
+/// Shorten a soroban_sdk::String to at most `max_len` bytes, appending `…`.
+///
+/// See `truncate_bytes`.
+pub fn truncate_string(env: &Env, s: &String, max_len: u32) -> Bytes {
+    truncate_bytes(env, &string_to_bytes(env, s), max_len)
+}
+
+// =============================================================================
+// Search and Replace
+// =============================================================================
+
+/// Window size used when scanning `Bytes` for a needle.
+const FIND_CHUNK_SIZE: u32 = 256;
+
+/// Find the first occurrence of `needle` at or after `from`.
+fn bytes_find_from(haystack: &Bytes, needle: &[u8], from: u32) -> Option<u32> {
+    let len = haystack.len();
+    let n = needle.len() as u32;
+    if n == 0 {
+        return (from <= len).then_some(from);
+    }
+
+    if n > FIND_CHUNK_SIZE {
+        // Too long to fit a window; compare candidate slices directly.
+        let mut buf = [0u8; FIND_CHUNK_SIZE as usize];
+        let mut start = from;
+        while start + n <= len {
+            let candidate = haystack.slice(start..start + n);
+            let mut matched = true;
+            let mut offset = 0u32;
+            while offset < n {
+                let end = (offset + FIND_CHUNK_SIZE).min(n);
+                let m = (end - offset) as usize;
+                candidate.slice(offset..end).copy_into_slice(&mut buf[..m]);
+                if buf[..m] != needle[offset as usize..end as usize] {
+                    matched = false;
+                    break;
+                }
+                offset = end;
+            }
+            if matched {
+                return Some(start);
+            }
+            start += 1;
+        }
+        return None;
+    }
+
+    // Scan overlapping windows so a match spanning two windows is still seen.
+    let mut buf = [0u8; FIND_CHUNK_SIZE as usize];
+    let mut start = from;
+    while start + n <= len {
+        let end = (start + FIND_CHUNK_SIZE).min(len);
+        let m = (end - start) as usize;
+        haystack.slice(start..end).copy_into_slice(&mut buf[..m]);
+        if let Some(i) = buf[..m].windows(n as usize).position(|w| w == needle) {
+            return Some(start + i as u32);
+        }
+        if end == len {
+            break;
+        }
+        start = end - (n - 1);
+    }
+    None
+}
+
+/// Find the byte offset of the first occurrence of `needle` in `haystack`.
+///
+/// An empty needle matches at offset 0. The haystack is read in 256-byte
+/// windows, so long content is searched with few host calls.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let text = Bytes::from_slice(&env, b"hello @alice");
+/// assert_eq!(bytes_find(&text, b"@"), Some(6));
+/// ```
+pub fn bytes_find(haystack: &Bytes, needle: &[u8]) -> Option<u32> {
+    bytes_find_from(haystack, needle, 0)
+}
+
+/// Check whether `needle` occurs anywhere in `haystack`.
+pub fn bytes_contains(haystack: &Bytes, needle: &[u8]) -> bool {
+    bytes_find(haystack, needle).is_some()
+}
+
+/// Replace every non-overlapping occurrence of `needle` with `replacement`.
+///
+/// Matches are found left to right and scanning resumes after each match,
+/// so a replacement that contains the needle is never rescanned. An empty
+/// needle returns the input unchanged.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let template = Bytes::from_slice(&env, b"Hello, {name}!");
+/// let out = bytes_replace_all(&env, &template, b"{name}", b"Alice");
+/// // out contains "Hello, Alice!"
+/// ```
+pub fn bytes_replace_all(env: &Env, input: &Bytes, needle: &[u8], replacement: &[u8]) -> Bytes {
+    if needle.is_empty() {
+        return input.clone();
+    }
+
+    let n = needle.len() as u32;
+    let mut result = Bytes::new(env);
+    let mut pos = 0;
+    while let Some(idx) = bytes_find_from(input, needle, pos) {
+        if idx > pos {
+            result.append(&input.slice(pos..idx));
+        }
+        result.extend_from_slice(replacement);
+        pos = idx + n;
+    }
+    if pos < input.len() {
+        result.append(&input.slice(pos..input.len()));
+    }
+    result
+}
+
+// =============================================================================
+// Split and Trim
+// =============================================================================
+
+/// Split Bytes on a delimiter byte.
+///
+/// With `skip_empty` set, empty segments (from leading, trailing, or
+/// consecutive delimiters) are dropped; otherwise they are kept, so `n`
+/// delimiters always yield `n + 1` segments. Segments are slices of the
+/// input and are not trimmed.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let tags = Bytes::from_slice(&env, b"rust,,soroban,");
+/// let parts = bytes_split(&env, &tags, b',', true);
+/// // parts contains "rust", "soroban"
+/// ```
+pub fn bytes_split(env: &Env, input: &Bytes, delimiter: u8, skip_empty: bool) -> Vec<Bytes> {
+    let mut result = Vec::new(env);
+    let len = input.len();
+    let mut buf = [0u8; FIND_CHUNK_SIZE as usize];
+    let mut seg_start = 0u32;
+    let mut chunk_start = 0u32;
+
+    while chunk_start < len {
+        let chunk_end = (chunk_start + FIND_CHUNK_SIZE).min(len);
+        let n = (chunk_end - chunk_start) as usize;
+        input
+            .slice(chunk_start..chunk_end)
+            .copy_into_slice(&mut buf[..n]);
+        for (i, b) in buf[..n].iter().enumerate() {
+            if *b == delimiter {
+                let pos = chunk_start + i as u32;
+                if !(skip_empty && pos == seg_start) {
+                    result.push_back(input.slice(seg_start..pos));
+                }
+                seg_start = pos + 1;
+            }
+        }
+        chunk_start = chunk_end;
+    }
+
+    if !(skip_empty && seg_start == len) {
+        result.push_back(input.slice(seg_start..len));
+    }
+    result
+}
+
+/// Strip ASCII whitespace from both ends of Bytes.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let input = Bytes::from_slice(&env, b"  hello \n");
+/// let trimmed = bytes_trim(&env, &input);
+/// // trimmed contains "hello"
+/// ```
+pub fn bytes_trim(env: &Env, input: &Bytes) -> Bytes {
+    let mut start = 0;
+    let mut end = input.len();
+    while start < end && input.get_unchecked(start).is_ascii_whitespace() {
+        start += 1;
+    }
+    while end > start && input.get_unchecked(end - 1).is_ascii_whitespace() {
+        end -= 1;
+    }
+    if start == end {
+        return Bytes::new(env);
+    }
+    input.slice(start..end)
+}
+
+// =============================================================================
+// Case Conversion and Slugs
+// =============================================================================
+
+/// Maximum length of a slug produced by `slugify`.
+pub const MAX_SLUG_LEN: usize = 64;
+
+/// Copy Bytes in chunks, applying `f` to every byte.
+fn map_bytes(env: &Env, input: &Bytes, f: fn(u8) -> u8) -> Bytes {
+    let len = input.len();
+    let mut result = Bytes::new(env);
+    let mut buf = [0u8; FIND_CHUNK_SIZE as usize];
+    let mut start = 0u32;
+    while start < len {
+        let end = (start + FIND_CHUNK_SIZE).min(len);
+        let n = (end - start) as usize;
+        input.slice(start..end).copy_into_slice(&mut buf[..n]);
+        for b in &mut buf[..n] {
+            *b = f(*b);
+        }
+        result.extend_from_slice(&buf[..n]);
+        start = end;
+    }
+    result
+}
+
+/// Convert ASCII letters in Bytes to lowercase.
+///
+/// Non-ASCII bytes are left unchanged, so UTF-8 content stays valid.
+pub fn bytes_to_lower(env: &Env, input: &Bytes) -> Bytes {
+    map_bytes(env, input, |b| b.to_ascii_lowercase())
+}
+
+/// Convert ASCII letters in Bytes to uppercase.
+///
+/// Non-ASCII bytes are left unchanged, so UTF-8 content stays valid.
+pub fn bytes_to_upper(env: &Env, input: &Bytes) -> Bytes {
+    map_bytes(env, input, |b| b.to_ascii_uppercase())
+}
+
+/// Turn a title into a URL slug.
+///
+/// ASCII letters are lowercased and digits kept; every run of other ASCII
+/// characters becomes a single `-`, with none at either end. Non-ASCII bytes
+/// are dropped for now, so "Café" becomes "caf". The result is capped at
+/// `MAX_SLUG_LEN` bytes.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let title = String::from_str(&env, "Hello, World!");
+/// let slug = slugify(&env, &title);
+/// // slug contains "hello-world"
+/// ```
+pub fn slugify(env: &Env, s: &String) -> Bytes {
+    let input = string_to_bytes(env, s);
+    let len = input.len();
+    let mut out = [0u8; MAX_SLUG_LEN];
+    let mut out_len = 0;
+    let mut pending_dash = false;
+    let mut buf = [0u8; FIND_CHUNK_SIZE as usize];
+    let mut start = 0u32;
+
+    'outer: while start < len {
+        let end = (start + FIND_CHUNK_SIZE).min(len);
+        let n = (end - start) as usize;
+        input.slice(start..end).copy_into_slice(&mut buf[..n]);
+        for &b in &buf[..n] {
+            if b.is_ascii_alphanumeric() {
+                let needed = if pending_dash && out_len > 0 { 2 } else { 1 };
+                if out_len + needed > MAX_SLUG_LEN {
+                    break 'outer;
+                }
+                if needed == 2 {
+                    out[out_len] = b'-';
+                    out_len += 1;
+                }
+                out[out_len] = b.to_ascii_lowercase();
+                out_len += 1;
+                pending_dash = false;
+            } else if b.is_ascii() {
+                pending_dash = true;
+            }
+        }
+        start = end;
+    }
+
+    Bytes::from_slice(env, &out[..out_len])
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+
+    use super::*;
+    use soroban_sdk::Env;
+
+    #[test]
+    fn test_concat_bytes() {
+        let env = Env::default();
+        let mut parts: Vec<Bytes> = Vec::new(&env);
+        parts.push_back(Bytes::from_slice(&env, b"Hello, "));
+        parts.push_back(Bytes::from_slice(&env, b"World!"));
+
+        let result = concat_bytes(&env, &parts);
+        assert_eq!(result.len(), 13);
+    }
+
+    #[test]
+    fn test_concat_bytes_empty() {
+        let env = Env::default();
+        let parts: Vec<Bytes> = Vec::new(&env);
+        let result = concat_bytes(&env, &parts);
+        assert_eq!(result.len(), 0);
+    }
+
+    #[test]
+    fn test_string_to_bytes() {
+        let env = Env::default();
+        let s = String::from_str(&env, "Hello");
+        let bytes = string_to_bytes(&env, &s);
+        assert_eq!(bytes.len(), 5);
+    }
+
+    #[test]
+    fn test_string_to_bytes_empty() {
+        let env = Env::default();
+        let s = String::from_str(&env, "");
+        let bytes = string_to_bytes(&env, &s);
+        assert_eq!(bytes.len(), 0);
+    }
+
+    #[test]
+    fn test_string_to_bytes_256_boundary() {
+        let env = Env::default();
+        // Exactly 256 bytes - should use first tier
+        let content = "a".repeat(256);
+        let s = String::from_str(&env, &content);
+        let bytes = string_to_bytes(&env, &s);
+        assert_eq!(bytes.len(), 256);
+    }
+
+    #[test]
+    fn test_string_to_bytes_257_uses_1kb_tier() {
+        let env = Env::default();
+        // 257 bytes - should use second tier (1KB buffer)
+        let content = "a".repeat(257);
+        let s = String::from_str(&env, &content);
+        let bytes = string_to_bytes(&env, &s);
+        assert_eq!(bytes.len(), 257);
+    }
+
+    #[test]
+    fn test_string_to_bytes_1kb_boundary() {
+        let env = Env::default();
+        let content = "a".repeat(1024);
+        let s = String::from_str(&env, &content);
+        let bytes = string_to_bytes(&env, &s);
+        assert_eq!(bytes.len(), 1024);
+    }
+
+    #[test]
+    fn test_string_to_bytes_4kb() {
+        let env = Env::default();
+        let content = "a".repeat(4000);
+        let s = String::from_str(&env, &content);
+        let bytes = string_to_bytes(&env, &s);
+        assert_eq!(bytes.len(), 4000);
+    }
+
+    #[test]
+    fn test_string_to_bytes_large() {
+        let env = Env::default();
+        // 10KB string - copied in chunks
+        let content = "a".repeat(10000);
+        let s = String::from_str(&env, &content);
+        let bytes = string_to_bytes(&env, &s);
+        assert_eq!(bytes.len(), 10000);
+    }
+
+    #[test]
+    fn test_string_to_bytes_max_size() {
+        let env = Env::default();
+        // Exactly at the 16KB limit
+        let content = "a".repeat(MAX_STRING_SIZE);
+        let s = String::from_str(&env, &content);
+        let bytes = string_to_bytes(&env, &s);
+        assert_eq!(bytes.len(), MAX_STRING_SIZE as u32);
+    }
+
+    #[test]
+    fn test_string_to_bytes_over_16kb() {
+        let env = Env::default();
+        let content = "a".repeat(MAX_STRING_SIZE + 1);
+        let s = String::from_str(&env, &content);
+        let bytes = string_to_bytes(&env, &s);
+        assert_eq!(bytes, Bytes::from_slice(&env, content.as_bytes()));
+    }
+
+    #[test]
+    fn test_string_to_bytes_64kb() {
+        let env = Env::default();
+        // Varied content so a misplaced chunk would be caught.
+        let content: alloc::string::String = (0..65_536u32)
+            .map(|i| (b'a' + (i % 26) as u8) as char)
+            .collect();
+        let s = String::from_str(&env, &content);
+        let bytes = string_to_bytes(&env, &s);
+        assert_eq!(bytes.len(), 65_536);
+        assert_eq!(bytes, Bytes::from_slice(&env, content.as_bytes()));
+    }
+
+    #[test]
+    fn test_string_to_bytes_chunk_boundaries() {
+        let env = Env::default();
+        for len in [4097usize, 5120, 5121] {
+            let content: alloc::string::String =
+                (0..len).map(|i| (b'0' + (i % 10) as u8) as char).collect();
+            let s = String::from_str(&env, &content);
+            assert_eq!(
+                string_to_bytes(&env, &s),
+                Bytes::from_slice(&env, content.as_bytes())
+            );
+        }
+    }
+
+    #[test]
+    fn test_u32_to_bytes_zero() {
+        let env = Env::default();
+        let bytes = u32_to_bytes(&env, 0);
+        assert_eq!(bytes.len(), 1);
+        assert_eq!(bytes.get(0), Some(b'0'));
+    }
+
+    #[test]
     fn test_u32_to_bytes_single_digit() {
         let env = Env::default();
-        let bytes = u32_to_bytes(&env, 7);
-        assert_eq!(bytes.len(), 1);
-        assert_eq!(bytes.get(0), Some(b'7'));
+        let bytes = u32_to_bytes(&env, 7);
+        assert_eq!(bytes.len(), 1);
+        assert_eq!(bytes.get(0), Some(b'7'));
+    }
+
+    #[test]
+    fn test_u32_to_bytes_multi_digit() {
+        let env = Env::default();
+        let bytes = u32_to_bytes(&env, 12345);
+        assert_eq!(bytes.len(), 5);
+        assert_eq!(bytes.get(0), Some(b'1'));
+        assert_eq!(bytes.get(1), Some(b'2'));
+        assert_eq!(bytes.get(2), Some(b'3'));
+        assert_eq!(bytes.get(3), Some(b'4'));
+        assert_eq!(bytes.get(4), Some(b'5'));
+    }
+
+    #[test]
+    fn test_i64_to_bytes_positive() {
+        let env = Env::default();
+        let bytes = i64_to_bytes(&env, 42);
+        assert_eq!(bytes.len(), 2);
+        assert_eq!(bytes.get(0), Some(b'4'));
+        assert_eq!(bytes.get(1), Some(b'2'));
+    }
+
+    #[test]
+    fn test_i64_to_bytes_negative() {
+        let env = Env::default();
+        let bytes = i64_to_bytes(&env, -42);
+        assert_eq!(bytes.len(), 3);
+        assert_eq!(bytes.get(0), Some(b'-'));
+        assert_eq!(bytes.get(1), Some(b'4'));
+        assert_eq!(bytes.get(2), Some(b'2'));
+    }
+
+    #[test]
+    fn test_i64_to_bytes_zero() {
+        let env = Env::default();
+        let bytes = i64_to_bytes(&env, 0);
+        assert_eq!(bytes.len(), 1);
+        assert_eq!(bytes.get(0), Some(b'0'));
+    }
+
+    #[test]
+    fn test_escape_json_control_characters() {
+        let env = Env::default();
+        let input = b"a\x01b\x0Bc\x1F\x00";
+        let expected = br#"a\u0001b\u000bc\u001f\u0000"#;
+        assert_eq!(
+            escape_json_bytes(&env, input),
+            Bytes::from_slice(&env, expected)
+        );
+
+        let s = String::from_bytes(&env, input);
+        assert_eq!(
+            escape_json_string(&env, &s),
+            Bytes::from_slice(&env, expected)
+        );
+    }
+
+    #[test]
+    fn test_escape_json_control_characters_parse() {
+        let env = Env::default();
+        let input = b"tab\there\x01vt\x0Bff\x0C\"end\"";
+        let escaped = escape_json_bytes(&env, input);
+
+        let mut doc: alloc::vec::Vec<u8> = b"{\"v\":\"".to_vec();
+        doc.extend(escaped.iter());
+        doc.extend_from_slice(b"\"}");
+        let parsed: serde_json::Value = serde_json::from_slice(&doc).unwrap();
+        assert_eq!(parsed["v"].as_str().unwrap().as_bytes(), input);
+    }
+
+    #[test]
+    fn test_escape_json_string_4kb_clean() {
+        let env = Env::default();
+        let content: alloc::string::String = (0..4096u32)
+            .map(|i| (b'a' + (i % 26) as u8) as char)
+            .collect();
+        let s = String::from_str(&env, &content);
+
+        let mut budget = env.cost_estimate().budget();
+        budget.reset_unlimited();
+        let plain = string_to_bytes(&env, &s);
+        let copy_cpu = budget.cpu_instruction_cost();
+
+        budget.reset_unlimited();
+        let escaped = escape_json_string(&env, &s);
+        let escape_cpu = budget.cpu_instruction_cost();
+
+        assert_eq!(escaped, plain);
+        // Clean input takes the same single copy as `string_to_bytes` instead
+        // of one host call per byte (thousands for a 4KB body).
+        assert!(escape_cpu <= copy_cpu * 2);
+    }
+
+    #[test]
+    fn test_escape_json_string_scattered_quotes() {
+        let env = Env::default();
+        let mut content = alloc::string::String::new();
+        let mut expected = alloc::string::String::new();
+        for i in 0..600 {
+            content.push_str("word ");
+            expected.push_str("word ");
+            if i % 7 == 0 {
+                content.push('"');
+                expected.push_str("\\\"");
+            }
+            if i % 50 == 0 {
+                content.push('\n');
+                expected.push_str("\\n");
+            }
+        }
+        let s = String::from_str(&env, &content);
+        assert!(s.len() > 3000);
+        assert_eq!(
+            escape_json_string(&env, &s),
+            Bytes::from_slice(&env, expected.as_bytes())
+        );
+        assert_eq!(
+            escape_json_bytes(&env, content.as_bytes()),
+            Bytes::from_slice(&env, expected.as_bytes())
+        );
+    }
+
+    #[test]
+    fn test_escape_json_string_chunked() {
+        let env = Env::default();
+        // Longer than the largest tier, with escapes on both sides of a chunk edge.
+        let mut content = "a".repeat(5119);
+        content.push_str("\"\"");
+        content.push_str(&"b".repeat(100));
+        let mut expected = "a".repeat(5119);
+        expected.push_str("\\\"\\\"");
+        expected.push_str(&"b".repeat(100));
+        let s = String::from_str(&env, &content);
+        assert_eq!(
+            escape_json_string(&env, &s),
+            Bytes::from_slice(&env, expected.as_bytes())
+        );
+        assert_eq!(
+            escape_json_string(&env, &String::from_str(&env, "")),
+            Bytes::new(&env)
+        );
+    }
+
+    #[test]
+    fn test_escape_json_bytes_quotes() {
+        let env = Env::default();
+        let bytes = escape_json_bytes(&env, b"Hello \"World\"");
+        // Should be: Hello \"World\"
+        assert_eq!(bytes.len(), 15); // 5 + 1 + 1 + 5 + 1 + 1 + 1 = 15
+    }
+
+    #[test]
+    fn test_escape_json_bytes_backslash() {
+        let env = Env::default();
+        let bytes = escape_json_bytes(&env, b"path\\to\\file");
+        // Should be: path\\to\\file
+        assert_eq!(bytes.len(), 14); // 4 + 2 + 2 + 2 + 4 = 14
+    }
+
+    #[test]
+    fn test_escape_json_bytes_newline() {
+        let env = Env::default();
+        let bytes = escape_json_bytes(&env, b"line1\nline2");
+        // Should be: line1\nline2
+        assert_eq!(bytes.len(), 12); // 5 + 2 + 5 = 12
+    }
+
+    // i32_to_bytes tests
+    #[test]
+    fn test_i32_to_bytes_zero() {
+        let env = Env::default();
+        let bytes = i32_to_bytes(&env, 0);
+        assert_eq!(bytes.len(), 1);
+        assert_eq!(bytes.get(0), Some(b'0'));
+    }
+
+    #[test]
+    fn test_i32_to_bytes_positive() {
+        let env = Env::default();
+        let bytes = i32_to_bytes(&env, 42);
+        assert_eq!(bytes.len(), 2);
+        assert_eq!(bytes.get(0), Some(b'4'));
+        assert_eq!(bytes.get(1), Some(b'2'));
+    }
+
+    #[test]
+    fn test_i32_to_bytes_negative() {
+        let env = Env::default();
+        let bytes = i32_to_bytes(&env, -42);
+        assert_eq!(bytes.len(), 3);
+        assert_eq!(bytes.get(0), Some(b'-'));
+        assert_eq!(bytes.get(1), Some(b'4'));
+        assert_eq!(bytes.get(2), Some(b'2'));
+    }
+
+    #[test]
+    fn test_i32_to_bytes_max() {
+        let env = Env::default();
+        let bytes = i32_to_bytes(&env, i32::MAX);
+        assert_eq!(bytes.len(), 10); // 2147483647 is 10 digits
+    }
+
+    #[test]
+    fn test_i32_to_bytes_min() {
+        let env = Env::default();
+        let bytes = i32_to_bytes(&env, i32::MIN);
+        assert_eq!(bytes.len(), 11); // -2147483648 is 11 chars
+    }
+
+    // u64_to_bytes tests
+    #[test]
+    fn test_u64_to_bytes_zero() {
+        let env = Env::default();
+        let bytes = u64_to_bytes(&env, 0);
+        assert_eq!(bytes.len(), 1);
+        assert_eq!(bytes.get(0), Some(b'0'));
+    }
+
+    #[test]
+    fn test_u64_to_bytes_single_digit() {
+        let env = Env::default();
+        let bytes = u64_to_bytes(&env, 7);
+        assert_eq!(bytes.len(), 1);
+        assert_eq!(bytes.get(0), Some(b'7'));
+    }
+
+    #[test]
+    fn test_u64_to_bytes_multi_digit() {
+        let env = Env::default();
+        let bytes = u64_to_bytes(&env, 12345678901234);
+        assert_eq!(bytes.len(), 14);
+    }
+
+    #[test]
+    fn test_u64_to_bytes_max() {
+        let env = Env::default();
+        let bytes = u64_to_bytes(&env, u64::MAX);
+        assert_eq!(bytes.len(), 20); // 18446744073709551615 is 20 digits
+    }
+
+    // bytes_to_u32 tests
+    #[test]
+    fn test_bytes_to_u32_zero() {
+        let env = Env::default();
+        let bytes = Bytes::from_slice(&env, b"0");
+        assert_eq!(bytes_to_u32(&bytes), Some(0));
+    }
+
+    #[test]
+    fn test_bytes_to_u32_simple() {
+        let env = Env::default();
+        let bytes = Bytes::from_slice(&env, b"42");
+        assert_eq!(bytes_to_u32(&bytes), Some(42));
+    }
+
+    #[test]
+    fn test_bytes_to_u32_max() {
+        let env = Env::default();
+        let bytes = Bytes::from_slice(&env, b"4294967295");
+        assert_eq!(bytes_to_u32(&bytes), Some(u32::MAX));
+    }
+
+    #[test]
+    fn test_bytes_to_u32_overflow() {
+        let env = Env::default();
+        let bytes = Bytes::from_slice(&env, b"4294967296"); // u32::MAX + 1
+        assert_eq!(bytes_to_u32(&bytes), None);
+    }
+
+    #[test]
+    fn test_bytes_to_u32_invalid() {
+        let env = Env::default();
+        let bytes = Bytes::from_slice(&env, b"abc");
+        assert_eq!(bytes_to_u32(&bytes), None);
     }
 
     #[test]
-    fn test_u32_to_bytes_multi_digit() {
+    fn test_bytes_to_u32_empty() {
         let env = Env::default();
-        let bytes = u32_to_bytes(&env, 12345);
-        assert_eq!(bytes.len(), 5);
-        assert_eq!(bytes.get(0), Some(b'1'));
-        assert_eq!(bytes.get(1), Some(b'2'));
-        assert_eq!(bytes.get(2), Some(b'3'));
-        assert_eq!(bytes.get(3), Some(b'4'));
-        assert_eq!(bytes.get(4), Some(b'5'));
+        let bytes = Bytes::from_slice(&env, b"");
+        assert_eq!(bytes_to_u32(&bytes), None);
     }
 
+    // bytes_to_i32 tests
     #[test]
-    fn test_i64_to_bytes_positive() {
+    fn test_bytes_to_i32_zero() {
         let env = Env::default();
-        let bytes = i64_to_bytes(&env, 42);
-        assert_eq!(bytes.len(), 2);
-        assert_eq!(bytes.get(0), Some(b'4'));
-        assert_eq!(bytes.get(1), Some(b'2'));
+        let bytes = Bytes::from_slice(&env, b"0");
+        assert_eq!(bytes_to_i32(&bytes), Some(0));
     }
 
     #[test]
-    fn test_i64_to_bytes_negative() {
+    fn test_bytes_to_i32_positive() {
         let env = Env::default();
-        let bytes = i64_to_bytes(&env, -42);
-        assert_eq!(bytes.len(), 3);
-        assert_eq!(bytes.get(0), Some(b'-'));
-        assert_eq!(bytes.get(1), Some(b'4'));
-        assert_eq!(bytes.get(2), Some(b'2'));
+        let bytes = Bytes::from_slice(&env, b"42");
+        assert_eq!(bytes_to_i32(&bytes), Some(42));
     }
 
     #[test]
-    fn test_i64_to_bytes_zero() {
+    fn test_bytes_to_i32_negative() {
         let env = Env::default();
-        let bytes = i64_to_bytes(&env, 0);
-        assert_eq!(bytes.len(), 1);
-        assert_eq!(bytes.get(0), Some(b'0'));
+        let bytes = Bytes::from_slice(&env, b"-42");
+        assert_eq!(bytes_to_i32(&bytes), Some(-42));
     }
 
     #[test]
-    fn test_escape_json_bytes_quotes() {
+    fn test_bytes_to_i32_max() {
         let env = Env::default();
-        let bytes = escape_json_bytes(&env, b"Hello \"World\"");
-        // Should be: Hello \"World\"
-        assert_eq!(bytes.len(), 15); // 5 + 1 + 1 + 5 + 1 + 1 + 1 = 15
+        let bytes = Bytes::from_slice(&env, b"2147483647");
+        assert_eq!(bytes_to_i32(&bytes), Some(i32::MAX));
     }
 
     #[test]
-    fn test_escape_json_bytes_backslash() {
+    fn test_bytes_to_i32_just_minus() {
         let env = Env::default();
-        let bytes = escape_json_bytes(&env, b"path\\to\\file");
-        // Should be: path\\to\\file
-        assert_eq!(bytes.len(), 14); // 4 + 2 + 2 + 2 + 4 = 14
+        let bytes = Bytes::from_slice(&env, b"-");
+        assert_eq!(bytes_to_i32(&bytes), None);
     }
 
+    // bytes_to_u64 tests
     #[test]
-    fn test_escape_json_bytes_newline() {
+    fn test_bytes_to_u64_zero() {
         let env = Env::default();
-        let bytes = escape_json_bytes(&env, b"line1\nline2");
-        // Should be: line1\nline2
-        assert_eq!(bytes.len(), 12); // 5 + 2 + 5 = 12
+        let bytes = Bytes::from_slice(&env, b"0");
+        assert_eq!(bytes_to_u64(&bytes), Some(0));
     }
 
-    // i32_to_bytes tests
     #[test]
-    fn test_i32_to_bytes_zero() {
+    fn test_bytes_to_u64_simple() {
         let env = Env::default();
-        let bytes = i32_to_bytes(&env, 0);
+        let bytes = Bytes::from_slice(&env, b"42");
+        assert_eq!(bytes_to_u64(&bytes), Some(42));
+    }
+
+    #[test]
+    fn test_bytes_to_u64_large() {
+        let env = Env::default();
+        let bytes = Bytes::from_slice(&env, b"18446744073709551615");
+        assert_eq!(bytes_to_u64(&bytes), Some(u64::MAX));
+    }
+
+    #[test]
+    fn test_bytes_to_u64_overflow() {
+        let env = Env::default();
+        let bytes = Bytes::from_slice(&env, b"18446744073709551616"); // u64::MAX + 1
+        assert_eq!(bytes_to_u64(&bytes), None);
+    }
+
+    // bytes_to_i64 tests
+    #[test]
+    fn test_bytes_to_i64_zero() {
+        let env = Env::default();
+        let bytes = Bytes::from_slice(&env, b"0");
+        assert_eq!(bytes_to_i64(&bytes), Some(0));
+    }
+
+    #[test]
+    fn test_bytes_to_i64_positive() {
+        let env = Env::default();
+        let bytes = Bytes::from_slice(&env, b"42");
+        assert_eq!(bytes_to_i64(&bytes), Some(42));
+    }
+
+    #[test]
+    fn test_bytes_to_i64_negative() {
+        let env = Env::default();
+        let bytes = Bytes::from_slice(&env, b"-42");
+        assert_eq!(bytes_to_i64(&bytes), Some(-42));
+    }
+
+    #[test]
+    fn test_bytes_to_i64_max() {
+        let env = Env::default();
+        let bytes = Bytes::from_slice(&env, b"9223372036854775807");
+        assert_eq!(bytes_to_i64(&bytes), Some(i64::MAX));
+    }
+
+    // u128_to_bytes tests
+    #[test]
+    fn test_u128_to_bytes_zero() {
+        let env = Env::default();
+        let bytes = u128_to_bytes(&env, 0);
         assert_eq!(bytes.len(), 1);
         assert_eq!(bytes.get(0), Some(b'0'));
     }
 
     #[test]
-    fn test_i32_to_bytes_positive() {
+    fn test_u128_to_bytes_simple() {
         let env = Env::default();
-        let bytes = i32_to_bytes(&env, 42);
+        let bytes = u128_to_bytes(&env, 42);
         assert_eq!(bytes.len(), 2);
-        assert_eq!(bytes.get(0), Some(b'4'));
-        assert_eq!(bytes.get(1), Some(b'2'));
     }
 
     #[test]
-    fn test_i32_to_bytes_negative() {
+    fn test_u128_to_bytes_max() {
         let env = Env::default();
-        let bytes = i32_to_bytes(&env, -42);
+        let bytes = u128_to_bytes(&env, u128::MAX);
+        // 340282366920938463463374607431768211455 is 39 digits
+        assert_eq!(bytes.len(), 39);
+    }
+
+    // i128_to_bytes tests
+    #[test]
+    fn test_i128_to_bytes_zero() {
+        let env = Env::default();
+        let bytes = i128_to_bytes(&env, 0);
+        assert_eq!(bytes.len(), 1);
+        assert_eq!(bytes.get(0), Some(b'0'));
+    }
+
+    #[test]
+    fn test_i128_to_bytes_positive() {
+        let env = Env::default();
+        let bytes = i128_to_bytes(&env, 42);
+        assert_eq!(bytes.len(), 2);
+    }
+
+    #[test]
+    fn test_i128_to_bytes_negative() {
+        let env = Env::default();
+        let bytes = i128_to_bytes(&env, -42);
         assert_eq!(bytes.len(), 3);
         assert_eq!(bytes.get(0), Some(b'-'));
-        assert_eq!(bytes.get(1), Some(b'4'));
-        assert_eq!(bytes.get(2), Some(b'2'));
     }
 
     #[test]
-    fn test_i32_to_bytes_max() {
+    fn test_i128_to_bytes_max() {
+        let env = Env::default();
+        let bytes = i128_to_bytes(&env, i128::MAX);
+        // 170141183460469231731687303715884105727 is 39 digits
+        assert_eq!(bytes.len(), 39);
+    }
+
+    #[test]
+    fn test_i128_to_bytes_min() {
+        let env = Env::default();
+        let bytes = i128_to_bytes(&env, i128::MIN);
+        // -170141183460469231731687303715884105728 is 40 chars
+        assert_eq!(bytes.len(), 40);
+        assert_eq!(bytes.get(0), Some(b'-'));
+    }
+
+    // bytes_to_u128 tests
+    #[test]
+    fn test_bytes_to_u128_zero() {
+        let env = Env::default();
+        let bytes = Bytes::from_slice(&env, b"0");
+        assert_eq!(bytes_to_u128(&bytes), Some(0));
+    }
+
+    #[test]
+    fn test_bytes_to_u128_simple() {
+        let env = Env::default();
+        let bytes = Bytes::from_slice(&env, b"42");
+        assert_eq!(bytes_to_u128(&bytes), Some(42));
+    }
+
+    #[test]
+    fn test_bytes_to_u128_max() {
+        let env = Env::default();
+        let bytes = Bytes::from_slice(&env, b"340282366920938463463374607431768211455");
+        assert_eq!(bytes_to_u128(&bytes), Some(u128::MAX));
+    }
+
+    #[test]
+    fn test_bytes_to_u128_overflow() {
+        let env = Env::default();
+        let bytes = Bytes::from_slice(&env, b"340282366920938463463374607431768211456"); // u128::MAX + 1
+        assert_eq!(bytes_to_u128(&bytes), None);
+    }
+
+    // bytes_to_i128 tests
+    #[test]
+    fn test_bytes_to_i128_zero() {
+        let env = Env::default();
+        let bytes = Bytes::from_slice(&env, b"0");
+        assert_eq!(bytes_to_i128(&bytes), Some(0));
+    }
+
+    #[test]
+    fn test_bytes_to_i128_positive() {
+        let env = Env::default();
+        let bytes = Bytes::from_slice(&env, b"42");
+        assert_eq!(bytes_to_i128(&bytes), Some(42));
+    }
+
+    #[test]
+    fn test_bytes_to_i128_negative() {
+        let env = Env::default();
+        let bytes = Bytes::from_slice(&env, b"-42");
+        assert_eq!(bytes_to_i128(&bytes), Some(-42));
+    }
+
+    #[test]
+    fn test_bytes_to_i128_max() {
+        let env = Env::default();
+        let bytes = Bytes::from_slice(&env, b"170141183460469231731687303715884105727");
+        assert_eq!(bytes_to_i128(&bytes), Some(i128::MAX));
+    }
+
+    #[test]
+    fn test_bytes_to_i128_min() {
+        let env = Env::default();
+        let bytes = Bytes::from_slice(&env, b"-170141183460469231731687303715884105728");
+        assert_eq!(bytes_to_i128(&bytes), Some(i128::MIN));
+    }
+
+    #[test]
+    fn test_bytes_to_i128_overflow_positive() {
+        let env = Env::default();
+        let bytes = Bytes::from_slice(&env, b"170141183460469231731687303715884105728"); // i128::MAX + 1
+        assert_eq!(bytes_to_i128(&bytes), None);
+    }
+
+    #[test]
+    fn test_bytes_to_i128_overflow_negative() {
+        let env = Env::default();
+        let bytes = Bytes::from_slice(&env, b"-170141183460469231731687303715884105729"); // i128::MIN - 1
+        assert_eq!(bytes_to_i128(&bytes), None);
+    }
+
+    // ==========================================================================
+    // Hex conversion tests
+    // ==========================================================================
+
+    // u32_to_hex tests
+    #[test]
+    fn test_u32_to_hex_zero() {
         let env = Env::default();
-        let bytes = i32_to_bytes(&env, i32::MAX);
-        assert_eq!(bytes.len(), 10); // 2147483647 is 10 digits
+        let bytes = u32_to_hex(&env, 0);
+        assert_eq!(bytes, Bytes::from_slice(&env, b"0x0"));
     }
 
     #[test]
-    fn test_i32_to_bytes_min() {
+    fn test_u32_to_hex_simple() {
         let env = Env::default();
-        let bytes = i32_to_bytes(&env, i32::MIN);
-        assert_eq!(bytes.len(), 11); // -2147483648 is 11 chars
+        let bytes = u32_to_hex(&env, 255);
+        assert_eq!(bytes, Bytes::from_slice(&env, b"0xff"));
     }
 
-    // u64_to_bytes tests
     #[test]
-    fn test_u64_to_bytes_zero() {
+    fn test_u32_to_hex_max() {
         let env = Env::default();
-        let bytes = u64_to_bytes(&env, 0);
-        assert_eq!(bytes.len(), 1);
-        assert_eq!(bytes.get(0), Some(b'0'));
+        let bytes = u32_to_hex(&env, u32::MAX);
+        assert_eq!(bytes, Bytes::from_slice(&env, b"0xffffffff"));
     }
 
+    // i32_to_hex tests
     #[test]
-    fn test_u64_to_bytes_single_digit() {
+    fn test_i32_to_hex_zero() {
         let env = Env::default();
-        let bytes = u64_to_bytes(&env, 7);
-        assert_eq!(bytes.len(), 1);
-        assert_eq!(bytes.get(0), Some(b'7'));
+        let bytes = i32_to_hex(&env, 0);
+        assert_eq!(bytes, Bytes::from_slice(&env, b"0x0"));
     }
 
     #[test]
-    fn test_u64_to_bytes_multi_digit() {
+    fn test_i32_to_hex_positive() {
         let env = Env::default();
-        let bytes = u64_to_bytes(&env, 12345678901234);
-        assert_eq!(bytes.len(), 14);
+        let bytes = i32_to_hex(&env, 255);
+        assert_eq!(bytes, Bytes::from_slice(&env, b"0xff"));
     }
 
     #[test]
-    fn test_u64_to_bytes_max() {
+    fn test_i32_to_hex_negative() {
         let env = Env::default();
-        let bytes = u64_to_bytes(&env, u64::MAX);
-        assert_eq!(bytes.len(), 20); // 18446744073709551615 is 20 digits
+        let bytes = i32_to_hex(&env, -255);
+        assert_eq!(bytes, Bytes::from_slice(&env, b"-0xff"));
     }
 
-    // bytes_to_u32 tests
+    // u64_to_hex tests
     #[test]
-    fn test_bytes_to_u32_zero() {
+    fn test_u64_to_hex_zero() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"0");
-        assert_eq!(bytes_to_u32(&bytes), Some(0));
+        let bytes = u64_to_hex(&env, 0);
+        assert_eq!(bytes, Bytes::from_slice(&env, b"0x0"));
     }
 
     #[test]
-    fn test_bytes_to_u32_simple() {
+    fn test_u64_to_hex_simple() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"42");
-        assert_eq!(bytes_to_u32(&bytes), Some(42));
+        let bytes = u64_to_hex(&env, 0xDEADBEEF);
+        assert_eq!(bytes, Bytes::from_slice(&env, b"0xdeadbeef"));
     }
 
     #[test]
-    fn test_bytes_to_u32_max() {
+    fn test_u64_to_hex_max() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"4294967295");
-        assert_eq!(bytes_to_u32(&bytes), Some(u32::MAX));
+        let bytes = u64_to_hex(&env, u64::MAX);
+        assert_eq!(bytes, Bytes::from_slice(&env, b"0xffffffffffffffff"));
     }
 
+    // i64_to_hex tests
     #[test]
-    fn test_bytes_to_u32_overflow() {
+    fn test_i64_to_hex_negative() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"4294967296"); // u32::MAX + 1
-        assert_eq!(bytes_to_u32(&bytes), None);
+        let bytes = i64_to_hex(&env, -0xFF);
+        assert_eq!(bytes, Bytes::from_slice(&env, b"-0xff"));
     }
 
+    // u128_to_hex tests
     #[test]
-    fn test_bytes_to_u32_invalid() {
+    fn test_u128_to_hex_zero() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"abc");
-        assert_eq!(bytes_to_u32(&bytes), None);
+        let bytes = u128_to_hex(&env, 0);
+        assert_eq!(bytes, Bytes::from_slice(&env, b"0x0"));
     }
 
     #[test]
-    fn test_bytes_to_u32_empty() {
+    fn test_u128_to_hex_simple() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"");
-        assert_eq!(bytes_to_u32(&bytes), None);
+        let bytes = u128_to_hex(&env, 255);
+        assert_eq!(bytes, Bytes::from_slice(&env, b"0xff"));
     }
 
-    // bytes_to_i32 tests
+    // i128_to_hex tests
     #[test]
-    fn test_bytes_to_i32_zero() {
+    fn test_i128_to_hex_negative() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"0");
-        assert_eq!(bytes_to_i32(&bytes), Some(0));
+        let bytes = i128_to_hex(&env, -255);
+        assert_eq!(bytes, Bytes::from_slice(&env, b"-0xff"));
     }
 
     #[test]
-    fn test_bytes_to_i32_positive() {
+    fn test_i128_to_hex_min() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"42");
-        assert_eq!(bytes_to_i32(&bytes), Some(42));
+        let bytes = i128_to_hex(&env, i128::MIN);
+        // i128::MIN = -170141183460469231731687303715884105728 = -0x80000000000000000000000000000000
+        assert_eq!(bytes.len(), 35); // "-0x" (3) + 32 hex digits = 35
     }
 
+    // hex_to_u32 tests
     #[test]
-    fn test_bytes_to_i32_negative() {
+    fn test_hex_to_u32_simple() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"-42");
-        assert_eq!(bytes_to_i32(&bytes), Some(-42));
+        let bytes = Bytes::from_slice(&env, b"0xff");
+        assert_eq!(hex_to_u32(&bytes), Some(255));
     }
 
     #[test]
-    fn test_bytes_to_i32_max() {
+    fn test_hex_to_u32_no_prefix() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"2147483647");
-        assert_eq!(bytes_to_i32(&bytes), Some(i32::MAX));
+        let bytes = Bytes::from_slice(&env, b"ff");
+        assert_eq!(hex_to_u32(&bytes), Some(255));
     }
 
     #[test]
-    fn test_bytes_to_i32_just_minus() {
+    fn test_hex_to_u32_uppercase() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"-");
-        assert_eq!(bytes_to_i32(&bytes), None);
+        let bytes = Bytes::from_slice(&env, b"0XFF");
+        assert_eq!(hex_to_u32(&bytes), Some(255));
     }
 
-    // bytes_to_u64 tests
     #[test]
-    fn test_bytes_to_u64_zero() {
+    fn test_hex_to_u32_mixed_case() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"0");
-        assert_eq!(bytes_to_u64(&bytes), Some(0));
+        let bytes = Bytes::from_slice(&env, b"0xDeAdBeEf");
+        assert_eq!(hex_to_u32(&bytes), Some(0xDEADBEEF));
     }
 
     #[test]
-    fn test_bytes_to_u64_simple() {
+    fn test_hex_to_u32_max() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"42");
-        assert_eq!(bytes_to_u64(&bytes), Some(42));
+        let bytes = Bytes::from_slice(&env, b"0xffffffff");
+        assert_eq!(hex_to_u32(&bytes), Some(u32::MAX));
     }
 
     #[test]
-    fn test_bytes_to_u64_large() {
+    fn test_hex_to_u32_overflow() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"18446744073709551615");
-        assert_eq!(bytes_to_u64(&bytes), Some(u64::MAX));
+        let bytes = Bytes::from_slice(&env, b"0x100000000"); // u32::MAX + 1
+        assert_eq!(hex_to_u32(&bytes), None);
     }
 
     #[test]
-    fn test_bytes_to_u64_overflow() {
+    fn test_hex_to_u32_invalid() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"18446744073709551616"); // u64::MAX + 1
-        assert_eq!(bytes_to_u64(&bytes), None);
+        let bytes = Bytes::from_slice(&env, b"0xgg");
+        assert_eq!(hex_to_u32(&bytes), None);
     }
 
-    // bytes_to_i64 tests
     #[test]
-    fn test_bytes_to_i64_zero() {
+    fn test_hex_to_u32_empty() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"0");
-        assert_eq!(bytes_to_i64(&bytes), Some(0));
+        let bytes = Bytes::from_slice(&env, b"");
+        assert_eq!(hex_to_u32(&bytes), None);
     }
 
     #[test]
-    fn test_bytes_to_i64_positive() {
+    fn test_hex_to_u32_just_prefix() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"42");
-        assert_eq!(bytes_to_i64(&bytes), Some(42));
+        let bytes = Bytes::from_slice(&env, b"0x");
+        assert_eq!(hex_to_u32(&bytes), None);
     }
 
+    // hex_to_i32 tests
     #[test]
-    fn test_bytes_to_i64_negative() {
+    fn test_hex_to_i32_positive() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"-42");
-        assert_eq!(bytes_to_i64(&bytes), Some(-42));
+        let bytes = Bytes::from_slice(&env, b"0xff");
+        assert_eq!(hex_to_i32(&bytes), Some(255));
     }
 
     #[test]
-    fn test_bytes_to_i64_max() {
+    fn test_hex_to_i32_negative() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"9223372036854775807");
-        assert_eq!(bytes_to_i64(&bytes), Some(i64::MAX));
+        let bytes = Bytes::from_slice(&env, b"-0xff");
+        assert_eq!(hex_to_i32(&bytes), Some(-255));
     }
 
-    // u128_to_bytes tests
     #[test]
-    fn test_u128_to_bytes_zero() {
+    fn test_hex_to_i32_negative_no_prefix() {
         let env = Env::default();
-        let bytes = u128_to_bytes(&env, 0);
-        assert_eq!(bytes.len(), 1);
-        assert_eq!(bytes.get(0), Some(b'0'));
+        let bytes = Bytes::from_slice(&env, b"-ff");
+        assert_eq!(hex_to_i32(&bytes), Some(-255));
     }
 
+    // hex_to_u64 tests
     #[test]
-    fn test_u128_to_bytes_simple() {
+    fn test_hex_to_u64_simple() {
         let env = Env::default();
-        let bytes = u128_to_bytes(&env, 42);
-        assert_eq!(bytes.len(), 2);
+        let bytes = Bytes::from_slice(&env, b"0xdeadbeef");
+        assert_eq!(hex_to_u64(&bytes), Some(0xDEADBEEF));
     }
 
     #[test]
-    fn test_u128_to_bytes_max() {
+    fn test_hex_to_u64_max() {
         let env = Env::default();
-        let bytes = u128_to_bytes(&env, u128::MAX);
-        // 340282366920938463463374607431768211455 is 39 digits
-        assert_eq!(bytes.len(), 39);
+        let bytes = Bytes::from_slice(&env, b"0xffffffffffffffff");
+        assert_eq!(hex_to_u64(&bytes), Some(u64::MAX));
     }
 
-    // i128_to_bytes tests
+    // hex_to_i64 tests
     #[test]
-    fn test_i128_to_bytes_zero() {
+    fn test_hex_to_i64_negative() {
         let env = Env::default();
-        let bytes = i128_to_bytes(&env, 0);
-        assert_eq!(bytes.len(), 1);
-        assert_eq!(bytes.get(0), Some(b'0'));
+        let bytes = Bytes::from_slice(&env, b"-0xff");
+        assert_eq!(hex_to_i64(&bytes), Some(-255));
     }
 
+    // hex_to_u128 tests
     #[test]
-    fn test_i128_to_bytes_positive() {
+    fn test_hex_to_u128_simple() {
         let env = Env::default();
-        let bytes = i128_to_bytes(&env, 42);
-        assert_eq!(bytes.len(), 2);
+        let bytes = Bytes::from_slice(&env, b"0xff");
+        assert_eq!(hex_to_u128(&bytes), Some(255));
     }
 
     #[test]
-    fn test_i128_to_bytes_negative() {
+    fn test_hex_to_u128_large() {
         let env = Env::default();
-        let bytes = i128_to_bytes(&env, -42);
-        assert_eq!(bytes.len(), 3);
-        assert_eq!(bytes.get(0), Some(b'-'));
+        let bytes = Bytes::from_slice(&env, b"0xffffffffffffffffffffffffffffffff");
+        assert_eq!(hex_to_u128(&bytes), Some(u128::MAX));
     }
 
+    // hex_to_i128 tests
     #[test]
-    fn test_i128_to_bytes_max() {
+    fn test_hex_to_i128_positive() {
         let env = Env::default();
-        let bytes = i128_to_bytes(&env, i128::MAX);
-        // 170141183460469231731687303715884105727 is 39 digits
-        assert_eq!(bytes.len(), 39);
+        let bytes = Bytes::from_slice(&env, b"0xff");
+        assert_eq!(hex_to_i128(&bytes), Some(255));
     }
 
     #[test]
-    fn test_i128_to_bytes_min() {
+    fn test_hex_to_i128_negative() {
         let env = Env::default();
-        let bytes = i128_to_bytes(&env, i128::MIN);
-        // -170141183460469231731687303715884105728 is 40 chars
-        assert_eq!(bytes.len(), 40);
-        assert_eq!(bytes.get(0), Some(b'-'));
+        let bytes = Bytes::from_slice(&env, b"-0xff");
+        assert_eq!(hex_to_i128(&bytes), Some(-255));
     }
 
-    // bytes_to_u128 tests
+    // ==========================================================================
+    // U256/I256 conversion tests
+    // ==========================================================================
+
+    // u256_to_bytes tests
     #[test]
-    fn test_bytes_to_u128_zero() {
+    fn test_u256_to_bytes_zero() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"0");
-        assert_eq!(bytes_to_u128(&bytes), Some(0));
+        let n = U256::from_u32(&env, 0);
+        let bytes = u256_to_bytes(&env, &n);
+        assert_eq!(bytes, Bytes::from_slice(&env, b"0"));
     }
 
     #[test]
-    fn test_bytes_to_u128_simple() {
+    fn test_u256_to_bytes_simple() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"42");
-        assert_eq!(bytes_to_u128(&bytes), Some(42));
+        let n = U256::from_u32(&env, 12345);
+        let bytes = u256_to_bytes(&env, &n);
+        assert_eq!(bytes, Bytes::from_slice(&env, b"12345"));
     }
 
     #[test]
-    fn test_bytes_to_u128_max() {
+    fn test_u256_to_bytes_large() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"340282366920938463463374607431768211455");
-        assert_eq!(bytes_to_u128(&bytes), Some(u128::MAX));
+        // Use from_u128 for a larger number
+        let n = U256::from_u128(&env, 123456789012345678901234567890);
+        let bytes = u256_to_bytes(&env, &n);
+        assert_eq!(
+            bytes,
+            Bytes::from_slice(&env, b"123456789012345678901234567890")
+        );
     }
 
+    // i256_to_bytes tests
     #[test]
-    fn test_bytes_to_u128_overflow() {
+    fn test_i256_to_bytes_zero() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"340282366920938463463374607431768211456"); // u128::MAX + 1
-        assert_eq!(bytes_to_u128(&bytes), None);
+        let n = I256::from_i32(&env, 0);
+        let bytes = i256_to_bytes(&env, &n);
+        assert_eq!(bytes, Bytes::from_slice(&env, b"0"));
     }
 
-    // bytes_to_i128 tests
     #[test]
-    fn test_bytes_to_i128_zero() {
+    fn test_i256_to_bytes_positive() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"0");
-        assert_eq!(bytes_to_i128(&bytes), Some(0));
+        let n = I256::from_i32(&env, 12345);
+        let bytes = i256_to_bytes(&env, &n);
+        assert_eq!(bytes, Bytes::from_slice(&env, b"12345"));
     }
 
     #[test]
-    fn test_bytes_to_i128_positive() {
+    fn test_i256_to_bytes_negative() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"42");
-        assert_eq!(bytes_to_i128(&bytes), Some(42));
+        let n = I256::from_i32(&env, -12345);
+        let bytes = i256_to_bytes(&env, &n);
+        assert_eq!(bytes, Bytes::from_slice(&env, b"-12345"));
     }
 
+    // u256_to_hex tests
     #[test]
-    fn test_bytes_to_i128_negative() {
+    fn test_u256_to_hex_zero() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"-42");
-        assert_eq!(bytes_to_i128(&bytes), Some(-42));
+        let n = U256::from_u32(&env, 0);
+        let bytes = u256_to_hex(&env, &n);
+        assert_eq!(bytes, Bytes::from_slice(&env, b"0x0"));
     }
 
     #[test]
-    fn test_bytes_to_i128_max() {
+    fn test_u256_to_hex_simple() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"170141183460469231731687303715884105727");
-        assert_eq!(bytes_to_i128(&bytes), Some(i128::MAX));
+        let n = U256::from_u32(&env, 255);
+        let bytes = u256_to_hex(&env, &n);
+        assert_eq!(bytes, Bytes::from_slice(&env, b"0xff"));
     }
 
     #[test]
-    fn test_bytes_to_i128_min() {
+    fn test_u256_to_hex_large() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"-170141183460469231731687303715884105728");
-        assert_eq!(bytes_to_i128(&bytes), Some(i128::MIN));
+        let n = U256::from_u32(&env, 0xDEADBEEF);
+        let bytes = u256_to_hex(&env, &n);
+        assert_eq!(bytes, Bytes::from_slice(&env, b"0xdeadbeef"));
     }
 
+    // i256_to_hex tests
     #[test]
-    fn test_bytes_to_i128_overflow_positive() {
+    fn test_i256_to_hex_zero() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"170141183460469231731687303715884105728"); // i128::MAX + 1
-        assert_eq!(bytes_to_i128(&bytes), None);
+        let n = I256::from_i32(&env, 0);
+        let bytes = i256_to_hex(&env, &n);
+        assert_eq!(bytes, Bytes::from_slice(&env, b"0x0"));
     }
 
     #[test]
-    fn test_bytes_to_i128_overflow_negative() {
+    fn test_i256_to_hex_positive() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"-170141183460469231731687303715884105729"); // i128::MIN - 1
-        assert_eq!(bytes_to_i128(&bytes), None);
+        let n = I256::from_i32(&env, 255);
+        let bytes = i256_to_hex(&env, &n);
+        assert_eq!(bytes, Bytes::from_slice(&env, b"0xff"));
     }
 
-    // ==========================================================================
-    // Hex conversion tests
-    // ==========================================================================
-
-    // u32_to_hex tests
     #[test]
-    fn test_u32_to_hex_zero() {
+    fn test_i256_to_hex_negative() {
         let env = Env::default();
-        let bytes = u32_to_hex(&env, 0);
-        assert_eq!(bytes, Bytes::from_slice(&env, b"0x0"));
+        let n = I256::from_i32(&env, -255);
+        let bytes = i256_to_hex(&env, &n);
+        assert_eq!(bytes, Bytes::from_slice(&env, b"-0xff"));
     }
 
+    // bytes_to_u256 tests
     #[test]
-    fn test_u32_to_hex_simple() {
+    fn test_bytes_to_u256_zero() {
         let env = Env::default();
-        let bytes = u32_to_hex(&env, 255);
-        assert_eq!(bytes, Bytes::from_slice(&env, b"0xff"));
+        let bytes = Bytes::from_slice(&env, b"0");
+        let n = bytes_to_u256(&env, &bytes);
+        assert!(n.is_some());
+        let result = u256_to_bytes(&env, &n.unwrap());
+        assert_eq!(result, Bytes::from_slice(&env, b"0"));
     }
 
     #[test]
-    fn test_u32_to_hex_max() {
+    fn test_bytes_to_u256_simple() {
         let env = Env::default();
-        let bytes = u32_to_hex(&env, u32::MAX);
-        assert_eq!(bytes, Bytes::from_slice(&env, b"0xffffffff"));
+        let bytes = Bytes::from_slice(&env, b"12345");
+        let n = bytes_to_u256(&env, &bytes);
+        assert!(n.is_some());
+        let result = u256_to_bytes(&env, &n.unwrap());
+        assert_eq!(result, Bytes::from_slice(&env, b"12345"));
     }
 
-    // i32_to_hex tests
     #[test]
-    fn test_i32_to_hex_zero() {
+    fn test_bytes_to_u256_empty() {
         let env = Env::default();
-        let bytes = i32_to_hex(&env, 0);
-        assert_eq!(bytes, Bytes::from_slice(&env, b"0x0"));
+        let bytes = Bytes::from_slice(&env, b"");
+        assert_eq!(bytes_to_u256(&env, &bytes), None);
     }
 
     #[test]
-    fn test_i32_to_hex_positive() {
+    fn test_bytes_to_u256_invalid() {
         let env = Env::default();
-        let bytes = i32_to_hex(&env, 255);
-        assert_eq!(bytes, Bytes::from_slice(&env, b"0xff"));
+        let bytes = Bytes::from_slice(&env, b"abc");
+        assert_eq!(bytes_to_u256(&env, &bytes), None);
     }
 
+    // bytes_to_i256 tests
     #[test]
-    fn test_i32_to_hex_negative() {
+    fn test_bytes_to_i256_positive() {
         let env = Env::default();
-        let bytes = i32_to_hex(&env, -255);
-        assert_eq!(bytes, Bytes::from_slice(&env, b"-0xff"));
+        let bytes = Bytes::from_slice(&env, b"12345");
+        let n = bytes_to_i256(&env, &bytes);
+        assert!(n.is_some());
+        let result = i256_to_bytes(&env, &n.unwrap());
+        assert_eq!(result, Bytes::from_slice(&env, b"12345"));
     }
 
-    // u64_to_hex tests
     #[test]
-    fn test_u64_to_hex_zero() {
+    fn test_bytes_to_i256_negative() {
         let env = Env::default();
-        let bytes = u64_to_hex(&env, 0);
-        assert_eq!(bytes, Bytes::from_slice(&env, b"0x0"));
+        let bytes = Bytes::from_slice(&env, b"-12345");
+        let n = bytes_to_i256(&env, &bytes);
+        assert!(n.is_some());
+        let result = i256_to_bytes(&env, &n.unwrap());
+        assert_eq!(result, Bytes::from_slice(&env, b"-12345"));
     }
 
+    // hex_to_u256 tests
     #[test]
-    fn test_u64_to_hex_simple() {
+    fn test_hex_to_u256_simple() {
         let env = Env::default();
-        let bytes = u64_to_hex(&env, 0xDEADBEEF);
-        assert_eq!(bytes, Bytes::from_slice(&env, b"0xdeadbeef"));
+        let bytes = Bytes::from_slice(&env, b"0xff");
+        let n = hex_to_u256(&env, &bytes);
+        assert!(n.is_some());
+        let result = u256_to_hex(&env, &n.unwrap());
+        assert_eq!(result, Bytes::from_slice(&env, b"0xff"));
     }
 
     #[test]
-    fn test_u64_to_hex_max() {
+    fn test_hex_to_u256_no_prefix() {
         let env = Env::default();
-        let bytes = u64_to_hex(&env, u64::MAX);
-        assert_eq!(bytes, Bytes::from_slice(&env, b"0xffffffffffffffff"));
+        let bytes = Bytes::from_slice(&env, b"ff");
+        let n = hex_to_u256(&env, &bytes);
+        assert!(n.is_some());
+        let result = u256_to_hex(&env, &n.unwrap());
+        assert_eq!(result, Bytes::from_slice(&env, b"0xff"));
     }
 
-    // i64_to_hex tests
     #[test]
-    fn test_i64_to_hex_negative() {
+    fn test_hex_to_u256_empty() {
         let env = Env::default();
-        let bytes = i64_to_hex(&env, -0xFF);
-        assert_eq!(bytes, Bytes::from_slice(&env, b"-0xff"));
+        let bytes = Bytes::from_slice(&env, b"");
+        assert_eq!(hex_to_u256(&env, &bytes), None);
     }
 
-    // u128_to_hex tests
+    // hex_to_i256 tests
     #[test]
-    fn test_u128_to_hex_zero() {
+    fn test_hex_to_i256_positive() {
         let env = Env::default();
-        let bytes = u128_to_hex(&env, 0);
-        assert_eq!(bytes, Bytes::from_slice(&env, b"0x0"));
+        let bytes = Bytes::from_slice(&env, b"0xff");
+        let n = hex_to_i256(&env, &bytes);
+        assert!(n.is_some());
+        let result = i256_to_hex(&env, &n.unwrap());
+        assert_eq!(result, Bytes::from_slice(&env, b"0xff"));
     }
 
     #[test]
-    fn test_u128_to_hex_simple() {
+    fn test_hex_to_i256_negative() {
         let env = Env::default();
-        let bytes = u128_to_hex(&env, 255);
-        assert_eq!(bytes, Bytes::from_slice(&env, b"0xff"));
+        let bytes = Bytes::from_slice(&env, b"-0xff");
+        let n = hex_to_i256(&env, &bytes);
+        assert!(n.is_some());
+        let result = i256_to_hex(&env, &n.unwrap());
+        assert_eq!(result, Bytes::from_slice(&env, b"-0xff"));
     }
 
-    // i128_to_hex tests
-    #[test]
-    fn test_i128_to_hex_negative() {
-        let env = Env::default();
-        let bytes = i128_to_hex(&env, -255);
-        assert_eq!(bytes, Bytes::from_slice(&env, b"-0xff"));
-    }
+    // ==========================================================================
+    // String convenience wrapper tests
+    // ==========================================================================
 
     #[test]
-    fn test_i128_to_hex_min() {
+    fn test_string_to_u32() {
         let env = Env::default();
-        let bytes = i128_to_hex(&env, i128::MIN);
-        // i128::MIN = -170141183460469231731687303715884105728 = -0x80000000000000000000000000000000
-        assert_eq!(bytes.len(), 35); // "-0x" (3) + 32 hex digits = 35
+        let s = String::from_str(&env, "12345");
+        assert_eq!(string_to_u32(&env, &s), Some(12345));
     }
 
-    // hex_to_u32 tests
     #[test]
-    fn test_hex_to_u32_simple() {
+    fn test_string_to_u32_invalid() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"0xff");
-        assert_eq!(hex_to_u32(&bytes), Some(255));
+        let s = String::from_str(&env, "abc");
+        assert_eq!(string_to_u32(&env, &s), None);
     }
 
     #[test]
-    fn test_hex_to_u32_no_prefix() {
+    fn test_string_to_i32() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"ff");
-        assert_eq!(hex_to_u32(&bytes), Some(255));
+        let s = String::from_str(&env, "-12345");
+        assert_eq!(string_to_i32(&env, &s), Some(-12345));
     }
 
     #[test]
-    fn test_hex_to_u32_uppercase() {
+    fn test_string_to_u64() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"0XFF");
-        assert_eq!(hex_to_u32(&bytes), Some(255));
+        let s = String::from_str(&env, "12345678901234");
+        assert_eq!(string_to_u64(&env, &s), Some(12345678901234));
     }
 
     #[test]
-    fn test_hex_to_u32_mixed_case() {
+    fn test_string_to_i64() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"0xDeAdBeEf");
-        assert_eq!(hex_to_u32(&bytes), Some(0xDEADBEEF));
+        let s = String::from_str(&env, "-12345678901234");
+        assert_eq!(string_to_i64(&env, &s), Some(-12345678901234));
     }
 
     #[test]
-    fn test_hex_to_u32_max() {
+    fn test_string_to_u128() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"0xffffffff");
-        assert_eq!(hex_to_u32(&bytes), Some(u32::MAX));
+        let s = String::from_str(&env, "12345678901234567890");
+        assert_eq!(string_to_u128(&env, &s), Some(12345678901234567890));
     }
 
     #[test]
-    fn test_hex_to_u32_overflow() {
+    fn test_string_to_i128() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"0x100000000"); // u32::MAX + 1
-        assert_eq!(hex_to_u32(&bytes), None);
+        let s = String::from_str(&env, "-12345678901234567890");
+        assert_eq!(string_to_i128(&env, &s), Some(-12345678901234567890));
     }
 
     #[test]
-    fn test_hex_to_u32_invalid() {
+    fn test_string_to_u256() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"0xgg");
-        assert_eq!(hex_to_u32(&bytes), None);
+        let s = String::from_str(&env, "12345");
+        let n = string_to_u256(&env, &s);
+        assert!(n.is_some());
+        let result = u256_to_bytes(&env, &n.unwrap());
+        assert_eq!(result, Bytes::from_slice(&env, b"12345"));
     }
 
     #[test]
-    fn test_hex_to_u32_empty() {
+    fn test_string_to_i256() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"");
-        assert_eq!(hex_to_u32(&bytes), None);
+        let s = String::from_str(&env, "-12345");
+        let n = string_to_i256(&env, &s);
+        assert!(n.is_some());
+        let result = i256_to_bytes(&env, &n.unwrap());
+        assert_eq!(result, Bytes::from_slice(&env, b"-12345"));
     }
 
+    // ==========================================================================
+    // &str convenience wrapper tests
+    // ==========================================================================
+
     #[test]
-    fn test_hex_to_u32_just_prefix() {
+    fn test_str_to_u32() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"0x");
-        assert_eq!(hex_to_u32(&bytes), None);
+        assert_eq!(str_to_u32(&env, "12345"), Some(12345));
     }
 
-    // hex_to_i32 tests
     #[test]
-    fn test_hex_to_i32_positive() {
+    fn test_str_to_u32_invalid() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"0xff");
-        assert_eq!(hex_to_i32(&bytes), Some(255));
+        assert_eq!(str_to_u32(&env, "abc"), None);
     }
 
     #[test]
-    fn test_hex_to_i32_negative() {
+    fn test_str_to_i32() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"-0xff");
-        assert_eq!(hex_to_i32(&bytes), Some(-255));
+        assert_eq!(str_to_i32(&env, "-12345"), Some(-12345));
     }
 
     #[test]
-    fn test_hex_to_i32_negative_no_prefix() {
+    fn test_str_to_u64() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"-ff");
-        assert_eq!(hex_to_i32(&bytes), Some(-255));
+        assert_eq!(str_to_u64(&env, "12345678901234"), Some(12345678901234));
     }
 
-    // hex_to_u64 tests
     #[test]
-    fn test_hex_to_u64_simple() {
+    fn test_str_to_i64() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"0xdeadbeef");
-        assert_eq!(hex_to_u64(&bytes), Some(0xDEADBEEF));
+        assert_eq!(str_to_i64(&env, "-12345678901234"), Some(-12345678901234));
     }
 
     #[test]
-    fn test_hex_to_u64_max() {
+    fn test_str_to_u128() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"0xffffffffffffffff");
-        assert_eq!(hex_to_u64(&bytes), Some(u64::MAX));
+        assert_eq!(
+            str_to_u128(&env, "12345678901234567890"),
+            Some(12345678901234567890)
+        );
     }
 
-    // hex_to_i64 tests
     #[test]
-    fn test_hex_to_i64_negative() {
+    fn test_str_to_i128() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"-0xff");
-        assert_eq!(hex_to_i64(&bytes), Some(-255));
+        assert_eq!(
+            str_to_i128(&env, "-12345678901234567890"),
+            Some(-12345678901234567890)
+        );
     }
 
-    // hex_to_u128 tests
     #[test]
-    fn test_hex_to_u128_simple() {
+    fn test_str_to_u256() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"0xff");
-        assert_eq!(hex_to_u128(&bytes), Some(255));
+        let n = str_to_u256(&env, "12345");
+        assert!(n.is_some());
+        let result = u256_to_bytes(&env, &n.unwrap());
+        assert_eq!(result, Bytes::from_slice(&env, b"12345"));
     }
 
     #[test]
-    fn test_hex_to_u128_large() {
+    fn test_str_to_i256() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"0xffffffffffffffffffffffffffffffff");
-        assert_eq!(hex_to_u128(&bytes), Some(u128::MAX));
+        let n = str_to_i256(&env, "-12345");
+        assert!(n.is_some());
+        let result = i256_to_bytes(&env, &n.unwrap());
+        assert_eq!(result, Bytes::from_slice(&env, b"-12345"));
     }
 
-    // hex_to_i128 tests
     #[test]
-    fn test_hex_to_i128_positive() {
+    fn test_str_to_u256_large() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"0xff");
-        assert_eq!(hex_to_i128(&bytes), Some(255));
+        // Test with a larger number
+        let n = str_to_u256(
+            &env,
+            "115792089237316195423570985008687907853269984665640564039457584007913129639935",
+        );
+        assert!(n.is_some());
     }
 
     #[test]
-    fn test_hex_to_i128_negative() {
+    fn test_str_to_u32_empty() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"-0xff");
-        assert_eq!(hex_to_i128(&bytes), Some(-255));
+        assert_eq!(str_to_u32(&env, ""), None);
     }
 
     // ==========================================================================
-    // U256/I256 conversion tests
+    // address_to_bytes tests
     // ==========================================================================
 
-    // u256_to_bytes tests
     #[test]
-    fn test_u256_to_bytes_zero() {
+    fn test_address_to_bytes_returns_56_chars() {
         let env = Env::default();
-        let n = U256::from_u32(&env, 0);
-        let bytes = u256_to_bytes(&env, &n);
-        assert_eq!(bytes, Bytes::from_slice(&env, b"0"));
+        use soroban_sdk::testutils::Address as _;
+        let addr = Address::generate(&env);
+        let bytes = address_to_bytes(&env, &addr);
+        // Stellar contract addresses are 56 characters
+        assert_eq!(bytes.len(), 56);
     }
 
     #[test]
-    fn test_u256_to_bytes_simple() {
+    fn test_address_to_bytes_valid_format() {
         let env = Env::default();
-        let n = U256::from_u32(&env, 12345);
-        let bytes = u256_to_bytes(&env, &n);
-        assert_eq!(bytes, Bytes::from_slice(&env, b"12345"));
+        use soroban_sdk::testutils::Address as _;
+        // Generated addresses start with either 'G' (account) or 'C' (contract)
+        let addr = Address::generate(&env);
+        let bytes = address_to_bytes(&env, &addr);
+        // Address should start with valid prefix (C for contract or G for account)
+        let first_byte = bytes.get(0).unwrap();
+        assert!(first_byte == b'C' || first_byte == b'G');
     }
 
-    #[test]
-    fn test_u256_to_bytes_large() {
-        let env = Env::default();
-        // Use from_u128 for a larger number
-        let n = U256::from_u128(&env, 123456789012345678901234567890);
-        let bytes = u256_to_bytes(&env, &n);
-        assert_eq!(
-            bytes,
-            Bytes::from_slice(&env, b"123456789012345678901234567890")
-        );
-    }
+    // ==========================================================================
+    // symbol_to_bytes tests
+    // ==========================================================================
 
-    // i256_to_bytes tests
-    #[test]
-    fn test_i256_to_bytes_zero() {
-        let env = Env::default();
-        let n = I256::from_i32(&env, 0);
-        let bytes = i256_to_bytes(&env, &n);
-        assert_eq!(bytes, Bytes::from_slice(&env, b"0"));
-    }
+    // Note: symbol_to_bytes works with short symbols that have tag=14 in their
+    // internal representation. The tests below verify the decode_symbol_char
+    // helper function which is the core of the decoding logic.
 
     #[test]
-    fn test_i256_to_bytes_positive() {
+    fn test_symbol_to_bytes_returns_bytes() {
         let env = Env::default();
-        let n = I256::from_i32(&env, 12345);
-        let bytes = i256_to_bytes(&env, &n);
-        assert_eq!(bytes, Bytes::from_slice(&env, b"12345"));
+        use soroban_sdk::symbol_short;
+        let sym = symbol_short!("theme");
+        let bytes = symbol_to_bytes(&env, &sym);
+        // Just verify the function doesn't panic and returns valid Bytes
+        // The actual content depends on internal symbol representation
+        assert!(bytes.len() <= 9); // Max 9 chars for short symbols
     }
 
     #[test]
-    fn test_i256_to_bytes_negative() {
-        let env = Env::default();
-        let n = I256::from_i32(&env, -12345);
-        let bytes = i256_to_bytes(&env, &n);
-        assert_eq!(bytes, Bytes::from_slice(&env, b"-12345"));
+    fn test_decode_symbol_char_null() {
+        // 0 -> 0 (null/end marker)
+        assert_eq!(decode_symbol_char(0), 0);
     }
 
-    // u256_to_hex tests
     #[test]
-    fn test_u256_to_hex_zero() {
-        let env = Env::default();
-        let n = U256::from_u32(&env, 0);
-        let bytes = u256_to_hex(&env, &n);
-        assert_eq!(bytes, Bytes::from_slice(&env, b"0x0"));
+    fn test_decode_symbol_char_underscore() {
+        // 1 -> '_'
+        assert_eq!(decode_symbol_char(1), b'_');
     }
 
     #[test]
-    fn test_u256_to_hex_simple() {
-        let env = Env::default();
-        let n = U256::from_u32(&env, 255);
-        let bytes = u256_to_hex(&env, &n);
-        assert_eq!(bytes, Bytes::from_slice(&env, b"0xff"));
+    fn test_decode_symbol_char_digits() {
+        // 2-11 -> '0'-'9'
+        assert_eq!(decode_symbol_char(2), b'0');
+        assert_eq!(decode_symbol_char(6), b'4');
+        assert_eq!(decode_symbol_char(11), b'9');
     }
 
     #[test]
-    fn test_u256_to_hex_large() {
-        let env = Env::default();
-        let n = U256::from_u32(&env, 0xDEADBEEF);
-        let bytes = u256_to_hex(&env, &n);
-        assert_eq!(bytes, Bytes::from_slice(&env, b"0xdeadbeef"));
+    fn test_decode_symbol_char_uppercase() {
+        // 12-37 -> 'A'-'Z'
+        assert_eq!(decode_symbol_char(12), b'A');
+        assert_eq!(decode_symbol_char(24), b'M');
+        assert_eq!(decode_symbol_char(37), b'Z');
     }
 
-    // i256_to_hex tests
     #[test]
-    fn test_i256_to_hex_zero() {
-        let env = Env::default();
-        let n = I256::from_i32(&env, 0);
-        let bytes = i256_to_hex(&env, &n);
-        assert_eq!(bytes, Bytes::from_slice(&env, b"0x0"));
+    fn test_decode_symbol_char_lowercase() {
+        // 38-63 -> 'a'-'z'
+        assert_eq!(decode_symbol_char(38), b'a');
+        assert_eq!(decode_symbol_char(50), b'm');
+        assert_eq!(decode_symbol_char(63), b'z');
     }
 
     #[test]
-    fn test_i256_to_hex_positive() {
-        let env = Env::default();
-        let n = I256::from_i32(&env, 255);
-        let bytes = i256_to_hex(&env, &n);
-        assert_eq!(bytes, Bytes::from_slice(&env, b"0xff"));
+    fn test_decode_symbol_char_out_of_range() {
+        // Out of range -> 0
+        assert_eq!(decode_symbol_char(64), 0);
+        assert_eq!(decode_symbol_char(100), 0);
+        assert_eq!(decode_symbol_char(255), 0);
     }
 
     #[test]
-    fn test_i256_to_hex_negative() {
+    fn test_bytes_hash_64_known_vectors() {
         let env = Env::default();
-        let n = I256::from_i32(&env, -255);
-        let bytes = i256_to_hex(&env, &n);
-        assert_eq!(bytes, Bytes::from_slice(&env, b"-0xff"));
+        // Reference FNV-1a 64 values
+        assert_eq!(bytes_hash_64(&env, &Bytes::new(&env)), 0xcbf29ce484222325);
+        assert_eq!(
+            bytes_hash_64(&env, &Bytes::from_slice(&env, b"a")),
+            0xaf63dc4c8601ec8c
+        );
+        assert_eq!(
+            bytes_hash_64(&env, &Bytes::from_slice(&env, b"foobar")),
+            0x85944171f73967e8
+        );
     }
 
-    // bytes_to_u256 tests
     #[test]
-    fn test_bytes_to_u256_zero() {
+    fn test_bytes_hash_64_stable() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"0");
-        let n = bytes_to_u256(&env, &bytes);
-        assert!(n.is_some());
-        let result = u256_to_bytes(&env, &n.unwrap());
-        assert_eq!(result, Bytes::from_slice(&env, b"0"));
+        let data = Bytes::from_slice(&env, "hello world ".repeat(20).as_bytes());
+        let first = bytes_hash_64(&env, &data);
+        let second = bytes_hash_64(&env, &data);
+        assert_eq!(first, second);
+
+        // Same content built separately hashes identically
+        let other = Bytes::from_slice(&env, "hello world ".repeat(20).as_bytes());
+        assert_eq!(bytes_hash_64(&env, &other), first);
     }
 
     #[test]
-    fn test_bytes_to_u256_simple() {
+    fn test_bytes_hash_64_differs() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"12345");
-        let n = bytes_to_u256(&env, &bytes);
-        assert!(n.is_some());
-        let result = u256_to_bytes(&env, &n.unwrap());
-        assert_eq!(result, Bytes::from_slice(&env, b"12345"));
+        let a = Bytes::from_slice(&env, "x".repeat(100).as_bytes());
+        let mut b_content = "x".repeat(100);
+        b_content.replace_range(99..100, "y");
+        let b = Bytes::from_slice(&env, b_content.as_bytes());
+        assert_ne!(bytes_hash_64(&env, &a), bytes_hash_64(&env, &b));
     }
 
     #[test]
-    fn test_bytes_to_u256_empty() {
+    fn test_bytes_eq_equal() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"");
-        assert_eq!(bytes_to_u256(&env, &bytes), None);
+        assert!(bytes_eq(&Bytes::new(&env), &Bytes::new(&env)));
+        let a = Bytes::from_slice(&env, "abc".repeat(50).as_bytes());
+        let b = Bytes::from_slice(&env, "abc".repeat(50).as_bytes());
+        assert!(bytes_eq(&a, &b));
     }
 
     #[test]
-    fn test_bytes_to_u256_invalid() {
+    fn test_bytes_eq_unequal() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"abc");
-        assert_eq!(bytes_to_u256(&env, &bytes), None);
+        let a = Bytes::from_slice(&env, b"hello");
+        let b = Bytes::from_slice(&env, b"hellp");
+        let c = Bytes::from_slice(&env, b"hello!");
+        assert!(!bytes_eq(&a, &b));
+        assert!(!bytes_eq(&a, &c));
+
+        // Difference in a later chunk
+        let mut long = "z".repeat(70);
+        let d = Bytes::from_slice(&env, long.as_bytes());
+        long.replace_range(65..66, "q");
+        let e = Bytes::from_slice(&env, long.as_bytes());
+        assert!(!bytes_eq(&d, &e));
     }
 
-    // bytes_to_i256 tests
     #[test]
-    fn test_bytes_to_i256_positive() {
+    fn test_bytes_eq_cheaper_than_per_byte() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"12345");
-        let n = bytes_to_i256(&env, &bytes);
-        assert!(n.is_some());
-        let result = i256_to_bytes(&env, &n.unwrap());
-        assert_eq!(result, Bytes::from_slice(&env, b"12345"));
+        let content = "0123456789".repeat(100);
+        let a = Bytes::from_slice(&env, content.as_bytes());
+        let b = Bytes::from_slice(&env, content.as_bytes());
+
+        let mut budget = env.cost_estimate().budget();
+        budget.reset_unlimited();
+        assert!(bytes_eq(&a, &b));
+        let chunked = budget.cpu_instruction_cost();
+
+        budget.reset_unlimited();
+        let mut same = true;
+        for i in 0..a.len() {
+            if a.get(i) != b.get(i) {
+                same = false;
+            }
+        }
+        assert!(same);
+        let per_byte = budget.cpu_instruction_cost();
+
+        assert!(chunked < per_byte);
     }
 
     #[test]
-    fn test_bytes_to_i256_negative() {
+    fn test_bytes_to_string_roundtrip() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"-12345");
-        let n = bytes_to_i256(&env, &bytes);
-        assert!(n.is_some());
-        let result = i256_to_bytes(&env, &n.unwrap());
-        assert_eq!(result, Bytes::from_slice(&env, b"-12345"));
+        assert_eq!(bytes_to_string(&env, &Bytes::new(&env)).len(), 0);
+
+        for n in [5usize, 300, 2000, 10000] {
+            let content = "a".repeat(n);
+            let bytes = Bytes::from_slice(&env, content.as_bytes());
+            let s = bytes_to_string(&env, &bytes);
+            assert_eq!(s, String::from_str(&env, &content));
+            assert_eq!(string_to_bytes(&env, &s), bytes);
+        }
     }
 
-    // hex_to_u256 tests
     #[test]
-    fn test_hex_to_u256_simple() {
+    fn test_bytes_to_string_exceeds_limit() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"0xff");
-        let n = hex_to_u256(&env, &bytes);
-        assert!(n.is_some());
-        let result = u256_to_hex(&env, &n.unwrap());
-        assert_eq!(result, Bytes::from_slice(&env, b"0xff"));
+        let content = "a".repeat(MAX_STRING_SIZE + 1);
+        let bytes = Bytes::from_slice(&env, content.as_bytes());
+        assert_eq!(
+            bytes_to_string(&env, &bytes),
+            String::from_str(&env, "[content exceeds 16KB limit]")
+        );
     }
 
     #[test]
-    fn test_hex_to_u256_no_prefix() {
+    fn test_percent_encode() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"ff");
-        let n = hex_to_u256(&env, &bytes);
-        assert!(n.is_some());
-        let result = u256_to_hex(&env, &n.unwrap());
-        assert_eq!(result, Bytes::from_slice(&env, b"0xff"));
+        let encode = |s: &[u8]| percent_encode(&env, &Bytes::from_slice(&env, s));
+        assert_eq!(
+            encode(b"abc-_.~XYZ09"),
+            Bytes::from_slice(&env, b"abc-_.~XYZ09")
+        );
+        assert_eq!(
+            encode(b"hello world/1?"),
+            Bytes::from_slice(&env, b"hello%20world%2F1%3F")
+        );
+        assert_eq!(
+            encode("caf\u{e9}".as_bytes()),
+            Bytes::from_slice(&env, b"caf%C3%A9")
+        );
+        assert_eq!(encode(b""), Bytes::new(&env));
+    }
+
+    #[test]
+    fn test_format_relative_time_thresholds() {
+        let env = Env::default();
+        let now = 1_000_000_000u64;
+        let cases: &[(u64, &[u8])] = &[
+            (0, b"just now"),
+            (59, b"just now"),
+            (60, b"1 minute ago"),
+            (119, b"1 minute ago"),
+            (120, b"2 minutes ago"),
+            (3599, b"59 minutes ago"),
+            (3600, b"1 hour ago"),
+            (7200, b"2 hours ago"),
+            (86_399, b"23 hours ago"),
+            (86_400, b"1 day ago"),
+            (172_800, b"2 days ago"),
+            (31_535_999, b"364 days ago"),
+            (31_536_000, b"1 year ago"),
+            (63_072_000, b"2 years ago"),
+        ];
+        for (delta, expected) in cases {
+            assert_eq!(
+                format_relative_time(&env, now, now - delta),
+                Bytes::from_slice(&env, expected),
+                "delta {}",
+                delta
+            );
+        }
     }
 
     #[test]
-    fn test_hex_to_u256_empty() {
+    fn test_format_relative_time_edges() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"");
-        assert_eq!(hex_to_u256(&env, &bytes), None);
+        // A timestamp in the future is treated as "just now".
+        assert_eq!(
+            format_relative_time(&env, 100, 200),
+            Bytes::from_slice(&env, b"just now")
+        );
+        assert_eq!(
+            format_relative_time(&env, u64::MAX, 0),
+            Bytes::from_slice(&env, b"584942417355 years ago")
+        );
     }
 
-    // hex_to_i256 tests
     #[test]
-    fn test_hex_to_i256_positive() {
+    fn test_format_timestamp_epoch() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"0xff");
-        let n = hex_to_i256(&env, &bytes);
-        assert!(n.is_some());
-        let result = i256_to_hex(&env, &n.unwrap());
-        assert_eq!(result, Bytes::from_slice(&env, b"0xff"));
+        assert_eq!(
+            format_timestamp(&env, 0),
+            Bytes::from_slice(&env, b"1970-01-01 00:00:00 UTC")
+        );
+        assert_eq!(format_date(&env, 0), Bytes::from_slice(&env, b"1970-01-01"));
     }
 
     #[test]
-    fn test_hex_to_i256_negative() {
+    fn test_format_timestamp_leap_day() {
         let env = Env::default();
-        let bytes = Bytes::from_slice(&env, b"-0xff");
-        let n = hex_to_i256(&env, &bytes);
-        assert!(n.is_some());
-        let result = i256_to_hex(&env, &n.unwrap());
-        assert_eq!(result, Bytes::from_slice(&env, b"-0xff"));
+        // 2024-02-29 12:34:56 UTC
+        assert_eq!(
+            format_timestamp(&env, 1_709_210_096),
+            Bytes::from_slice(&env, b"2024-02-29 12:34:56 UTC")
+        );
+        // One day later rolls into March.
+        assert_eq!(
+            format_date(&env, 1_709_210_096 + 86_400),
+            Bytes::from_slice(&env, b"2024-03-01")
+        );
+        // 2000 was a leap year (divisible by 400).
+        assert_eq!(
+            format_date(&env, 951_782_400),
+            Bytes::from_slice(&env, b"2000-02-29")
+        );
     }
 
-    // ==========================================================================
-    // String convenience wrapper tests
-    // ==========================================================================
-
     #[test]
-    fn test_string_to_u32() {
+    fn test_format_timestamp_after_2100() {
         let env = Env::default();
-        let s = String::from_str(&env, "12345");
-        assert_eq!(string_to_u32(&env, &s), Some(12345));
+        // 2100 is not a leap year: Feb 28 is followed by Mar 1.
+        assert_eq!(
+            format_timestamp(&env, 4_107_542_399),
+            Bytes::from_slice(&env, b"2100-02-28 23:59:59 UTC")
+        );
+        assert_eq!(
+            format_timestamp(&env, 4_107_542_400),
+            Bytes::from_slice(&env, b"2100-03-01 00:00:00 UTC")
+        );
+        assert_eq!(
+            format_date(&env, 4_133_980_799),
+            Bytes::from_slice(&env, b"2100-12-31")
+        );
     }
 
     #[test]
-    fn test_string_to_u32_invalid() {
+    fn test_format_timestamp_max() {
         let env = Env::default();
-        let s = String::from_str(&env, "abc");
-        assert_eq!(string_to_u32(&env, &s), None);
+        // Must not overflow or run past the stack buffer.
+        let out = format_timestamp(&env, u64::MAX);
+        assert!(out.len() > 23);
+        let date = format_date(&env, u64::MAX);
+        assert_eq!(date.len() + 13, out.len());
     }
 
     #[test]
-    fn test_string_to_i32() {
+    fn test_truncate_bytes_fits() {
         let env = Env::default();
-        let s = String::from_str(&env, "-12345");
-        assert_eq!(string_to_i32(&env, &s), Some(-12345));
+        let input = Bytes::from_slice(&env, b"short");
+        assert_eq!(truncate_bytes(&env, &input, 5), input);
+        assert_eq!(truncate_bytes(&env, &input, 140), input);
+        assert_eq!(truncate_bytes(&env, &Bytes::new(&env), 0), Bytes::new(&env));
     }
 
     #[test]
-    fn test_string_to_u64() {
+    fn test_truncate_bytes_ascii() {
         let env = Env::default();
-        let s = String::from_str(&env, "12345678901234");
-        assert_eq!(string_to_u64(&env, &s), Some(12345678901234));
+        let input = Bytes::from_slice(&env, b"A long post body");
+        assert_eq!(
+            truncate_bytes(&env, &input, 6),
+            Bytes::from_slice(&env, "A long\u{2026}".as_bytes())
+        );
+        assert_eq!(
+            truncate_bytes(&env, &input, 0),
+            Bytes::from_slice(&env, "\u{2026}".as_bytes())
+        );
     }
 
     #[test]
-    fn test_string_to_i64() {
+    fn test_truncate_bytes_multibyte_boundary() {
         let env = Env::default();
-        let s = String::from_str(&env, "-12345678901234");
-        assert_eq!(string_to_i64(&env, &s), Some(-12345678901234));
+        // "caf" + "é" (2 bytes) + "!" ; a cut at 4 lands inside "é".
+        let input = Bytes::from_slice(&env, "café!".as_bytes());
+        assert_eq!(
+            truncate_bytes(&env, &input, 4),
+            Bytes::from_slice(&env, "caf\u{2026}".as_bytes())
+        );
+        assert_eq!(
+            truncate_bytes(&env, &input, 5),
+            Bytes::from_slice(&env, "café\u{2026}".as_bytes())
+        );
+
+        // "a" + "☕" (3 bytes): cuts at 2 and 3 both back up to 1.
+        let input = Bytes::from_slice(&env, "a☕b".as_bytes());
+        for max in [2, 3] {
+            assert_eq!(
+                truncate_bytes(&env, &input, max),
+                Bytes::from_slice(&env, "a\u{2026}".as_bytes())
+            );
+        }
     }
 
     #[test]
-    fn test_string_to_u128() {
+    fn test_truncate_string() {
         let env = Env::default();
-        let s = String::from_str(&env, "12345678901234567890");
-        assert_eq!(string_to_u128(&env, &s), Some(12345678901234567890));
+        let s = String::from_str(&env, "日本語テキスト");
+        // Each character is 3 bytes; 7 bytes keeps two characters.
+        assert_eq!(
+            truncate_string(&env, &s, 7),
+            Bytes::from_slice(&env, "日本\u{2026}".as_bytes())
+        );
     }
 
     #[test]
-    fn test_string_to_i128() {
+    fn test_bytes_find() {
         let env = Env::default();
-        let s = String::from_str(&env, "-12345678901234567890");
-        assert_eq!(string_to_i128(&env, &s), Some(-12345678901234567890));
+        let text = Bytes::from_slice(&env, b"hello @alice and @bob");
+        assert_eq!(bytes_find(&text, b"hello"), Some(0));
+        assert_eq!(bytes_find(&text, b"@"), Some(6));
+        assert_eq!(bytes_find(&text, b"@bob"), Some(17));
+        assert_eq!(bytes_find(&text, b"bob"), Some(18));
+        assert_eq!(bytes_find(&text, b"carol"), None);
+        assert_eq!(bytes_find(&text, b""), Some(0));
+        assert_eq!(bytes_find(&Bytes::new(&env), b"a"), None);
+        assert!(bytes_contains(&text, b"and"));
+        assert!(!bytes_contains(&text, b"AND"));
     }
 
     #[test]
-    fn test_string_to_u256() {
+    fn test_bytes_find_across_windows() {
         let env = Env::default();
-        let s = String::from_str(&env, "12345");
-        let n = string_to_u256(&env, &s);
-        assert!(n.is_some());
-        let result = u256_to_bytes(&env, &n.unwrap());
-        assert_eq!(result, Bytes::from_slice(&env, b"12345"));
+        let mut text = Bytes::new(&env);
+        for _ in 0..254 {
+            text.push_back(b'x');
+        }
+        // Needle straddles the first 256-byte window.
+        text.extend_from_slice(b"needle");
+        assert_eq!(bytes_find(&text, b"needle"), Some(254));
+
+        // A needle longer than a window is still found.
+        let mut long = [b'y'; 300];
+        long[299] = b'z';
+        text.extend_from_slice(&long);
+        assert_eq!(bytes_find(&text, &long), Some(260));
     }
 
     #[test]
-    fn test_string_to_i256() {
+    fn test_bytes_replace_all() {
         let env = Env::default();
-        let s = String::from_str(&env, "-12345");
-        let n = string_to_i256(&env, &s);
-        assert!(n.is_some());
-        let result = i256_to_bytes(&env, &n.unwrap());
-        assert_eq!(result, Bytes::from_slice(&env, b"-12345"));
-    }
+        let text = Bytes::from_slice(&env, b"{x} and {x} or {x}");
+        assert_eq!(
+            bytes_replace_all(&env, &text, b"{x}", b"y"),
+            Bytes::from_slice(&env, b"y and y or y")
+        );
 
-    // ==========================================================================
-    // &str convenience wrapper tests
-    // ==========================================================================
+        // Needle at start and end with nothing else.
+        let text = Bytes::from_slice(&env, b"abXYab");
+        assert_eq!(
+            bytes_replace_all(&env, &text, b"ab", b"-"),
+            Bytes::from_slice(&env, b"-XY-")
+        );
+
+        // Overlapping candidates are consumed left to right.
+        let text = Bytes::from_slice(&env, b"aaaaa");
+        assert_eq!(
+            bytes_replace_all(&env, &text, b"aa", b"b"),
+            Bytes::from_slice(&env, b"bba")
+        );
+
+        // No match leaves the input unchanged.
+        assert_eq!(bytes_replace_all(&env, &text, b"zz", b"b"), text);
+    }
 
     #[test]
-    fn test_str_to_u32() {
+    fn test_bytes_replace_all_no_rescan() {
         let env = Env::default();
-        assert_eq!(str_to_u32(&env, "12345"), Some(12345));
+        let text = Bytes::from_slice(&env, b"a-a");
+        assert_eq!(
+            bytes_replace_all(&env, &text, b"a", b"aa"),
+            Bytes::from_slice(&env, b"aa-aa")
+        );
     }
 
     #[test]
-    fn test_str_to_u32_invalid() {
+    fn test_bytes_replace_all_empty_needle() {
         let env = Env::default();
-        assert_eq!(str_to_u32(&env, "abc"), None);
+        let text = Bytes::from_slice(&env, b"unchanged");
+        assert_eq!(bytes_replace_all(&env, &text, b"", b"x"), text);
     }
 
     #[test]
-    fn test_str_to_i32() {
+    fn test_bytes_split() {
         let env = Env::default();
-        assert_eq!(str_to_i32(&env, "-12345"), Some(-12345));
+        let input = Bytes::from_slice(&env, b"rust,soroban,render");
+        let parts = bytes_split(&env, &input, b',', false);
+        assert_eq!(parts.len(), 3);
+        assert_eq!(parts.get(0).unwrap(), Bytes::from_slice(&env, b"rust"));
+        assert_eq!(parts.get(1).unwrap(), Bytes::from_slice(&env, b"soroban"));
+        assert_eq!(parts.get(2).unwrap(), Bytes::from_slice(&env, b"render"));
     }
 
     #[test]
-    fn test_str_to_u64() {
+    fn test_bytes_split_empty_segments() {
         let env = Env::default();
-        assert_eq!(str_to_u64(&env, "12345678901234"), Some(12345678901234));
+        let input = Bytes::from_slice(&env, b",a,,b,");
+
+        let kept = bytes_split(&env, &input, b',', false);
+        assert_eq!(kept.len(), 5);
+        assert_eq!(kept.get(0).unwrap(), Bytes::new(&env));
+        assert_eq!(kept.get(1).unwrap(), Bytes::from_slice(&env, b"a"));
+        assert_eq!(kept.get(2).unwrap(), Bytes::new(&env));
+        assert_eq!(kept.get(3).unwrap(), Bytes::from_slice(&env, b"b"));
+        assert_eq!(kept.get(4).unwrap(), Bytes::new(&env));
+
+        let skipped = bytes_split(&env, &input, b',', true);
+        assert_eq!(skipped.len(), 2);
+        assert_eq!(skipped.get(0).unwrap(), Bytes::from_slice(&env, b"a"));
+        assert_eq!(skipped.get(1).unwrap(), Bytes::from_slice(&env, b"b"));
+
+        assert_eq!(bytes_split(&env, &Bytes::new(&env), b',', false).len(), 1);
+        assert_eq!(bytes_split(&env, &Bytes::new(&env), b',', true).len(), 0);
     }
 
     #[test]
-    fn test_str_to_i64() {
+    fn test_bytes_split_long_input() {
         let env = Env::default();
-        assert_eq!(str_to_i64(&env, "-12345678901234"), Some(-12345678901234));
+        let mut input = Bytes::new(&env);
+        for _ in 0..100 {
+            input.extend_from_slice(b"tag,");
+        }
+        let parts = bytes_split(&env, &input, b',', true);
+        assert_eq!(parts.len(), 100);
+        for part in parts.iter() {
+            assert_eq!(part, Bytes::from_slice(&env, b"tag"));
+        }
     }
 
     #[test]
-    fn test_str_to_u128() {
+    fn test_bytes_trim() {
         let env = Env::default();
         assert_eq!(
-            str_to_u128(&env, "12345678901234567890"),
-            Some(12345678901234567890)
+            bytes_trim(&env, &Bytes::from_slice(&env, b" \t hello world \r\n")),
+            Bytes::from_slice(&env, b"hello world")
+        );
+        assert_eq!(
+            bytes_trim(&env, &Bytes::from_slice(&env, b"no-padding")),
+            Bytes::from_slice(&env, b"no-padding")
+        );
+        assert_eq!(
+            bytes_trim(&env, &Bytes::from_slice(&env, b" \n\t  ")),
+            Bytes::new(&env)
         );
+        assert_eq!(bytes_trim(&env, &Bytes::new(&env)), Bytes::new(&env));
     }
 
     #[test]
-    fn test_str_to_i128() {
+    fn test_bytes_case_conversion() {
         let env = Env::default();
+        let input = Bytes::from_slice(&env, "Hello, Wörld 42!".as_bytes());
         assert_eq!(
-            str_to_i128(&env, "-12345678901234567890"),
-            Some(-12345678901234567890)
+            bytes_to_lower(&env, &input),
+            Bytes::from_slice(&env, "hello, wörld 42!".as_bytes())
         );
+        assert_eq!(
+            bytes_to_upper(&env, &input),
+            Bytes::from_slice(&env, "HELLO, WöRLD 42!".as_bytes())
+        );
+        assert_eq!(bytes_to_lower(&env, &Bytes::new(&env)), Bytes::new(&env));
+    }
+
+    #[test]
+    fn test_slugify_punctuation() {
+        let env = Env::default();
+        let cases: &[(&str, &[u8])] = &[
+            ("Hello, World!", b"hello-world"),
+            (
+                "  --Rust & Soroban: A Guide (2024)--  ",
+                b"rust-soroban-a-guide-2024",
+            ),
+            ("What?!... Really???", b"what-really"),
+            ("a   b___c", b"a-b-c"),
+            ("Café Menu", b"caf-menu"),
+            ("already-clean-slug", b"already-clean-slug"),
+            ("!!!", b""),
+        ];
+        for (title, expected) in cases {
+            assert_eq!(
+                slugify(&env, &String::from_str(&env, title)),
+                Bytes::from_slice(&env, expected),
+                "{}",
+                title
+            );
+        }
     }
 
     #[test]
-    fn test_str_to_u256() {
+    fn test_slugify_caps_length() {
         let env = Env::default();
-        let n = str_to_u256(&env, "12345");
-        assert!(n.is_some());
-        let result = u256_to_bytes(&env, &n.unwrap());
-        assert_eq!(result, Bytes::from_slice(&env, b"12345"));
+        // 63 letters then a separator and more words: the cap must not leave a trailing dash.
+        let title = alloc::format!("{} more words", "a".repeat(63));
+        let slug = slugify(&env, &String::from_str(&env, &title));
+        assert_eq!(slug.len(), 63);
+        assert_ne!(slug.get(62), Some(b'-'));
+
+        let title = "word ".repeat(40);
+        let slug = slugify(&env, &String::from_str(&env, &title));
+        assert!(slug.len() as usize <= MAX_SLUG_LEN);
+        assert_ne!(slug.get(slug.len() - 1), Some(b'-'));
     }
 
-    #[test]
-    fn test_str_to_i256() {
-        let env = Env::default();
-        let n = str_to_i256(&env, "-12345");
-        assert!(n.is_some());
-        let result = i256_to_bytes(&env, &n.unwrap());
-        assert_eq!(result, Bytes::from_slice(&env, b"-12345"));
+    /// Build Bytes the way the converters used to: one `push_back` per byte.
+    fn push_back_each(env: &Env, s: &str) -> Bytes {
+        let mut result = Bytes::new(env);
+        for b in s.bytes() {
+            result.push_back(b);
+        }
+        result
     }
 
     #[test]
-    fn test_str_to_u256_large() {
+    fn test_bytes_writer_small() {
         let env = Env::default();
-        // Test with a larger number
-        let n = str_to_u256(
-            &env,
-            "115792089237316195423570985008687907853269984665640564039457584007913129639935",
-        );
-        assert!(n.is_some());
+        let mut out = BytesWriter::new(&env);
+        out.write(b"0x");
+        out.write_byte(b'f');
+        out.write(b"");
+        assert_eq!(out.into_bytes(), Bytes::from_slice(&env, b"0xf"));
+        assert_eq!(BytesWriter::new(&env).into_bytes(), Bytes::new(&env));
     }
 
     #[test]
-    fn test_str_to_u32_empty() {
+    fn test_bytes_writer_spills_past_buffer() {
         let env = Env::default();
-        assert_eq!(str_to_u32(&env, ""), None);
+        let mut expected = alloc::vec::Vec::new();
+        let mut out = BytesWriter::new(&env);
+        for i in 0..300u32 {
+            let b = b'a' + (i % 26) as u8;
+            out.write_byte(b);
+            expected.push(b);
+        }
+        // A write larger than the buffer goes straight through.
+        let big = [b'Z'; 200];
+        out.write(&big);
+        expected.extend_from_slice(&big);
+        out.write(b"tail");
+        expected.extend_from_slice(b"tail");
+        assert_eq!(out.into_bytes(), Bytes::from_slice(&env, &expected));
     }
 
-    // ==========================================================================
-    // address_to_bytes tests
-    // ==========================================================================
-
     #[test]
-    fn test_address_to_bytes_returns_56_chars() {
+    fn test_bytes_writer_flush_into() {
         let env = Env::default();
-        use soroban_sdk::testutils::Address as _;
-        let addr = Address::generate(&env);
-        let bytes = address_to_bytes(&env, &addr);
-        // Stellar contract addresses are 56 characters
-        assert_eq!(bytes.len(), 56);
+        let mut target = Bytes::from_slice(&env, b"start:");
+        let mut out = BytesWriter::new(&env);
+        out.write(&[b'x'; 130]);
+        out.write(b"end");
+        out.flush_into(&mut target);
+        assert_eq!(target.len(), 6 + 130 + 3);
+        assert_eq!(target.slice(0..6), Bytes::from_slice(&env, b"start:"));
+        assert_eq!(target.slice(136..139), Bytes::from_slice(&env, b"end"));
+
+        // The writer is empty again after flushing.
+        out.write(b"again");
+        out.flush_into(&mut target);
+        assert_eq!(target.len(), 144);
+        assert_eq!(out.into_bytes(), Bytes::new(&env));
     }
 
     #[test]
-    fn test_address_to_bytes_valid_format() {
+    fn test_writer_conversions_match_push_back() {
+        use alloc::format;
         let env = Env::default();
-        use soroban_sdk::testutils::Address as _;
-        // Generated addresses start with either 'G' (account) or 'C' (contract)
-        let addr = Address::generate(&env);
-        let bytes = address_to_bytes(&env, &addr);
-        // Address should start with valid prefix (C for contract or G for account)
-        let first_byte = bytes.get(0).unwrap();
-        assert!(first_byte == b'C' || first_byte == b'G');
-    }
 
-    // ==========================================================================
-    // symbol_to_bytes tests
-    // ==========================================================================
+        for n in [0u32, 7, 42, 1000, u32::MAX] {
+            assert_eq!(
+                u32_to_bytes(&env, n),
+                push_back_each(&env, &format!("{}", n))
+            );
+            assert_eq!(
+                u32_to_hex(&env, n),
+                push_back_each(&env, &format!("{:#x}", n))
+            );
+        }
+        for n in [0u64, 99, u64::MAX] {
+            assert_eq!(
+                u64_to_bytes(&env, n),
+                push_back_each(&env, &format!("{}", n))
+            );
+            assert_eq!(
+                u64_to_hex(&env, n),
+                push_back_each(&env, &format!("{:#x}", n))
+            );
+        }
+        for n in [0u128, 12345, u128::MAX] {
+            assert_eq!(
+                u128_to_bytes(&env, n),
+                push_back_each(&env, &format!("{}", n))
+            );
+            assert_eq!(
+                u128_to_hex(&env, n),
+                push_back_each(&env, &format!("{:#x}", n))
+            );
+        }
+        for n in [0i32, -1, 42, i32::MIN, i32::MAX] {
+            assert_eq!(
+                i32_to_bytes(&env, n),
+                push_back_each(&env, &format!("{}", n))
+            );
+        }
+        for n in [0i64, -255, i64::MIN, i64::MAX] {
+            assert_eq!(
+                i64_to_bytes(&env, n),
+                push_back_each(&env, &format!("{}", n))
+            );
+        }
+        for n in [-255i64, 255] {
+            let expected = if n < 0 {
+                format!("-{:#x}", -n)
+            } else {
+                format!("{:#x}", n)
+            };
+            assert_eq!(i64_to_hex(&env, n), push_back_each(&env, &expected));
+        }
+        for n in [0i128, -7, i128::MIN, i128::MAX] {
+            assert_eq!(
+                i128_to_bytes(&env, n),
+                push_back_each(&env, &format!("{}", n))
+            );
+        }
+        assert_eq!(i128_to_hex(&env, -255), push_back_each(&env, "-0xff"));
 
-    // Note: symbol_to_bytes works with short symbols that have tag=14 in their
-    // internal representation. The tests below verify the decode_symbol_char
-    // helper function which is the core of the decoding logic.
+        let big = U256::from_u128(&env, u128::MAX);
+        assert_eq!(
+            u256_to_bytes(&env, &big),
+            push_back_each(&env, &format!("{}", u128::MAX))
+        );
+        assert_eq!(
+            u256_to_hex(&env, &big),
+            push_back_each(&env, &format!("{:#x}", u128::MAX))
+        );
+        let neg = I256::from_i128(&env, -1_000_000_007);
+        assert_eq!(
+            i256_to_bytes(&env, &neg),
+            push_back_each(&env, "-1000000007")
+        );
+        assert_eq!(i256_to_hex(&env, &neg), push_back_each(&env, "-0x3b9aca07"));
 
-    #[test]
-    fn test_symbol_to_bytes_returns_bytes() {
-        let env = Env::default();
-        use soroban_sdk::symbol_short;
-        let sym = symbol_short!("theme");
-        let bytes = symbol_to_bytes(&env, &sym);
-        // Just verify the function doesn't panic and returns valid Bytes
-        // The actual content depends on internal symbol representation
-        assert!(bytes.len() <= 9); // Max 9 chars for short symbols
+        let segment = Bytes::from_slice(&env, b"hello world/1");
+        assert_eq!(
+            percent_encode(&env, &segment),
+            push_back_each(&env, "hello%20world%2F1")
+        );
     }
 
     #[test]
-    fn test_decode_symbol_char_null() {
-        // 0 -> 0 (null/end marker)
-        assert_eq!(decode_symbol_char(0), 0);
-    }
+    fn test_concat_bytes_many_tiny_parts() {
+        let env = Env::default();
+        let mut parts: Vec<Bytes> = Vec::new(&env);
+        for i in 0..1000u32 {
+            let n = (i % 5) as usize;
+            parts.push_back(Bytes::from_slice(&env, &b"abcde"[..n]));
+            if i % 97 == 0 {
+                // Occasional large part between runs of small ones.
+                parts.push_back(Bytes::from_slice(&env, &[b'L'; 100]));
+            }
+        }
 
-    #[test]
-    fn test_decode_symbol_char_underscore() {
-        // 1 -> '_'
-        assert_eq!(decode_symbol_char(1), b'_');
-    }
+        let mut naive = Bytes::new(&env);
+        for part in parts.iter() {
+            naive.append(&part);
+        }
 
-    #[test]
-    fn test_decode_symbol_char_digits() {
-        // 2-11 -> '0'-'9'
-        assert_eq!(decode_symbol_char(2), b'0');
-        assert_eq!(decode_symbol_char(6), b'4');
-        assert_eq!(decode_symbol_char(11), b'9');
-    }
+        let mut budget = env.cost_estimate().budget();
+        budget.reset_unlimited();
+        let merged = concat_bytes(&env, &parts);
+        let merged_cpu = budget.cpu_instruction_cost();
 
-    #[test]
-    fn test_decode_symbol_char_uppercase() {
-        // 12-37 -> 'A'-'Z'
-        assert_eq!(decode_symbol_char(12), b'A');
-        assert_eq!(decode_symbol_char(24), b'M');
-        assert_eq!(decode_symbol_char(37), b'Z');
-    }
+        budget.reset_unlimited();
+        let mut again = Bytes::new(&env);
+        for part in parts.iter() {
+            again.append(&part);
+        }
+        let naive_cpu = budget.cpu_instruction_cost();
 
-    #[test]
-    fn test_decode_symbol_char_lowercase() {
-        // 38-63 -> 'a'-'z'
-        assert_eq!(decode_symbol_char(38), b'a');
-        assert_eq!(decode_symbol_char(50), b'm');
-        assert_eq!(decode_symbol_char(63), b'z');
+        assert_eq!(merged, naive);
+        assert_eq!(again, naive);
+        assert!(merged_cpu < naive_cpu);
     }
 
     #[test]
-    fn test_decode_symbol_char_out_of_range() {
-        // Out of range -> 0
-        assert_eq!(decode_symbol_char(64), 0);
-        assert_eq!(decode_symbol_char(100), 0);
-        assert_eq!(decode_symbol_char(255), 0);
+    fn test_concat_bytes_slab_boundary() {
+        let env = Env::default();
+        let mut parts: Vec<Bytes> = Vec::new(&env);
+        let mut expected = alloc::vec::Vec::new();
+        // 63-byte parts never divide the 1KB slab evenly.
+        for i in 0..40u8 {
+            let chunk = [b'a' + i % 26; 63];
+            parts.push_back(Bytes::from_slice(&env, &chunk));
+            expected.extend_from_slice(&chunk);
+        }
+        assert_eq!(
+            concat_bytes(&env, &parts),
+            Bytes::from_slice(&env, &expected)
+        );
+        assert_eq!(concat_bytes(&env, &Vec::new(&env)), Bytes::new(&env));
+    }
+
+    #[test]
+    fn test_i128_to_bytes_grouped() {
+        let env = Env::default();
+        let cases: &[(i128, &[u8])] = &[
+            (0, b"0"),
+            (7, b"7"),
+            (999, b"999"),
+            (1000, b"1,000"),
+            (1204, b"1,204"),
+            (-1204, b"-1,204"),
+            (123_456_789, b"123,456,789"),
+            (-100_000, b"-100,000"),
+            (
+                i128::MIN,
+                b"-170,141,183,460,469,231,731,687,303,715,884,105,728",
+            ),
+        ];
+        for (n, expected) in cases {
+            assert_eq!(
+                i128_to_bytes_grouped(&env, *n),
+                Bytes::from_slice(&env, expected),
+                "{}",
+                n
+            );
+        }
     }
 }
@@ -0,0 +1,222 @@
+//! Chunked storage for content too large for one ledger entry.
+//!
+//! `ChunkedContent` splits a `Bytes` value into fixed-size pieces, each in
+//! its own persistent storage entry, keyed by a collection `Symbol` and an
+//! index. The pieces line up with the `{{chunk}}` markers written by
+//! `MarkdownBuilder::chunk_ref`, so a render function can emit one marker
+//! per chunk and let the viewer fetch them through `get_chunk`.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use soroban_render_sdk::prelude::*;
+//!
+//! // Store an article in 8KB pieces
+//! ChunkedContent::write(&env, symbol_short!("article"), &body, 8192);
+//!
+//! // In render(), reference each piece for lazy loading
+//! let mut b = MarkdownBuilder::new(&env).h1("Article");
+//! for i in 0..ChunkedContent::chunk_count(&env, symbol_short!("article")) {
+//!     b = b.chunk_ref("article", i);
+//! }
+//!
+//! // Serve the pieces to the viewer
+//! pub fn get_chunk(env: Env, collection: Symbol, index: u32) -> Option<Bytes> {
+//!     ChunkedContent::read_chunk(&env, collection, index)
+//! }
+//! ```
+
+use soroban_sdk::{Bytes, Env, Symbol, contracttype};
+
+/// Storage keys used by `ChunkedContent`.
+///
+/// All entries live in persistent storage.
+#[contracttype]
+#[derive(Clone)]
+pub enum ChunkKey {
+    /// Number of chunks stored under a prefix
+    Count(Symbol),
+    /// One chunk, by prefix and index
+    Chunk(Symbol, u32),
+}
+
+/// Helpers for storing large content across several storage entries.
+pub struct ChunkedContent;
+
+impl ChunkedContent {
+    /// Store `content` under `key_prefix` in pieces of `chunk_size` bytes.
+    ///
+    /// Replaces anything previously stored under the prefix, removing
+    /// chunks the new content no longer needs. Empty content stores zero
+    /// chunks. Returns the number of chunks written.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is 0.
+    pub fn write(env: &Env, key_prefix: Symbol, content: &Bytes, chunk_size: u32) -> u32 {
+        if chunk_size == 0 {
+            panic!("chunk_size must be greater than zero");
+        }
+        let storage = env.storage().persistent();
+        let old_count = Self::chunk_count(env, key_prefix.clone());
+        let count = content.len().div_ceil(chunk_size);
+
+        for index in 0..count {
+            let start = index * chunk_size;
+            let end = content.len().min(start + chunk_size);
+            storage.set(
+                &ChunkKey::Chunk(key_prefix.clone(), index),
+                &content.slice(start..end),
+            );
+        }
+        for index in count..old_count {
+            storage.remove(&ChunkKey::Chunk(key_prefix.clone(), index));
+        }
+        storage.set(&ChunkKey::Count(key_prefix), &count);
+        count
+    }
+
+    /// Reassemble the content stored under `key_prefix`.
+    ///
+    /// Returns empty Bytes if nothing is stored. Reading every chunk costs
+    /// one storage read per chunk, so for very large content prefer
+    /// `chunk_ref` markers and let the viewer load chunks on demand.
+    pub fn read(env: &Env, key_prefix: Symbol) -> Bytes {
+        let mut content = Bytes::new(env);
+        for index in 0..Self::chunk_count(env, key_prefix.clone()) {
+            if let Some(chunk) = Self::read_chunk(env, key_prefix.clone(), index) {
+                content.append(&chunk);
+            }
+        }
+        content
+    }
+
+    /// Read one chunk, or `None` if the index is out of range.
+    pub fn read_chunk(env: &Env, key_prefix: Symbol, index: u32) -> Option<Bytes> {
+        env.storage()
+            .persistent()
+            .get(&ChunkKey::Chunk(key_prefix, index))
+    }
+
+    /// Number of chunks stored under `key_prefix` (0 if none).
+    pub fn chunk_count(env: &Env, key_prefix: Symbol) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&ChunkKey::Count(key_prefix))
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::{contract, symbol_short};
+
+    extern crate alloc;
+
+    #[contract]
+    struct TestContract;
+
+    /// Run `f` inside a registered contract so storage is available.
+    fn with_contract<T>(f: impl FnOnce(&Env) -> T) -> T {
+        let env = Env::default();
+        let id = env.register(TestContract, ());
+        env.as_contract(&id, || f(&env))
+    }
+
+    /// Bytes of `len` where each byte is its index modulo 251.
+    fn sample(env: &Env, len: u32) -> Bytes {
+        let buf: alloc::vec::Vec<u8> = (0..len).map(|i| (i % 251) as u8).collect();
+        Bytes::from_slice(env, &buf)
+    }
+
+    #[test]
+    fn test_round_trip_40kb() {
+        with_contract(|env| {
+            let content = sample(env, 40 * 1024);
+            let prefix = symbol_short!("article");
+            assert_eq!(
+                ChunkedContent::write(env, prefix.clone(), &content, 8 * 1024),
+                5
+            );
+            assert_eq!(ChunkedContent::chunk_count(env, prefix.clone()), 5);
+            assert_eq!(ChunkedContent::read(env, prefix), content);
+        });
+    }
+
+    #[test]
+    fn test_partial_reads() {
+        with_contract(|env| {
+            let content = sample(env, 40 * 1024);
+            let prefix = symbol_short!("article");
+            ChunkedContent::write(env, prefix.clone(), &content, 8 * 1024);
+
+            let second = ChunkedContent::read_chunk(env, prefix.clone(), 1).unwrap();
+            assert_eq!(second, content.slice(8192..16384));
+            let last = ChunkedContent::read_chunk(env, prefix.clone(), 4).unwrap();
+            assert_eq!(last, content.slice(32768..40960));
+            assert_eq!(ChunkedContent::read_chunk(env, prefix, 5), None);
+        });
+    }
+
+    #[test]
+    fn test_uneven_last_chunk() {
+        with_contract(|env| {
+            let content = sample(env, 10);
+            let prefix = symbol_short!("note");
+            assert_eq!(ChunkedContent::write(env, prefix.clone(), &content, 4), 3);
+            let last = ChunkedContent::read_chunk(env, prefix.clone(), 2).unwrap();
+            assert_eq!(last, content.slice(8..10));
+            assert_eq!(ChunkedContent::read(env, prefix), content);
+        });
+    }
+
+    #[test]
+    fn test_rewrite_removes_stale_chunks() {
+        with_contract(|env| {
+            let prefix = symbol_short!("doc");
+            ChunkedContent::write(env, prefix.clone(), &sample(env, 100), 10);
+            let shorter = sample(env, 25);
+            assert_eq!(ChunkedContent::write(env, prefix.clone(), &shorter, 10), 3);
+            assert_eq!(ChunkedContent::chunk_count(env, prefix.clone()), 3);
+            assert_eq!(ChunkedContent::read_chunk(env, prefix.clone(), 3), None);
+            assert_eq!(ChunkedContent::read(env, prefix), shorter);
+        });
+    }
+
+    #[test]
+    fn test_empty_and_missing() {
+        with_contract(|env| {
+            let prefix = symbol_short!("empty");
+            assert_eq!(ChunkedContent::chunk_count(env, prefix.clone()), 0);
+            assert!(ChunkedContent::read(env, prefix.clone()).is_empty());
+
+            assert_eq!(
+                ChunkedContent::write(env, prefix.clone(), &Bytes::new(env), 8),
+                0
+            );
+            assert_eq!(ChunkedContent::read_chunk(env, prefix.clone(), 0), None);
+            assert!(ChunkedContent::read(env, prefix).is_empty());
+        });
+    }
+
+    #[test]
+    fn test_prefixes_are_independent() {
+        with_contract(|env| {
+            let a = sample(env, 30);
+            let b = sample(env, 5);
+            ChunkedContent::write(env, symbol_short!("a"), &a, 8);
+            ChunkedContent::write(env, symbol_short!("b"), &b, 8);
+            assert_eq!(ChunkedContent::read(env, symbol_short!("a")), a);
+            assert_eq!(ChunkedContent::read(env, symbol_short!("b")), b);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk_size must be greater than zero")]
+    fn test_zero_chunk_size_panics() {
+        with_contract(|env| {
+            ChunkedContent::write(env, symbol_short!("a"), &sample(env, 4), 0);
+        });
+    }
+}
@@ -0,0 +1,218 @@
+//! Reusable page sections.
+//!
+//! A `Renderable` value appends itself to a `MarkdownBuilder`, and a
+//! `JsonRenderable` value does the same for a `JsonDocument`. Headers,
+//! footers, and other repeated sections can then be built once and passed
+//! around as values instead of copied between render functions.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use soroban_render_sdk::prelude::*;
+//!
+//! const HEADER: PageHeader = PageHeader {
+//!     title: "My Tasks",
+//!     nav_items: &[("Home", "/"), ("Tasks", "/tasks")],
+//! };
+//! const FOOTER: Footer = Footer { text: "Powered by Soroban" };
+//!
+//! MarkdownBuilder::new(&env)
+//!     .component(&HEADER)
+//!     .paragraph("Nothing to do.")
+//!     .component(&FOOTER)
+//!     .build()
+//! ```
+
+#[cfg(feature = "json")]
+use crate::json::JsonDocument;
+#[cfg(feature = "markdown")]
+use crate::markdown::MarkdownBuilder;
+
+/// A section that renders itself as markdown.
+#[cfg(feature = "markdown")]
+pub trait Renderable {
+    /// Append this section to the builder.
+    fn render_into<'a>(&self, b: MarkdownBuilder<'a>) -> MarkdownBuilder<'a>;
+}
+
+/// A section that renders itself as JSON components.
+#[cfg(feature = "json")]
+pub trait JsonRenderable {
+    /// Append this section's components to the document.
+    fn render_json_into<'a>(&self, doc: JsonDocument<'a>) -> JsonDocument<'a>;
+}
+
+#[cfg(feature = "markdown")]
+impl<'a> MarkdownBuilder<'a> {
+    /// Append a `Renderable` section.
+    pub fn component(self, section: &impl Renderable) -> Self {
+        section.render_into(self)
+    }
+}
+
+#[cfg(feature = "json")]
+impl<'a> JsonDocument<'a> {
+    /// Append a `JsonRenderable` section.
+    pub fn component(self, section: &impl JsonRenderable) -> Self {
+        section.render_json_into(self)
+    }
+}
+
+/// A page title followed by a row of navigation links.
+///
+/// Each nav item is a `(label, path)` pair. Markdown output separates the
+/// links with ` | `; JSON output uses a navigation component.
+pub struct PageHeader<'a> {
+    pub title: &'a str,
+    pub nav_items: &'a [(&'a str, &'a str)],
+}
+
+#[cfg(feature = "markdown")]
+impl Renderable for PageHeader<'_> {
+    fn render_into<'a>(&self, b: MarkdownBuilder<'a>) -> MarkdownBuilder<'a> {
+        let mut b = b.h1(self.title);
+        if self.nav_items.is_empty() {
+            return b;
+        }
+        for (i, (label, path)) in self.nav_items.iter().enumerate() {
+            if i > 0 {
+                b = b.text(" | ");
+            }
+            b = b.render_link(label, path);
+        }
+        b.newline().newline()
+    }
+}
+
+#[cfg(feature = "json")]
+impl JsonRenderable for PageHeader<'_> {
+    fn render_json_into<'a>(&self, doc: JsonDocument<'a>) -> JsonDocument<'a> {
+        let mut doc = doc.heading(1, self.title);
+        if self.nav_items.is_empty() {
+            return doc;
+        }
+        doc = doc.nav_start();
+        for (label, path) in self.nav_items {
+            doc = doc.nav_link(label, path, false);
+        }
+        doc.nav_end()
+    }
+}
+
+/// A horizontal rule followed by a line of text.
+pub struct Footer<'a> {
+    pub text: &'a str,
+}
+
+#[cfg(feature = "markdown")]
+impl Renderable for Footer<'_> {
+    fn render_into<'a>(&self, b: MarkdownBuilder<'a>) -> MarkdownBuilder<'a> {
+        b.hr().paragraph(self.text)
+    }
+}
+
+#[cfg(feature = "json")]
+impl JsonRenderable for Footer<'_> {
+    fn render_json_into<'a>(&self, doc: JsonDocument<'a>) -> JsonDocument<'a> {
+        doc.divider().text(self.text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::Env;
+
+    const HEADER: PageHeader = PageHeader {
+        title: "My Tasks",
+        nav_items: &[("Home", "/"), ("Tasks", "/tasks")],
+    };
+
+    const FOOTER: Footer = Footer {
+        text: "Powered by Soroban",
+    };
+
+    struct Notice<'a> {
+        message: &'a str,
+    }
+
+    #[cfg(feature = "markdown")]
+    impl Renderable for Notice<'_> {
+        fn render_into<'a>(&self, b: MarkdownBuilder<'a>) -> MarkdownBuilder<'a> {
+            b.h2("Notice").paragraph(self.message)
+        }
+    }
+
+    #[cfg(feature = "json")]
+    impl JsonRenderable for Notice<'_> {
+        fn render_json_into<'a>(&self, doc: JsonDocument<'a>) -> JsonDocument<'a> {
+            doc.heading(2, "Notice").text(self.message)
+        }
+    }
+
+    #[cfg(feature = "markdown")]
+    #[test]
+    fn test_markdown_components_match_manual_build() {
+        let env = Env::default();
+        let notice = Notice {
+            message: "Nothing to do.",
+        };
+        let composed = MarkdownBuilder::new(&env)
+            .component(&HEADER)
+            .component(&notice)
+            .component(&FOOTER)
+            .build();
+        let manual = MarkdownBuilder::new(&env)
+            .h1("My Tasks")
+            .render_link("Home", "/")
+            .text(" | ")
+            .render_link("Tasks", "/tasks")
+            .newline()
+            .newline()
+            .h2("Notice")
+            .paragraph("Nothing to do.")
+            .hr()
+            .paragraph("Powered by Soroban")
+            .build();
+        assert_eq!(composed, manual);
+    }
+
+    #[cfg(feature = "markdown")]
+    #[test]
+    fn test_page_header_without_nav() {
+        let env = Env::default();
+        let header = PageHeader {
+            title: "About",
+            nav_items: &[],
+        };
+        let composed = MarkdownBuilder::new(&env).component(&header).build();
+        let manual = MarkdownBuilder::new(&env).h1("About").build();
+        assert_eq!(composed, manual);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json_components_match_manual_build() {
+        let env = Env::default();
+        let notice = Notice {
+            message: "Nothing to do.",
+        };
+        let composed = JsonDocument::new(&env, "Tasks")
+            .component(&HEADER)
+            .component(&notice)
+            .component(&FOOTER)
+            .build();
+        let manual = JsonDocument::new(&env, "Tasks")
+            .heading(1, "My Tasks")
+            .nav_start()
+            .nav_link("Home", "/", false)
+            .nav_link("Tasks", "/tasks", false)
+            .nav_end()
+            .heading(2, "Notice")
+            .text("Nothing to do.")
+            .divider()
+            .text("Powered by Soroban")
+            .build();
+        assert_eq!(composed, manual);
+    }
+}
@@ -0,0 +1,68 @@
+//! The `render_entry!` macro.
+//!
+//! Most contracts wrap a `Router` chain in the same
+//! `render(env, path, viewer)` function. `render_entry!` writes that
+//! function, and the `#[contractimpl]` block around it, from a list of
+//! route arms.
+
+/// Generate a contract's `render` function from route arms.
+///
+/// The first argument is the contract type, followed by `pattern => handler`
+/// arms and a final `_ => handler` fallback. Arms are tried in order, and
+/// patterns use the same syntax as `Router`. Every handler is a
+/// `fn(&Env, Request, Option<Address>) -> Bytes` and receives the viewer
+/// passed to `render`.
+///
+/// The macro expands to its own `#[contractimpl]` block, so it must be used
+/// at module level, not inside the contract's other impl block.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use soroban_render_sdk::prelude::*;
+/// use soroban_sdk::{contract, Address, Bytes, Env};
+///
+/// soroban_render!(markdown);
+///
+/// #[contract]
+/// pub struct TaskContract;
+///
+/// render_entry! {
+///     TaskContract;
+///     "/" => home,
+///     "/task/{id}" => task_page,
+///     _ => not_found,
+/// }
+///
+/// fn task_page(env: &Env, req: Request, viewer: Option<Address>) -> Bytes {
+///     let id = req.get_var_u32(b"id").unwrap_or(0);
+///     // ...
+/// }
+/// ```
+#[macro_export]
+macro_rules! render_entry {
+    (
+        $contract:ident;
+        $first:literal => $first_handler:path,
+        $($pattern:literal => $handler:path,)*
+        _ => $fallback:path $(,)?
+    ) => {
+        #[::soroban_sdk::contractimpl]
+        impl $contract {
+            pub fn render(
+                env: ::soroban_sdk::Env,
+                path: ::core::option::Option<::soroban_sdk::String>,
+                viewer: ::core::option::Option<::soroban_sdk::Address>,
+            ) -> ::soroban_sdk::Bytes {
+                $crate::router::Router::new_with_viewer(&env, path, viewer.clone())
+                    .handle($first.as_bytes(), |req| {
+                        $first_handler(&env, req, viewer.clone())
+                    })
+                    $(.or_handle($pattern.as_bytes(), |req| {
+                        $handler(&env, req, viewer.clone())
+                    }))*
+                    .or_default(|req| $fallback(&env, req, viewer.clone()))
+            }
+        }
+    };
+}
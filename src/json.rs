@@ -14,110 +14,479 @@
 //!     .divider()
 //!     .build();
 //! ```
+//!
+//! # Numbers
+//!
+//! JavaScript viewers parse JSON numbers as doubles, which only hold
+//! integers exactly up to 2^53 - 1. Signed and wide values (`i64`, `i128`)
+//! within that range are emitted as bare numbers; anything beyond it is
+//! emitted as a quoted decimal string so no precision is silently lost.
+//! Transaction arguments built with `TxArgs` follow the same rule.
+
+use crate::args::TxArgs;
+use crate::bytes::{
+    address_to_bytes, bytes_to_string, escape_json_bytes, escape_json_string, format_timestamp,
+    i64_to_bytes, i128_to_bytes_grouped, json_number_bytes, u32_to_bytes,
+};
+#[cfg(feature = "router")]
+use crate::router::{path_eq, path_starts_with};
+use crate::sink::Sink;
+use soroban_sdk::{Address, Bytes, Env, IntoVal, String, TryFromVal, Val, Vec};
+
+/// Start of every document, up to the opening quote of the title.
+const HEADER_PREFIX: &[u8] = b"{\"format\":\"soroban-render-json-v1\",\"title\":\"";
+
+/// Keys of the optional header fields, including their separators.
+const DESCRIPTION_KEY: &[u8] = b",\"description\":\"";
+const VERSION_KEY: &[u8] = b",\"version\":";
+const THEME_KEY: &[u8] = b",\"theme\":\"";
+
+/// End of the document header, opening the components array.
+const HEADER_SUFFIX: &[u8] = b",\"components\":[";
+
+/// A block opened by a `*_start` method and closed by the matching `*_end`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Block {
+    Nav,
+    PieChart,
+    Container,
+    List,
+    KeyValue,
+    Table,
+    TableRow,
+    Grid,
+    Row,
+}
+
+impl Block {
+    /// All blocks, indexed by their discriminant.
+    const ALL: [Block; 9] = [
+        Block::Nav,
+        Block::PieChart,
+        Block::Container,
+        Block::List,
+        Block::KeyValue,
+        Block::Table,
+        Block::TableRow,
+        Block::Grid,
+        Block::Row,
+    ];
+
+    fn from_u32(value: u32) -> Self {
+        Self::ALL[value as usize]
+    }
+
+    /// Name used in panic messages.
+    fn name(self) -> &'static str {
+        match self {
+            Block::Nav => "nav",
+            Block::PieChart => "pie_chart",
+            Block::Container => "container",
+            Block::List => "list",
+            Block::KeyValue => "kv_list",
+            Block::Table => "table",
+            Block::TableRow => "table_row",
+            Block::Grid => "grid",
+            Block::Row => "row",
+        }
+    }
 
-use crate::bytes::{concat_bytes, escape_json_bytes, escape_json_string, u32_to_bytes};
-use soroban_sdk::{Bytes, Env, String, Vec};
+    /// Bytes that close the block.
+    fn closer(self) -> &'static [u8] {
+        match self {
+            Block::TableRow => b"]",
+            _ => b"]}",
+        }
+    }
+
+    /// Whether the block holds nested components with their own count.
+    fn nests_components(self) -> bool {
+        matches!(self, Block::Container | Block::Grid | Block::Row)
+    }
+}
 
 /// A builder for constructing JSON UI documents.
 ///
 /// Outputs JSON following the `soroban-render-json-v1` format.
+///
+/// Blocks opened with `*_start` methods must be closed with the matching
+/// `*_end` in reverse order. Ending the wrong block, adding an item
+/// (such as `nav_link` or `list_item`) outside its block, or adding a
+/// component inside a block that holds items, panics. Blocks
+/// still open at `build()` panic in debug builds and are closed
+/// automatically in release builds.
 pub struct JsonDocument<'a> {
     env: &'a Env,
-    parts: Vec<Bytes>,
+    /// Escaped document title.
+    title: Bytes,
+    /// Escaped page description, if set.
+    description: Option<Bytes>,
+    /// Content version, if set.
+    version: Option<u32>,
+    /// Escaped theme contract reference, if set.
+    theme: Option<Bytes>,
+    /// Component output; the header is assembled separately in `build()`.
+    parts: Sink,
+    /// Components at the current nesting level.
     component_count: u32,
+    /// Saved `component_count` of each enclosing level, innermost last.
+    outer_counts: Vec<u32>,
+    /// Open blocks as `Block` discriminants, innermost last.
+    open_blocks: Vec<u32>,
+    /// Whether the last component's closing brace is still pending, so
+    /// that `with_class` can add to it.
+    component_open: bool,
+    /// Whether `with_class` has already added a class to that component.
+    component_has_class: bool,
+    /// Entries in the open list or table (items or rows).
+    item_count: u32,
+    /// Cells in the open table row.
+    cell_count: u32,
 }
 
 impl<'a> JsonDocument<'a> {
     /// Create a new JSON document with a title.
     pub fn new(env: &'a Env, title: &str) -> Self {
-        let mut parts = Vec::new(env);
-        parts.push_back(Bytes::from_slice(
-            env,
-            b"{\"format\":\"soroban-render-json-v1\",\"title\":\"",
-        ));
-        parts.push_back(escape_json_bytes(env, title.as_bytes()));
-        parts.push_back(Bytes::from_slice(env, b"\",\"components\":["));
+        Self::with_sink(env, Sink::new(env), title)
+    }
+
+    /// Create a new JSON document that accumulates into a heap buffer.
+    ///
+    /// Requires the `alloc` feature and an allocator.
+    #[cfg(feature = "alloc")]
+    pub fn new_buffered(env: &'a Env, title: &str) -> Self {
+        Self::with_sink(env, Sink::buffered(), title)
+    }
 
+    /// Create a document writing its components to the given sink.
+    ///
+    /// The header is kept out of the sink so that metadata setters such as
+    /// `with_description` can be called at any point before `build()`.
+    fn with_sink(env: &'a Env, parts: Sink, title: &str) -> Self {
         Self {
             env,
+            title: escape_json_bytes(env, title.as_bytes()),
+            description: None,
+            version: None,
+            theme: None,
             parts,
             component_count: 0,
+            outer_counts: Vec::new(env),
+            open_blocks: Vec::new(env),
+            component_open: false,
+            component_has_class: false,
+            item_count: 0,
+            cell_count: 0,
         }
     }
 
-    /// Add a comma separator if needed.
+    /// Add a comma separator if needed, closing the previous component.
+    ///
+    /// Panics if the innermost open block holds items rather than
+    /// components, such as a list or table.
     fn maybe_comma(&mut self) {
+        if let Some(top) = self.open_blocks.last() {
+            let block = Block::from_u32(top);
+            if !block.nests_components() {
+                panic!("components cannot be added inside a {} block", block.name());
+            }
+        }
+        self.flush_component();
         if self.component_count > 0 {
-            self.parts.push_back(Bytes::from_slice(self.env, b","));
+            self.parts.push_slice(self.env, b",");
         }
         self.component_count += 1;
     }
 
+    /// Add a comma separator before a list item or table row if needed.
+    fn maybe_item_comma(&mut self) {
+        if self.item_count > 0 {
+            self.parts.push_slice(self.env, b",");
+        }
+        self.item_count += 1;
+    }
+
+    /// Add a comma separator before a table cell if needed.
+    fn maybe_cell_comma(&mut self) {
+        if self.cell_count > 0 {
+            self.parts.push_slice(self.env, b",");
+        }
+        self.cell_count += 1;
+    }
+
+    /// Mark the component just written as complete except for its closing
+    /// brace, which is deferred so `with_class` can still add keys.
+    fn finish_component(&mut self) {
+        self.component_open = true;
+        self.component_has_class = false;
+    }
+
+    /// Push the start of a heading component, clamping the level to 1-6.
+    fn push_heading_level(&mut self, level: u8) {
+        self.parts
+            .push_slice(self.env, b"{\"type\":\"heading\",\"level\":");
+        self.parts
+            .push_back(u32_to_bytes(self.env, level.clamp(1, 6) as u32));
+    }
+
+    /// Emit the closing brace of the last component if it is still pending.
+    fn flush_component(&mut self) {
+        if self.component_open {
+            self.parts.push_slice(self.env, b"}");
+            self.component_open = false;
+        }
+    }
+
+    /// Record a newly opened block.
+    fn open_block(&mut self, block: Block) {
+        self.open_blocks.push_back(block as u32);
+    }
+
+    /// Close `block`, which must be the innermost open block.
+    fn end_block(&mut self, block: Block) {
+        self.flush_component();
+        match self.open_blocks.pop_back() {
+            Some(top) if top == block as u32 => self.close_block(block),
+            Some(top) => panic!(
+                "{}_end called while a {} block is open",
+                block.name(),
+                Block::from_u32(top).name()
+            ),
+            None => panic!("{}_end called with no open block", block.name()),
+        }
+    }
+
+    /// Emit the closing bytes of a block that has been popped.
+    fn close_block(&mut self, block: Block) {
+        self.parts.push_slice(self.env, block.closer());
+        if block.nests_components()
+            && let Some(count) = self.outer_counts.pop_back()
+        {
+            self.component_count = count;
+        }
+    }
+
+    /// Panic unless `block` is the innermost open block.
+    fn require_open(&self, block: Block, method: &str) {
+        if self.open_blocks.last() != Some(block as u32) {
+            panic!("{} called outside a {} block", method, block.name());
+        }
+    }
+
+    /// Close every open block, innermost first.
+    fn close_open_blocks(&mut self) {
+        self.flush_component();
+        while let Some(top) = self.open_blocks.pop_back() {
+            self.close_block(Block::from_u32(top));
+        }
+    }
+
+    /// Push a quoted, escaped JSON string.
+    fn push_json_str(&mut self, text: &str) {
+        self.parts.push_slice(self.env, b"\"");
+        self.parts
+            .push_back(escape_json_bytes(self.env, text.as_bytes()));
+        self.parts.push_slice(self.env, b"\"");
+    }
+
+    /// Serialize an integer as a JSON value.
+    ///
+    /// Values outside the safe integer range are quoted (see the module docs).
+    fn number_value(&self, value: i128) -> Bytes {
+        json_number_bytes(self.env, value)
+    }
+
+    // ========================================================================
+    // Document Metadata
+    // ========================================================================
+
+    /// Set a page description for viewers to show or index.
+    ///
+    /// Header fields are emitted in a fixed order (`format`, `title`,
+    /// `description`, `version`, `theme`) regardless of when the setters are
+    /// called. Calling a setter again replaces the earlier value.
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = Some(escape_json_bytes(self.env, description.as_bytes()));
+        self
+    }
+
+    /// Set a content version that viewers can use for caching.
+    pub fn with_version(mut self, version: u32) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    /// Reference the contract providing this document's stylesheet or theme.
+    pub fn with_theme(mut self, contract_id: &str) -> Self {
+        self.theme = Some(escape_json_bytes(self.env, contract_id.as_bytes()));
+        self
+    }
+
+    /// Assemble the document header up to and including the opening of the
+    /// components array.
+    fn header(&self) -> Bytes {
+        let env = self.env;
+        let mut header = Bytes::from_slice(env, HEADER_PREFIX);
+        header.append(&self.title);
+        header.push_back(b'"');
+        if let Some(description) = &self.description {
+            header.extend_from_slice(DESCRIPTION_KEY);
+            header.append(description);
+            header.push_back(b'"');
+        }
+        if let Some(version) = self.version {
+            header.extend_from_slice(VERSION_KEY);
+            header.append(&u32_to_bytes(env, version));
+        }
+        if let Some(theme) = &self.theme {
+            header.extend_from_slice(THEME_KEY);
+            header.append(theme);
+            header.push_back(b'"');
+        }
+        header.extend_from_slice(HEADER_SUFFIX);
+        header
+    }
+
+    /// Length of `header()` without building it.
+    fn header_len(&self) -> u32 {
+        let mut len = (HEADER_PREFIX.len() + 1 + HEADER_SUFFIX.len()) as u32 + self.title.len();
+        if let Some(description) = &self.description {
+            len += (DESCRIPTION_KEY.len() + 1) as u32 + description.len();
+        }
+        if let Some(version) = self.version {
+            len += VERSION_KEY.len() as u32 + u32_to_bytes(self.env, version).len();
+        }
+        if let Some(theme) = &self.theme {
+            len += (THEME_KEY.len() + 1) as u32 + theme.len();
+        }
+        len
+    }
+
     // ========================================================================
     // Basic Components
     // ========================================================================
 
     /// Add a heading component.
+    ///
+    /// Level 0 is treated as 1 and levels above 6 as 6.
     pub fn heading(mut self, level: u8, text: &str) -> Self {
         self.maybe_comma();
-        self.parts.push_back(Bytes::from_slice(
-            self.env,
-            b"{\"type\":\"heading\",\"level\":",
-        ));
-        self.parts.push_back(u32_to_bytes(self.env, level as u32));
-        self.parts
-            .push_back(Bytes::from_slice(self.env, b",\"text\":\""));
+        self.push_heading_level(level);
+        self.parts.push_slice(self.env, b",\"text\":\"");
         self.parts
             .push_back(escape_json_bytes(self.env, text.as_bytes()));
-        self.parts.push_back(Bytes::from_slice(self.env, b"\"}"));
+        self.parts.push_slice(self.env, b"\"");
+        self.finish_component();
         self
     }
 
     /// Add a heading with dynamic text from a String.
+    ///
+    /// Levels are clamped as in `heading`.
     pub fn heading_string(mut self, level: u8, text: &String) -> Self {
         self.maybe_comma();
-        self.parts.push_back(Bytes::from_slice(
-            self.env,
-            b"{\"type\":\"heading\",\"level\":",
-        ));
-        self.parts.push_back(u32_to_bytes(self.env, level as u32));
-        self.parts
-            .push_back(Bytes::from_slice(self.env, b",\"text\":\""));
+        self.push_heading_level(level);
+        self.parts.push_slice(self.env, b",\"text\":\"");
         self.parts.push_back(escape_json_string(self.env, text));
-        self.parts.push_back(Bytes::from_slice(self.env, b"\"}"));
+        self.parts.push_slice(self.env, b"\"");
+        self.finish_component();
         self
     }
 
     /// Add a text component.
     pub fn text(mut self, content: &str) -> Self {
         self.maybe_comma();
-        self.parts.push_back(Bytes::from_slice(
-            self.env,
-            b"{\"type\":\"text\",\"content\":\"",
-        ));
+        self.parts
+            .push_slice(self.env, b"{\"type\":\"text\",\"content\":\"");
         self.parts
             .push_back(escape_json_bytes(self.env, content.as_bytes()));
-        self.parts.push_back(Bytes::from_slice(self.env, b"\"}"));
+        self.parts.push_slice(self.env, b"\"");
+        self.finish_component();
+        self
+    }
+
+    /// Add a text component showing a unix timestamp.
+    ///
+    /// The content is formatted as `YYYY-MM-DD HH:MM:SS UTC`.
+    pub fn text_timestamp(mut self, unix_secs: u64) -> Self {
+        self.maybe_comma();
+        self.parts
+            .push_slice(self.env, b"{\"type\":\"text\",\"content\":\"");
+        self.parts.push_back(format_timestamp(self.env, unix_secs));
+        self.parts.push_slice(self.env, b"\"");
+        self.finish_component();
         self
     }
 
     /// Add a text component with dynamic content from a String.
     pub fn text_string(mut self, content: &String) -> Self {
         self.maybe_comma();
-        self.parts.push_back(Bytes::from_slice(
-            self.env,
-            b"{\"type\":\"text\",\"content\":\"",
-        ));
+        self.parts
+            .push_slice(self.env, b"{\"type\":\"text\",\"content\":\"");
         self.parts.push_back(escape_json_string(self.env, content));
-        self.parts.push_back(Bytes::from_slice(self.env, b"\"}"));
+        self.parts.push_slice(self.env, b"\"");
+        self.finish_component();
         self
     }
 
     /// Add a divider component.
     pub fn divider(mut self) -> Self {
+        self.maybe_comma();
+        self.parts.push_slice(self.env, b"{\"type\":\"divider\"");
+        self.finish_component();
+        self
+    }
+
+    // ========================================================================
+    // Markdown and Raw Components
+    // ========================================================================
+
+    /// Add a component whose body is markdown, for viewers that render it.
+    ///
+    /// Creates: `{"type":"markdown","content":"..."}`
+    pub fn markdown(mut self, content: &str) -> Self {
+        self.maybe_comma();
+        self.parts
+            .push_slice(self.env, b"{\"type\":\"markdown\",\"content\":");
+        self.push_json_str(content);
+        self.finish_component();
+        self
+    }
+
+    /// Add a markdown component with content from a String.
+    pub fn markdown_string(mut self, content: &String) -> Self {
         self.maybe_comma();
         self.parts
-            .push_back(Bytes::from_slice(self.env, b"{\"type\":\"divider\"}"));
+            .push_slice(self.env, b"{\"type\":\"markdown\",\"content\":\"");
+        self.parts.push_back(escape_json_string(self.env, content));
+        self.parts.push_slice(self.env, b"\"");
+        self.finish_component();
+        self
+    }
+
+    /// Insert a caller-built JSON object as a component.
+    ///
+    /// The text is inserted verbatim and is **not validated or escaped**;
+    /// only the separating comma is managed. Use it for component types this
+    /// builder doesn't support yet, and never with user-provided input.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// doc.raw_component(r#"{"type":"spacer","size":2}"#)
+    /// ```
+    pub fn raw_component(mut self, json: &str) -> Self {
+        self.maybe_comma();
+        self.parts.push_slice(self.env, json.as_bytes());
+        self
+    }
+
+    /// Insert a pre-built JSON object fragment as a component.
+    ///
+    /// Like `raw_component`, the content is not validated.
+    pub fn raw_component_bytes(mut self, json: Bytes) -> Self {
+        self.maybe_comma();
+        self.parts.push_back(json);
         self
     }
 
@@ -128,18 +497,16 @@ impl<'a> JsonDocument<'a> {
     /// Start a form component. Returns a FormBuilder.
     pub fn form(mut self, action: &str) -> FormBuilder<'a> {
         self.maybe_comma();
-        self.parts.push_back(Bytes::from_slice(
-            self.env,
-            b"{\"type\":\"form\",\"action\":\"",
-        ));
         self.parts
-            .push_back(escape_json_bytes(self.env, action.as_bytes()));
+            .push_slice(self.env, b"{\"type\":\"form\",\"action\":\"");
         self.parts
-            .push_back(Bytes::from_slice(self.env, b"\",\"fields\":["));
+            .push_back(escape_json_bytes(self.env, action.as_bytes()));
+        self.parts.push_slice(self.env, b"\",\"fields\":[");
 
         FormBuilder {
             doc: self,
             field_count: 0,
+            redirect: None,
         }
     }
 
@@ -150,39 +517,53 @@ impl<'a> JsonDocument<'a> {
     /// Start a navigation component.
     pub fn nav_start(mut self) -> Self {
         self.maybe_comma();
-        self.parts.push_back(Bytes::from_slice(
-            self.env,
-            b"{\"type\":\"navigation\",\"items\":[",
-        ));
+        self.parts
+            .push_slice(self.env, b"{\"type\":\"navigation\",\"items\":[");
+        self.item_count = 0;
+        self.open_block(Block::Nav);
         self
     }
 
     /// Add a navigation item. Must be called between nav_start and nav_end.
-    /// Set first=true for the first item (no comma prefix).
-    pub fn nav_item(mut self, label: &str, path: &str, active: bool, first: bool) -> Self {
-        if !first {
-            self.parts.push_back(Bytes::from_slice(self.env, b","));
-        }
-        self.parts
-            .push_back(Bytes::from_slice(self.env, b"{\"label\":\""));
-        self.parts
-            .push_back(escape_json_bytes(self.env, label.as_bytes()));
-        self.parts
-            .push_back(Bytes::from_slice(self.env, b"\",\"path\":\""));
-        self.parts
-            .push_back(escape_json_bytes(self.env, path.as_bytes()));
-        self.parts.push_back(Bytes::from_slice(self.env, b"\""));
+    ///
+    /// Separators between items are managed automatically.
+    pub fn nav_link(mut self, label: &str, path: &str, active: bool) -> Self {
+        self.require_open(Block::Nav, "nav_link");
+        self.maybe_item_comma();
+        self.parts.push_slice(self.env, b"{\"label\":");
+        self.push_json_str(label);
+        self.parts.push_slice(self.env, b",\"path\":");
+        self.push_json_str(path);
         if active {
-            self.parts
-                .push_back(Bytes::from_slice(self.env, b",\"active\":true"));
+            self.parts.push_slice(self.env, b",\"active\":true");
         }
-        self.parts.push_back(Bytes::from_slice(self.env, b"}"));
+        self.parts.push_slice(self.env, b"}");
         self
     }
 
+    /// Add a navigation item, marking it active based on the current path.
+    ///
+    /// The item is active when `path` equals `current_path`, or when it is a
+    /// leading segment of it (`/posts` is active for `/posts/5`). The root
+    /// path `/` only matches exactly. `current_path` should not include the
+    /// query string.
+    #[cfg(feature = "router")]
+    pub fn nav_item_auto(self, label: &str, path: &str, current_path: &Bytes) -> Self {
+        let active = nav_path_active(path.as_bytes(), current_path);
+        self.nav_link(label, path, active)
+    }
+
+    /// Add a navigation item. Must be called between nav_start and nav_end.
+    ///
+    /// The `first` flag is ignored; separators are now tracked internally.
+    #[deprecated(note = "use `nav_link` or `nav_item_auto`; `first` is no longer needed")]
+    pub fn nav_item(self, label: &str, path: &str, active: bool, _first: bool) -> Self {
+        self.nav_link(label, path, active)
+    }
+
     /// End a navigation component.
     pub fn nav_end(mut self) -> Self {
-        self.parts.push_back(Bytes::from_slice(self.env, b"]}"));
+        self.end_block(Block::Nav);
         self
     }
 
@@ -193,62 +574,190 @@ impl<'a> JsonDocument<'a> {
     /// Start a pie chart component.
     pub fn pie_chart_start(mut self, title: &str) -> Self {
         self.maybe_comma();
-        self.parts.push_back(Bytes::from_slice(
+        self.parts.push_slice(
             self.env,
             b"{\"type\":\"chart\",\"chartType\":\"pie\",\"title\":\"",
-        ));
+        );
         self.parts
             .push_back(escape_json_bytes(self.env, title.as_bytes()));
-        self.parts
-            .push_back(Bytes::from_slice(self.env, b"\",\"data\":["));
+        self.parts.push_slice(self.env, b"\",\"data\":[");
+        self.item_count = 0;
+        self.open_block(Block::PieChart);
         self
     }
 
-    /// Add a pie chart slice. Set first=true for the first slice.
-    pub fn pie_slice(mut self, label: &str, value: u32, color: &str, first: bool) -> Self {
-        if !first {
-            self.parts.push_back(Bytes::from_slice(self.env, b","));
-        }
-        self.parts
-            .push_back(Bytes::from_slice(self.env, b"{\"label\":\""));
+    /// Add a pie chart slice. Must be called between pie_chart_start and
+    /// pie_chart_end.
+    ///
+    /// Separators between slices are managed automatically.
+    pub fn pie_item(mut self, label: &str, value: u32, color: &str) -> Self {
+        self.require_open(Block::PieChart, "pie_item");
+        self.maybe_item_comma();
+        self.parts.push_slice(self.env, b"{\"label\":\"");
         self.parts
             .push_back(escape_json_bytes(self.env, label.as_bytes()));
-        self.parts
-            .push_back(Bytes::from_slice(self.env, b"\",\"value\":"));
+        self.parts.push_slice(self.env, b"\",\"value\":");
         self.parts.push_back(u32_to_bytes(self.env, value));
-        self.parts
-            .push_back(Bytes::from_slice(self.env, b",\"color\":\""));
+        self.parts.push_slice(self.env, b",\"color\":\"");
         self.parts
             .push_back(escape_json_bytes(self.env, color.as_bytes()));
-        self.parts.push_back(Bytes::from_slice(self.env, b"\"}"));
+        self.parts.push_slice(self.env, b"\"}");
+        self
+    }
+
+    /// Add a pie slice with a signed or large value.
+    pub fn pie_item_i64(mut self, label: &str, value: i64, color: &str) -> Self {
+        self.require_open(Block::PieChart, "pie_item_i64");
+        self.maybe_item_comma();
+        self.parts.push_slice(self.env, b"{\"label\":");
+        self.push_json_str(label);
+        self.parts.push_slice(self.env, b",\"value\":");
+        self.parts.push_back(self.number_value(value as i128));
+        self.parts.push_slice(self.env, b",\"color\":");
+        self.push_json_str(color);
+        self.parts.push_slice(self.env, b"}");
         self
     }
 
+    /// Add a pie chart slice.
+    ///
+    /// The `first` flag is ignored; separators are now tracked internally.
+    #[deprecated(note = "use `pie_item`; `first` is no longer needed")]
+    pub fn pie_slice(self, label: &str, value: u32, color: &str, _first: bool) -> Self {
+        self.pie_item(label, value, color)
+    }
+
     /// End a pie chart component.
     pub fn pie_chart_end(mut self) -> Self {
-        self.parts.push_back(Bytes::from_slice(self.env, b"]}"));
+        self.end_block(Block::PieChart);
         self
     }
 
     /// Add a gauge chart component.
     pub fn gauge(mut self, value: u32, max: u32, label: &str) -> Self {
         self.maybe_comma();
-        self.parts.push_back(Bytes::from_slice(
+        self.parts.push_slice(
             self.env,
             b"{\"type\":\"chart\",\"chartType\":\"gauge\",\"value\":",
-        ));
+        );
         self.parts.push_back(u32_to_bytes(self.env, value));
-        self.parts
-            .push_back(Bytes::from_slice(self.env, b",\"max\":"));
+        self.parts.push_slice(self.env, b",\"max\":");
         self.parts.push_back(u32_to_bytes(self.env, max));
-        self.parts
-            .push_back(Bytes::from_slice(self.env, b",\"label\":\""));
+        self.parts.push_slice(self.env, b",\"label\":\"");
         self.parts
             .push_back(escape_json_bytes(self.env, label.as_bytes()));
-        self.parts.push_back(Bytes::from_slice(self.env, b"\"}"));
+        self.parts.push_slice(self.env, b"\"");
+        self.finish_component();
+        self
+    }
+
+    /// Add a gauge chart component with i64 values, e.g. token amounts.
+    pub fn gauge_i64(mut self, value: i64, max: i64, label: &str) -> Self {
+        self.maybe_comma();
+        self.parts.push_slice(
+            self.env,
+            b"{\"type\":\"chart\",\"chartType\":\"gauge\",\"value\":",
+        );
+        self.parts.push_back(self.number_value(value as i128));
+        self.parts.push_slice(self.env, b",\"max\":");
+        self.parts.push_back(self.number_value(max as i128));
+        self.parts.push_slice(self.env, b",\"label\":");
+        self.push_json_str(label);
+        self.finish_component();
+        self
+    }
+
+    // ========================================================================
+    // Stats
+    // ========================================================================
+
+    /// Add a stat card showing a headline value.
+    ///
+    /// Creates: `{"type":"stat","label":"...","value":"..."}`
+    pub fn stat(mut self, label: &str, value: &str) -> Self {
+        self.push_stat_label(label);
+        self.push_json_str(value);
+        self.finish_component();
+        self
+    }
+
+    /// Add a stat card with a u32 value, formatted with digit grouping.
+    pub fn stat_u32(self, label: &str, value: u32) -> Self {
+        self.stat_i128(label, value as i128)
+    }
+
+    /// Add a stat card with an i128 value, formatted with digit grouping.
+    ///
+    /// Creates: `{"type":"stat","label":"...","value":"1,204"}`
+    pub fn stat_i128(mut self, label: &str, value: i128) -> Self {
+        self.push_stat_label(label);
+        self.push_grouped(value);
+        self.finish_component();
         self
     }
 
+    /// Add a stat card with a raw numeric value, for viewers that format
+    /// numbers themselves.
+    ///
+    /// Creates: `{"type":"stat","label":"...","value":1204}`
+    pub fn stat_number(mut self, label: &str, value: i128) -> Self {
+        self.push_stat_label(label);
+        self.parts.push_back(self.number_value(value));
+        self.finish_component();
+        self
+    }
+
+    /// Add a stat card with a change indicator.
+    ///
+    /// Creates: `{"type":"stat","label":"...","value":"...","delta":-3,"trend":"down","positive":false}`
+    ///
+    /// `trend` gives the direction of `delta`, and `positive` says whether
+    /// that direction is good news, as set by `good_direction_up` (true for
+    /// votes, false for error counts). Both are omitted when `delta` is 0.
+    pub fn stat_with_delta(
+        mut self,
+        label: &str,
+        value: &str,
+        delta: i64,
+        good_direction_up: bool,
+    ) -> Self {
+        self.push_stat_label(label);
+        self.push_json_str(value);
+        self.parts.push_slice(self.env, b",\"delta\":");
+        self.parts.push_back(self.number_value(delta as i128));
+        if delta != 0 {
+            let up = delta > 0;
+            if up {
+                self.parts.push_slice(self.env, b",\"trend\":\"up\"");
+            } else {
+                self.parts.push_slice(self.env, b",\"trend\":\"down\"");
+            }
+            if up == good_direction_up {
+                self.parts.push_slice(self.env, b",\"positive\":true");
+            } else {
+                self.parts.push_slice(self.env, b",\"positive\":false");
+            }
+        }
+        self.finish_component();
+        self
+    }
+
+    /// Push a stat component up to its `"value":` key.
+    fn push_stat_label(&mut self, label: &str) {
+        self.maybe_comma();
+        self.parts
+            .push_slice(self.env, b"{\"type\":\"stat\",\"label\":");
+        self.push_json_str(label);
+        self.parts.push_slice(self.env, b",\"value\":");
+    }
+
+    /// Push a number as a quoted string with digit grouping.
+    fn push_grouped(&mut self, value: i128) {
+        self.parts.push_slice(self.env, b"\"");
+        self.parts.push_back(i128_to_bytes_grouped(self.env, value));
+        self.parts.push_slice(self.env, b"\"");
+    }
+
     // ========================================================================
     // Container
     // ========================================================================
@@ -256,488 +765,2645 @@ impl<'a> JsonDocument<'a> {
     /// Start a container component.
     pub fn container_start(mut self, class_name: &str) -> Self {
         self.maybe_comma();
-        self.parts.push_back(Bytes::from_slice(
-            self.env,
-            b"{\"type\":\"container\",\"className\":\"",
-        ));
         self.parts
-            .push_back(escape_json_bytes(self.env, class_name.as_bytes()));
+            .push_slice(self.env, b"{\"type\":\"container\",\"className\":\"");
         self.parts
-            .push_back(Bytes::from_slice(self.env, b"\",\"components\":["));
-        // Reset component count for nested components
-        self.component_count = 0;
+            .push_back(escape_json_bytes(self.env, class_name.as_bytes()));
+        self.parts.push_slice(self.env, b"\",\"components\":[");
+        self.open_nested(Block::Container);
         self
     }
 
+    /// Open a block of nested components.
+    ///
+    /// Children are counted separately; the parent's count resumes when the
+    /// block is closed. Callers go through `maybe_comma` first, which
+    /// rejects nesting inside item blocks.
+    fn open_nested(&mut self, block: Block) {
+        self.outer_counts.push_back(self.component_count);
+        self.component_count = 0;
+        self.open_block(block);
+    }
+
     /// End a container component.
     pub fn container_end(mut self) -> Self {
-        self.parts.push_back(Bytes::from_slice(self.env, b"]}"));
-        self.component_count = 1; // Mark that we have content after container
+        self.end_block(Block::Container);
         self
     }
 
     // ========================================================================
-    // Task Component
+    // Layout
     // ========================================================================
 
-    /// Add a task component with actions.
-    pub fn task(mut self, id: u32, text: &str, completed: bool) -> TaskBuilder<'a> {
+    /// Start a grid whose child components are laid out in columns.
+    ///
+    /// Creates: `{"type":"grid","columns":2,"gap":16,"components":[...]}`
+    ///
+    /// `gap` is the spacing between cells in pixels.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// doc.grid_start(2, 16)
+    ///     .gauge(75, 100, "Quorum")
+    ///     .stat_u32("Voters", 1204)
+    ///     .grid_end()
+    /// ```
+    pub fn grid_start(mut self, columns: u32, gap: u32) -> Self {
         self.maybe_comma();
         self.parts
-            .push_back(Bytes::from_slice(self.env, b"{\"type\":\"task\",\"id\":"));
-        self.parts.push_back(u32_to_bytes(self.env, id));
-        self.parts
-            .push_back(Bytes::from_slice(self.env, b",\"text\":\""));
-        self.parts
-            .push_back(escape_json_bytes(self.env, text.as_bytes()));
-        self.parts
-            .push_back(Bytes::from_slice(self.env, b"\",\"completed\":"));
-        if completed {
-            self.parts.push_back(Bytes::from_slice(self.env, b"true"));
-        } else {
-            self.parts.push_back(Bytes::from_slice(self.env, b"false"));
-        }
-        self.parts
-            .push_back(Bytes::from_slice(self.env, b",\"actions\":["));
+            .push_slice(self.env, b"{\"type\":\"grid\",\"columns\":");
+        self.parts.push_back(u32_to_bytes(self.env, columns));
+        self.parts.push_slice(self.env, b",\"gap\":");
+        self.parts.push_back(u32_to_bytes(self.env, gap));
+        self.parts.push_slice(self.env, b",\"components\":[");
+        self.open_nested(Block::Grid);
+        self
+    }
 
-        TaskBuilder {
-            doc: self,
-            action_count: 0,
-        }
+    /// End a grid component.
+    pub fn grid_end(mut self) -> Self {
+        self.end_block(Block::Grid);
+        self
     }
 
-    /// Add a task component with dynamic text.
-    pub fn task_string(mut self, id: u32, text: &String, completed: bool) -> TaskBuilder<'a> {
+    /// Start a row whose child components are placed side by side.
+    ///
+    /// Creates: `{"type":"row","components":[...]}`
+    pub fn row_start(mut self) -> Self {
         self.maybe_comma();
         self.parts
-            .push_back(Bytes::from_slice(self.env, b"{\"type\":\"task\",\"id\":"));
-        self.parts.push_back(u32_to_bytes(self.env, id));
-        self.parts
-            .push_back(Bytes::from_slice(self.env, b",\"text\":\""));
-        self.parts.push_back(escape_json_string(self.env, text));
-        self.parts
-            .push_back(Bytes::from_slice(self.env, b"\",\"completed\":"));
-        if completed {
-            self.parts.push_back(Bytes::from_slice(self.env, b"true"));
-        } else {
-            self.parts.push_back(Bytes::from_slice(self.env, b"false"));
-        }
-        self.parts
-            .push_back(Bytes::from_slice(self.env, b",\"actions\":["));
+            .push_slice(self.env, b"{\"type\":\"row\",\"components\":[");
+        self.open_nested(Block::Row);
+        self
+    }
 
-        TaskBuilder {
-            doc: self,
-            action_count: 0,
-        }
+    /// End a row component.
+    pub fn row_end(mut self) -> Self {
+        self.end_block(Block::Row);
+        self
     }
 
     // ========================================================================
-    // Build
+    // Buttons and Links
     // ========================================================================
 
-    /// Build the final JSON Bytes output.
-    pub fn build(mut self) -> Bytes {
-        self.parts.push_back(Bytes::from_slice(self.env, b"]}"));
-        concat_bytes(self.env, &self.parts)
+    /// Add a button that invokes a contract method.
+    ///
+    /// Creates: `{"type":"button","label":"...","action":"tx:method"}`
+    pub fn button(mut self, label: &str, action: &str) -> Self {
+        self.push_button(label, action);
+        self.finish_component();
+        self
     }
-}
 
-/// Builder for form fields.
-pub struct FormBuilder<'a> {
-    doc: JsonDocument<'a>,
-    field_count: u32,
-}
+    /// Add a button that invokes a contract method with arguments.
+    ///
+    /// Creates: `{"type":"button","label":"...","action":"tx:method","args":{...}}`
+    ///
+    /// The arguments are emitted as a JSON object rather than a string.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let args = TxArgs::new(&env).add_u32("id", 5);
+    /// doc.button_tx("Delete", "delete_task", &args)
+    /// ```
+    pub fn button_tx(mut self, label: &str, method: &str, args: &TxArgs) -> Self {
+        self.push_button(label, method);
+        self.parts.push_slice(self.env, b",\"args\":");
+        self.parts.push_back(args.to_bytes());
+        self.finish_component();
+        self
+    }
 
-impl<'a> FormBuilder<'a> {
-    /// Add a comma separator if needed.
-    fn maybe_comma(&mut self) {
-        if self.field_count > 0 {
-            self.doc
-                .parts
-                .push_back(Bytes::from_slice(self.doc.env, b","));
-        }
-        self.field_count += 1;
+    /// Add a navigation link to another render path.
+    ///
+    /// Creates: `{"type":"link","label":"...","path":"..."}`
+    pub fn link(mut self, label: &str, path: &str) -> Self {
+        self.maybe_comma();
+        self.parts
+            .push_slice(self.env, b"{\"type\":\"link\",\"label\":");
+        self.push_json_str(label);
+        self.parts.push_slice(self.env, b",\"path\":");
+        self.push_json_str(path);
+        self.finish_component();
+        self
     }
 
-    /// Add a text field.
-    pub fn text_field(mut self, name: &str, placeholder: &str, required: bool) -> Self {
+    /// Push a button component up to (not including) its closing brace.
+    fn push_button(&mut self, label: &str, method: &str) {
         self.maybe_comma();
+        self.parts
+            .push_slice(self.env, b"{\"type\":\"button\",\"label\":");
+        self.push_json_str(label);
+        self.parts.push_slice(self.env, b",\"action\":\"tx:");
+        self.parts
+            .push_back(escape_json_bytes(self.env, method.as_bytes()));
+        self.parts.push_slice(self.env, b"\"");
+    }
+
+    // ========================================================================
+    // Images
+    // ========================================================================
+
+    /// Add an image component.
+    ///
+    /// Creates: `{"type":"image","src":"...","alt":"..."}`
+    pub fn image(mut self, src: &str, alt: &str) -> Self {
+        self.push_image(src, alt);
+        self.finish_component();
+        self
+    }
+
+    /// Add an image component with explicit dimensions in pixels.
+    ///
+    /// Creates: `{"type":"image","src":"...","alt":"...","width":W,"height":H}`
+    pub fn image_sized(mut self, src: &str, alt: &str, width: u32, height: u32) -> Self {
+        self.push_image(src, alt);
+        self.parts.push_slice(self.env, b",\"width\":");
+        self.parts.push_back(u32_to_bytes(self.env, width));
+        self.parts.push_slice(self.env, b",\"height\":");
+        self.parts.push_back(u32_to_bytes(self.env, height));
+        self.finish_component();
+        self
+    }
+
+    /// Add an avatar for an account or contract.
+    ///
+    /// Creates: `{"type":"avatar","address":"G...","size":N}`
+    ///
+    /// The viewer derives the image from the address, so every account gets
+    /// a stable avatar without storing one.
+    pub fn avatar(mut self, address: &Address, size: u32) -> Self {
+        self.maybe_comma();
+        self.parts
+            .push_slice(self.env, b"{\"type\":\"avatar\",\"address\":\"");
+        self.parts.push_back(address_to_bytes(self.env, address));
+        self.parts.push_slice(self.env, b"\",\"size\":");
+        self.parts.push_back(u32_to_bytes(self.env, size));
+        self.finish_component();
+        self
+    }
+
+    /// Push an image component up to (not including) its closing brace.
+    fn push_image(&mut self, src: &str, alt: &str) {
+        self.maybe_comma();
+        self.parts
+            .push_slice(self.env, b"{\"type\":\"image\",\"src\":");
+        self.push_json_str(src);
+        self.parts.push_slice(self.env, b",\"alt\":");
+        self.push_json_str(alt);
+    }
+
+    // ========================================================================
+    // Lists
+    // ========================================================================
+
+    /// Start a list component.
+    ///
+    /// Creates: `{"type":"list","ordered":false,"items":[...]}`
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// doc.list_start(false)
+    ///     .list_item("First")
+    ///     .list_item("Second")
+    ///     .list_end()
+    /// ```
+    pub fn list_start(mut self, ordered: bool) -> Self {
+        self.maybe_comma();
+        self.parts
+            .push_slice(self.env, b"{\"type\":\"list\",\"ordered\":");
+        if ordered {
+            self.parts.push_slice(self.env, b"true");
+        } else {
+            self.parts.push_slice(self.env, b"false");
+        }
+        self.parts.push_slice(self.env, b",\"items\":[");
+        self.item_count = 0;
+        self.open_block(Block::List);
+        self
+    }
+
+    /// Add a list item. Must be called between list_start and list_end.
+    pub fn list_item(mut self, text: &str) -> Self {
+        self.require_open(Block::List, "list_item");
+        self.maybe_item_comma();
+        self.push_json_str(text);
+        self
+    }
+
+    /// Add a list item with dynamic text from a String.
+    pub fn list_item_string(mut self, text: &String) -> Self {
+        self.require_open(Block::List, "list_item_string");
+        self.maybe_item_comma();
+        self.parts.push_slice(self.env, b"\"");
+        self.parts.push_back(escape_json_string(self.env, text));
+        self.parts.push_slice(self.env, b"\"");
+        self
+    }
+
+    /// End a list component.
+    pub fn list_end(mut self) -> Self {
+        self.end_block(Block::List);
+        self
+    }
+
+    // ========================================================================
+    // Key-Value Lists
+    // ========================================================================
+
+    /// Start a key-value list component.
+    ///
+    /// Creates: `{"type":"keyvalue","items":[{"label":"...","value":...},...]}`
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// doc.kv_list_start()
+    ///     .kv_address("Owner", &owner)
+    ///     .kv_u32("Votes", 12)
+    ///     .kv("Status", "Open")
+    ///     .kv_list_end()
+    /// ```
+    pub fn kv_list_start(mut self) -> Self {
+        self.maybe_comma();
+        self.parts
+            .push_slice(self.env, b"{\"type\":\"keyvalue\",\"items\":[");
+        self.item_count = 0;
+        self.open_block(Block::KeyValue);
+        self
+    }
+
+    /// Add a labelled text value to the open key-value list.
+    pub fn kv(mut self, label: &str, value: &str) -> Self {
+        self.push_kv_label(label);
+        self.push_json_str(value);
+        self.parts.push_slice(self.env, b"}");
+        self
+    }
+
+    /// Add a labelled soroban_sdk::String value.
+    pub fn kv_string(mut self, label: &str, value: &String) -> Self {
+        self.push_kv_label(label);
+        self.parts.push_slice(self.env, b"\"");
+        self.parts.push_back(escape_json_string(self.env, value));
+        self.parts.push_slice(self.env, b"\"}");
+        self
+    }
+
+    /// Add a labelled numeric value.
+    pub fn kv_u32(mut self, label: &str, value: u32) -> Self {
+        self.push_kv_label(label);
+        self.parts.push_back(u32_to_bytes(self.env, value));
+        self.parts.push_slice(self.env, b"}");
+        self
+    }
+
+    /// Add a labelled signed or wide numeric value.
+    pub fn kv_i128(mut self, label: &str, value: i128) -> Self {
+        self.push_kv_label(label);
+        self.parts.push_back(self.number_value(value));
+        self.parts.push_slice(self.env, b"}");
+        self
+    }
+
+    /// Add a labelled Address, rendered as its full strkey.
+    pub fn kv_address(mut self, label: &str, value: &Address) -> Self {
+        self.push_kv_label(label);
+        self.parts.push_slice(self.env, b"\"");
+        self.parts.push_back(address_to_bytes(self.env, value));
+        self.parts.push_slice(self.env, b"\"}");
+        self
+    }
+
+    /// End a key-value list component.
+    pub fn kv_list_end(mut self) -> Self {
+        self.end_block(Block::KeyValue);
+        self
+    }
+
+    /// Push a key-value item up to its `"value":` key.
+    fn push_kv_label(&mut self, label: &str) {
+        self.require_open(Block::KeyValue, "kv");
+        self.maybe_item_comma();
+        self.parts.push_slice(self.env, b"{\"label\":");
+        self.push_json_str(label);
+        self.parts.push_slice(self.env, b",\"value\":");
+    }
+
+    // ========================================================================
+    // Tables
+    // ========================================================================
+
+    /// Start a table component with column headers.
+    ///
+    /// Creates: `{"type":"table","columns":[...],"rows":[[...],...]}`
+    ///
+    /// Rows are added with `table_row()` for whole rows of text, or with
+    /// `table_row_start()`, `table_cell*()`, and `table_row_end()` when
+    /// cells mix text and numbers.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// doc.table_start(&["Name", "Votes"])
+    ///     .table_row(&["Alice", "12"])
+    ///     .table_row_start()
+    ///     .table_cell("Bob")
+    ///     .table_cell_u32(7)
+    ///     .table_row_end()
+    ///     .table_end()
+    /// ```
+    pub fn table_start(mut self, columns: &[&str]) -> Self {
+        self.maybe_comma();
+        self.parts
+            .push_slice(self.env, b"{\"type\":\"table\",\"columns\":[");
+        for (i, column) in columns.iter().enumerate() {
+            if i > 0 {
+                self.parts.push_slice(self.env, b",");
+            }
+            self.push_json_str(column);
+        }
+        self.parts.push_slice(self.env, b"],\"rows\":[");
+        self.item_count = 0;
+        self.open_block(Block::Table);
+        self
+    }
+
+    /// Add a complete row of text cells.
+    pub fn table_row(self, cells: &[&str]) -> Self {
+        let mut doc = self.table_row_start();
+        for cell in cells {
+            doc = doc.table_cell(cell);
+        }
+        doc.table_row_end()
+    }
+
+    /// Start a table row to be filled with `table_cell*()` calls.
+    pub fn table_row_start(mut self) -> Self {
+        self.require_open(Block::Table, "table_row_start");
+        self.maybe_item_comma();
+        self.parts.push_slice(self.env, b"[");
+        self.cell_count = 0;
+        self.open_block(Block::TableRow);
+        self
+    }
+
+    /// Add a text cell to the open row.
+    pub fn table_cell(mut self, text: &str) -> Self {
+        self.require_open(Block::TableRow, "table_cell");
+        self.maybe_cell_comma();
+        self.push_json_str(text);
+        self
+    }
+
+    /// Add a numeric cell to the open row.
+    pub fn table_cell_u32(mut self, value: u32) -> Self {
+        self.require_open(Block::TableRow, "table_cell_u32");
+        self.maybe_cell_comma();
+        self.parts.push_back(u32_to_bytes(self.env, value));
+        self
+    }
+
+    /// Add a signed or wide numeric cell to the open row.
+    pub fn table_cell_i128(mut self, value: i128) -> Self {
+        self.require_open(Block::TableRow, "table_cell_i128");
+        self.maybe_cell_comma();
+        self.parts.push_back(self.number_value(value));
+        self
+    }
+
+    /// End the open table row.
+    pub fn table_row_end(mut self) -> Self {
+        self.end_block(Block::TableRow);
+        self
+    }
+
+    /// End a table component.
+    pub fn table_end(mut self) -> Self {
+        self.end_block(Block::Table);
+        self
+    }
+
+    // ========================================================================
+    // Task Component
+    // ========================================================================
+
+    /// Add a task component with actions.
+    pub fn task(mut self, id: u32, text: &str, completed: bool) -> TaskBuilder<'a> {
+        self.maybe_comma();
+        self.parts
+            .push_slice(self.env, b"{\"type\":\"task\",\"id\":");
+        self.parts.push_back(u32_to_bytes(self.env, id));
+        self.parts.push_slice(self.env, b",\"text\":\"");
+        self.parts
+            .push_back(escape_json_bytes(self.env, text.as_bytes()));
+        self.parts.push_slice(self.env, b"\",\"completed\":");
+        if completed {
+            self.parts.push_slice(self.env, b"true");
+        } else {
+            self.parts.push_slice(self.env, b"false");
+        }
+        self.parts.push_slice(self.env, b",\"actions\":[");
+
+        TaskBuilder {
+            doc: self,
+            action_count: 0,
+            action_open: false,
+            action_has_confirm: false,
+        }
+    }
+
+    /// Add a task component with dynamic text.
+    pub fn task_string(mut self, id: u32, text: &String, completed: bool) -> TaskBuilder<'a> {
+        self.maybe_comma();
+        self.parts
+            .push_slice(self.env, b"{\"type\":\"task\",\"id\":");
+        self.parts.push_back(u32_to_bytes(self.env, id));
+        self.parts.push_slice(self.env, b",\"text\":\"");
+        self.parts.push_back(escape_json_string(self.env, text));
+        self.parts.push_slice(self.env, b"\",\"completed\":");
+        if completed {
+            self.parts.push_slice(self.env, b"true");
+        } else {
+            self.parts.push_slice(self.env, b"false");
+        }
+        self.parts.push_slice(self.env, b",\"actions\":[");
+
+        TaskBuilder {
+            doc: self,
+            action_count: 0,
+            action_open: false,
+            action_has_confirm: false,
+        }
+    }
+
+    // ========================================================================
+    // Progressive Loading / Continuation
+    // ========================================================================
+
+    /// Add a continuation marker for the rest of a chunked collection.
+    ///
+    /// Attribute names match the markdown `{{continue ...}}` tag so viewers
+    /// can share loading logic between formats. `total` is omitted when `None`.
+    ///
+    /// Creates: `{"type":"continue","collection":"comments","from":5,"total":50}`
+    pub fn continuation(mut self, collection: &str, from: u32, total: Option<u32>) -> Self {
+        self.maybe_comma();
+        self.parts
+            .push_slice(self.env, b"{\"type\":\"continue\",\"collection\":");
+        self.push_json_str(collection);
+        self.parts.push_slice(self.env, b",\"from\":");
+        self.parts.push_back(u32_to_bytes(self.env, from));
+        if let Some(total) = total {
+            self.parts.push_slice(self.env, b",\"total\":");
+            self.parts.push_back(u32_to_bytes(self.env, total));
+        }
+        self.finish_component();
+        self
+    }
+
+    /// Add a reference to a single chunk for the viewer to fetch and insert.
+    ///
+    /// The placeholder, shown while loading, is omitted when `None`.
+    ///
+    /// Creates: `{"type":"chunk","collection":"...","index":N,"placeholder":"..."}`
+    pub fn chunk_ref(mut self, collection: &str, index: u32, placeholder: Option<&str>) -> Self {
+        self.maybe_comma();
+        self.parts
+            .push_slice(self.env, b"{\"type\":\"chunk\",\"collection\":");
+        self.push_json_str(collection);
+        self.parts.push_slice(self.env, b",\"index\":");
+        self.parts.push_back(u32_to_bytes(self.env, index));
+        if let Some(placeholder) = placeholder {
+            self.parts.push_slice(self.env, b",\"placeholder\":");
+            self.push_json_str(placeholder);
+        }
+        self.finish_component();
+        self
+    }
+
+    /// Add a marker telling the viewer to render another path in place.
+    ///
+    /// Creates: `{"type":"render","path":"..."}`
+    pub fn render_continue(mut self, path: &str) -> Self {
+        self.maybe_comma();
+        self.parts
+            .push_slice(self.env, b"{\"type\":\"render\",\"path\":");
+        self.push_json_str(path);
+        self.finish_component();
+        self
+    }
+
+    // ========================================================================
+    // Styling
+    // ========================================================================
+
+    /// Add a CSS class to the component added just before this call.
+    ///
+    /// Applies to single components such as headings, text, stats, buttons,
+    /// and to forms and tasks once closed. Lists, tables, and other blocks
+    /// are already closed by their `*_end` call, so wrap them in
+    /// `container_start` instead.
+    ///
+    /// A component takes one class attribute; pass several classes in one
+    /// space-separated string. A second `with_class` on the same component
+    /// panics in debug builds and is ignored in release builds, so the
+    /// output never repeats the `className` key.
+    ///
+    /// # Panics
+    ///
+    /// If no component can take a class at this point.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// doc.heading(1, "Proposals")
+    ///     .with_class("page-title")
+    ///     .button("Vote", "vote")
+    ///     .with_class("primary")
+    /// ```
+    pub fn with_class(mut self, class_name: &str) -> Self {
+        if !self.component_open {
+            panic!("with_class called with no component to apply it to");
+        }
+        if self.component_has_class {
+            if cfg!(debug_assertions) {
+                panic!("with_class called twice on the same component");
+            }
+            return self;
+        }
+        self.component_has_class = true;
+        self.parts.push_slice(self.env, b",\"className\":");
+        self.push_json_str(class_name);
+        self
+    }
+
+    // ========================================================================
+    // Introspection
+    // ========================================================================
+
+    /// Number of bytes `build()` would currently return.
+    ///
+    /// Includes the closing `]}` that `build()` appends. Tracked as
+    /// components are added, so this is O(1).
+    pub fn approx_len(&self) -> u32 {
+        self.header_len() + self.parts.len() + self.component_open as u32 + 2
+    }
+
+    /// Check whether no components have been added yet.
+    ///
+    /// Looks at the top level of the document, so an open container counts
+    /// as a component even before anything is added to it.
+    pub fn is_empty(&self) -> bool {
+        let root_count = self.outer_counts.first().unwrap_or(self.component_count);
+        root_count == 0
+    }
+
+    /// Number of fragments accumulated so far, including the document header.
+    pub fn part_count(&self) -> u32 {
+        self.parts.count() + 1
+    }
+
+    // ========================================================================
+    // Combinators
+    // ========================================================================
+
+    /// Apply `f` only when `cond` is true.
+    pub fn when<F>(self, cond: bool, f: F) -> Self
+    where
+        F: FnOnce(Self) -> Self,
+    {
+        if cond { f(self) } else { self }
+    }
+
+    /// Apply `f` with the contained value when `opt` is `Some`.
+    pub fn when_some<T, F>(self, opt: Option<T>, f: F) -> Self
+    where
+        F: FnOnce(Self, T) -> Self,
+    {
+        match opt {
+            Some(value) => f(self, value),
+            None => self,
+        }
+    }
+
+    /// Apply `f` for each item in a soroban_sdk::Vec, in order.
+    pub fn each<T, F>(self, items: &Vec<T>, mut f: F) -> Self
+    where
+        T: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone,
+        F: FnMut(Self, T) -> Self,
+    {
+        let mut builder = self;
+        for item in items.iter() {
+            builder = f(builder, item);
+        }
+        builder
+    }
+
+    // ========================================================================
+    // Error Pages
+    // ========================================================================
+
+    /// Build a complete error document.
+    ///
+    /// Mirrors `MarkdownBuilder::error_page`: a heading with the status code
+    /// and title, the message as a markdown warning callout with every line
+    /// quoted, and a link back to `back_path`.
+    pub fn error_page(
+        env: &'a Env,
+        code: u32,
+        title: &str,
+        message: &str,
+        back_path: &str,
+    ) -> Bytes {
+        let mut heading = u32_to_bytes(env, code);
+        heading.push_back(b' ');
+        heading.extend_from_slice(title.as_bytes());
+
+        let mut callout = Bytes::from_slice(env, b"> [!WARNING]");
+        for line in message.split('\n') {
+            let prefix: &[u8] = if line.is_empty() { b"\n>" } else { b"\n> " };
+            callout.extend_from_slice(prefix);
+            callout.extend_from_slice(line.as_bytes());
+        }
+
+        Self::new(env, title)
+            .heading_string(1, &bytes_to_string(env, &heading))
+            .markdown_string(&bytes_to_string(env, &callout))
+            .link("Go back", back_path)
+            .build()
+    }
+
+    // ========================================================================
+    // Build
+    // ========================================================================
+
+    /// Build the final JSON Bytes output.
+    ///
+    /// Blocks left open panic in debug builds and are closed in release
+    /// builds, so the output is always well-formed.
+    pub fn build(mut self) -> Bytes {
+        if cfg!(debug_assertions)
+            && let Some(top) = self.open_blocks.last()
+        {
+            panic!(
+                "JsonDocument built with an unclosed {} block",
+                Block::from_u32(top).name()
+            );
+        }
+        self.close_open_blocks();
+        self.parts.push_slice(self.env, b"]}");
+        let mut output = self.header();
+        output.append(&self.parts.build(self.env));
+        output
+    }
+
+    /// Build the final JSON output as a String.
+    ///
+    /// Documents over 16KB are replaced with a placeholder (see
+    /// `bytes_to_string`).
+    pub fn build_string(self) -> String {
+        let env = self.env;
+        bytes_to_string(env, &self.build())
+    }
+}
+
+/// Whether a navigation item for `path` is active at `current`.
+#[cfg(feature = "router")]
+fn nav_path_active(path: &[u8], current: &Bytes) -> bool {
+    if path_eq(current, path) {
+        return true;
+    }
+    if path.len() <= 1 || !path_starts_with(current, path) {
+        return false;
+    }
+    // Only match whole segments, so `/post` is not active for `/posts`.
+    path.ends_with(b"/") || current.get(path.len() as u32) == Some(b'/')
+}
+
+/// Build a 404 document with a link back to `back_path`.
+pub fn not_found(env: &Env, back_path: &str) -> Bytes {
+    JsonDocument::error_page(
+        env,
+        404,
+        "Not Found",
+        "The page you requested does not exist.",
+        back_path,
+    )
+}
+
+/// Build a 401 document with a link back to `back_path`.
+pub fn unauthorized(env: &Env, back_path: &str) -> Bytes {
+    JsonDocument::error_page(
+        env,
+        401,
+        "Unauthorized",
+        "You do not have permission to view this page.",
+        back_path,
+    )
+}
+
+/// Builder for form fields.
+pub struct FormBuilder<'a> {
+    doc: JsonDocument<'a>,
+    field_count: u32,
+    redirect: Option<Bytes>,
+}
+
+impl<'a> FormBuilder<'a> {
+    /// Add a comma separator if needed.
+    fn maybe_comma(&mut self) {
+        if self.field_count > 0 {
+            self.doc.parts.push_slice(self.doc.env, b",");
+        }
+        self.field_count += 1;
+    }
+
+    /// Add a text field.
+    pub fn text_field(mut self, name: &str, placeholder: &str, required: bool) -> Self {
+        self.maybe_comma();
+        self.doc.parts.push_slice(self.doc.env, b"{\"name\":\"");
         self.doc
             .parts
-            .push_back(Bytes::from_slice(self.doc.env, b"{\"name\":\""));
-        self.doc
-            .parts
-            .push_back(escape_json_bytes(self.doc.env, name.as_bytes()));
-        self.doc.parts.push_back(Bytes::from_slice(
-            self.doc.env,
-            b"\",\"type\":\"text\",\"placeholder\":\"",
-        ));
-        self.doc
-            .parts
-            .push_back(escape_json_bytes(self.doc.env, placeholder.as_bytes()));
+            .push_back(escape_json_bytes(self.doc.env, name.as_bytes()));
+        self.doc
+            .parts
+            .push_slice(self.doc.env, b"\",\"type\":\"text\",\"placeholder\":\"");
         self.doc
             .parts
-            .push_back(Bytes::from_slice(self.doc.env, b"\""));
+            .push_back(escape_json_bytes(self.doc.env, placeholder.as_bytes()));
+        self.doc.parts.push_slice(self.doc.env, b"\"");
         if required {
             self.doc
                 .parts
-                .push_back(Bytes::from_slice(self.doc.env, b",\"required\":true"));
+                .push_slice(self.doc.env, b",\"required\":true");
         }
-        self.doc
-            .parts
-            .push_back(Bytes::from_slice(self.doc.env, b"}"));
+        self.doc.parts.push_slice(self.doc.env, b"}");
         self
     }
 
     /// Add a textarea field.
     pub fn textarea_field(mut self, name: &str, placeholder: &str) -> Self {
         self.maybe_comma();
+        self.doc.parts.push_slice(self.doc.env, b"{\"name\":\"");
         self.doc
             .parts
-            .push_back(Bytes::from_slice(self.doc.env, b"{\"name\":\""));
+            .push_back(escape_json_bytes(self.doc.env, name.as_bytes()));
         self.doc
             .parts
-            .push_back(escape_json_bytes(self.doc.env, name.as_bytes()));
-        self.doc.parts.push_back(Bytes::from_slice(
-            self.doc.env,
-            b"\",\"type\":\"textarea\",\"placeholder\":\"",
-        ));
+            .push_slice(self.doc.env, b"\",\"type\":\"textarea\",\"placeholder\":\"");
         self.doc
             .parts
             .push_back(escape_json_bytes(self.doc.env, placeholder.as_bytes()));
+        self.doc.parts.push_slice(self.doc.env, b"\"}");
+        self
+    }
+
+    /// Push a field that carries a pre-populated (already escaped) value.
+    fn push_value_field(
+        &mut self,
+        name: &str,
+        field_type: &[u8],
+        placeholder: &str,
+        escaped_value: Bytes,
+        required: bool,
+    ) {
+        self.push_field_head(name, field_type);
         self.doc
             .parts
-            .push_back(Bytes::from_slice(self.doc.env, b"\"}"));
+            .push_slice(self.doc.env, b",\"placeholder\":");
+        self.doc.push_json_str(placeholder);
+        self.doc.parts.push_slice(self.doc.env, b",\"value\":\"");
+        self.doc.parts.push_back(escaped_value);
+        self.doc.parts.push_slice(self.doc.env, b"\"");
+        if required {
+            self.doc
+                .parts
+                .push_slice(self.doc.env, b",\"required\":true");
+        }
+        self.doc.parts.push_slice(self.doc.env, b"}");
+    }
+
+    /// Add a text field pre-populated with a value, for edit forms.
+    pub fn text_field_with_value(
+        mut self,
+        name: &str,
+        placeholder: &str,
+        value: &str,
+        required: bool,
+    ) -> Self {
+        let value = escape_json_bytes(self.doc.env, value.as_bytes());
+        self.push_value_field(name, b"text", placeholder, value, required);
+        self
+    }
+
+    /// Add a text field pre-populated with a value from a String.
+    pub fn text_field_with_value_string(
+        mut self,
+        name: &str,
+        placeholder: &str,
+        value: &String,
+        required: bool,
+    ) -> Self {
+        let value = escape_json_string(self.doc.env, value);
+        self.push_value_field(name, b"text", placeholder, value, required);
+        self
+    }
+
+    /// Add a textarea field pre-populated with a value.
+    pub fn textarea_field_with_value(mut self, name: &str, placeholder: &str, value: &str) -> Self {
+        let value = escape_json_bytes(self.doc.env, value.as_bytes());
+        self.push_value_field(name, b"textarea", placeholder, value, false);
         self
     }
 
-    /// Complete the form with a submit label.
-    pub fn submit(mut self, label: &str) -> JsonDocument<'a> {
-        self.doc
-            .parts
-            .push_back(Bytes::from_slice(self.doc.env, b"],\"submitLabel\":\""));
-        self.doc
-            .parts
-            .push_back(escape_json_bytes(self.doc.env, label.as_bytes()));
-        self.doc
-            .parts
-            .push_back(Bytes::from_slice(self.doc.env, b"\"}"));
-        self.doc
+    /// Set the path viewers navigate to after a successful submission.
+    ///
+    /// Emitted as `"redirect"` on the form object when `submit()` closes it;
+    /// calling this again replaces the earlier path.
+    pub fn redirect(mut self, path: &str) -> Self {
+        self.redirect = Some(escape_json_bytes(self.doc.env, path.as_bytes()));
+        self
+    }
+
+    /// Start a field object with its name and type.
+    fn push_field_head(&mut self, name: &str, field_type: &[u8]) {
+        self.maybe_comma();
+        self.doc.parts.push_slice(self.doc.env, b"{\"name\":");
+        self.doc.push_json_str(name);
+        self.doc.parts.push_slice(self.doc.env, b",\"type\":\"");
+        self.doc.parts.push_slice(self.doc.env, field_type);
+        self.doc.parts.push_slice(self.doc.env, b"\"");
+    }
+
+    /// Add a select (dropdown) field.
+    ///
+    /// Options are `(value, label)` pairs. `selected` names the value that
+    /// starts selected and is omitted when `None`.
+    ///
+    /// Creates: `{"name":"...","type":"select","label":"...","options":[{"value":"...","label":"..."}]}`
+    pub fn select_field(
+        mut self,
+        name: &str,
+        label: &str,
+        options: &[(&str, &str)],
+        selected: Option<&str>,
+    ) -> Self {
+        self.push_field_head(name, b"select");
+        self.doc.parts.push_slice(self.doc.env, b",\"label\":");
+        self.doc.push_json_str(label);
+        self.doc.parts.push_slice(self.doc.env, b",\"options\":[");
+        for (i, (value, option_label)) in options.iter().enumerate() {
+            if i > 0 {
+                self.doc.parts.push_slice(self.doc.env, b",");
+            }
+            self.doc.parts.push_slice(self.doc.env, b"{\"value\":");
+            self.doc.push_json_str(value);
+            self.doc.parts.push_slice(self.doc.env, b",\"label\":");
+            self.doc.push_json_str(option_label);
+            self.doc.parts.push_slice(self.doc.env, b"}");
+        }
+        self.doc.parts.push_slice(self.doc.env, b"]");
+        if let Some(selected) = selected {
+            self.doc.parts.push_slice(self.doc.env, b",\"selected\":");
+            self.doc.push_json_str(selected);
+        }
+        self.doc.parts.push_slice(self.doc.env, b"}");
+        self
+    }
+
+    /// Add a number field with optional bounds.
+    pub fn number_field(
+        mut self,
+        name: &str,
+        placeholder: &str,
+        min: Option<i64>,
+        max: Option<i64>,
+        required: bool,
+    ) -> Self {
+        self.push_field_head(name, b"number");
+        self.doc
+            .parts
+            .push_slice(self.doc.env, b",\"placeholder\":");
+        self.doc.push_json_str(placeholder);
+        if let Some(min) = min {
+            self.doc.parts.push_slice(self.doc.env, b",\"min\":");
+            self.doc.parts.push_back(i64_to_bytes(self.doc.env, min));
+        }
+        if let Some(max) = max {
+            self.doc.parts.push_slice(self.doc.env, b",\"max\":");
+            self.doc.parts.push_back(i64_to_bytes(self.doc.env, max));
+        }
+        if required {
+            self.doc
+                .parts
+                .push_slice(self.doc.env, b",\"required\":true");
+        }
+        self.doc.parts.push_slice(self.doc.env, b"}");
+        self
+    }
+
+    /// Add a hidden field carrying a fixed value with the submission.
+    pub fn hidden_field(mut self, name: &str, value: &str) -> Self {
+        self.push_field_head(name, b"hidden");
+        self.doc.parts.push_slice(self.doc.env, b",\"value\":");
+        self.doc.push_json_str(value);
+        self.doc.parts.push_slice(self.doc.env, b"}");
+        self
+    }
+
+    /// Add a hidden field with a numeric value.
+    pub fn hidden_field_u32(mut self, name: &str, value: u32) -> Self {
+        self.push_field_head(name, b"hidden");
+        self.doc.parts.push_slice(self.doc.env, b",\"value\":");
+        self.doc.parts.push_back(u32_to_bytes(self.doc.env, value));
+        self.doc.parts.push_slice(self.doc.env, b"}");
+        self
+    }
+
+    /// Add a checkbox field. `"checked"` is only emitted when true.
+    pub fn checkbox_field(mut self, name: &str, label: &str, checked: bool) -> Self {
+        self.push_field_head(name, b"checkbox");
+        self.doc.parts.push_slice(self.doc.env, b",\"label\":");
+        self.doc.push_json_str(label);
+        if checked {
+            self.doc
+                .parts
+                .push_slice(self.doc.env, b",\"checked\":true");
+        }
+        self.doc.parts.push_slice(self.doc.env, b"}");
+        self
+    }
+
+    /// Complete the form with a submit label.
+    pub fn submit(mut self, label: &str) -> JsonDocument<'a> {
+        self.doc.parts.push_slice(self.doc.env, b"]");
+        if let Some(redirect) = self.redirect {
+            self.doc.parts.push_slice(self.doc.env, b",\"redirect\":\"");
+            self.doc.parts.push_back(redirect);
+            self.doc.parts.push_slice(self.doc.env, b"\"");
+        }
+        self.doc
+            .parts
+            .push_slice(self.doc.env, b",\"submitLabel\":\"");
+        self.doc
+            .parts
+            .push_back(escape_json_bytes(self.doc.env, label.as_bytes()));
+        self.doc.parts.push_slice(self.doc.env, b"\"");
+        self.doc.finish_component();
+        self.doc
+    }
+}
+
+/// Builder for task actions.
+pub struct TaskBuilder<'a> {
+    doc: JsonDocument<'a>,
+    action_count: u32,
+    /// Whether the last action's closing brace is still pending, so that
+    /// modifiers like `confirm` can add keys to it.
+    action_open: bool,
+    /// Whether `confirm` has already added a message to that action.
+    action_has_confirm: bool,
+}
+
+impl<'a> TaskBuilder<'a> {
+    /// Add a comma separator if needed, closing the previous action first.
+    fn maybe_comma(&mut self) {
+        self.close_action();
+        if self.action_count > 0 {
+            self.doc.parts.push_slice(self.doc.env, b",");
+        }
+        self.action_count += 1;
+    }
+
+    /// Emit the closing brace of the last action if it is still open.
+    fn close_action(&mut self) {
+        if self.action_open {
+            self.doc.parts.push_slice(self.doc.env, b"}");
+            self.action_open = false;
+        }
+    }
+
+    /// Push a transaction action, leaving its object open.
+    fn push_tx_action(&mut self, method: &str, args: Bytes, label: &str) {
+        self.maybe_comma();
+        self.doc
+            .parts
+            .push_slice(self.doc.env, b"{\"type\":\"tx\",\"method\":");
+        self.doc.push_json_str(method);
+        self.doc.parts.push_slice(self.doc.env, b",\"args\":");
+        self.doc.parts.push_back(args);
+        self.doc.parts.push_slice(self.doc.env, b",\"label\":");
+        self.doc.push_json_str(label);
+        self.action_open = true;
+        self.action_has_confirm = false;
+    }
+
+    /// Add a transaction action.
+    pub fn tx_action(mut self, method: &str, id: u32, label: &str) -> Self {
+        let args = TxArgs::new(self.doc.env).add_u32("id", id).to_bytes();
+        self.push_tx_action(method, args, label);
+        self
+    }
+
+    /// Add a transaction action with arbitrary arguments.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// doc.task(5, "Write docs", false)
+    ///     .tx_action_args(
+    ///         "set_status",
+    ///         &TxArgs::new(&env).add_u32("id", 5).add_str("status", "archived"),
+    ///         "Archive",
+    ///     )
+    ///     .end()
+    /// ```
+    pub fn tx_action_args(mut self, method: &str, args: &TxArgs, label: &str) -> Self {
+        self.push_tx_action(method, args.to_bytes(), label);
+        self
+    }
+
+    /// Add a transaction action with a single string argument.
+    pub fn tx_action_str(mut self, method: &str, key: &str, value: &str, label: &str) -> Self {
+        let args = TxArgs::new(self.doc.env).add_str(key, value).to_bytes();
+        self.push_tx_action(method, args, label);
+        self
+    }
+
+    /// Ask viewers to confirm before signing the previous action.
+    ///
+    /// Adds `"confirm":"message"` to the most recently added action. Has no
+    /// effect when no action has been added yet. Calling it twice on the
+    /// same action panics in debug builds; release builds keep the first
+    /// message so the output never repeats the `confirm` key.
+    pub fn confirm(mut self, message: &str) -> Self {
+        if !self.action_open {
+            return self;
+        }
+        if self.action_has_confirm {
+            if cfg!(debug_assertions) {
+                panic!("confirm called twice on the same action");
+            }
+            return self;
+        }
+        self.action_has_confirm = true;
+        self.doc.parts.push_slice(self.doc.env, b",\"confirm\":");
+        self.doc.push_json_str(message);
+        self
+    }
+
+    /// Complete the task.
+    pub fn end(mut self) -> JsonDocument<'a> {
+        self.close_action();
+        self.doc.parts.push_slice(self.doc.env, b"]");
+        self.doc.finish_component();
+        self.doc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testutils::{assert_contains, assert_output_eq, to_str};
+
+    #[cfg(test)]
+    extern crate alloc;
+
+    /// The output of a document titled "Test" holding `components`.
+    fn document(components: &str) -> alloc::string::String {
+        alloc::format!(
+            "{{\"format\":\"soroban-render-json-v1\",\"title\":\"Test\",\"components\":[{components}]}}"
+        )
+    }
+
+    #[test]
+    fn test_empty_document() {
+        let env = Env::default();
+        let output = JsonDocument::new(&env, "Test").build();
+        assert_output_eq(&output, &document(""));
+    }
+
+    #[test]
+    fn test_heading() {
+        let env = Env::default();
+        let output = JsonDocument::new(&env, "Test").heading(1, "Hello").build();
+        assert_output_eq(
+            &output,
+            &document(r#"{"type":"heading","level":1,"text":"Hello"}"#),
+        );
+    }
+
+    #[test]
+    fn test_heading_level_clamping() {
+        let env = Env::default();
+        let title = String::from_str(&env, "Dynamic");
+        let output = JsonDocument::new(&env, "Test")
+            .heading(0, "Zero")
+            .heading(7, "Seven")
+            .heading(255, "Max")
+            .heading_string(0, &title)
+            .heading_string(255, &title)
+            .build();
+        assert_output_eq(
+            &output,
+            &document(concat!(
+                r#"{"type":"heading","level":1,"text":"Zero"},"#,
+                r#"{"type":"heading","level":6,"text":"Seven"},"#,
+                r#"{"type":"heading","level":6,"text":"Max"},"#,
+                r#"{"type":"heading","level":1,"text":"Dynamic"},"#,
+                r#"{"type":"heading","level":6,"text":"Dynamic"}"#,
+            )),
+        );
+    }
+
+    #[test]
+    fn test_text() {
+        let env = Env::default();
+        let output = JsonDocument::new(&env, "Test")
+            .text("Hello, World!")
+            .build();
+        assert_output_eq(
+            &output,
+            &document(r#"{"type":"text","content":"Hello, World!"}"#),
+        );
+    }
+
+    #[test]
+    fn test_divider() {
+        let env = Env::default();
+        let output = JsonDocument::new(&env, "Test").divider().build();
+        assert_output_eq(&output, &document(r#"{"type":"divider"}"#));
+    }
+
+    #[test]
+    fn test_multiple_components() {
+        let env = Env::default();
+        let output = JsonDocument::new(&env, "Test")
+            .heading(1, "Title")
+            .text("Content")
+            .divider()
+            .build();
+        assert_output_eq(
+            &output,
+            &document(
+                r#"{"type":"heading","level":1,"text":"Title"},{"type":"text","content":"Content"},{"type":"divider"}"#,
+            ),
+        );
+    }
+
+    #[test]
+    fn test_form() {
+        let env = Env::default();
+        let output = JsonDocument::new(&env, "Test")
+            .form("add_item")
+            .text_field("name", "Enter name", true)
+            .submit("Add")
+            .build();
+        assert_output_eq(
+            &output,
+            &document(
+                r#"{"type":"form","action":"add_item","fields":[{"name":"name","type":"text","placeholder":"Enter name","required":true}],"submitLabel":"Add"}"#,
+            ),
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_navigation() {
+        let env = Env::default();
+        let output = JsonDocument::new(&env, "Test")
+            .nav_start()
+            .nav_item("Home", "/", true, true)
+            .nav_item("About", "/about", false, false)
+            .nav_end()
+            .build();
+        assert_output_eq(
+            &output,
+            &document(
+                r#"{"type":"navigation","items":[{"label":"Home","path":"/","active":true},{"label":"About","path":"/about"}]}"#,
+            ),
+        );
+    }
+
+    #[test]
+    fn test_pie_chart() {
+        let env = Env::default();
+        let output = JsonDocument::new(&env, "Test")
+            .pie_chart_start("Status")
+            .pie_item("Done", 5, "#22c55e")
+            .pie_item("Pending", 3, "#eab308")
+            .pie_chart_end()
+            .build();
+        assert_output_eq(
+            &output,
+            &document(
+                r##"{"type":"chart","chartType":"pie","title":"Status","data":[{"label":"Done","value":5,"color":"#22c55e"},{"label":"Pending","value":3,"color":"#eab308"}]}"##,
+            ),
+        );
+    }
+
+    #[test]
+    fn test_gauge() {
+        let env = Env::default();
+        let output = JsonDocument::new(&env, "Test")
+            .gauge(75, 100, "Progress")
+            .build();
+        assert_output_eq(
+            &output,
+            &document(
+                r#"{"type":"chart","chartType":"gauge","value":75,"max":100,"label":"Progress"}"#,
+            ),
+        );
+    }
+
+    #[test]
+    fn test_task() {
+        let env = Env::default();
+        let output = JsonDocument::new(&env, "Test")
+            .task(1, "My Task", false)
+            .tx_action("complete", 1, "Done")
+            .tx_action("delete", 1, "Delete")
+            .end()
+            .build();
+        assert_output_eq(
+            &output,
+            &document(
+                r#"{"type":"task","id":1,"text":"My Task","completed":false,"actions":[{"type":"tx","method":"complete","args":{"id":1},"label":"Done"},{"type":"tx","method":"delete","args":{"id":1},"label":"Delete"}]}"#,
+            ),
+        );
+    }
+
+    // ==========================================================================
+    // Content validation tests
+    // ==========================================================================
+
+    #[test]
+    fn test_heading_string_with_soroban_string() {
+        let env = Env::default();
+        let title = String::from_str(&env, "Dynamic Title");
+        let output = JsonDocument::new(&env, "Test")
+            .heading_string(1, &title)
+            .build();
+        let content = to_str(&output);
+        assert!(content.contains(r#""type":"heading""#));
+        assert!(content.contains(r#""level":1"#));
+        assert!(content.contains(r#""text":"Dynamic Title""#));
+    }
+
+    #[test]
+    fn test_text_string_with_soroban_string() {
+        let env = Env::default();
+        let text = String::from_str(&env, "Dynamic content");
+        let output = JsonDocument::new(&env, "Test").text_string(&text).build();
+        let content = to_str(&output);
+        assert!(content.contains(r#""type":"text""#));
+        assert!(content.contains(r#""content":"Dynamic content""#));
+    }
+
+    #[test]
+    fn test_task_string_with_soroban_string() {
+        let env = Env::default();
+        let task_text = String::from_str(&env, "Dynamic task");
+        let output = JsonDocument::new(&env, "Test")
+            .task_string(1, &task_text, true)
+            .end()
+            .build();
+        let content = to_str(&output);
+        assert!(content.contains(r#""type":"task""#));
+        assert!(content.contains(r#""text":"Dynamic task""#));
+        assert!(content.contains(r#""completed":true"#));
+    }
+
+    #[test]
+    fn test_empty_document_valid_json() {
+        let env = Env::default();
+        let output = JsonDocument::new(&env, "My App").build();
+        let content = to_str(&output);
+        // Verify structure
+        assert!(
+            content.starts_with(
+                r#"{"format":"soroban-render-json-v1","title":"My App","components":["#
+            )
+        );
+        assert!(content.ends_with("]}"));
+    }
+
+    #[test]
+    fn test_heading_contains_correct_json() {
+        let env = Env::default();
+        let output = JsonDocument::new(&env, "Test")
+            .heading(1, "Welcome")
+            .build();
+        let content = to_str(&output);
+        assert!(content.contains(r#"{"type":"heading","level":1,"text":"Welcome"}"#));
+    }
+
+    #[test]
+    fn test_text_contains_correct_json() {
+        let env = Env::default();
+        let output = JsonDocument::new(&env, "Test").text("Hello World").build();
+        let content = to_str(&output);
+        assert!(content.contains(r#"{"type":"text","content":"Hello World"}"#));
+    }
+
+    #[test]
+    fn test_divider_contains_correct_json() {
+        let env = Env::default();
+        let output = JsonDocument::new(&env, "Test").divider().build();
+        let content = to_str(&output);
+        assert!(content.contains(r#"{"type":"divider"}"#));
+    }
+
+    #[test]
+    fn test_form_textarea_field() {
+        let env = Env::default();
+        let output = JsonDocument::new(&env, "Test")
+            .form("submit")
+            .textarea_field("description", "Enter description")
+            .submit("Submit")
+            .build();
+        let content = to_str(&output);
+        assert!(content.contains(r#""type":"textarea""#));
+        assert!(content.contains(r#""name":"description""#));
+        assert!(content.contains(r#""placeholder":"Enter description""#));
+    }
+
+    #[test]
+    fn test_container_nesting() {
+        let env = Env::default();
+        let output = JsonDocument::new(&env, "Test")
+            .container_start("outer-class")
+            .text("Inside container")
+            .container_end()
+            .build();
+        let content = to_str(&output);
+        assert!(content.contains(r#""type":"container""#));
+        assert!(content.contains(r#""className":"outer-class""#));
+        assert!(content.contains(r#""components":["#));
+    }
+
+    #[test]
+    fn test_container_resets_component_count() {
+        let env = Env::default();
+        // After container, new components should work correctly
+        let output = JsonDocument::new(&env, "Test")
+            .container_start("box")
+            .text("First")
+            .text("Second")
+            .container_end()
+            .text("After container")
+            .build();
+        let content = to_str(&output);
+        // Verify commas are correctly placed (no double commas)
+        assert!(!content.contains(",,"));
+        assert!(content.contains("After container"));
+    }
+
+    #[test]
+    fn test_json_escaping_in_text() {
+        let env = Env::default();
+        // Test that special characters are escaped
+        let output = JsonDocument::new(&env, "Test")
+            .text("Hello \"World\"")
+            .build();
+        let content = to_str(&output);
+        // Quotes should be escaped
+        assert!(content.contains(r#"Hello \"World\""#));
+    }
+
+    #[test]
+    fn test_json_escaping_in_heading() {
+        let env = Env::default();
+        let output = JsonDocument::new(&env, "Test")
+            .heading(1, "Quote: \"test\"")
+            .build();
+        let content = to_str(&output);
+        assert!(content.contains(r#"Quote: \"test\""#));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_buffered_budget() {
+        let env = Env::default();
+        // 75 text components of 4 fragments each = 300 parts
+        let build = |mut doc: JsonDocument| {
+            for _ in 0..75 {
+                doc = doc.text("x");
+            }
+            doc.build()
+        };
+
+        let mut budget = env.cost_estimate().budget();
+        budget.reset_unlimited();
+        let from_parts = build(JsonDocument::with_sink(&env, Sink::parts(&env), "Doc"));
+        let parts_cpu = budget.cpu_instruction_cost();
+
+        budget.reset_unlimited();
+        let from_buffer = build(JsonDocument::new_buffered(&env, "Doc"));
+        let buffered_cpu = budget.cpu_instruction_cost();
+
+        assert_eq!(from_parts, from_buffer);
+        assert!(buffered_cpu < parts_cpu);
+    }
+
+    #[test]
+    fn test_combinators() {
+        let env = Env::default();
+        let mut items: Vec<String> = Vec::new(&env);
+        items.push_back(String::from_str(&env, "One"));
+        items.push_back(String::from_str(&env, "Two"));
+
+        let output = JsonDocument::new(&env, "List")
+            .each(&items, |doc, item| doc.text_string(&item))
+            .when(false, |doc| doc.divider())
+            .when_some(Some("Footer"), |doc, t| doc.text(t))
+            .build();
+        assert_eq!(
+            to_str(&output),
+            r#"{"format":"soroban-render-json-v1","title":"List","components":[{"type":"text","content":"One"},{"type":"text","content":"Two"},{"type":"text","content":"Footer"}]}"#
+        );
+    }
+
+    #[test]
+    fn test_introspection() {
+        let env = Env::default();
+        let doc = JsonDocument::new(&env, "Doc");
+        assert!(doc.is_empty());
+        assert_eq!(doc.approx_len(), doc.build().len());
+
+        let doc = JsonDocument::new(&env, "Doc")
+            .heading(1, "Title")
+            .text("Body \"quoted\"")
+            .divider()
+            .gauge(50, 100, "Progress");
+        assert!(!doc.is_empty());
+        assert!(doc.part_count() > 3);
+        let approx = doc.approx_len();
+        assert_eq!(approx, doc.build().len());
+    }
+
+    #[test]
+    fn test_is_empty_inside_nested_blocks() {
+        let env = Env::default();
+        let doc = JsonDocument::new(&env, "Doc").container_start("box");
+        assert!(!doc.is_empty());
+        let doc = doc.grid_start(2, 8).row_start();
+        assert!(!doc.is_empty());
+        let doc = doc.text("Cell").row_end().grid_end().container_end();
+        assert!(!doc.is_empty());
+    }
+
+    #[test]
+    fn test_build_string() {
+        let env = Env::default();
+        let s = JsonDocument::new(&env, "Ünïcødé")
+            .text("€100")
+            .build_string();
+        assert_eq!(
+            s,
+            String::from_str(
+                &env,
+                r#"{"format":"soroban-render-json-v1","title":"Ünïcødé","components":[{"type":"text","content":"€100"}]}"#
+            )
+        );
+
+        let empty = JsonDocument::new(&env, "").build_string();
+        assert_eq!(
+            empty,
+            String::from_str(
+                &env,
+                r#"{"format":"soroban-render-json-v1","title":"","components":[]}"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_text_timestamp() {
+        let env = Env::default();
+        let json = JsonDocument::new(&env, "T").text_timestamp(0).build();
+        assert_eq!(
+            to_str(&json),
+            r#"{"format":"soroban-render-json-v1","title":"T","components":[{"type":"text","content":"1970-01-01 00:00:00 UTC"}]}"#
+        );
+    }
+
+    /// Parse a built document, panicking if it is not valid JSON.
+    fn parse_json(bytes: &Bytes) -> serde_json::Value {
+        let buf: alloc::vec::Vec<u8> = bytes.iter().collect();
+        serde_json::from_slice(&buf).unwrap()
+    }
+
+    #[test]
+    fn test_list() {
+        let env = Env::default();
+        let json = JsonDocument::new(&env, "Lists")
+            .list_start(false)
+            .list_item("First")
+            .list_item_string(&String::from_str(&env, "Second \"quoted\""))
+            .list_end()
+            .list_start(true)
+            .list_end()
+            .text("after")
+            .build();
+        assert_eq!(
+            to_str(&json),
+            r#"{"format":"soroban-render-json-v1","title":"Lists","components":[{"type":"list","ordered":false,"items":["First","Second \"quoted\""]},{"type":"list","ordered":true,"items":[]},{"type":"text","content":"after"}]}"#
+        );
+        parse_json(&json);
+    }
+
+    #[test]
+    fn test_table() {
+        let env = Env::default();
+        let json = JsonDocument::new(&env, "Scores")
+            .table_start(&["Name", "Votes"])
+            .table_row(&["Alice", "12"])
+            .table_row_start()
+            .table_cell("Bob")
+            .table_cell_u32(7)
+            .table_row_end()
+            .table_row(&["Carol \"C\"", "3"])
+            .table_end()
+            .build();
+        assert_eq!(
+            to_str(&json),
+            r#"{"format":"soroban-render-json-v1","title":"Scores","components":[{"type":"table","columns":["Name","Votes"],"rows":[["Alice","12"],["Bob",7],["Carol \"C\"","3"]]}]}"#
+        );
+
+        let parsed = parse_json(&json);
+        let table = &parsed["components"][0];
+        assert_eq!(table["columns"].as_array().unwrap().len(), 2);
+        let rows = table["rows"].as_array().unwrap();
+        assert_eq!(rows.len(), 3);
+        assert!(rows.iter().all(|r| r.as_array().unwrap().len() == 2));
+        assert_eq!(rows[1][1], 7);
+    }
+
+    #[test]
+    fn test_button_and_link() {
+        let env = Env::default();
+        let json = JsonDocument::new(&env, "Actions")
+            .button("Refresh", "refresh")
+            .link("Home \"main\"", "/")
+            .build();
+        assert_eq!(
+            to_str(&json),
+            r#"{"format":"soroban-render-json-v1","title":"Actions","components":[{"type":"button","label":"Refresh","action":"tx:refresh"},{"type":"link","label":"Home \"main\"","path":"/"}]}"#
+        );
+        parse_json(&json);
+    }
+
+    #[test]
+    fn test_button_tx_with_quoted_args() {
+        let env = Env::default();
+        let args = TxArgs::new(&env)
+            .add_u32("id", 5)
+            .add_str("title", "Say \"hi\"");
+        let json = JsonDocument::new(&env, "T")
+            .button_tx("Rename \"it\"", "rename", &args)
+            .build();
+        assert_eq!(
+            to_str(&json),
+            r#"{"format":"soroban-render-json-v1","title":"T","components":[{"type":"button","label":"Rename \"it\"","action":"tx:rename","args":{"id":5,"title":"Say \"hi\""}}]}"#
+        );
+
+        let parsed = parse_json(&json);
+        let button = &parsed["components"][0];
+        assert_eq!(button["args"]["id"], 5);
+        assert_eq!(button["args"]["title"], "Say \"hi\"");
+    }
+
+    #[test]
+    fn test_button_tx_quotes_unsafe_integers() {
+        let env = Env::default();
+        let safe = (1u64 << 53) - 1;
+        let args = TxArgs::new(&env)
+            .add_u64("safe", safe)
+            .add_u64("amount", 1 << 53)
+            .add_i64("delta", i64::MIN)
+            .add_i128("supply", -(1 << 60));
+        let json = JsonDocument::new(&env, "T")
+            .button_tx("Send", "send", &args)
+            .build();
+
+        let parsed = parse_json(&json);
+        let args = &parsed["components"][0]["args"];
+        assert_eq!(args["safe"], safe);
+        assert_eq!(args["amount"], "9007199254740992");
+        assert_eq!(args["delta"], "-9223372036854775808");
+        assert_eq!(args["supply"], "-1152921504606846976");
+    }
+
+    #[test]
+    fn test_image() {
+        let env = Env::default();
+        let json = JsonDocument::new(&env, "Img")
+            .image("https://example.com/a.png", "An \"A\"")
+            .image_sized("/logo.svg", "Logo", 64, 32)
+            .build();
+        let s = to_str(&json);
+        assert_eq!(
+            s,
+            r#"{"format":"soroban-render-json-v1","title":"Img","components":[{"type":"image","src":"https://example.com/a.png","alt":"An \"A\""},{"type":"image","src":"/logo.svg","alt":"Logo","width":64,"height":32}]}"#
+        );
+        assert!(!s.contains("null"));
+
+        let parsed = parse_json(&json);
+        assert!(parsed["components"][0].get("width").is_none());
+        assert!(parsed["components"][0].get("height").is_none());
+        assert_eq!(parsed["components"][1]["width"], 64);
+    }
+
+    #[test]
+    fn test_avatar() {
+        use soroban_sdk::testutils::Address as _;
+
+        let env = Env::default();
+        let user = Address::generate(&env);
+        let json = JsonDocument::new(&env, "A").avatar(&user, 48).build();
+        let expected_address = to_str(&address_to_bytes(&env, &user));
+
+        let parsed = parse_json(&json);
+        let avatar = &parsed["components"][0];
+        assert_eq!(avatar["type"], "avatar");
+        assert_eq!(avatar["address"], expected_address.as_str());
+        assert_eq!(avatar["size"], 48);
+    }
+
+    #[test]
+    fn test_stat() {
+        let env = Env::default();
+        let json = JsonDocument::new(&env, "Dash")
+            .stat("Status", "Open")
+            .stat_u32("Total Votes", 1204)
+            .stat_i128("Balance", -12_345_678)
+            .build();
+        assert_eq!(
+            to_str(&json),
+            r#"{"format":"soroban-render-json-v1","title":"Dash","components":[{"type":"stat","label":"Status","value":"Open"},{"type":"stat","label":"Total Votes","value":"1,204"},{"type":"stat","label":"Balance","value":"-12,345,678"}]}"#
+        );
+        parse_json(&json);
+    }
+
+    #[test]
+    fn test_stat_with_delta() {
+        let env = Env::default();
+        let json = JsonDocument::new(&env, "Dash")
+            .stat_with_delta("Votes", "1,204", 3, true)
+            .stat_with_delta("Errors", "12", -4, false)
+            .stat_with_delta("Latency", "80ms", 5, false)
+            .stat_with_delta("Flat", "7", 0, true)
+            .build();
+        let parsed = parse_json(&json);
+        let c = &parsed["components"];
+
+        assert_eq!(c[0]["delta"], 3);
+        assert_eq!(c[0]["trend"], "up");
+        assert_eq!(c[0]["positive"], true);
+
+        assert_eq!(c[1]["delta"], -4);
+        assert_eq!(c[1]["trend"], "down");
+        assert_eq!(c[1]["positive"], true);
+
+        assert_eq!(c[2]["trend"], "up");
+        assert_eq!(c[2]["positive"], false);
+
+        assert_eq!(c[3]["delta"], 0);
+        assert!(c[3].get("trend").is_none());
+        assert!(c[3].get("positive").is_none());
+    }
+
+    #[test]
+    fn test_kv_list() {
+        use soroban_sdk::testutils::Address as _;
+
+        let env = Env::default();
+        let owner = Address::generate(&env);
+        let doc = JsonDocument::new(&env, "Detail")
+            .text("before")
+            .kv_list_start()
+            .kv("Status", "Open \"now\"")
+            .kv_address("Owner", &owner)
+            .kv_u32("Votes", 12)
+            .kv_string("Note", &String::from_str(&env, "line\nbreak"))
+            .kv_list_end();
+        // The nested items must not count as document components.
+        assert_eq!(doc.component_count, 2);
+        let doc = doc.text("after");
+        assert_eq!(doc.component_count, 3);
+        let json = doc.build();
+
+        let parsed = parse_json(&json);
+        let components = parsed["components"].as_array().unwrap();
+        assert_eq!(components.len(), 3);
+        let items = components[1]["items"].as_array().unwrap();
+        assert_eq!(items.len(), 4);
+        assert_eq!(items[0]["label"], "Status");
+        assert_eq!(items[0]["value"], "Open \"now\"");
+        assert_eq!(
+            items[1]["value"],
+            to_str(&address_to_bytes(&env, &owner)).as_str()
+        );
+        assert_eq!(items[2]["value"], 12);
+        assert_eq!(items[3]["value"], "line\nbreak");
+        assert_eq!(components[2]["content"], "after");
+    }
+
+    #[test]
+    fn test_container_first_with_siblings() {
+        let env = Env::default();
+        let json = JsonDocument::new(&env, "T")
+            .container_start("box")
+            .text("inside")
+            .container_end()
+            .text("after 1")
+            .text("after 2")
+            .build();
+        let parsed = parse_json(&json);
+        let components = parsed["components"].as_array().unwrap();
+        assert_eq!(components.len(), 3);
+        assert_eq!(components[0]["components"][0]["content"], "inside");
+        assert_eq!(components[2]["content"], "after 2");
+    }
+
+    #[test]
+    fn test_sibling_containers() {
+        let env = Env::default();
+        let json = JsonDocument::new(&env, "T")
+            .text("before")
+            .container_start("a")
+            .container_end()
+            .container_start("b")
+            .text("b1")
+            .text("b2")
+            .container_end()
+            .build();
+        let parsed = parse_json(&json);
+        let components = parsed["components"].as_array().unwrap();
+        assert_eq!(components.len(), 3);
+        assert_eq!(components[1]["components"].as_array().unwrap().len(), 0);
+        assert_eq!(components[2]["components"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_nested_containers() {
+        let env = Env::default();
+        let json = JsonDocument::new(&env, "T")
+            .container_start("outer")
+            .text("o1")
+            .container_start("middle")
+            .container_start("inner")
+            .text("i1")
+            .text("i2")
+            .container_end()
+            .text("m1")
+            .container_end()
+            .text("o2")
+            .container_end()
+            .text("root")
+            .build();
+        let parsed = parse_json(&json);
+        let root = parsed["components"].as_array().unwrap();
+        assert_eq!(root.len(), 2);
+        let outer = root[0]["components"].as_array().unwrap();
+        assert_eq!(outer.len(), 3);
+        let middle = outer[1]["components"].as_array().unwrap();
+        assert_eq!(middle.len(), 2);
+        assert_eq!(middle[0]["components"].as_array().unwrap().len(), 2);
+        assert_eq!(middle[1]["content"], "m1");
+        assert_eq!(outer[2]["content"], "o2");
+        assert_eq!(root[1]["content"], "root");
+    }
+
+    #[test]
+    fn test_markdown_component() {
+        let env = Env::default();
+        let json = JsonDocument::new(&env, "M")
+            .markdown("# Title\n\nSome \"quoted\" **bold**")
+            .markdown_string(&String::from_str(&env, "- item"))
+            .build();
+        assert_eq!(
+            to_str(&json),
+            r##"{"format":"soroban-render-json-v1","title":"M","components":[{"type":"markdown","content":"# Title\n\nSome \"quoted\" **bold**"},{"type":"markdown","content":"- item"}]}"##
+        );
+        parse_json(&json);
+    }
+
+    #[test]
+    fn test_raw_component_commas() {
+        let env = Env::default();
+        let json = JsonDocument::new(&env, "R")
+            .raw_component(r#"{"type":"spacer","size":2}"#)
+            .text("middle")
+            .raw_component_bytes(Bytes::from_slice(&env, br#"{"type":"custom"}"#))
+            .raw_component(r#"{"type":"spacer","size":1}"#)
+            .build();
+        assert_eq!(
+            to_str(&json),
+            r#"{"format":"soroban-render-json-v1","title":"R","components":[{"type":"spacer","size":2},{"type":"text","content":"middle"},{"type":"custom"},{"type":"spacer","size":1}]}"#
+        );
+        let parsed = parse_json(&json);
+        assert_eq!(parsed["components"].as_array().unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_form_all_field_types() {
+        let env = Env::default();
+        let json = JsonDocument::new(&env, "F")
+            .form("save")
+            .text_field("title", "Title", true)
+            .select_field(
+                "size",
+                "Size",
+                &[("s", "Small \"S\""), ("m", "Medium")],
+                Some("m"),
+            )
+            .number_field("qty", "Quantity", Some(-5), Some(100), true)
+            .number_field("note_id", "Optional", None, None, false)
+            .hidden_field("mode", "edit")
+            .hidden_field_u32("id", 42)
+            .checkbox_field("agree", "I agree", true)
+            .checkbox_field("news", "Newsletter", false)
+            .submit("Save")
+            .build();
+        let content = to_str(&json);
+        assert!(content.contains(
+            r#"{"name":"size","type":"select","label":"Size","options":[{"value":"s","label":"Small \"S\""},{"value":"m","label":"Medium"}],"selected":"m"}"#
+        ));
+        assert!(content.contains(
+            r#"{"name":"qty","type":"number","placeholder":"Quantity","min":-5,"max":100,"required":true}"#
+        ));
+        assert!(content.contains(r#"{"name":"note_id","type":"number","placeholder":"Optional"}"#));
+        assert!(content.contains(r#"{"name":"mode","type":"hidden","value":"edit"}"#));
+        assert!(content.contains(r#"{"name":"id","type":"hidden","value":42}"#));
+        assert!(
+            content
+                .contains(r#"{"name":"agree","type":"checkbox","label":"I agree","checked":true}"#)
+        );
+        assert!(content.contains(r#"{"name":"news","type":"checkbox","label":"Newsletter"}"#));
+
+        let parsed = parse_json(&json);
+        let form = &parsed["components"][0];
+        assert_eq!(form["fields"].as_array().unwrap().len(), 8);
+        assert_eq!(form["fields"][1]["options"][0]["label"], "Small \"S\"");
+        assert_eq!(form["submitLabel"], "Save");
+    }
+
+    #[test]
+    fn test_select_field_without_selection() {
+        let env = Env::default();
+        let json = JsonDocument::new(&env, "F")
+            .form("pick")
+            .select_field("color", "Color", &[], None)
+            .submit("Go")
+            .build();
+        assert!(
+            to_str(&json)
+                .contains(r#"{"name":"color","type":"select","label":"Color","options":[]}"#)
+        );
+        parse_json(&json);
+    }
+
+    #[test]
+    fn test_form_values_and_redirect() {
+        let env = Env::default();
+        let json = JsonDocument::new(&env, "Edit")
+            .form("update_item")
+            .redirect("/items")
+            .hidden_field_u32("id", 7)
+            .text_field_with_value("title", "Title", "Old \"title\"", true)
+            .text_field_with_value_string("owner", "Owner", &String::from_str(&env, "alice"), false)
+            .textarea_field_with_value("body", "Body", "line1\nline2")
+            .redirect("/items/7")
+            .submit("Update")
+            .build();
+        let content = to_str(&json);
+        assert!(content.contains(
+            r#"{"name":"title","type":"text","placeholder":"Title","value":"Old \"title\"","required":true}"#
+        ));
+        assert!(
+            content.contains(
+                r#"{"name":"owner","type":"text","placeholder":"Owner","value":"alice"}"#
+            )
+        );
+        assert!(content.contains(
+            r#"{"name":"body","type":"textarea","placeholder":"Body","value":"line1\nline2"}"#
+        ));
+        assert!(content.contains(r#"],"redirect":"/items/7","submitLabel":"Update"}"#));
+        assert_eq!(content.matches(r#""redirect""#).count(), 1);
+
+        let parsed = parse_json(&json);
+        assert_eq!(parsed["components"][0]["redirect"], "/items/7");
+        assert_eq!(
+            parsed["components"][0]["fields"][1]["value"],
+            "Old \"title\""
+        );
+    }
+
+    #[test]
+    fn test_form_without_redirect() {
+        let env = Env::default();
+        let content = to_str(
+            &JsonDocument::new(&env, "F")
+                .form("add")
+                .text_field("name", "Name", false)
+                .submit("Add")
+                .build(),
+        );
+        assert!(!content.contains("redirect"));
+        assert!(content.contains(r#"],"submitLabel":"Add"}"#));
+    }
+
+    #[test]
+    fn test_task_tx_action_output() {
+        let env = Env::default();
+        let json = JsonDocument::new(&env, "T")
+            .task(1, "Task", false)
+            .tx_action("complete", 1, "Done")
+            .end()
+            .build();
+        assert_contains(
+            &json,
+            r#""actions":[{"type":"tx","method":"complete","args":{"id":1},"label":"Done"}]}"#,
+        );
+        parse_json(&json);
+    }
+
+    #[test]
+    fn test_task_action_variants_and_confirm() {
+        let env = Env::default();
+        let json = JsonDocument::new(&env, "T")
+            .task(5, "Write docs", false)
+            .tx_action_args(
+                "set_status",
+                &TxArgs::new(&env)
+                    .add_u32("id", 5)
+                    .add_str("status", "archived"),
+                "Archive",
+            )
+            .tx_action_str("tag", "name", "say \"hi\"", "Tag")
+            .tx_action("delete", 5, "Delete")
+            .confirm("Delete \"Write docs\"?")
+            .end()
+            .text("after")
+            .build();
+        let content = to_str(&json);
+        assert!(content.contains(
+            r#"{"type":"tx","method":"set_status","args":{"id":5,"status":"archived"},"label":"Archive"},"#
+        ));
+        assert!(content.contains(
+            r#"{"type":"tx","method":"tag","args":{"name":"say \"hi\""},"label":"Tag"},"#
+        ));
+        assert!(content.contains(
+            r#"{"type":"tx","method":"delete","args":{"id":5},"label":"Delete","confirm":"Delete \"Write docs\"?"}]}"#
+        ));
+
+        let parsed = parse_json(&json);
+        let actions = parsed["components"][0]["actions"].as_array().unwrap();
+        assert_eq!(actions.len(), 3);
+        assert!(actions[0].get("confirm").is_none());
+        assert_eq!(actions[2]["confirm"], "Delete \"Write docs\"?");
+        assert_eq!(parsed["components"][1]["content"], "after");
+    }
+
+    #[test]
+    fn test_confirm_without_action_is_ignored() {
+        let env = Env::default();
+        let json = JsonDocument::new(&env, "T")
+            .task(1, "Task", true)
+            .confirm("Sure?")
+            .end()
+            .build();
+        assert!(!to_str(&json).contains("confirm"));
+        parse_json(&json);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "confirm called twice on the same action")]
+    fn test_confirm_twice_panics() {
+        let env = Env::default();
+        JsonDocument::new(&env, "T")
+            .task(1, "Task", false)
+            .tx_action("complete", 1, "Done")
+            .confirm("Sure?")
+            .confirm("Really?");
+    }
+
+    #[test]
+    #[cfg(not(debug_assertions))]
+    fn test_confirm_twice_keeps_first() {
+        let env = Env::default();
+        let json = JsonDocument::new(&env, "T")
+            .task(1, "Task", false)
+            .tx_action("complete", 1, "Done")
+            .confirm("Sure?")
+            .confirm("Really?")
+            .end()
+            .build();
+        let parsed = parse_json(&json);
+        assert_eq!(parsed["components"][0]["actions"][0]["confirm"], "Sure?");
+        assert!(!to_str(&json).contains("Really?"));
+    }
+
+    #[test]
+    fn test_confirm_on_each_action() {
+        let env = Env::default();
+        let json = JsonDocument::new(&env, "T")
+            .task(1, "Task", false)
+            .tx_action("complete", 1, "Done")
+            .confirm("Complete?")
+            .tx_action("delete", 1, "Delete")
+            .confirm("Delete?")
+            .end()
+            .build();
+        let parsed = parse_json(&json);
+        let actions = &parsed["components"][0]["actions"];
+        assert_eq!(actions[0]["confirm"], "Complete?");
+        assert_eq!(actions[1]["confirm"], "Delete?");
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_pie_slice_shim_ignores_first() {
+        let env = Env::default();
+        let json = JsonDocument::new(&env, "P")
+            .pie_chart_start("Votes")
+            .pie_slice("Yes", 3, "#0f0", true)
+            .pie_slice("No", 1, "#f00", true)
+            .pie_chart_end()
+            .build();
+        assert_contains(
+            &json,
+            r##""data":[{"label":"Yes","value":3,"color":"#0f0"},{"label":"No","value":1,"color":"#f00"}]"##,
+        );
+    }
+
+    #[test]
+    fn test_pie_items_in_consecutive_charts() {
+        let env = Env::default();
+        let json = JsonDocument::new(&env, "P")
+            .pie_chart_start("A")
+            .pie_item("x", 1, "#000")
+            .pie_item("y", 2, "#111")
+            .pie_chart_end()
+            .pie_chart_start("B")
+            .pie_item("z", 3, "#222")
+            .pie_chart_end()
+            .build();
+        let parsed = parse_json(&json);
+        assert_eq!(parsed["components"][0]["data"][1]["label"], "y");
+        assert_eq!(parsed["components"][1]["data"][0]["label"], "z");
+    }
+
+    #[test]
+    #[should_panic(expected = "pie_item called outside a pie_chart block")]
+    fn test_pie_item_outside_chart_panics() {
+        let env = Env::default();
+        JsonDocument::new(&env, "P").pie_item("x", 1, "#000");
     }
-}
 
-/// Builder for task actions.
-pub struct TaskBuilder<'a> {
-    doc: JsonDocument<'a>,
-    action_count: u32,
-}
+    #[test]
+    #[allow(deprecated)]
+    fn test_nav_item_shim_ignores_first() {
+        let env = Env::default();
+        let json = JsonDocument::new(&env, "N")
+            .nav_start()
+            .nav_item("Home", "/", true, true)
+            .nav_item("About", "/about", false, true)
+            .nav_end()
+            .build();
+        assert_contains(
+            &json,
+            r#"{"type":"navigation","items":[{"label":"Home","path":"/","active":true},{"label":"About","path":"/about"}]}"#,
+        );
+        parse_json(&json);
+    }
 
-impl<'a> TaskBuilder<'a> {
-    /// Add a comma separator if needed.
-    fn maybe_comma(&mut self) {
-        if self.action_count > 0 {
-            self.doc
-                .parts
-                .push_back(Bytes::from_slice(self.doc.env, b","));
-        }
-        self.action_count += 1;
+    #[test]
+    #[cfg(feature = "router")]
+    fn test_nav_item_auto() {
+        let env = Env::default();
+        let current = Bytes::from_slice(&env, b"/posts/5");
+        let json = JsonDocument::new(&env, "N")
+            .nav_start()
+            .nav_item_auto("Home", "/", &current)
+            .nav_item_auto("Posts", "/posts", &current)
+            .nav_item_auto("Post", "/post", &current)
+            .nav_end()
+            .text("after")
+            .build();
+        let content = to_str(&json);
+        assert_eq!(content.matches(r#""active":true"#).count(), 1);
+        assert!(content.contains(r#"{"label":"Posts","path":"/posts","active":true}"#));
+
+        let parsed = parse_json(&json);
+        let items = parsed["components"][0]["items"].as_array().unwrap();
+        assert_eq!(items.len(), 3);
+        assert_eq!(parsed["components"][1]["content"], "after");
     }
 
-    /// Add a transaction action.
-    pub fn tx_action(mut self, method: &str, id: u32, label: &str) -> Self {
-        self.maybe_comma();
-        self.doc.parts.push_back(Bytes::from_slice(
-            self.doc.env,
-            b"{\"type\":\"tx\",\"method\":\"",
+    #[test]
+    #[cfg(feature = "router")]
+    fn test_nav_path_active() {
+        let env = Env::default();
+        let root = Bytes::from_slice(&env, b"/");
+        let about = Bytes::from_slice(&env, b"/about");
+        assert!(nav_path_active(b"/", &root));
+        assert!(!nav_path_active(b"/", &about));
+        assert!(nav_path_active(b"/about", &about));
+        assert!(!nav_path_active(b"/about/team", &about));
+        assert!(nav_path_active(
+            b"/admin/",
+            &Bytes::from_slice(&env, b"/admin/users")
         ));
-        self.doc
-            .parts
-            .push_back(escape_json_bytes(self.doc.env, method.as_bytes()));
-        self.doc
-            .parts
-            .push_back(Bytes::from_slice(self.doc.env, b"\",\"args\":{\"id\":"));
-        self.doc.parts.push_back(u32_to_bytes(self.doc.env, id));
-        self.doc
-            .parts
-            .push_back(Bytes::from_slice(self.doc.env, b"},\"label\":\""));
-        self.doc
-            .parts
-            .push_back(escape_json_bytes(self.doc.env, label.as_bytes()));
-        self.doc
-            .parts
-            .push_back(Bytes::from_slice(self.doc.env, b"\"}"));
-        self
     }
 
-    /// Complete the task.
-    pub fn end(mut self) -> JsonDocument<'a> {
-        self.doc
-            .parts
-            .push_back(Bytes::from_slice(self.doc.env, b"]}"));
-        self.doc
+    #[test]
+    fn test_document_metadata_order() {
+        let env = Env::default();
+        let json = JsonDocument::new(&env, "Blog")
+            .with_theme("CTHEME")
+            .with_version(3)
+            .with_description("A \"small\" blog")
+            .text("hi")
+            .build();
+        assert_eq!(
+            to_str(&json),
+            r#"{"format":"soroban-render-json-v1","title":"Blog","description":"A \"small\" blog","version":3,"theme":"CTHEME","components":[{"type":"text","content":"hi"}]}"#
+        );
+        parse_json(&json);
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[cfg(test)]
-    extern crate alloc;
 
-    /// Convert Bytes to a String for content validation in tests
-    fn bytes_to_string(bytes: &Bytes) -> alloc::string::String {
-        let mut s = alloc::string::String::new();
-        for i in 0..bytes.len() {
-            s.push(bytes.get(i).unwrap() as char);
-        }
-        s
+    #[test]
+    fn test_document_metadata_after_components() {
+        let env = Env::default();
+        let doc = JsonDocument::new(&env, "Late")
+            .heading(1, "Top")
+            .with_version(12)
+            .text("body")
+            .with_description("set late")
+            .with_description("replaced");
+        let approx = doc.approx_len();
+        let json = doc.build();
+        assert_eq!(approx, json.len());
+        let parsed = parse_json(&json);
+        assert_eq!(parsed["description"], "replaced");
+        assert_eq!(parsed["version"], 12);
+        assert!(parsed.get("theme").is_none());
+        assert_eq!(parsed["components"].as_array().unwrap().len(), 2);
+        assert!(to_str(&json).starts_with(
+            r#"{"format":"soroban-render-json-v1","title":"Late","description":"replaced","version":12,"components":["#
+        ));
     }
 
     #[test]
-    fn test_empty_document() {
+    fn test_document_metadata_approx_len() {
         let env = Env::default();
-        let output = JsonDocument::new(&env, "Test").build();
-        // Should contain format, title, and empty components array
-        assert!(output.len() > 50);
+        let doc = JsonDocument::new(&env, "T")
+            .with_description("d")
+            .with_version(4_000_000_000)
+            .with_theme("C");
+        assert_eq!(doc.approx_len(), doc.build().len());
     }
 
     #[test]
-    fn test_heading() {
+    fn test_continuation_markers() {
         let env = Env::default();
-        let output = JsonDocument::new(&env, "Test").heading(1, "Hello").build();
-        assert!(output.len() > 60);
+        let json = JsonDocument::new(&env, "C")
+            .continuation("comments", 5, Some(50))
+            .continuation("comments", 10, None)
+            .chunk_ref("posts", 2, None)
+            .chunk_ref("posts", 3, Some("Loading \"posts\"..."))
+            .render_continue("/b/1/t/0/replies/10")
+            .build();
+        let content = to_str(&json);
+        assert!(content.contains(
+            r#"[{"type":"continue","collection":"comments","from":5,"total":50},{"type":"continue","collection":"comments","from":10},"#
+        ));
+        assert!(content.contains(r#"{"type":"chunk","collection":"posts","index":2},"#));
+        assert!(content.contains(
+            r#"{"type":"chunk","collection":"posts","index":3,"placeholder":"Loading \"posts\"..."}"#
+        ));
+        assert!(content.contains(r#"{"type":"render","path":"/b/1/t/0/replies/10"}]}"#));
+
+        let parsed = parse_json(&json);
+        assert_eq!(parsed["components"].as_array().unwrap().len(), 5);
+        assert!(parsed["components"][1].get("total").is_none());
     }
 
     #[test]
-    fn test_text() {
+    fn test_number_value_safe_range() {
         let env = Env::default();
-        let output = JsonDocument::new(&env, "Test")
-            .text("Hello, World!")
-            .build();
-        assert!(output.len() > 60);
+        let doc = JsonDocument::new(&env, "N");
+        let safe = (1i128 << 53) - 1;
+        assert_eq!(to_str(&doc.number_value(safe)), "9007199254740991");
+        assert_eq!(to_str(&doc.number_value(-safe)), "-9007199254740991");
+        assert_eq!(to_str(&doc.number_value(safe + 1)), "\"9007199254740992\"");
+        assert_eq!(
+            to_str(&doc.number_value(-safe - 1)),
+            "\"-9007199254740992\""
+        );
+        assert_eq!(
+            to_str(&doc.number_value(i128::MIN)),
+            "\"-170141183460469231731687303715884105728\""
+        );
     }
 
     #[test]
-    fn test_divider() {
+    fn test_wide_numeric_components() {
         let env = Env::default();
-        let output = JsonDocument::new(&env, "Test").divider().build();
-        assert!(output.len() > 50);
+        let big: i64 = 1 << 60;
+        let json = JsonDocument::new(&env, "N")
+            .gauge_i64(-250, 1_000, "Balance")
+            .gauge_i64(big, big, "Supply")
+            .pie_chart_start("Holdings")
+            .pie_item_i64("A", 3_000_000_000, "#f00")
+            .pie_item_i64("B", -5, "#0f0")
+            .pie_chart_end()
+            .stat_number("Staked", 12_345)
+            .stat_number("Minted", 1i128 << 100)
+            .stat_with_delta("Flow", "big", big, true)
+            .kv_list_start()
+            .kv_i128("Debt", -42)
+            .kv_list_end()
+            .table_start(&["Amount"])
+            .table_row_start()
+            .table_cell_i128(-7)
+            .table_row_end()
+            .table_end()
+            .build();
+        let content = to_str(&json);
+        assert!(content.contains(r#""value":"1152921504606846976","max":"1152921504606846976""#));
+        assert!(content.contains(r#""value":"1267650600228229401496703205376""#));
+
+        let parsed = parse_json(&json);
+        let c = &parsed["components"];
+        assert_eq!(c[0]["value"], -250);
+        assert_eq!(c[0]["max"], 1000);
+        assert_eq!(c[1]["value"], "1152921504606846976");
+        assert_eq!(c[2]["data"][0]["value"], 3_000_000_000i64);
+        assert_eq!(c[2]["data"][1]["value"], -5);
+        assert_eq!(c[3]["value"], 12345);
+        assert_eq!(c[5]["delta"], "1152921504606846976");
+        assert_eq!(c[6]["items"][0]["value"], -42);
+        assert_eq!(c[7]["rows"][0][0], -7);
     }
 
     #[test]
-    fn test_multiple_components() {
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "JsonDocument built with an unclosed pie_chart block")]
+    fn test_build_with_unclosed_block_panics() {
         let env = Env::default();
-        let output = JsonDocument::new(&env, "Test")
-            .heading(1, "Title")
-            .text("Content")
-            .divider()
+        JsonDocument::new(&env, "T")
+            .pie_chart_start("Votes")
+            .pie_item("Yes", 3, "#0f0")
             .build();
-        // Should have commas between components
-        assert!(output.len() > 100);
     }
 
     #[test]
-    fn test_form() {
+    #[cfg(not(debug_assertions))]
+    fn test_build_closes_unclosed_blocks() {
         let env = Env::default();
-        let output = JsonDocument::new(&env, "Test")
-            .form("add_item")
-            .text_field("name", "Enter name", true)
-            .submit("Add")
+        let json = JsonDocument::new(&env, "T")
+            .pie_chart_start("Votes")
+            .pie_item("Yes", 3, "#0f0")
             .build();
-        assert!(output.len() > 100);
+        assert_eq!(parse_json(&json)["components"][0]["data"][0]["value"], 3);
     }
 
     #[test]
-    fn test_navigation() {
+    #[should_panic(expected = "container_end called while a nav block is open")]
+    fn test_mismatched_end_panics() {
         let env = Env::default();
-        let output = JsonDocument::new(&env, "Test")
+        JsonDocument::new(&env, "T")
+            .container_start("box")
             .nav_start()
-            .nav_item("Home", "/", true, true)
-            .nav_item("About", "/about", false, false)
-            .nav_end()
-            .build();
-        assert!(output.len() > 100);
+            .container_end();
     }
 
     #[test]
-    fn test_pie_chart() {
+    #[should_panic(expected = "components cannot be added inside a list block")]
+    fn test_component_inside_list_panics() {
         let env = Env::default();
-        let output = JsonDocument::new(&env, "Test")
-            .pie_chart_start("Status")
-            .pie_slice("Done", 5, "#22c55e", true)
-            .pie_slice("Pending", 3, "#eab308", false)
-            .pie_chart_end()
-            .build();
-        assert!(output.len() > 100);
+        JsonDocument::new(&env, "T")
+            .list_start(false)
+            .text("x")
+            .list_item("b");
     }
 
     #[test]
-    fn test_gauge() {
+    #[should_panic(expected = "components cannot be added inside a list block")]
+    fn test_list_inside_list_panics() {
         let env = Env::default();
-        let output = JsonDocument::new(&env, "Test")
-            .gauge(75, 100, "Progress")
-            .build();
-        assert!(output.len() > 80);
+        JsonDocument::new(&env, "T")
+            .list_start(false)
+            .list_item("a")
+            .list_start(false)
+            .list_end()
+            .list_item("b");
     }
 
     #[test]
-    fn test_task() {
+    #[should_panic(expected = "components cannot be added inside a table_row block")]
+    fn test_container_inside_table_row_panics() {
+        let env = Env::default();
+        JsonDocument::new(&env, "T")
+            .table_start(&["A"])
+            .table_row_start()
+            .container_start("cell");
+    }
+
+    #[test]
+    fn test_item_blocks_inside_component_blocks() {
         let env = Env::default();
         let output = JsonDocument::new(&env, "Test")
-            .task(1, "My Task", false)
-            .tx_action("complete", 1, "Done")
-            .tx_action("delete", 1, "Delete")
-            .end()
+            .row_start()
+            .list_start(false)
+            .list_item("a")
+            .list_item("b")
+            .list_end()
+            .text("after")
+            .row_end()
             .build();
-        assert!(output.len() > 150);
+        assert_output_eq(
+            &output,
+            &document(
+                r#"{"type":"row","components":[{"type":"list","ordered":false,"items":["a","b"]},{"type":"text","content":"after"}]}"#,
+            ),
+        );
     }
 
-    // ==========================================================================
-    // Content validation tests
-    // ==========================================================================
+    #[test]
+    #[should_panic(expected = "container_end called with no open block")]
+    fn test_end_without_start_panics() {
+        let env = Env::default();
+        JsonDocument::new(&env, "T").container_end();
+    }
 
     #[test]
-    fn test_heading_string_with_soroban_string() {
+    #[should_panic(expected = "nav_link called outside a nav block")]
+    fn test_nav_link_outside_nav_panics() {
         let env = Env::default();
-        let title = String::from_str(&env, "Dynamic Title");
-        let output = JsonDocument::new(&env, "Test")
-            .heading_string(1, &title)
-            .build();
-        let content = bytes_to_string(&output);
-        assert!(content.contains(r#""type":"heading""#));
-        assert!(content.contains(r#""level":1"#));
-        assert!(content.contains(r#""text":"Dynamic Title""#));
+        JsonDocument::new(&env, "T").nav_link("Home", "/", true);
     }
 
     #[test]
-    fn test_text_string_with_soroban_string() {
+    #[should_panic(expected = "table_cell called outside a table_row block")]
+    fn test_table_cell_outside_row_panics() {
         let env = Env::default();
-        let text = String::from_str(&env, "Dynamic content");
-        let output = JsonDocument::new(&env, "Test").text_string(&text).build();
-        let content = bytes_to_string(&output);
-        assert!(content.contains(r#""type":"text""#));
-        assert!(content.contains(r#""content":"Dynamic content""#));
+        JsonDocument::new(&env, "T")
+            .table_start(&["A"])
+            .table_cell("x");
     }
 
     #[test]
-    fn test_task_string_with_soroban_string() {
+    fn test_close_open_blocks() {
         let env = Env::default();
-        let task_text = String::from_str(&env, "Dynamic task");
-        let output = JsonDocument::new(&env, "Test")
-            .task_string(1, &task_text, true)
-            .end()
-            .build();
-        let content = bytes_to_string(&output);
-        assert!(content.contains(r#""type":"task""#));
-        assert!(content.contains(r#""text":"Dynamic task""#));
-        assert!(content.contains(r#""completed":true"#));
+        let mut doc = JsonDocument::new(&env, "T")
+            .container_start("outer")
+            .table_start(&["A"])
+            .table_row_start()
+            .table_cell_u32(1);
+        doc.close_open_blocks();
+        let json = doc.text("after").build();
+        assert_eq!(
+            to_str(&json),
+            r#"{"format":"soroban-render-json-v1","title":"T","components":[{"type":"container","className":"outer","components":[{"type":"table","columns":["A"],"rows":[[1]]}]},{"type":"text","content":"after"}]}"#
+        );
+        parse_json(&json);
     }
 
     #[test]
-    fn test_empty_document_valid_json() {
+    fn test_grid_layout() {
         let env = Env::default();
-        let output = JsonDocument::new(&env, "My App").build();
-        let content = bytes_to_string(&output);
-        // Verify structure
-        assert!(
-            content.starts_with(
-                r#"{"format":"soroban-render-json-v1","title":"My App","components":["#
-            )
+        let json = JsonDocument::new(&env, "Dash")
+            .heading(1, "Overview")
+            .grid_start(2, 16)
+            .gauge(75, 100, "Quorum")
+            .stat_u32("Voters", 1204)
+            .grid_end()
+            .text("footer")
+            .build();
+        assert_eq!(
+            to_str(&json),
+            r#"{"format":"soroban-render-json-v1","title":"Dash","components":[{"type":"heading","level":1,"text":"Overview"},{"type":"grid","columns":2,"gap":16,"components":[{"type":"chart","chartType":"gauge","value":75,"max":100,"label":"Quorum"},{"type":"stat","label":"Voters","value":"1,204"}]},{"type":"text","content":"footer"}]}"#
         );
-        assert!(content.ends_with("]}"));
+        parse_json(&json);
     }
 
     #[test]
-    fn test_heading_contains_correct_json() {
+    fn test_rows_nested_in_grid() {
         let env = Env::default();
-        let output = JsonDocument::new(&env, "Test")
-            .heading(1, "Welcome")
+        let json = JsonDocument::new(&env, "L")
+            .grid_start(1, 8)
+            .row_start()
+            .text("a")
+            .text("b")
+            .row_end()
+            .row_start()
+            .container_start("c")
+            .text("c")
+            .container_end()
+            .row_end()
+            .grid_end()
+            .divider()
             .build();
-        let content = bytes_to_string(&output);
-        assert!(content.contains(r#"{"type":"heading","level":1,"text":"Welcome"}"#));
+        let parsed = parse_json(&json);
+        let grid = &parsed["components"][0];
+        assert_eq!(grid["components"].as_array().unwrap().len(), 2);
+        assert_eq!(grid["components"][0]["type"], "row");
+        assert_eq!(grid["components"][0]["components"][1]["content"], "b");
+        assert_eq!(grid["components"][1]["components"][0]["type"], "container");
+        assert_eq!(parsed["components"][1]["type"], "divider");
     }
 
     #[test]
-    fn test_text_contains_correct_json() {
+    #[should_panic(expected = "grid_end called while a row block is open")]
+    fn test_grid_end_inside_row_panics() {
         let env = Env::default();
-        let output = JsonDocument::new(&env, "Test").text("Hello World").build();
-        let content = bytes_to_string(&output);
-        assert!(content.contains(r#"{"type":"text","content":"Hello World"}"#));
+        JsonDocument::new(&env, "L")
+            .grid_start(2, 0)
+            .row_start()
+            .grid_end();
     }
 
     #[test]
-    fn test_divider_contains_correct_json() {
+    fn test_with_class() {
         let env = Env::default();
-        let output = JsonDocument::new(&env, "Test").divider().build();
-        let content = bytes_to_string(&output);
-        assert!(content.contains(r#"{"type":"divider"}"#));
+        let json = JsonDocument::new(&env, "S")
+            .heading(1, "Title")
+            .with_class("page-title")
+            .text("plain")
+            .button("Vote", "vote")
+            .with_class("btn primary")
+            .divider()
+            .build();
+        assert_eq!(
+            to_str(&json),
+            r#"{"format":"soroban-render-json-v1","title":"S","components":[{"type":"heading","level":1,"text":"Title","className":"page-title"},{"type":"text","content":"plain"},{"type":"button","label":"Vote","action":"tx:vote","className":"btn primary"},{"type":"divider"}]}"#
+        );
+        parse_json(&json);
     }
 
     #[test]
-    fn test_form_textarea_field() {
+    fn test_with_class_untouched_output_unchanged() {
         let env = Env::default();
-        let output = JsonDocument::new(&env, "Test")
-            .form("submit")
-            .textarea_field("description", "Enter description")
-            .submit("Submit")
+        let plain = JsonDocument::new(&env, "S")
+            .heading(2, "A")
+            .stat_u32("Votes", 5)
+            .grid_start(2, 4)
+            .text("x")
+            .grid_end()
             .build();
-        let content = bytes_to_string(&output);
-        assert!(content.contains(r#""type":"textarea""#));
-        assert!(content.contains(r#""name":"description""#));
-        assert!(content.contains(r#""placeholder":"Enter description""#));
+        let styled = JsonDocument::new(&env, "S")
+            .heading(2, "A")
+            .stat_u32("Votes", 5)
+            .with_class("big")
+            .grid_start(2, 4)
+            .text("x")
+            .grid_end()
+            .build();
+        let plain = to_str(&plain);
+        let styled = to_str(&styled);
+        assert_eq!(styled.replace(r#","className":"big""#, ""), plain);
+        assert!(plain.contains(r#"{"type":"heading","level":2,"text":"A"},{"type":"stat","#));
     }
 
     #[test]
-    fn test_container_nesting() {
+    fn test_with_class_on_form_and_task() {
         let env = Env::default();
-        let output = JsonDocument::new(&env, "Test")
-            .container_start("outer-class")
-            .text("Inside container")
-            .container_end()
+        let json = JsonDocument::new(&env, "S")
+            .form("add")
+            .text_field("name", "Name", true)
+            .submit("Add")
+            .with_class("compact")
+            .task(1, "Task", false)
+            .tx_action("done", 1, "Done")
+            .end()
+            .with_class("task-card")
             .build();
-        let content = bytes_to_string(&output);
-        assert!(content.contains(r#""type":"container""#));
-        assert!(content.contains(r#""className":"outer-class""#));
-        assert!(content.contains(r#""components":["#));
+        let parsed = parse_json(&json);
+        assert_eq!(parsed["components"][0]["className"], "compact");
+        assert_eq!(parsed["components"][1]["className"], "task-card");
     }
 
     #[test]
-    fn test_container_resets_component_count() {
+    fn test_with_class_inside_container_then_end() {
         let env = Env::default();
-        // After container, new components should work correctly
-        let output = JsonDocument::new(&env, "Test")
+        let doc = JsonDocument::new(&env, "S")
             .container_start("box")
-            .text("First")
-            .text("Second")
-            .container_end()
-            .text("After container")
-            .build();
-        let content = bytes_to_string(&output);
-        // Verify commas are correctly placed (no double commas)
-        assert!(!content.contains(",,"));
-        assert!(content.contains("After container"));
+            .text("inner")
+            .with_class("muted")
+            .container_end();
+        let approx = doc.approx_len();
+        let json = doc.build();
+        assert_eq!(approx, json.len());
+        assert_eq!(
+            parse_json(&json)["components"][0]["components"][0]["className"],
+            "muted"
+        );
     }
 
     #[test]
-    fn test_json_escaping_in_text() {
+    #[should_panic(expected = "with_class called with no component to apply it to")]
+    fn test_with_class_after_block_panics() {
         let env = Env::default();
-        // Test that special characters are escaped
-        let output = JsonDocument::new(&env, "Test")
-            .text("Hello \"World\"")
+        JsonDocument::new(&env, "S")
+            .list_start(false)
+            .list_item("a")
+            .list_end()
+            .with_class("x");
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "with_class called twice on the same component")]
+    fn test_with_class_twice_panics() {
+        let env = Env::default();
+        JsonDocument::new(&env, "S")
+            .text("a")
+            .with_class("x")
+            .with_class("y");
+    }
+
+    #[test]
+    #[cfg(not(debug_assertions))]
+    fn test_with_class_twice_keeps_first() {
+        let env = Env::default();
+        let json = JsonDocument::new(&env, "S")
+            .text("a")
+            .with_class("x")
+            .with_class("y")
             .build();
-        let content = bytes_to_string(&output);
-        // Quotes should be escaped
-        assert!(content.contains(r#"Hello \"World\""#));
+        assert_output_eq(
+            &json,
+            r#"{"format":"soroban-render-json-v1","title":"S","components":[{"type":"text","content":"a","className":"x"}]}"#,
+        );
     }
 
     #[test]
-    fn test_json_escaping_in_heading() {
+    fn test_with_class_on_each_component() {
         let env = Env::default();
-        let output = JsonDocument::new(&env, "Test")
-            .heading(1, "Quote: \"test\"")
+        let json = JsonDocument::new(&env, "S")
+            .text("a")
+            .with_class("x")
+            .text("b")
+            .with_class("y")
             .build();
-        let content = bytes_to_string(&output);
-        assert!(content.contains(r#"Quote: \"test\""#));
+        let parsed = parse_json(&json);
+        assert_eq!(parsed["components"][0]["className"], "x");
+        assert_eq!(parsed["components"][1]["className"], "y");
+    }
+
+    #[test]
+    fn test_error_page() {
+        let env = Env::default();
+        let output = to_str(&JsonDocument::error_page(
+            &env,
+            500,
+            "Server Error",
+            "Try again later.",
+            "/home",
+        ));
+        assert!(
+            output.contains("{\"type\":\"heading\",\"level\":1,\"text\":\"500 Server Error\"}")
+        );
+        assert!(
+            output.contains(
+                "{\"type\":\"markdown\",\"content\":\"> [!WARNING]\\n> Try again later.\"}"
+            )
+        );
+        assert!(output.ends_with("{\"type\":\"link\",\"label\":\"Go back\",\"path\":\"/home\"}]}"));
+    }
+
+    #[test]
+    fn test_error_page_escapes_and_quotes_every_line() {
+        let env = Env::default();
+        let output =
+            JsonDocument::error_page(&env, 503, "Down \"now\"", "Paused.\n\nBack soon.", "/");
+        let parsed = parse_json(&output);
+        assert_eq!(parsed["components"][0]["text"], "503 Down \"now\"");
+        assert_eq!(
+            parsed["components"][1]["content"],
+            "> [!WARNING]\n> Paused.\n>\n> Back soon."
+        );
+    }
+
+    #[test]
+    fn test_not_found_and_unauthorized() {
+        let env = Env::default();
+        let output = to_str(&not_found(&env, "/tasks"));
+        assert!(output.contains("\"text\":\"404 Not Found\""));
+        assert!(output.contains("\"path\":\"/tasks\""));
+
+        let output = to_str(&unauthorized(&env, "/"));
+        assert!(output.contains("\"text\":\"401 Unauthorized\""));
+        assert!(output.contains("\"path\":\"/\""));
     }
 }
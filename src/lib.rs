@@ -31,25 +31,59 @@
 //! - `json` - JsonDocument builder for JSON UI format (default)
 //! - `router` - Router and path utilities (default)
 //! - `styles` - StyleBuilder for CSS stylesheet output (default)
+//! - `alloc` - Heap-buffered builder backend via `new_buffered` (requires an allocator)
+//! - `testutils` - Output assertions for contract tests (`testutils` module)
 
 #![no_std]
 
+#[cfg(any(feature = "alloc", feature = "testutils", test))]
+extern crate alloc;
+
 // Core bytes module - always available
 pub mod bytes;
 
+// Transaction argument builder - always available
+pub mod args;
+
+// Chunked content storage - always available
+pub mod chunks;
+
+// Output assertions for tests
+#[cfg(any(feature = "testutils", test))]
+pub mod testutils;
+
 // Metadata macros - always available
 mod metadata;
+#[doc(hidden)]
+pub use metadata::{__render_routes_len, __render_routes_xdr};
+
+// Output accumulator used by the builders
+#[cfg(any(feature = "markdown", feature = "json", feature = "styles"))]
+mod sink;
 
 // Feature-gated modules
 #[cfg(feature = "markdown")]
 pub mod markdown;
 
+#[cfg(feature = "markdown")]
+pub mod render_trait;
+
+#[cfg(any(feature = "markdown", feature = "json"))]
+pub mod component;
+
 #[cfg(feature = "json")]
 pub mod json;
 
 #[cfg(feature = "router")]
 pub mod router;
 
+#[cfg(feature = "router")]
+pub mod paginate;
+
+// render_entry! macro
+#[cfg(feature = "router")]
+mod entry;
+
 #[cfg(feature = "styles")]
 pub mod styles;
 
@@ -15,16 +15,24 @@
 //!     .build();
 //! ```
 
-use crate::bytes::{concat_bytes, string_to_bytes, u32_to_bytes};
-use soroban_sdk::{Bytes, Env, String, Vec};
+use crate::args::TxArgs;
+use crate::bytes::{
+    address_to_bytes, bytes_to_string, format_relative_time, format_timestamp, i64_to_bytes,
+    i128_to_bytes, percent_encode, string_to_bytes, truncate_string, u32_to_bytes,
+};
+#[cfg(feature = "router")]
+use crate::paginate::Pagination;
+use crate::sink::Sink;
+use soroban_sdk::{Address, Bytes, Env, IntoVal, String, TryFromVal, Val, Vec};
 
 /// A builder for constructing markdown content.
 ///
 /// Uses the `Vec<Bytes>` accumulator pattern internally for efficient
-/// string building in Soroban's no_std environment.
+/// string building in Soroban's no_std environment. With the `alloc`
+/// feature, `new_buffered` accumulates into a single heap buffer instead.
 pub struct MarkdownBuilder<'a> {
     env: &'a Env,
-    parts: Vec<Bytes>,
+    parts: Sink,
 }
 
 impl<'a> MarkdownBuilder<'a> {
@@ -32,7 +40,19 @@ impl<'a> MarkdownBuilder<'a> {
     pub fn new(env: &'a Env) -> Self {
         Self {
             env,
-            parts: Vec::new(env),
+            parts: Sink::new(env),
+        }
+    }
+
+    /// Create a new MarkdownBuilder that accumulates into a heap buffer.
+    ///
+    /// Produces identical output to `new`, but avoids creating a host object
+    /// for every fragment. Requires the `alloc` feature and an allocator.
+    #[cfg(feature = "alloc")]
+    pub fn new_buffered(env: &'a Env) -> Self {
+        Self {
+            env,
+            parts: Sink::buffered(),
         }
     }
 
@@ -42,13 +62,204 @@ impl<'a> MarkdownBuilder<'a> {
 
     /// Push a byte slice as Bytes.
     fn push_bytes(&mut self, bytes: &[u8]) {
-        self.parts.push_back(Bytes::from_slice(self.env, bytes));
+        self.parts.push_slice(self.env, bytes);
     }
 
     /// Push a string as Bytes.
     fn push_str(&mut self, s: &str) {
-        self.parts
-            .push_back(Bytes::from_slice(self.env, s.as_bytes()));
+        self.parts.push_slice(self.env, s.as_bytes());
+    }
+
+    /// Push text, backslash-escaping any of the given bytes.
+    ///
+    /// Unescaped runs are pushed as whole slices rather than byte by byte.
+    fn push_escaped(&mut self, text: &str, specials: &[u8]) {
+        let bytes = text.as_bytes();
+        let mut start = 0;
+        for (i, b) in bytes.iter().enumerate() {
+            if specials.contains(b) {
+                if i > start {
+                    self.push_bytes(&bytes[start..i]);
+                }
+                self.push_bytes(&[b'\\', *b]);
+                start = i + 1;
+            }
+        }
+        if start < bytes.len() {
+            self.push_bytes(&bytes[start..]);
+        }
+    }
+
+    /// Push text escaped for use in an HTML attribute value or element body.
+    ///
+    /// Escapes `&`, `"`, `<`, and `>` as HTML entities.
+    fn push_attr(&mut self, value: &str) {
+        let bytes = value.as_bytes();
+        let mut start = 0;
+        for (i, b) in bytes.iter().enumerate() {
+            let entity: &[u8] = match b {
+                b'&' => b"&amp;",
+                b'"' => b"&quot;",
+                b'<' => b"&lt;",
+                b'>' => b"&gt;",
+                _ => continue,
+            };
+            if i > start {
+                self.push_bytes(&bytes[start..i]);
+            }
+            self.push_bytes(entity);
+            start = i + 1;
+        }
+        if start < bytes.len() {
+            self.push_bytes(&bytes[start..]);
+        }
+    }
+
+    /// Push `](render:prefix{id})`.
+    fn push_render_id(&mut self, prefix: &str, id: u32) {
+        self.push_bytes(b"](render:");
+        self.push_str(prefix);
+        self.parts.push_back(u32_to_bytes(self.env, id));
+        self.push_bytes(b")");
+    }
+
+    /// Push text for the body of an HTML comment.
+    ///
+    /// A `>` that would close the comment (after `--` or `--!`) is written
+    /// as `&gt;`, so the text cannot end the comment early.
+    fn push_comment_text(&mut self, text: &str) {
+        let bytes = text.as_bytes();
+        let mut start = 0;
+        for (i, b) in bytes.iter().enumerate() {
+            if *b != b'>' {
+                continue;
+            }
+            let head = &bytes[..i];
+            if head.ends_with(b"--") || head.ends_with(b"--!") {
+                self.push_bytes(&bytes[start..i]);
+                self.push_bytes(b"&gt;");
+                start = i + 1;
+            }
+        }
+        if start < bytes.len() {
+            self.push_bytes(&bytes[start..]);
+        }
+    }
+
+    /// Push the `>` prefix for one quoted line, `depth` levels deep.
+    ///
+    /// Empty lines get a bare prefix so the quote block stays unbroken.
+    fn push_quote_prefix(&mut self, depth: u8, empty: bool) {
+        for _ in 0..depth {
+            self.push_bytes(b">");
+        }
+        if !empty {
+            self.push_bytes(b" ");
+        }
+    }
+
+    /// Push text as quoted lines, splitting on `\n`.
+    fn push_quoted_str(&mut self, depth: u8, text: &str) {
+        for line in text.split('\n') {
+            self.push_quote_prefix(depth, line.is_empty());
+            self.push_str(line);
+            self.push_bytes(b"\n");
+        }
+    }
+
+    /// Push Bytes as quoted lines, splitting on `\n`.
+    fn push_quoted_bytes(&mut self, depth: u8, text: &Bytes) {
+        let mut start = 0;
+        let len = text.len();
+        for i in 0..=len {
+            if i < len && text.get_unchecked(i) != b'\n' {
+                continue;
+            }
+            self.push_quote_prefix(depth, i == start);
+            if i > start {
+                self.parts.push_back(text.slice(start..i));
+            }
+            self.push_bytes(b"\n");
+            start = i + 1;
+        }
+    }
+
+    /// Push the `#` prefix for a heading, clamping the level to 1-6.
+    fn push_heading_prefix(&mut self, level: u8) {
+        let level = level.clamp(1, 6) as usize;
+        self.push_bytes(&b"###### "[6 - level..]);
+    }
+
+    /// Push `](tx:method {"id":n})`.
+    fn push_tx_id(&mut self, method: &str, id: u32) {
+        self.push_bytes(b"](tx:");
+        self.push_str(method);
+        self.push_bytes(b" {\"id\":");
+        self.parts.push_back(u32_to_bytes(self.env, id));
+        self.push_bytes(b"})");
+    }
+
+    /// Push `**label:** ` for a key-value line.
+    fn push_kv_label(&mut self, label: &str) {
+        self.push_bytes(b"**");
+        self.push_str(label);
+        self.push_bytes(b":** ");
+    }
+
+    /// Push `[text](render:{base_path}/{page})`.
+    fn push_page_link(&mut self, text: Bytes, base_path: &str, page: u32) {
+        self.push_bytes(b"[");
+        self.parts.push_back(text);
+        self.push_bytes(b"](render:");
+        self.push_str(base_path);
+        self.push_bytes(b"/");
+        self.parts.push_back(u32_to_bytes(self.env, page));
+        self.push_bytes(b")");
+    }
+
+    /// Push a numbered pager entry; the current page is bold and unlinked.
+    fn push_page_number(&mut self, base_path: &str, page: u32, current: u32) {
+        if page == current {
+            self.push_bytes(b"**");
+            self.parts.push_back(u32_to_bytes(self.env, page));
+            self.push_bytes(b"**");
+        } else {
+            self.push_page_link(u32_to_bytes(self.env, page), base_path, page);
+        }
+    }
+
+    /// Push the separator between pager entries, with an ellipsis for skipped pages.
+    fn push_pager_gap(&mut self, skipped: bool) {
+        if skipped {
+            self.push_str(" … ");
+        } else {
+            self.push_bytes(b" ");
+        }
+    }
+
+    /// Push `](render:prefix{segment})` with the segment percent-encoded.
+    fn push_render_segment(&mut self, prefix: &str, segment: &String) {
+        self.push_bytes(b"](render:");
+        self.push_str(prefix);
+        let segment = string_to_bytes(self.env, segment);
+        self.parts.push_back(percent_encode(self.env, &segment));
+        self.push_bytes(b")");
+    }
+
+    /// Push ` {args})`, or just `)` when there are no arguments.
+    fn push_tx_args(&mut self, args: &TxArgs) {
+        if !args.is_empty() {
+            self.push_bytes(b" ");
+            self.parts.push_back(args.to_bytes());
+        }
+        self.push_bytes(b")");
+    }
+
+    /// Push two spaces of indentation per nesting level.
+    fn push_indent(&mut self, depth: u8) {
+        for _ in 0..depth {
+            self.push_bytes(b"  ");
+        }
     }
 
     /// Wrap text with a prefix and suffix (for bold, italic, code, strikethrough).
@@ -59,6 +270,25 @@ impl<'a> MarkdownBuilder<'a> {
         self
     }
 
+    /// Wrap dynamic text with prefix and suffix bytes.
+    fn wrap_string(mut self, prefix: &[u8], text: &String, suffix: &[u8]) -> Self {
+        self.push_bytes(prefix);
+        self.parts.push_back(string_to_bytes(self.env, text));
+        self.push_bytes(suffix);
+        self
+    }
+
+    /// Build a markdown link with dynamic text: `[text](protocol:target)`
+    fn build_link_string(mut self, text: &String, protocol: &[u8], target: &str) -> Self {
+        self.push_bytes(b"[");
+        self.parts.push_back(string_to_bytes(self.env, text));
+        self.push_bytes(b"](");
+        self.push_bytes(protocol);
+        self.push_str(target);
+        self.push_bytes(b")");
+        self
+    }
+
     /// Build a markdown link: `[text](protocol:target)`
     fn build_link(mut self, text: &str, protocol: &[u8], target: &str) -> Self {
         self.push_bytes(b"[");
@@ -116,6 +346,84 @@ impl<'a> MarkdownBuilder<'a> {
         self
     }
 
+    /// Build an image: `![alt](protocol:src "title")`
+    fn build_image(mut self, alt: &str, protocol: &[u8], src: &str, title: Option<&str>) -> Self {
+        self.push_bytes(b"![");
+        self.push_escaped(alt, b"\\[]");
+        self.push_bytes(b"](");
+        self.push_bytes(protocol);
+        self.push_str(src);
+        if let Some(title) = title {
+            self.push_bytes(b" \"");
+            self.push_escaped(title, b"\\\"");
+            self.push_bytes(b"\"");
+        }
+        self.push_bytes(b")");
+        self
+    }
+
+    /// Build a number input, omitting constraints that are `None`.
+    fn build_number_input(
+        mut self,
+        name: &str,
+        placeholder: &str,
+        min: Option<i64>,
+        max: Option<i64>,
+        step: Option<u32>,
+        value: Option<i64>,
+    ) -> Self {
+        self.push_bytes(b"<input type=\"number\" name=\"");
+        self.push_attr(name);
+        self.push_bytes(b"\" placeholder=\"");
+        self.push_attr(placeholder);
+        self.push_bytes(b"\"");
+        if let Some(min) = min {
+            self.push_bytes(b" min=\"");
+            self.parts.push_back(i64_to_bytes(self.env, min));
+            self.push_bytes(b"\"");
+        }
+        if let Some(max) = max {
+            self.push_bytes(b" max=\"");
+            self.parts.push_back(i64_to_bytes(self.env, max));
+            self.push_bytes(b"\"");
+        }
+        if let Some(step) = step {
+            self.push_bytes(b" step=\"");
+            self.parts.push_back(u32_to_bytes(self.env, step));
+            self.push_bytes(b"\"");
+        }
+        if let Some(value) = value {
+            self.push_bytes(b" value=\"");
+            self.parts.push_back(i64_to_bytes(self.env, value));
+            self.push_bytes(b"\"");
+        }
+        self.push_bytes(b" />\n");
+        self
+    }
+
+    /// Close a label-wrapped input after its attributes have been written.
+    fn finish_labeled_input(mut self, label: &str, checked: bool) -> Self {
+        if checked {
+            self.push_bytes(b" checked");
+        }
+        self.push_bytes(b" /> ");
+        self.push_attr(label);
+        self.push_bytes(b"</label>\n");
+        self
+    }
+
+    /// Close an option tag after its value has been written.
+    fn finish_option(mut self, label: &str, selected: bool) -> Self {
+        if selected {
+            self.push_bytes(b"\" selected>");
+        } else {
+            self.push_bytes(b"\">");
+        }
+        self.push_attr(label);
+        self.push_bytes(b"</option>\n");
+        self
+    }
+
     // ========================================================================
     // Headings
     // ========================================================================
@@ -136,21 +444,25 @@ impl<'a> MarkdownBuilder<'a> {
     }
 
     /// Add a heading at a specific level (1-6).
+    ///
+    /// Level 0 is treated as 1 and levels above 6 as 6.
     pub fn heading(mut self, level: u8, text: &str) -> Self {
-        let prefix = match level {
-            1 => b"# ".as_slice(),
-            2 => b"## ".as_slice(),
-            3 => b"### ".as_slice(),
-            4 => b"#### ".as_slice(),
-            5 => b"##### ".as_slice(),
-            _ => b"###### ".as_slice(),
-        };
-        self.push_bytes(prefix);
+        self.push_heading_prefix(level);
         self.push_str(text);
         self.push_bytes(b"\n\n");
         self
     }
 
+    /// Add a heading with dynamic text from a String.
+    ///
+    /// Levels are clamped as in `heading`.
+    pub fn heading_string(mut self, level: u8, text: &String) -> Self {
+        self.push_heading_prefix(level);
+        self.parts.push_back(string_to_bytes(self.env, text));
+        self.push_bytes(b"\n\n");
+        self
+    }
+
     // ========================================================================
     // Text Content
     // ========================================================================
@@ -208,12 +520,92 @@ impl<'a> MarkdownBuilder<'a> {
         self
     }
 
+    /// Add bold text from a soroban_sdk::String.
+    ///
+    /// The delimiters are written even when the string is empty.
+    pub fn bold_string(self, text: &String) -> Self {
+        self.wrap_string(b"**", text, b"**")
+    }
+
+    /// Add italic text from a soroban_sdk::String.
+    ///
+    /// The delimiters are written even when the string is empty.
+    pub fn italic_string(self, text: &String) -> Self {
+        self.wrap_string(b"*", text, b"*")
+    }
+
+    /// Add inline code from a soroban_sdk::String.
+    ///
+    /// The delimiters are written even when the string is empty.
+    pub fn code_string(self, text: &String) -> Self {
+        self.wrap_string(b"`", text, b"`")
+    }
+
+    /// Add strikethrough text from a soroban_sdk::String.
+    ///
+    /// The delimiters are written even when the string is empty.
+    pub fn strikethrough_string(self, text: &String) -> Self {
+        self.wrap_string(b"~~", text, b"~~")
+    }
+
     /// Add a u32 as text.
     pub fn number(mut self, n: u32) -> Self {
         self.parts.push_back(u32_to_bytes(self.env, n));
         self
     }
 
+    /// Add the time since `then` as a relative phrase, e.g. "5 minutes ago".
+    ///
+    /// Both arguments are unix timestamps in seconds; `now` is usually
+    /// `env.ledger().timestamp()`. See `format_relative_time`.
+    pub fn relative_time(mut self, now: u64, then: u64) -> Self {
+        self.parts
+            .push_back(format_relative_time(self.env, now, then));
+        self
+    }
+
+    /// Add a unix timestamp as `YYYY-MM-DD HH:MM:SS UTC`.
+    pub fn timestamp(mut self, unix_secs: u64) -> Self {
+        self.parts.push_back(format_timestamp(self.env, unix_secs));
+        self
+    }
+
+    /// Add a one-line preview of a String, truncated to `max_len` bytes.
+    ///
+    /// Runs of line breaks become a single space so the preview stays on one
+    /// line, and `…` is appended when the text was cut (see `truncate_string`).
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// .preview(&post.body, 140)
+    /// ```
+    pub fn preview(mut self, text: &String, max_len: u32) -> Self {
+        let truncated = truncate_string(self.env, text, max_len);
+        let len = truncated.len();
+        let mut start = 0;
+        let mut i = 0;
+        while i < len {
+            let b = truncated.get_unchecked(i);
+            if b != b'\n' && b != b'\r' {
+                i += 1;
+                continue;
+            }
+            if i > start {
+                self.parts.push_back(truncated.slice(start..i));
+            }
+            self.push_bytes(b" ");
+            while i < len && matches!(truncated.get_unchecked(i), b'\n' | b'\r') {
+                i += 1;
+            }
+            start = i;
+        }
+        if start < len {
+            self.parts.push_back(truncated.slice(start..len));
+        }
+        self
+    }
+
     /// Add raw Bytes.
     pub fn raw(mut self, bytes: Bytes) -> Self {
         self.parts.push_back(bytes);
@@ -226,6 +618,82 @@ impl<'a> MarkdownBuilder<'a> {
         self
     }
 
+    // ========================================================================
+    // Key-Value Metadata
+    // ========================================================================
+
+    /// Add a labelled value on its own line.
+    ///
+    /// Creates: `**label:** value`
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// builder
+    ///     .kv_block_start()
+    ///     .kv_address("Owner", &owner)
+    ///     .kv_u32("Votes", 12)
+    ///     .kv("Status", "Open")
+    ///     .kv_block_end()
+    /// ```
+    pub fn kv(mut self, label: &str, value: &str) -> Self {
+        self.push_kv_label(label);
+        self.push_str(value);
+        self.push_bytes(b"\n\n");
+        self
+    }
+
+    /// Add a labelled soroban_sdk::String value.
+    pub fn kv_string(mut self, label: &str, value: &String) -> Self {
+        self.push_kv_label(label);
+        self.parts.push_back(string_to_bytes(self.env, value));
+        self.push_bytes(b"\n\n");
+        self
+    }
+
+    /// Add a labelled u32 value.
+    pub fn kv_u32(mut self, label: &str, value: u32) -> Self {
+        self.push_kv_label(label);
+        self.parts.push_back(u32_to_bytes(self.env, value));
+        self.push_bytes(b"\n\n");
+        self
+    }
+
+    /// Add a labelled i128 value, such as a token amount.
+    pub fn kv_i128(mut self, label: &str, value: i128) -> Self {
+        self.push_kv_label(label);
+        self.parts.push_back(i128_to_bytes(self.env, value));
+        self.push_bytes(b"\n\n");
+        self
+    }
+
+    /// Add a labelled bool, rendered as `true` or `false`.
+    pub fn kv_bool(self, label: &str, value: bool) -> Self {
+        self.kv(label, if value { "true" } else { "false" })
+    }
+
+    /// Add a labelled Address, rendered as its full strkey.
+    pub fn kv_address(mut self, label: &str, value: &Address) -> Self {
+        self.push_kv_label(label);
+        self.parts.push_back(address_to_bytes(self.env, value));
+        self.push_bytes(b"\n\n");
+        self
+    }
+
+    /// Start a key-value block for styling.
+    ///
+    /// Creates: `<div class="kv">`
+    ///
+    /// Must be paired with `kv_block_end()`.
+    pub fn kv_block_start(self) -> Self {
+        self.div_start("kv")
+    }
+
+    /// End a key-value block.
+    pub fn kv_block_end(self) -> Self {
+        self.div_end()
+    }
+
     // ========================================================================
     // Links
     // ========================================================================
@@ -242,6 +710,73 @@ impl<'a> MarkdownBuilder<'a> {
         self.build_link(text, b"render:", path)
     }
 
+    /// Add a standard markdown link with text from a soroban_sdk::String.
+    ///
+    /// Creates: `[text](href)`
+    pub fn link_string(self, text: &String, href: &str) -> Self {
+        self.build_link_string(text, b"", href)
+    }
+
+    /// Add a render: link with text from a soroban_sdk::String.
+    ///
+    /// Creates: `[text](render:path)`
+    pub fn render_link_string_text(self, text: &String, path: &str) -> Self {
+        self.build_link_string(text, b"render:", path)
+    }
+
+    /// Add a render: link to a record identified by a u32.
+    ///
+    /// Creates: `[text](render:prefix{id})`
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// .render_link_id("View", "/task/", 42)
+    /// // Creates: [View](render:/task/42)
+    /// ```
+    pub fn render_link_id(mut self, text: &str, prefix: &str, id: u32) -> Self {
+        self.push_bytes(b"[");
+        self.push_str(text);
+        self.push_render_id(prefix, id);
+        self
+    }
+
+    /// Add a render: link with link text from a soroban_sdk::String.
+    ///
+    /// Creates: `[text](render:prefix{id})`
+    pub fn render_link_id_with_text(mut self, text: &String, prefix: &str, id: u32) -> Self {
+        self.push_bytes(b"[");
+        self.parts.push_back(string_to_bytes(self.env, text));
+        self.push_render_id(prefix, id);
+        self
+    }
+
+    /// Add a render: link to a record identified by a String path segment.
+    ///
+    /// Creates: `[text](render:prefix{segment})`, percent-encoding the segment
+    /// so spaces, slashes, and parentheses can't break the path or the link.
+    pub fn render_link_string(mut self, text: &str, prefix: &str, segment: &String) -> Self {
+        self.push_bytes(b"[");
+        self.push_str(text);
+        self.push_render_segment(prefix, segment);
+        self
+    }
+
+    /// Add a render: link with a String segment and link text from a soroban_sdk::String.
+    ///
+    /// Creates: `[text](render:prefix{segment})`
+    pub fn render_link_string_with_text(
+        mut self,
+        text: &String,
+        prefix: &str,
+        segment: &String,
+    ) -> Self {
+        self.push_bytes(b"[");
+        self.parts.push_back(string_to_bytes(self.env, text));
+        self.push_render_segment(prefix, segment);
+        self
+    }
+
     /// Add a tx: protocol link for transactions.
     ///
     /// Creates: `[text](tx:method args)`
@@ -262,11 +797,7 @@ impl<'a> MarkdownBuilder<'a> {
     pub fn tx_link_id(mut self, text: &str, method: &str, id: u32) -> Self {
         self.push_bytes(b"[");
         self.push_str(text);
-        self.push_bytes(b"](tx:");
-        self.push_str(method);
-        self.push_bytes(b" {\"id\":");
-        self.parts.push_back(u32_to_bytes(self.env, id));
-        self.push_bytes(b"})");
+        self.push_tx_id(method, id);
         self
     }
 
@@ -305,8 +836,69 @@ impl<'a> MarkdownBuilder<'a> {
         self.build_aliased_link(text, b"tx:", alias, method, args)
     }
 
+    /// Add a tx: link with arguments from a `TxArgs` builder.
+    ///
+    /// Creates: `[text](tx:method {"key":value,...})`, omitting the arguments
+    /// when none were added.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let args = TxArgs::new(&env).add_u32("id", 5).add_str("title", title);
+    /// builder.tx_link_args("Save", "update_task", &args)
+    /// ```
+    pub fn tx_link_args(mut self, text: &str, method: &str, args: &TxArgs) -> Self {
+        self.push_bytes(b"[");
+        self.push_str(text);
+        self.push_bytes(b"](tx:");
+        self.push_str(method);
+        self.push_tx_args(args);
+        self
+    }
+
+    /// Add a tx: link targeting a contract via registry alias, with `TxArgs`.
+    ///
+    /// Creates: `[text](tx:@alias:method {"key":value,...})`
+    pub fn tx_link_to_args(mut self, text: &str, alias: &str, method: &str, args: &TxArgs) -> Self {
+        self.push_bytes(b"[");
+        self.push_str(text);
+        self.push_bytes(b"](tx:@");
+        self.push_str(alias);
+        self.push_bytes(b":");
+        self.push_str(method);
+        self.push_tx_args(args);
+        self
+    }
+
     // ========================================================================
-    // Alerts / Callouts
+    // Images
+    // ========================================================================
+
+    /// Add an image.
+    ///
+    /// Creates: `![alt](src)`
+    ///
+    /// Brackets and backslashes in the alt text are escaped.
+    pub fn image(self, alt: &str, src: &str) -> Self {
+        self.build_image(alt, b"", src, None)
+    }
+
+    /// Add an image served by a render function.
+    ///
+    /// Creates: `![alt](render:path)`
+    pub fn image_render(self, alt: &str, path: &str) -> Self {
+        self.build_image(alt, b"render:", path, None)
+    }
+
+    /// Add an image with a title (shown as a tooltip by most viewers).
+    ///
+    /// Creates: `![alt](src "title")`
+    pub fn image_with_title(self, alt: &str, src: &str, title: &str) -> Self {
+        self.build_image(alt, b"", src, Some(title))
+    }
+
+    // ========================================================================
+    // Alerts / Callouts
     // ========================================================================
 
     /// Add a TIP alert callout.
@@ -350,6 +942,59 @@ impl<'a> MarkdownBuilder<'a> {
         self
     }
 
+    /// Add an alert whose body spans several lines.
+    ///
+    /// Each line is prefixed with `> ` so the whole body stays in the callout.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// .alert_lines("WARNING", &["Withdrawals are paused.", "Deposits still work."])
+    /// ```
+    pub fn alert_lines(self, alert_type: &str, lines: &[&str]) -> Self {
+        let mut builder = self.alert_start(alert_type);
+        for line in lines {
+            builder = builder.alert_line(line);
+        }
+        builder.alert_end()
+    }
+
+    /// Add an alert whose body is a soroban_sdk::String.
+    ///
+    /// The body is split on `\n` and every line is prefixed with `> `.
+    pub fn alert_string(mut self, alert_type: &str, content: &String) -> Self {
+        let bytes = string_to_bytes(self.env, content);
+        self = self.alert_start(alert_type);
+        self.push_quoted_bytes(1, &bytes);
+        self.alert_end()
+    }
+
+    /// Start an alert to be filled with `alert_line()` calls.
+    ///
+    /// Creates: `> [!TYPE]`
+    ///
+    /// Must be closed with `alert_end()`.
+    pub fn alert_start(mut self, alert_type: &str) -> Self {
+        self.push_bytes(b"> [!");
+        self.push_str(alert_type);
+        self.push_bytes(b"]\n");
+        self
+    }
+
+    /// Add a line to an alert opened with `alert_start()`.
+    ///
+    /// Creates: `> text`
+    pub fn alert_line(mut self, text: &str) -> Self {
+        self.push_quoted_str(1, text);
+        self
+    }
+
+    /// End an alert opened with `alert_start()`.
+    pub fn alert_end(mut self) -> Self {
+        self.push_bytes(b"\n");
+        self
+    }
+
     // ========================================================================
     // Columns Layout
     // ========================================================================
@@ -408,10 +1053,134 @@ impl<'a> MarkdownBuilder<'a> {
         self
     }
 
+    /// Include content from a contract registered under an alias.
+    ///
+    /// Creates: `{{include contract=@alias func="name"}}`
+    ///
+    /// The viewer resolves the alias through the app's registry.
+    pub fn include_alias(mut self, alias: &str, func: &str) -> Self {
+        self.push_bytes(b"{{include contract=@");
+        self.push_str(alias);
+        self.push_bytes(b" func=\"");
+        self.push_str(func);
+        self.push_bytes(b"\"}}");
+        self
+    }
+
+    /// Include content from an aliased contract with a path argument.
+    ///
+    /// Creates: `{{include contract=@alias func="name" path="path"}}`
+    pub fn include_alias_with_path(mut self, alias: &str, func: &str, path: &str) -> Self {
+        self.push_bytes(b"{{include contract=@");
+        self.push_str(alias);
+        self.push_bytes(b" func=\"");
+        self.push_str(func);
+        self.push_bytes(b"\" path=\"");
+        self.push_str(path);
+        self.push_bytes(b"\"}}");
+        self
+    }
+
+    /// Include content from a contract resolved on-chain.
+    ///
+    /// Creates: `{{include contract=CONTRACT_ID func="name"}}`
+    ///
+    /// Use this when the contract has already looked the address up, for
+    /// example with `BaseRegistry::get_by_alias`, so the viewer does not
+    /// need to resolve an alias.
+    pub fn include_resolved(mut self, address: &Address, func: &str) -> Self {
+        self.push_bytes(b"{{include contract=");
+        self.parts.push_back(address_to_bytes(self.env, address));
+        self.push_bytes(b" func=\"");
+        self.push_str(func);
+        self.push_bytes(b"\"}}");
+        self
+    }
+
+    // ========================================================================
+    // Comments and Markers
+    // ========================================================================
+
+    /// Add an HTML comment, invisible in rendered output.
+    ///
+    /// Creates: `<!-- text -->`
+    ///
+    /// A `-->` inside the text is written as `--&gt;` so it cannot close the
+    /// comment early and expose the rest as markup.
+    pub fn html_comment(mut self, text: &str) -> Self {
+        self.push_bytes(b"<!-- ");
+        self.push_comment_text(text);
+        self.push_bytes(b" -->\n");
+        self
+    }
+
+    /// Add a structured marker comment for viewers and tooling.
+    ///
+    /// Creates: `<!-- render:key=value -->`
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// builder.marker("section", "comments")
+    /// // Creates: <!-- render:section=comments -->
+    /// ```
+    pub fn marker(mut self, key: &str, value: &str) -> Self {
+        self.push_bytes(b"<!-- render:");
+        self.push_comment_text(key);
+        self.push_bytes(b"=");
+        self.push_comment_text(value);
+        self.push_bytes(b" -->\n");
+        self
+    }
+
     // ========================================================================
     // Form Elements (HTML)
     // ========================================================================
 
+    /// Start a form element.
+    ///
+    /// Creates: `<form action="action" method="method">`
+    ///
+    /// Must be paired with `form_end()` to close the element.
+    pub fn form_start(mut self, action: &str, method: &str) -> Self {
+        self.push_bytes(b"<form action=\"");
+        self.push_attr(action);
+        self.push_bytes(b"\" method=\"");
+        self.push_attr(method);
+        self.push_bytes(b"\">\n");
+        self
+    }
+
+    /// Start a form that submits a transaction to a contract method.
+    ///
+    /// Creates: `<form action="tx:method_name" method="POST">`
+    pub fn form_tx(mut self, method_name: &str) -> Self {
+        self.push_bytes(b"<form action=\"tx:");
+        self.push_attr(method_name);
+        self.push_bytes(b"\" method=\"POST\">\n");
+        self
+    }
+
+    /// End a form element.
+    ///
+    /// Creates: `</form>`
+    pub fn form_end(mut self) -> Self {
+        self.push_bytes(b"</form>\n");
+        self
+    }
+
+    /// Add a button element.
+    ///
+    /// Creates: `<button type="button_type">label</button>`
+    pub fn button(mut self, button_type: &str, label: &str) -> Self {
+        self.push_bytes(b"<button type=\"");
+        self.push_attr(button_type);
+        self.push_bytes(b"\">");
+        self.push_attr(label);
+        self.push_bytes(b"</button>\n");
+        self
+    }
+
     /// Add an input element.
     ///
     /// Creates: `<input name="name" placeholder="placeholder" />`
@@ -512,6 +1281,165 @@ impl<'a> MarkdownBuilder<'a> {
         self
     }
 
+    /// Add a label for a form element.
+    ///
+    /// Creates: `<label for="for_name">text</label>`
+    pub fn label(mut self, for_name: &str, text: &str) -> Self {
+        self.push_bytes(b"<label for=\"");
+        self.push_attr(for_name);
+        self.push_bytes(b"\">");
+        self.push_attr(text);
+        self.push_bytes(b"</label>\n");
+        self
+    }
+
+    /// Start a fieldset that groups related form elements.
+    ///
+    /// Creates: `<fieldset>\n<legend>legend</legend>`
+    ///
+    /// Must be paired with `fieldset_end()` to close the element.
+    pub fn fieldset_start(mut self, legend: &str) -> Self {
+        self.push_bytes(b"<fieldset>\n<legend>");
+        self.push_attr(legend);
+        self.push_bytes(b"</legend>\n");
+        self
+    }
+
+    /// End a fieldset.
+    ///
+    /// Creates: `</fieldset>`
+    pub fn fieldset_end(mut self) -> Self {
+        self.push_bytes(b"</fieldset>\n");
+        self
+    }
+
+    /// Add a number input with optional constraints.
+    ///
+    /// Creates: `<input type="number" name="name" placeholder="placeholder" min="0" max="100" step="5" />`
+    ///
+    /// Constraints that are `None` are omitted.
+    pub fn number_input(
+        self,
+        name: &str,
+        placeholder: &str,
+        min: Option<i64>,
+        max: Option<i64>,
+        step: Option<u32>,
+    ) -> Self {
+        self.build_number_input(name, placeholder, min, max, step, None)
+    }
+
+    /// Add a number input with constraints and a pre-populated value.
+    ///
+    /// Creates: `<input type="number" name="name" placeholder="placeholder" min="0" value="42" />`
+    pub fn number_input_with_value(
+        self,
+        name: &str,
+        placeholder: &str,
+        min: Option<i64>,
+        max: Option<i64>,
+        step: Option<u32>,
+        value: i64,
+    ) -> Self {
+        self.build_number_input(name, placeholder, min, max, step, Some(value))
+    }
+
+    /// Add a date input, optionally pre-populated with a `YYYY-MM-DD` value.
+    ///
+    /// Creates: `<input type="date" name="name" value="2025-01-31" />`
+    pub fn date_input(mut self, name: &str, value: Option<&str>) -> Self {
+        self.push_bytes(b"<input type=\"date\" name=\"");
+        self.push_attr(name);
+        self.push_bytes(b"\"");
+        if let Some(value) = value {
+            self.push_bytes(b" value=\"");
+            self.push_attr(value);
+            self.push_bytes(b"\"");
+        }
+        self.push_bytes(b" />\n");
+        self
+    }
+
+    /// Add a range (slider) input.
+    ///
+    /// Creates: `<input type="range" name="name" min="0" max="100" value="50" />`
+    pub fn range_input(mut self, name: &str, min: u32, max: u32, value: u32) -> Self {
+        self.push_bytes(b"<input type=\"range\" name=\"");
+        self.push_attr(name);
+        self.push_bytes(b"\" min=\"");
+        self.parts.push_back(u32_to_bytes(self.env, min));
+        self.push_bytes(b"\" max=\"");
+        self.parts.push_back(u32_to_bytes(self.env, max));
+        self.push_bytes(b"\" value=\"");
+        self.parts.push_back(u32_to_bytes(self.env, value));
+        self.push_bytes(b"\" />\n");
+        self
+    }
+
+    /// Add a checkbox input wrapped in a label.
+    ///
+    /// Creates: `<label><input type="checkbox" name="name" value="true" /> label</label>`,
+    /// with a bare `checked` attribute when `checked` is true.
+    pub fn checkbox_input(mut self, name: &str, label: &str, checked: bool) -> Self {
+        self.push_bytes(b"<label><input type=\"checkbox\" name=\"");
+        self.push_attr(name);
+        self.push_bytes(b"\" value=\"true\"");
+        self.finish_labeled_input(label, checked)
+    }
+
+    /// Add a radio input wrapped in a label.
+    ///
+    /// Creates: `<label><input type="radio" name="name" value="value" /> label</label>`,
+    /// with a bare `checked` attribute when `checked` is true. Radios sharing a
+    /// `name` form one group.
+    pub fn radio_input(mut self, name: &str, value: &str, label: &str, checked: bool) -> Self {
+        self.push_bytes(b"<label><input type=\"radio\" name=\"");
+        self.push_attr(name);
+        self.push_bytes(b"\" value=\"");
+        self.push_attr(value);
+        self.push_bytes(b"\"");
+        self.finish_labeled_input(label, checked)
+    }
+
+    /// Start a select (dropdown) element.
+    ///
+    /// Creates: `<select name="name">`
+    ///
+    /// Add choices with `option()`/`option_u32()` and close with `select_end()`.
+    pub fn select_start(mut self, name: &str) -> Self {
+        self.push_bytes(b"<select name=\"");
+        self.push_attr(name);
+        self.push_bytes(b"\">\n");
+        self
+    }
+
+    /// Add an option to a select element.
+    ///
+    /// Creates: `<option value="value">label</option>`, with a bare
+    /// `selected` attribute when `selected` is true.
+    pub fn option(mut self, value: &str, label: &str, selected: bool) -> Self {
+        self.push_bytes(b"<option value=\"");
+        self.push_attr(value);
+        self.finish_option(label, selected)
+    }
+
+    /// Add an option with a numeric value to a select element.
+    ///
+    /// Creates: `<option value="123">label</option>`
+    pub fn option_u32(mut self, value: u32, label: &str, selected: bool) -> Self {
+        self.push_bytes(b"<option value=\"");
+        self.parts.push_back(u32_to_bytes(self.env, value));
+        self.finish_option(label, selected)
+    }
+
+    /// End a select element.
+    ///
+    /// Creates: `</select>`
+    pub fn select_end(mut self) -> Self {
+        self.push_bytes(b"</select>\n");
+        self
+    }
+
     /// Add a redirect instruction for form submission.
     ///
     /// After successful transaction, the viewer will navigate to this path.
@@ -704,6 +1632,82 @@ impl<'a> MarkdownBuilder<'a> {
         self.wrap_text(b"- ", text, b"\n")
     }
 
+    /// Add a list item from a soroban_sdk::String.
+    ///
+    /// Creates: `- text`
+    pub fn list_item_string(mut self, text: &String) -> Self {
+        self.push_bytes(b"- ");
+        self.parts.push_back(string_to_bytes(self.env, text));
+        self.push_bytes(b"\n");
+        self
+    }
+
+    /// Add an unordered list item that is a render: link.
+    ///
+    /// Creates: `- [text](render:path)`
+    pub fn list_item_link(mut self, text: &str, path: &str) -> Self {
+        self.push_bytes(b"- ");
+        self.render_link(text, path).newline()
+    }
+
+    /// Add an unordered list item linking to a record identified by a u32.
+    ///
+    /// Creates: `- [text](render:prefix{id})`
+    pub fn list_item_render_link_id(mut self, text: &str, prefix: &str, id: u32) -> Self {
+        self.push_bytes(b"- ");
+        self.render_link_id(text, prefix, id).newline()
+    }
+
+    /// Add an unordered list item linking to a record, with text from a soroban_sdk::String.
+    ///
+    /// Creates: `- [text](render:path_prefix{id})`
+    pub fn list_item_string_link(mut self, text: &String, path_prefix: &str, id: u32) -> Self {
+        self.push_bytes(b"- ");
+        self.render_link_id_with_text(text, path_prefix, id)
+            .newline()
+    }
+
+    /// Add a numbered list item.
+    ///
+    /// Creates: `N. text`
+    pub fn ordered_item(mut self, n: u32, text: &str) -> Self {
+        self.parts.push_back(u32_to_bytes(self.env, n));
+        self.wrap_text(b". ", text, b"\n")
+    }
+
+    /// Add a numbered list item from a soroban_sdk::String.
+    ///
+    /// Creates: `N. text`
+    pub fn ordered_item_string(mut self, n: u32, text: &String) -> Self {
+        self.parts.push_back(u32_to_bytes(self.env, n));
+        self.push_bytes(b". ");
+        self.parts.push_back(string_to_bytes(self.env, text));
+        self.push_bytes(b"\n");
+        self
+    }
+
+    /// Add a list item nested `depth` levels deep.
+    ///
+    /// Creates: `- text` prefixed with two spaces per level.
+    pub fn list_item_nested(mut self, depth: u8, text: &str) -> Self {
+        self.push_indent(depth);
+        self.wrap_text(b"- ", text, b"\n")
+    }
+
+    /// Add a nested list item from a soroban_sdk::String.
+    pub fn list_item_nested_string(mut self, depth: u8, text: &String) -> Self {
+        self.push_indent(depth);
+        self.list_item_string(text)
+    }
+
+    /// End a list.
+    ///
+    /// Emits the blank line that separates a list from following content.
+    pub fn list_end(mut self) -> Self {
+        self.push_bytes(b"\n");
+        self
+    }
+
     /// Add a checkbox list item.
     ///
     /// Creates: `- [x] text` or `- [ ] text`
@@ -715,6 +1719,41 @@ impl<'a> MarkdownBuilder<'a> {
         self
     }
 
+    /// Add a checkbox list item whose text toggles it through a transaction.
+    ///
+    /// Creates: `- [x] [text](tx:method {"id":n})` or the `- [ ]` form.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// .checkbox_tx(task.done, "Write docs", "toggle_task", task.id)
+    /// // Creates: - [ ] [Write docs](tx:toggle_task {"id":7})
+    /// ```
+    pub fn checkbox_tx(mut self, checked: bool, text: &str, method: &str, id: u32) -> Self {
+        self.push_bytes(if checked { b"- [x] [" } else { b"- [ ] [" });
+        self.push_str(text);
+        self.push_tx_id(method, id);
+        self.push_bytes(b"\n");
+        self
+    }
+
+    /// Add a transaction-bound checkbox list item with text from a soroban_sdk::String.
+    ///
+    /// Creates: `- [x] [text](tx:method {"id":n})` or the `- [ ]` form.
+    pub fn checkbox_tx_string(
+        mut self,
+        checked: bool,
+        text: &String,
+        method: &str,
+        id: u32,
+    ) -> Self {
+        self.push_bytes(if checked { b"- [x] [" } else { b"- [ ] [" });
+        self.parts.push_back(string_to_bytes(self.env, text));
+        self.push_tx_id(method, id);
+        self.push_bytes(b"\n");
+        self
+    }
+
     // ========================================================================
     // Blockquotes
     // ========================================================================
@@ -726,6 +1765,33 @@ impl<'a> MarkdownBuilder<'a> {
         self.wrap_text(b"> ", text, b"\n\n")
     }
 
+    /// Add a blockquote that may span several lines.
+    ///
+    /// Every line is prefixed with `> `; empty lines become a bare `>` so
+    /// paragraphs inside the quote stay in one block.
+    pub fn blockquote_multiline(self, text: &str) -> Self {
+        self.blockquote_nested(1, text)
+    }
+
+    /// Add a blockquote from a soroban_sdk::String, split on `\n`.
+    ///
+    /// Useful for quoting stored replies that contain newlines.
+    pub fn blockquote_string(mut self, text: &String) -> Self {
+        let bytes = string_to_bytes(self.env, text);
+        self.push_quoted_bytes(1, &bytes);
+        self.push_bytes(b"\n");
+        self
+    }
+
+    /// Add a blockquote nested `depth` levels deep.
+    ///
+    /// Creates: `>> text` for depth 2. A depth of 0 is treated as 1.
+    pub fn blockquote_nested(mut self, depth: u8, text: &str) -> Self {
+        self.push_quoted_str(depth.max(1), text);
+        self.push_bytes(b"\n");
+        self
+    }
+
     // ========================================================================
     // HTML Containers (div/span)
     // ========================================================================
@@ -763,32 +1829,168 @@ impl<'a> MarkdownBuilder<'a> {
         self
     }
 
-    /// End a div element.
+    /// Start a div element with an id and CSS classes.
     ///
-    /// Creates: `</div>`
-    pub fn div_end(mut self) -> Self {
-        self.push_bytes(b"</div>\n");
-        self
-    }
-
-    /// Start a span element with CSS classes.
+    /// Both values are HTML-escaped.
     ///
-    /// Creates: `<span class="classes">`
-    pub fn span_start(mut self, classes: &str) -> Self {
-        self.push_bytes(b"<span class=\"");
-        self.push_str(classes);
-        self.push_bytes(b"\">");
+    /// Creates: `<div id="id" class="classes">`
+    pub fn div_start_with_id(mut self, id: &str, classes: &str) -> Self {
+        self.push_bytes(b"<div id=\"");
+        self.push_attr(id);
+        self.push_bytes(b"\" class=\"");
+        self.push_attr(classes);
+        self.push_bytes(b"\">\n");
         self
     }
 
-    /// End a span element.
+    /// Start a div element with CSS classes and extra attributes.
     ///
-    /// Creates: `</span>`
-    pub fn span_end(mut self) -> Self {
+    /// Attribute names must be `id`, `style`, `role`, or start with
+    /// `data-`; values are HTML-escaped.
+    ///
+    /// Creates: `<div class="classes" name="value" ...>`
+    ///
+    /// # Panics
+    ///
+    /// Panics if an attribute name is not allowed.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// builder
+    ///     .div_start_attrs("comments", &[("data-collection", "comments")])
+    ///     .continuation("comments", 10, Some(50))
+    ///     .div_end()
+    /// ```
+    pub fn div_start_attrs(mut self, classes: &str, attrs: &[(&str, &str)]) -> Self {
+        self.push_bytes(b"<div class=\"");
+        self.push_attr(classes);
+        self.push_bytes(b"\"");
+        for (name, value) in attrs {
+            let allowed = matches!(*name, "id" | "style" | "role")
+                || (name.len() > 5 && name.starts_with("data-"));
+            if !allowed {
+                panic!("div attributes must be id, style, role, or data-*");
+            }
+            require_html_name(name);
+            self.push_bytes(b" ");
+            self.push_str(name);
+            self.push_bytes(b"=\"");
+            self.push_attr(value);
+            self.push_bytes(b"\"");
+        }
+        self.push_bytes(b">\n");
+        self
+    }
+
+    /// End a div element.
+    ///
+    /// Creates: `</div>`
+    pub fn div_end(mut self) -> Self {
+        self.push_bytes(b"</div>\n");
+        self
+    }
+
+    /// Start a span element with CSS classes.
+    ///
+    /// Creates: `<span class="classes">`
+    pub fn span_start(mut self, classes: &str) -> Self {
+        self.push_bytes(b"<span class=\"");
+        self.push_str(classes);
+        self.push_bytes(b"\">");
+        self
+    }
+
+    /// Start a span element with CSS classes and inline style.
+    ///
+    /// Creates: `<span class="classes" style="style">`
+    ///
+    /// Both values are escaped for use in attributes.
+    pub fn span_start_styled(mut self, classes: &str, style: &str) -> Self {
+        self.push_bytes(b"<span class=\"");
+        self.push_attr(classes);
+        self.push_bytes(b"\" style=\"");
+        self.push_attr(style);
+        self.push_bytes(b"\">");
+        self
+    }
+
+    /// End a span element.
+    ///
+    /// Creates: `</span>`
+    pub fn span_end(mut self) -> Self {
+        self.push_bytes(b"</span>");
+        self
+    }
+
+    /// Add a badge: a span with the `badge` class plus `class`.
+    ///
+    /// The text is HTML-escaped.
+    ///
+    /// Creates: `<span class="badge class">text</span>`
+    pub fn badge(mut self, text: &str, class: &str) -> Self {
+        self.push_bytes(b"<span class=\"badge");
+        if !class.is_empty() {
+            self.push_bytes(b" ");
+            self.push_attr(class);
+        }
+        self.push_bytes(b"\">");
+        self.push_attr(text);
         self.push_bytes(b"</span>");
         self
     }
 
+    /// Start an arbitrary HTML element.
+    ///
+    /// Attribute values are HTML-escaped. Use for elements without a
+    /// dedicated method, such as `<mark>`, `<small>`, or `<time>`, and close
+    /// with `element_end(tag)`.
+    ///
+    /// Creates: `<tag name="value" ...>`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the tag or an attribute name is empty or contains
+    /// whitespace, quotes, `<`, `>`, `/`, or `=`. Also panics on event
+    /// handler attributes (`on*`), `style`, and attributes that take a URL
+    /// (`href`, `src`, `srcdoc`, `action`, `formaction`); use
+    /// `div_start_attrs` or `span_start_styled` for inline styles.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// builder
+    ///     .element_start("time", &[("datetime", "2024-05-01")])
+    ///     .text("May 1")
+    ///     .element_end("time")
+    /// ```
+    pub fn element_start(mut self, tag: &str, attrs: &[(&str, &str)]) -> Self {
+        require_html_name(tag);
+        self.push_bytes(b"<");
+        self.push_str(tag);
+        for (name, value) in attrs {
+            require_element_attr(name);
+            self.push_bytes(b" ");
+            self.push_str(name);
+            self.push_bytes(b"=\"");
+            self.push_attr(value);
+            self.push_bytes(b"\"");
+        }
+        self.push_bytes(b">");
+        self
+    }
+
+    /// End an element started with `element_start`.
+    ///
+    /// Creates: `</tag>`
+    pub fn element_end(mut self, tag: &str) -> Self {
+        require_html_name(tag);
+        self.push_bytes(b"</");
+        self.push_str(tag);
+        self.push_bytes(b">");
+        self
+    }
+
     // ========================================================================
     // Progressive Loading / Continuation
     // ========================================================================
@@ -875,6 +2077,14 @@ impl<'a> MarkdownBuilder<'a> {
         self
     }
 
+    /// Add a paginated continuation marker for a `Pagination`.
+    ///
+    /// Equivalent to `continue_page(collection, p.page(), p.per_page(), p.total())`.
+    #[cfg(feature = "router")]
+    pub fn continue_page_for(self, collection: &str, p: &Pagination) -> Self {
+        self.continue_page(collection, p.page(), p.per_page(), p.total())
+    }
+
     /// Add a render continuation marker for waterfall loading.
     ///
     /// Used for progressive loading that triggers additional render() calls.
@@ -899,54 +2109,309 @@ impl<'a> MarkdownBuilder<'a> {
         self
     }
 
+    // ========================================================================
+    // Pagination
+    // ========================================================================
+
+    /// Add a pager bar of render: links.
+    ///
+    /// Emits `« Prev`/`Next »` links (omitted on the first/last page),
+    /// the first and last pages, and up to two pages either side of the
+    /// current one, with `…` marking any gap. The current page is bold and
+    /// unlinked. Links take the form `render:{base_path}/{page}`, matching
+    /// the page numbers used by `continue_page`.
+    ///
+    /// Pages are 1-based. Nothing is emitted when `total_pages` is 0, and
+    /// `current` is clamped into range.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// .pagination("/items", 3, 5)
+    /// // Creates: [« Prev](render:/items/2) [1](render:/items/1) [2](render:/items/2)
+    /// //          **3** [4](render:/items/4) [5](render:/items/5) [Next »](render:/items/4)
+    /// ```
+    pub fn pagination(mut self, base_path: &str, current: u32, total_pages: u32) -> Self {
+        const WINDOW: u32 = 2;
+        if total_pages == 0 {
+            return self;
+        }
+        let current = current.clamp(1, total_pages);
+        let start = current.saturating_sub(WINDOW).max(1);
+        let end = current.saturating_add(WINDOW).min(total_pages);
+
+        if current > 1 {
+            self.push_page_link(
+                Bytes::from_slice(self.env, "« Prev".as_bytes()),
+                base_path,
+                current - 1,
+            );
+            self.push_bytes(b" ");
+        }
+        if start > 1 {
+            self.push_page_number(base_path, 1, current);
+            self.push_pager_gap(start > 2);
+        }
+        for page in start..=end {
+            if page > start {
+                self.push_bytes(b" ");
+            }
+            self.push_page_number(base_path, page, current);
+        }
+        if end < total_pages {
+            self.push_pager_gap(end < total_pages - 1);
+            self.push_page_number(base_path, total_pages, current);
+        }
+        if current < total_pages {
+            self.push_bytes(b" ");
+            self.push_page_link(
+                Bytes::from_slice(self.env, "Next »".as_bytes()),
+                base_path,
+                current + 1,
+            );
+        }
+        self.push_bytes(b"\n\n");
+        self
+    }
+
+    /// Add a pager bar for a `Pagination`.
+    ///
+    /// Equivalent to `pagination(base_path, p.page(), p.total_pages())`.
+    #[cfg(feature = "router")]
+    pub fn pagination_for(self, base_path: &str, p: &Pagination) -> Self {
+        self.pagination(base_path, p.page(), p.total_pages())
+    }
+
+    // ========================================================================
+    // Introspection
+    // ========================================================================
+
+    /// Number of bytes `build()` would currently return.
+    ///
+    /// Tracked as content is added, so this is O(1). Useful for stopping
+    /// before the output outgrows the return-size budget.
+    pub fn approx_len(&self) -> u32 {
+        self.parts.len()
+    }
+
+    /// Check whether nothing has been added yet.
+    pub fn is_empty(&self) -> bool {
+        self.parts.len() == 0
+    }
+
+    /// Number of fragments accumulated so far.
+    pub fn part_count(&self) -> u32 {
+        self.parts.count()
+    }
+
+    // ========================================================================
+    // Combinators
+    // ========================================================================
+
+    /// Apply `f` only when `cond` is true.
+    ///
+    /// Keeps optional sections inside a single fluent chain.
+    pub fn when<F>(self, cond: bool, f: F) -> Self
+    where
+        F: FnOnce(Self) -> Self,
+    {
+        if cond { f(self) } else { self }
+    }
+
+    /// Apply `f` with the contained value when `opt` is `Some`.
+    pub fn when_some<T, F>(self, opt: Option<T>, f: F) -> Self
+    where
+        F: FnOnce(Self, T) -> Self,
+    {
+        match opt {
+            Some(value) => f(self, value),
+            None => self,
+        }
+    }
+
+    /// Apply `f` for each item in a soroban_sdk::Vec, in order.
+    pub fn each<T, F>(self, items: &Vec<T>, mut f: F) -> Self
+    where
+        T: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone,
+        F: FnMut(Self, T) -> Self,
+    {
+        let mut builder = self;
+        for item in items.iter() {
+            builder = f(builder, item);
+        }
+        builder
+    }
+
+    /// Apply `f` for each item until the output would exceed a byte budget.
+    ///
+    /// After each item the running `approx_len()` is checked against
+    /// `max_output_bytes`; the item that crosses it is discarded and no
+    /// further items are rendered. Returns the builder and the number of
+    /// items rendered, which is the index to resume from. The budget covers
+    /// everything in the builder, so leave room for content added later,
+    /// such as the continuation marker.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let (b, rendered) = MarkdownBuilder::new(&env)
+    ///     .h2("Items")
+    ///     .each_budgeted(&items, 8_000, |b, item| b.list_item_string(&item));
+    /// b.when(rendered < items.len(), |b| {
+    ///     b.continuation("items", rendered, Some(items.len()))
+    /// })
+    /// ```
+    pub fn each_budgeted<T, F>(self, items: &Vec<T>, max_output_bytes: u32, mut f: F) -> (Self, u32)
+    where
+        T: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone,
+        F: FnMut(Self, T) -> Self,
+    {
+        let mut builder = self;
+        let mut rendered = 0;
+        for item in items.iter() {
+            let mark = builder.parts.mark();
+            builder = f(builder, item);
+            if builder.approx_len() > max_output_bytes {
+                builder.parts.rewind(mark);
+                break;
+            }
+            rendered += 1;
+        }
+        (builder, rendered)
+    }
+
+    // ========================================================================
+    // Error Pages
+    // ========================================================================
+
+    /// Build a complete error page.
+    ///
+    /// The heading carries the status code and title, the message is shown in
+    /// a warning callout with each of its lines quoted, and a render: link
+    /// leads back to `back_path`.
+    ///
+    /// Creates:
+    /// ```text
+    /// # 404 Not Found
+    ///
+    /// > [!WARNING]
+    /// > The page you requested does not exist.
+    ///
+    /// [Go back](render:/)
+    /// ```
+    pub fn error_page(
+        env: &'a Env,
+        code: u32,
+        title: &str,
+        message: &str,
+        back_path: &str,
+    ) -> Bytes {
+        let mut builder = Self::new(env);
+        builder.push_bytes(b"# ");
+        builder.parts.push_back(u32_to_bytes(env, code));
+        builder.push_bytes(b" ");
+        builder.push_str(title);
+        builder.push_bytes(b"\n\n");
+        builder
+            .alert_lines("WARNING", &[message])
+            .render_link("Go back", back_path)
+            .newline()
+            .build()
+    }
+
     // ========================================================================
     // Build
     // ========================================================================
 
     /// Build the final Bytes output.
     pub fn build(self) -> Bytes {
-        concat_bytes(self.env, &self.parts)
+        self.parts.build(self.env)
+    }
+
+    /// Build the output as a String.
+    ///
+    /// For callers that expect `String` rather than `Bytes`. Output over
+    /// 16KB is replaced with a placeholder (see `bytes_to_string`).
+    pub fn build_string(self) -> String {
+        let env = self.env;
+        bytes_to_string(env, &self.build())
+    }
+}
+
+/// Panic unless `name` is usable as an HTML tag or attribute name.
+fn require_html_name(name: &str) {
+    let valid = !name.is_empty()
+        && !name.bytes().any(|b| {
+            b.is_ascii_whitespace() || matches!(b, b'"' | b'\'' | b'<' | b'>' | b'/' | b'=')
+        });
+    if !valid {
+        panic!("invalid HTML tag or attribute name");
+    }
+}
+
+/// Panic unless `name` is an attribute `element_start` may write.
+///
+/// Names are compared case-insensitively, as browsers do.
+fn require_element_attr(name: &str) {
+    require_html_name(name);
+    let handler = name.len() > 2 && name.as_bytes()[..2].eq_ignore_ascii_case(b"on");
+    let blocked = ["style", "href", "src", "srcdoc", "action", "formaction"]
+        .iter()
+        .any(|blocked| name.eq_ignore_ascii_case(blocked));
+    if handler || blocked {
+        panic!("element attributes cannot be event handlers, style, or URLs");
     }
 }
 
+/// Build a 404 page with a link back to `back_path`.
+pub fn not_found(env: &Env, back_path: &str) -> Bytes {
+    MarkdownBuilder::error_page(
+        env,
+        404,
+        "Not Found",
+        "The page you requested does not exist.",
+        back_path,
+    )
+}
+
+/// Build a 401 page with a link back to `back_path`.
+pub fn unauthorized(env: &Env, back_path: &str) -> Bytes {
+    MarkdownBuilder::error_page(
+        env,
+        401,
+        "Unauthorized",
+        "You do not have permission to view this page.",
+        back_path,
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::testutils::{assert_output_eq, to_str};
 
     #[cfg(test)]
     extern crate alloc;
 
-    /// Convert Bytes to a String for content validation in tests
-    fn bytes_to_string(bytes: &Bytes) -> alloc::string::String {
-        let mut s = alloc::string::String::new();
-        for i in 0..bytes.len() {
-            s.push(bytes.get(i).unwrap() as char);
-        }
-        s
-    }
-
     #[test]
     fn test_h1() {
         let env = Env::default();
         let output = MarkdownBuilder::new(&env).h1("Hello").build();
-        // "# Hello\n\n" = 9 bytes (2 + 5 + 2)
-        assert_eq!(output.len(), 9);
+        assert_output_eq(&output, "# Hello\n\n");
     }
 
     #[test]
     fn test_paragraph() {
         let env = Env::default();
         let output = MarkdownBuilder::new(&env).paragraph("Test").build();
-        // "Test\n\n" = 6 bytes
-        assert_eq!(output.len(), 6);
+        assert_output_eq(&output, "Test\n\n");
     }
 
     #[test]
     fn test_render_link() {
         let env = Env::default();
         let output = MarkdownBuilder::new(&env).render_link("Home", "/").build();
-        // "[Home](render:/)" = 16 bytes
-        assert_eq!(output.len(), 16);
+        assert_output_eq(&output, "[Home](render:/)");
     }
 
     #[test]
@@ -955,8 +2420,44 @@ mod tests {
         let output = MarkdownBuilder::new(&env)
             .tx_link_id("Delete", "delete_task", 42)
             .build();
-        // "[Delete](tx:delete_task {"id":42})" = 34 bytes
-        assert_eq!(output.len(), 34);
+        assert_output_eq(&output, "[Delete](tx:delete_task {\"id\":42})");
+    }
+
+    #[test]
+    fn test_tx_link_empty_args() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .tx_link("Reset", "reset", "")
+            .tx_link("Vote", "vote", "{\"id\":1}")
+            .build();
+        assert_output_eq(&output, "[Reset](tx:reset)[Vote](tx:vote {\"id\":1})");
+    }
+
+    #[test]
+    fn test_heading_level_clamping() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .heading(0, "Zero")
+            .heading(7, "Seven")
+            .heading(255, "Max")
+            .build();
+        assert_output_eq(&output, "# Zero\n\n###### Seven\n\n###### Max\n\n");
+    }
+
+    #[test]
+    fn test_heading_string() {
+        let env = Env::default();
+        let title = String::from_str(&env, "Dynamic");
+        let output = MarkdownBuilder::new(&env)
+            .heading_string(3, &title)
+            .heading_string(0, &title)
+            .heading_string(7, &title)
+            .heading_string(255, &title)
+            .build();
+        assert_output_eq(
+            &output,
+            "### Dynamic\n\n# Dynamic\n\n###### Dynamic\n\n###### Dynamic\n\n",
+        );
     }
 
     #[test]
@@ -965,16 +2466,14 @@ mod tests {
         let output = MarkdownBuilder::new(&env)
             .form_link("Submit", "add_task")
             .build();
-        // "[Submit](form:add_task)" = 23 bytes
-        assert_eq!(output.len(), 23);
+        assert_output_eq(&output, "[Submit](form:add_task)");
     }
 
     #[test]
     fn test_tip_alert() {
         let env = Env::default();
         let output = MarkdownBuilder::new(&env).tip("This is a tip").build();
-        // "> [!TIP]\n> This is a tip\n\n" = 26 bytes
-        assert_eq!(output.len(), 26);
+        assert_output_eq(&output, "> [!TIP]\n> This is a tip\n\n");
     }
 
     #[test]
@@ -987,8 +2486,7 @@ mod tests {
             .text("Col2")
             .columns_end()
             .build();
-        // ":::columns\nCol1|||\nCol2:::\n\n"
-        assert!(output.len() > 0);
+        assert_output_eq(&output, ":::columns\nCol1|||\nCol2:::\n\n");
     }
 
     #[test]
@@ -997,8 +2495,50 @@ mod tests {
         let output = MarkdownBuilder::new(&env)
             .include("CABCD123", "header")
             .build();
-        // {{include contract=CABCD123 func="header"}}
-        assert!(output.len() > 30);
+        assert_output_eq(&output, "{{include contract=CABCD123 func=\"header\"}}");
+    }
+
+    #[test]
+    fn test_include_with_path() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .include_with_path("CABCD123", "render", "/posts/1")
+            .build();
+        assert_output_eq(
+            &output,
+            "{{include contract=CABCD123 func=\"render\" path=\"/posts/1\"}}",
+        );
+    }
+
+    #[test]
+    fn test_include_alias() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .include_alias("content", "render_header")
+            .include_alias_with_path("blog", "render", "/posts/1")
+            .build();
+        assert_output_eq(
+            &output,
+            "{{include contract=@content func=\"render_header\"}}\
+             {{include contract=@blog func=\"render\" path=\"/posts/1\"}}",
+        );
+    }
+
+    #[test]
+    fn test_include_resolved() {
+        use soroban_sdk::testutils::Address as _;
+
+        let env = Env::default();
+        let address = Address::generate(&env);
+        let output = MarkdownBuilder::new(&env)
+            .include_resolved(&address, "render")
+            .build();
+        let id = to_str(&address_to_bytes(&env, &address));
+        assert_eq!(id.len(), 56);
+        assert_eq!(
+            to_str(&output),
+            alloc::format!("{{{{include contract={} func=\"render\"}}}}", id)
+        );
     }
 
     #[test]
@@ -1007,7 +2547,10 @@ mod tests {
         let output = MarkdownBuilder::new(&env)
             .input("name", "Enter name")
             .build();
-        assert!(output.len() > 20);
+        assert_output_eq(
+            &output,
+            "<input name=\"name\" placeholder=\"Enter name\" />\n",
+        );
     }
 
     #[test]
@@ -1016,9 +2559,10 @@ mod tests {
         let output = MarkdownBuilder::new(&env)
             .textarea_markdown("content", 10, "Enter markdown...")
             .build();
-        // <textarea name="content" data-editor="markdown" rows="10" placeholder="Enter markdown..."></textarea>\n
-        // Should contain the data-editor attribute
-        assert!(output.len() > 60);
+        assert_output_eq(
+            &output,
+            "<textarea name=\"content\" data-editor=\"markdown\" rows=\"10\" placeholder=\"Enter markdown...\"></textarea>\n",
+        );
     }
 
     #[test]
@@ -1027,8 +2571,10 @@ mod tests {
         let output = MarkdownBuilder::new(&env)
             .input_with_value("name", "Enter name", "John Doe")
             .build();
-        // <input name="name" placeholder="Enter name" value="John Doe" />\n
-        assert!(output.len() > 40);
+        assert_output_eq(
+            &output,
+            "<input name=\"name\" placeholder=\"Enter name\" value=\"John Doe\" />\n",
+        );
     }
 
     #[test]
@@ -1037,8 +2583,10 @@ mod tests {
         let output = MarkdownBuilder::new(&env)
             .textarea_with_value("bio", 5, "Enter bio", "Hello world")
             .build();
-        // <textarea name="bio" rows="5" placeholder="Enter bio">Hello world</textarea>\n
-        assert!(output.len() > 50);
+        assert_output_eq(
+            &output,
+            "<textarea name=\"bio\" rows=\"5\" placeholder=\"Enter bio\">Hello world</textarea>\n",
+        );
     }
 
     #[test]
@@ -1047,8 +2595,10 @@ mod tests {
         let output = MarkdownBuilder::new(&env)
             .textarea_markdown_with_value("content", 10, "Enter markdown...", "# Hello")
             .build();
-        // <textarea name="content" data-editor="markdown" rows="10" placeholder="Enter markdown..."># Hello</textarea>\n
-        assert!(output.len() > 70);
+        assert_output_eq(
+            &output,
+            "<textarea name=\"content\" data-editor=\"markdown\" rows=\"10\" placeholder=\"Enter markdown...\"># Hello</textarea>\n",
+        );
     }
 
     #[test]
@@ -1057,8 +2607,7 @@ mod tests {
         let output = MarkdownBuilder::new(&env)
             .checkbox(true, "Done task")
             .build();
-        // "- [x] Done task\n" = 16 bytes
-        assert_eq!(output.len(), 16);
+        assert_output_eq(&output, "- [x] Done task\n");
     }
 
     #[test]
@@ -1067,8 +2616,7 @@ mod tests {
         let output = MarkdownBuilder::new(&env)
             .checkbox(false, "Todo task")
             .build();
-        // "- [ ] Todo task\n" = 16 bytes
-        assert_eq!(output.len(), 16);
+        assert_output_eq(&output, "- [ ] Todo task\n");
     }
 
     #[test]
@@ -1079,15 +2627,60 @@ mod tests {
             .paragraph("Content")
             .render_link("Home", "/")
             .build();
-        assert!(output.len() > 30);
+        assert_output_eq(&output, "# Title\n\nContent\n\n[Home](render:/)");
+    }
+
+    #[test]
+    fn test_list_item_links() {
+        let env = Env::default();
+        let title = String::from_str(&env, "Second post");
+        let output = MarkdownBuilder::new(&env)
+            .list_item_link("Home", "/")
+            .list_item_render_link_id("First post", "/posts/", 1)
+            .list_item_string_link(&title, "/posts/", 2)
+            .list_item_render_link_id("Draft", "/posts/", 0)
+            .build();
+        assert_output_eq(
+            &output,
+            "- [Home](render:/)\n\
+             - [First post](render:/posts/1)\n\
+             - [Second post](render:/posts/2)\n\
+             - [Draft](render:/posts/0)\n",
+        );
+    }
+
+    #[test]
+    fn test_checkbox_tx() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .checkbox_tx(true, "Done task", "toggle", 7)
+            .checkbox_tx(false, "Todo task", "toggle", 0)
+            .build();
+        assert_output_eq(
+            &output,
+            "- [x] [Done task](tx:toggle {\"id\":7})\n- [ ] [Todo task](tx:toggle {\"id\":0})\n",
+        );
+    }
+
+    #[test]
+    fn test_checkbox_tx_string() {
+        let env = Env::default();
+        let text = String::from_str(&env, "Buy milk");
+        let output = MarkdownBuilder::new(&env)
+            .checkbox_tx_string(false, &text, "toggle", 0)
+            .checkbox_tx_string(true, &text, "toggle", 12)
+            .build();
+        assert_output_eq(
+            &output,
+            "- [ ] [Buy milk](tx:toggle {\"id\":0})\n- [x] [Buy milk](tx:toggle {\"id\":12})\n",
+        );
     }
 
     #[test]
     fn test_blockquote() {
         let env = Env::default();
         let output = MarkdownBuilder::new(&env).blockquote("Quote text").build();
-        // "> Quote text\n\n" = 14 bytes
-        assert_eq!(output.len(), 14);
+        assert_output_eq(&output, "> Quote text\n\n");
     }
 
     #[test]
@@ -1096,8 +2689,10 @@ mod tests {
         let output = MarkdownBuilder::new(&env)
             .continuation("comments", 5, Some(50))
             .build();
-        // {{continue collection="comments" from=5 total=50}}
-        assert!(output.len() > 40);
+        assert_output_eq(
+            &output,
+            "{{continue collection=\"comments\" from=5 total=50}}",
+        );
     }
 
     #[test]
@@ -1106,16 +2701,14 @@ mod tests {
         let output = MarkdownBuilder::new(&env)
             .continuation("data", 10, None)
             .build();
-        // {{continue collection="data" from=10}}
-        assert!(output.len() > 30);
+        assert_output_eq(&output, "{{continue collection=\"data\" from=10}}");
     }
 
     #[test]
     fn test_chunk_ref() {
         let env = Env::default();
         let output = MarkdownBuilder::new(&env).chunk_ref("chunks", 3).build();
-        // {{chunk collection="chunks" index=3}}
-        assert!(output.len() > 30);
+        assert_output_eq(&output, "{{chunk collection=\"chunks\" index=3}}");
     }
 
     #[test]
@@ -1124,8 +2717,10 @@ mod tests {
         let output = MarkdownBuilder::new(&env)
             .chunk_ref_placeholder("content", 7, "Loading...")
             .build();
-        // {{chunk collection="content" index=7 placeholder="Loading..."}}
-        assert!(output.len() > 50);
+        assert_output_eq(
+            &output,
+            "{{chunk collection=\"content\" index=7 placeholder=\"Loading...\"}}",
+        );
     }
 
     #[test]
@@ -1134,8 +2729,10 @@ mod tests {
         let output = MarkdownBuilder::new(&env)
             .continue_page("items", 2, 10, 47)
             .build();
-        // {{continue collection="items" page=2 per_page=10 total=47}}
-        assert!(output.len() > 50);
+        assert_output_eq(
+            &output,
+            "{{continue collection=\"items\" page=2 per_page=10 total=47}}",
+        );
     }
 
     #[test]
@@ -1144,16 +2741,20 @@ mod tests {
         let output = MarkdownBuilder::new(&env)
             .hidden_input("board_id", "42")
             .build();
-        // <input type="hidden" name="board_id" value="42" />\n
-        assert!(output.len() > 40);
+        assert_output_eq(
+            &output,
+            "<input type=\"hidden\" name=\"board_id\" value=\"42\" />\n",
+        );
     }
 
     #[test]
     fn test_redirect() {
         let env = Env::default();
         let output = MarkdownBuilder::new(&env).redirect("/b/0").build();
-        // <input type="hidden" name="_redirect" value="/b/0" />\n
-        assert!(output.len() > 45);
+        assert_output_eq(
+            &output,
+            "<input type=\"hidden\" name=\"_redirect\" value=\"/b/0\" />\n",
+        );
     }
 
     #[test]
@@ -1164,8 +2765,10 @@ mod tests {
             .text("Content")
             .div_end()
             .build();
-        // <div class="reply reply-depth-1">\nContent</div>\n
-        assert!(output.len() > 30);
+        assert_output_eq(
+            &output,
+            "<div class=\"reply reply-depth-1\">\nContent</div>\n",
+        );
     }
 
     #[test]
@@ -1176,8 +2779,10 @@ mod tests {
             .text("Indented")
             .div_end()
             .build();
-        // <div class="container" style="margin-left: 24px;">\nIndented</div>\n
-        assert!(output.len() > 50);
+        assert_output_eq(
+            &output,
+            "<div class=\"container\" style=\"margin-left: 24px;\">\nIndented</div>\n",
+        );
     }
 
     #[test]
@@ -1188,47 +2793,213 @@ mod tests {
             .text("Important")
             .span_end()
             .build();
-        // <span class="highlight">Important</span>
-        assert!(output.len() > 30);
+        assert_output_eq(&output, "<span class=\"highlight\">Important</span>");
     }
 
     #[test]
-    fn test_nested_divs() {
+    fn test_div_start_with_id() {
         let env = Env::default();
         let output = MarkdownBuilder::new(&env)
-            .div_start("parent")
-            .text("Parent content")
-            .div_start("child")
-            .text("Child content")
-            .div_end()
+            .div_start_with_id("replies", "thread \"main\"")
+            .text("Content")
             .div_end()
             .build();
-        assert!(output.len() > 50);
+        assert_output_eq(
+            &output,
+            "<div id=\"replies\" class=\"thread &quot;main&quot;\">\nContent</div>\n",
+        );
     }
 
-    // ==========================================================================
-    // Content validation tests (replacing length-only assertions)
-    // ==========================================================================
-
     #[test]
-    fn test_h1_content_validation() {
+    fn test_div_start_attrs() {
         let env = Env::default();
-        let output = MarkdownBuilder::new(&env).h1("Hello").build();
-        assert_eq!(bytes_to_string(&output), "# Hello\n\n");
+        let output = MarkdownBuilder::new(&env)
+            .div_start_attrs(
+                "comments",
+                &[
+                    ("id", "c1"),
+                    ("role", "list"),
+                    ("data-collection", "comments"),
+                    ("data-label", "say \"hi\" & <go>"),
+                ],
+            )
+            .div_end()
+            .build();
+        assert_output_eq(
+            &output,
+            "<div class=\"comments\" id=\"c1\" role=\"list\" data-collection=\"comments\" \
+             data-label=\"say &quot;hi&quot; &amp; &lt;go&gt;\">\n</div>\n",
+        );
     }
 
     #[test]
-    fn test_h2_content_validation() {
+    #[should_panic(expected = "div attributes must be id, style, role, or data-*")]
+    fn test_div_start_attrs_rejects_event_handler() {
+        let env = Env::default();
+        MarkdownBuilder::new(&env).div_start_attrs("x", &[("onclick", "alert(1)")]);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid HTML tag or attribute name")]
+    fn test_div_start_attrs_rejects_quote_in_data_name() {
+        let env = Env::default();
+        MarkdownBuilder::new(&env).div_start_attrs("x", &[("data-a\" onclick", "x")]);
+    }
+
+    #[test]
+    fn test_span_start_styled() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .span_start_styled("chip", "color: #fff; background: #16a34a;")
+            .text("Active")
+            .span_end()
+            .build();
+        assert_output_eq(
+            &output,
+            "<span class=\"chip\" style=\"color: #fff; background: #16a34a;\">Active</span>",
+        );
+    }
+
+    #[test]
+    fn test_span_start_styled_escapes_values() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .span_start_styled("a\"b", "color: red\" onclick=\"x()")
+            .span_end()
+            .build();
+        assert_output_eq(
+            &output,
+            "<span class=\"a&quot;b\" style=\"color: red&quot; onclick=&quot;x()\"></span>",
+        );
+    }
+
+    #[test]
+    fn test_badge() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .badge("New", "success")
+            .badge("<3", "")
+            .build();
+        assert_output_eq(
+            &output,
+            "<span class=\"badge success\">New</span><span class=\"badge\">&lt;3</span>",
+        );
+    }
+
+    #[test]
+    fn test_element_time() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .element_start("time", &[("datetime", "2024-05-01T12:00:00Z")])
+            .text("May 1")
+            .element_end("time")
+            .build();
+        assert_output_eq(
+            &output,
+            "<time datetime=\"2024-05-01T12:00:00Z\">May 1</time>",
+        );
+    }
+
+    #[test]
+    fn test_element_escapes_values() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .element_start(
+                "mark",
+                &[("title", "\"quoted\" & <tagged>"), ("data-x", "")],
+            )
+            .element_end("mark")
+            .element_start("small", &[])
+            .element_end("small")
+            .build();
+        assert_output_eq(
+            &output,
+            "<mark title=\"&quot;quoted&quot; &amp; &lt;tagged&gt;\" data-x=\"\"></mark><small></small>",
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "element attributes cannot be event handlers, style, or URLs")]
+    fn test_element_rejects_event_handler() {
+        let env = Env::default();
+        MarkdownBuilder::new(&env).element_start("div", &[("onclick", "alert(1)")]);
+    }
+
+    #[test]
+    #[should_panic(expected = "element attributes cannot be event handlers, style, or URLs")]
+    fn test_element_rejects_uppercase_event_handler() {
+        let env = Env::default();
+        MarkdownBuilder::new(&env).element_start("img", &[("ONERROR", "alert(1)")]);
+    }
+
+    #[test]
+    #[should_panic(expected = "element attributes cannot be event handlers, style, or URLs")]
+    fn test_element_rejects_style() {
+        let env = Env::default();
+        MarkdownBuilder::new(&env).element_start("mark", &[("style", "position: fixed")]);
+    }
+
+    #[test]
+    #[should_panic(expected = "element attributes cannot be event handlers, style, or URLs")]
+    fn test_element_rejects_url_attribute() {
+        let env = Env::default();
+        MarkdownBuilder::new(&env).element_start("a", &[("href", "javascript:alert(1)")]);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid HTML tag or attribute name")]
+    fn test_element_rejects_attribute_name_with_space() {
+        let env = Env::default();
+        MarkdownBuilder::new(&env).element_start("time", &[("date time", "x")]);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid HTML tag or attribute name")]
+    fn test_element_rejects_attribute_name_with_quote() {
+        let env = Env::default();
+        MarkdownBuilder::new(&env).element_start("time", &[("x\"onclick", "x")]);
+    }
+
+    #[test]
+    fn test_nested_divs() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .div_start("parent")
+            .text("Parent content")
+            .div_start("child")
+            .text("Child content")
+            .div_end()
+            .div_end()
+            .build();
+        assert_output_eq(
+            &output,
+            "<div class=\"parent\">\nParent content<div class=\"child\">\nChild content</div>\n</div>\n",
+        );
+    }
+
+    // ==========================================================================
+    // Content validation tests (replacing length-only assertions)
+    // ==========================================================================
+
+    #[test]
+    fn test_h1_content_validation() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env).h1("Hello").build();
+        assert_output_eq(&output, "# Hello\n\n");
+    }
+
+    #[test]
+    fn test_h2_content_validation() {
         let env = Env::default();
         let output = MarkdownBuilder::new(&env).h2("Title").build();
-        assert_eq!(bytes_to_string(&output), "## Title\n\n");
+        assert_output_eq(&output, "## Title\n\n");
     }
 
     #[test]
     fn test_h3_content_validation() {
         let env = Env::default();
         let output = MarkdownBuilder::new(&env).h3("Section").build();
-        assert_eq!(bytes_to_string(&output), "### Section\n\n");
+        assert_output_eq(&output, "### Section\n\n");
     }
 
     #[test]
@@ -1237,37 +3008,37 @@ mod tests {
         let h4 = MarkdownBuilder::new(&env).heading(4, "H4").build();
         let h5 = MarkdownBuilder::new(&env).heading(5, "H5").build();
         let h6 = MarkdownBuilder::new(&env).heading(6, "H6").build();
-        assert_eq!(bytes_to_string(&h4), "#### H4\n\n");
-        assert_eq!(bytes_to_string(&h5), "##### H5\n\n");
-        assert_eq!(bytes_to_string(&h6), "###### H6\n\n");
+        assert_output_eq(&h4, "#### H4\n\n");
+        assert_output_eq(&h5, "##### H5\n\n");
+        assert_output_eq(&h6, "###### H6\n\n");
     }
 
     #[test]
     fn test_bold_content() {
         let env = Env::default();
         let output = MarkdownBuilder::new(&env).bold("text").build();
-        assert_eq!(bytes_to_string(&output), "**text**");
+        assert_output_eq(&output, "**text**");
     }
 
     #[test]
     fn test_italic_content() {
         let env = Env::default();
         let output = MarkdownBuilder::new(&env).italic("text").build();
-        assert_eq!(bytes_to_string(&output), "*text*");
+        assert_output_eq(&output, "*text*");
     }
 
     #[test]
     fn test_code_content() {
         let env = Env::default();
         let output = MarkdownBuilder::new(&env).code("code").build();
-        assert_eq!(bytes_to_string(&output), "`code`");
+        assert_output_eq(&output, "`code`");
     }
 
     #[test]
     fn test_strikethrough_content() {
         let env = Env::default();
         let output = MarkdownBuilder::new(&env).strikethrough("old").build();
-        assert_eq!(bytes_to_string(&output), "~~old~~");
+        assert_output_eq(&output, "~~old~~");
     }
 
     #[test]
@@ -1275,7 +3046,7 @@ mod tests {
         let env = Env::default();
         let output = MarkdownBuilder::new(&env).text("hello").build();
         // text() adds no newline
-        assert_eq!(bytes_to_string(&output), "hello");
+        assert_output_eq(&output, "hello");
     }
 
     #[test]
@@ -1283,42 +3054,42 @@ mod tests {
         let env = Env::default();
         let output = MarkdownBuilder::new(&env).paragraph("hello").build();
         // paragraph adds double newline
-        assert_eq!(bytes_to_string(&output), "hello\n\n");
+        assert_output_eq(&output, "hello\n\n");
     }
 
     #[test]
     fn test_list_item_content() {
         let env = Env::default();
         let output = MarkdownBuilder::new(&env).list_item("item").build();
-        assert_eq!(bytes_to_string(&output), "- item\n");
+        assert_output_eq(&output, "- item\n");
     }
 
     #[test]
     fn test_note_alert_content() {
         let env = Env::default();
         let output = MarkdownBuilder::new(&env).note("Note text").build();
-        assert_eq!(bytes_to_string(&output), "> [!NOTE]\n> Note text\n\n");
+        assert_output_eq(&output, "> [!NOTE]\n> Note text\n\n");
     }
 
     #[test]
     fn test_warning_alert_content() {
         let env = Env::default();
         let output = MarkdownBuilder::new(&env).warning("Warning text").build();
-        assert_eq!(bytes_to_string(&output), "> [!WARNING]\n> Warning text\n\n");
+        assert_output_eq(&output, "> [!WARNING]\n> Warning text\n\n");
     }
 
     #[test]
     fn test_info_alert_content() {
         let env = Env::default();
         let output = MarkdownBuilder::new(&env).info("Info text").build();
-        assert_eq!(bytes_to_string(&output), "> [!INFO]\n> Info text\n\n");
+        assert_output_eq(&output, "> [!INFO]\n> Info text\n\n");
     }
 
     #[test]
     fn test_caution_alert_content() {
         let env = Env::default();
         let output = MarkdownBuilder::new(&env).caution("Caution text").build();
-        assert_eq!(bytes_to_string(&output), "> [!CAUTION]\n> Caution text\n\n");
+        assert_output_eq(&output, "> [!CAUTION]\n> Caution text\n\n");
     }
 
     #[test]
@@ -1327,10 +3098,7 @@ mod tests {
         let output = MarkdownBuilder::new(&env)
             .form_link_to("Update Settings", "admin", "set_chunk_size")
             .build();
-        assert_eq!(
-            bytes_to_string(&output),
-            "[Update Settings](form:@admin:set_chunk_size)"
-        );
+        assert_output_eq(&output, "[Update Settings](form:@admin:set_chunk_size)");
     }
 
     #[test]
@@ -1340,7 +3108,7 @@ mod tests {
             .tx_link_to("Flag Post", "content", "flag_reply", r#"{"id":123}"#)
             .build();
         assert_eq!(
-            bytes_to_string(&output),
+            to_str(&output),
             r#"[Flag Post](tx:@content:flag_reply {"id":123})"#
         );
     }
@@ -1352,6 +3120,954 @@ mod tests {
         let output = MarkdownBuilder::new(&env)
             .tx_link_to("Delete", "admin", "delete", "")
             .build();
-        assert_eq!(bytes_to_string(&output), "[Delete](tx:@admin:delete)");
+        assert_output_eq(&output, "[Delete](tx:@admin:delete)");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_buffered_matches_parts() {
+        let env = Env::default();
+        let build = |builder: MarkdownBuilder| {
+            builder
+                .h1("Title")
+                .paragraph("Some text")
+                .render_link("Home", "/")
+                .number(42)
+                .build()
+        };
+        let parts = MarkdownBuilder {
+            env: &env,
+            parts: Sink::parts(&env),
+        };
+        assert_eq!(build(parts), build(MarkdownBuilder::new_buffered(&env)));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_buffered_budget() {
+        let env = Env::default();
+        // 75 iterations x 4 fragments = 300 parts
+        let build = |mut builder: MarkdownBuilder| {
+            for _ in 0..75 {
+                builder = builder.bold("x").text(" ");
+            }
+            builder.build()
+        };
+
+        let mut budget = env.cost_estimate().budget();
+        budget.reset_unlimited();
+        let from_parts = build(MarkdownBuilder {
+            env: &env,
+            parts: Sink::parts(&env),
+        });
+        let parts_cpu = budget.cpu_instruction_cost();
+        let parts_mem = budget.memory_bytes_cost();
+
+        budget.reset_unlimited();
+        let from_buffer = build(MarkdownBuilder::new_buffered(&env));
+        let buffered_cpu = budget.cpu_instruction_cost();
+        let buffered_mem = budget.memory_bytes_cost();
+
+        assert_eq!(from_parts, from_buffer);
+        assert!(buffered_cpu < parts_cpu);
+        assert!(buffered_mem < parts_mem);
+    }
+
+    #[test]
+    fn test_ordered_item() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .ordered_item(1, "First")
+            .ordered_item(2, "Second")
+            .ordered_item(10, "Tenth")
+            .build();
+        assert_output_eq(&output, "1. First\n2. Second\n10. Tenth\n");
+    }
+
+    #[test]
+    fn test_list_item_nested_indentation() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .list_item_nested(0, "Top")
+            .list_item_nested(1, "Child")
+            .list_item_nested(2, "Grandchild")
+            .build();
+        assert_output_eq(&output, "- Top\n  - Child\n    - Grandchild\n");
+    }
+
+    #[test]
+    fn test_list_string_variants() {
+        let env = Env::default();
+        let text = String::from_str(&env, "Dynamic");
+        let output = MarkdownBuilder::new(&env)
+            .list_item_string(&text)
+            .ordered_item_string(3, &text)
+            .list_item_nested_string(1, &text)
+            .build();
+        assert_output_eq(&output, "- Dynamic\n3. Dynamic\n  - Dynamic\n");
+    }
+
+    #[test]
+    fn test_list_end() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .list_item("One")
+            .list_end()
+            .paragraph("After")
+            .build();
+        assert_output_eq(&output, "- One\n\nAfter\n\n");
+    }
+
+    #[test]
+    fn test_image() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .image("Logo", "https://example.com/logo.png")
+            .build();
+        assert_output_eq(&output, "![Logo](https://example.com/logo.png)");
+    }
+
+    #[test]
+    fn test_image_render() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .image_render("NFT #5", "/nft/5/image")
+            .build();
+        assert_output_eq(&output, "![NFT #5](render:/nft/5/image)");
+    }
+
+    #[test]
+    fn test_image_with_title() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .image_with_title("Avatar", "/a.png", "The \"best\" avatar")
+            .build();
+        assert_eq!(
+            to_str(&output),
+            r#"![Avatar](/a.png "The \"best\" avatar")"#
+        );
+    }
+
+    #[test]
+    fn test_image_empty_alt() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env).image("", "/a.png").build();
+        assert_output_eq(&output, "![](/a.png)");
+    }
+
+    #[test]
+    fn test_image_alt_escaping() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .image("see [1] and \\", "/a.png")
+            .build();
+        assert_eq!(to_str(&output), r"![see \[1\] and \\](/a.png)");
+    }
+
+    #[test]
+    fn test_form_documented_example() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .form_start("tx:create_thread", "POST")
+            .redirect("/b/0")
+            .input("title", "Enter title")
+            .button("submit", "Create")
+            .form_end()
+            .build();
+        assert_output_eq(
+            &output,
+            "<form action=\"tx:create_thread\" method=\"POST\">\n\
+             <input type=\"hidden\" name=\"_redirect\" value=\"/b/0\" />\n\
+             <input name=\"title\" placeholder=\"Enter title\" />\n\
+             <button type=\"submit\">Create</button>\n\
+             </form>\n",
+        );
+    }
+
+    #[test]
+    fn test_form_tx() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .form_tx("create_thread")
+            .form_end()
+            .build();
+        assert_output_eq(
+            &output,
+            "<form action=\"tx:create_thread\" method=\"POST\">\n</form>\n",
+        );
+    }
+
+    #[test]
+    fn test_form_attribute_escaping() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .form_start("tx:a\"b", "POST")
+            .button("submit", "Save & <Close>")
+            .form_end()
+            .build();
+        assert_output_eq(
+            &output,
+            "<form action=\"tx:a&quot;b\" method=\"POST\">\n\
+             <button type=\"submit\">Save &amp; &lt;Close&gt;</button>\n\
+             </form>\n",
+        );
+    }
+
+    #[test]
+    fn test_select_options() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .select_start("priority")
+            .option("low", "Low", false)
+            .option("medium", "Medium", true)
+            .option("high", "High", false)
+            .select_end()
+            .build();
+        assert_output_eq(
+            &output,
+            "<select name=\"priority\">\n\
+             <option value=\"low\">Low</option>\n\
+             <option value=\"medium\" selected>Medium</option>\n\
+             <option value=\"high\">High</option>\n\
+             </select>\n",
+        );
+    }
+
+    #[test]
+    fn test_select_option_u32() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .select_start("board")
+            .option_u32(0, "General", false)
+            .option_u32(42, "Announcements", true)
+            .select_end()
+            .build();
+        assert_output_eq(
+            &output,
+            "<select name=\"board\">\n\
+             <option value=\"0\">General</option>\n\
+             <option value=\"42\" selected>Announcements</option>\n\
+             </select>\n",
+        );
+    }
+
+    #[test]
+    fn test_select_option_escaping() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .option("a\"b", "Q&A", false)
+            .build();
+        assert_output_eq(&output, "<option value=\"a&quot;b\">Q&amp;A</option>\n");
+    }
+
+    #[test]
+    fn test_checkbox_input() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .checkbox_input("notify", "Email me", true)
+            .checkbox_input("public", "Public", false)
+            .build();
+        assert_output_eq(
+            &output,
+            "<label><input type=\"checkbox\" name=\"notify\" value=\"true\" checked /> Email me</label>\n\
+             <label><input type=\"checkbox\" name=\"public\" value=\"true\" /> Public</label>\n",
+        );
+    }
+
+    #[test]
+    fn test_radio_group() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .radio_input("size", "s", "Small", false)
+            .radio_input("size", "m", "Medium", true)
+            .radio_input("size", "l", "Large", false)
+            .build();
+        assert_output_eq(
+            &output,
+            "<label><input type=\"radio\" name=\"size\" value=\"s\" /> Small</label>\n\
+             <label><input type=\"radio\" name=\"size\" value=\"m\" checked /> Medium</label>\n\
+             <label><input type=\"radio\" name=\"size\" value=\"l\" /> Large</label>\n",
+        );
+    }
+
+    #[test]
+    fn test_checkbox_radio_escaping() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .radio_input("a\"b", "<v>", "x & y", false)
+            .build();
+        assert_output_eq(
+            &output,
+            "<label><input type=\"radio\" name=\"a&quot;b\" value=\"&lt;v&gt;\" /> x &amp; y</label>\n",
+        );
+    }
+
+    #[test]
+    fn test_number_input_all_constraints() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .number_input("amount", "Amount", Some(-10), Some(100), Some(5))
+            .build();
+        assert_output_eq(
+            &output,
+            "<input type=\"number\" name=\"amount\" placeholder=\"Amount\" min=\"-10\" max=\"100\" step=\"5\" />\n",
+        );
+    }
+
+    #[test]
+    fn test_number_input_omits_none() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .number_input("qty", "Qty", None, Some(9), None)
+            .build();
+        let s = to_str(&output);
+        assert_eq!(
+            s,
+            "<input type=\"number\" name=\"qty\" placeholder=\"Qty\" max=\"9\" />\n"
+        );
+        assert!(!s.contains("min="));
+        assert!(!s.contains("step="));
+    }
+
+    #[test]
+    fn test_number_input_with_value() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .number_input_with_value("qty", "Qty", Some(0), None, None, 42)
+            .build();
+        assert_output_eq(
+            &output,
+            "<input type=\"number\" name=\"qty\" placeholder=\"Qty\" min=\"0\" value=\"42\" />\n",
+        );
+    }
+
+    #[test]
+    fn test_date_input() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .date_input("deadline", Some("2025-01-31"))
+            .date_input("start", None)
+            .build();
+        assert_output_eq(
+            &output,
+            "<input type=\"date\" name=\"deadline\" value=\"2025-01-31\" />\n\
+             <input type=\"date\" name=\"start\" />\n",
+        );
+    }
+
+    #[test]
+    fn test_range_input() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .range_input("volume", 0, 100, 50)
+            .build();
+        assert_output_eq(
+            &output,
+            "<input type=\"range\" name=\"volume\" min=\"0\" max=\"100\" value=\"50\" />\n",
+        );
+    }
+
+    #[test]
+    fn test_label() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .label("title", "Title <required>")
+            .build();
+        assert_output_eq(
+            &output,
+            "<label for=\"title\">Title &lt;required&gt;</label>\n",
+        );
+    }
+
+    #[test]
+    fn test_fieldset_with_inputs() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .form_tx("update_profile")
+            .fieldset_start("Profile & Settings")
+            .label("name", "Name")
+            .input("name", "Your name")
+            .label("bio", "Bio")
+            .input("bio", "About you")
+            .fieldset_end()
+            .form_end()
+            .build();
+        let s = to_str(&output);
+        assert_eq!(
+            s,
+            "<form action=\"tx:update_profile\" method=\"POST\">\n\
+             <fieldset>\n<legend>Profile &amp; Settings</legend>\n\
+             <label for=\"name\">Name</label>\n\
+             <input name=\"name\" placeholder=\"Your name\" />\n\
+             <label for=\"bio\">Bio</label>\n\
+             <input name=\"bio\" placeholder=\"About you\" />\n\
+             </fieldset>\n\
+             </form>\n"
+        );
+        assert_eq!(
+            s.matches("<fieldset>").count(),
+            s.matches("</fieldset>").count()
+        );
+        assert_eq!(s.matches("<label").count(), s.matches("</label>").count());
+    }
+
+    #[test]
+    fn test_tx_link_args() {
+        let env = Env::default();
+        let args = TxArgs::new(&env)
+            .add_u32("id", 5)
+            .add_str("title", "A \"quoted\" title")
+            .add_bool("pin", true);
+        let output = MarkdownBuilder::new(&env)
+            .tx_link_args("Save", "update_task", &args)
+            .build();
+        assert_eq!(
+            to_str(&output),
+            r#"[Save](tx:update_task {"id":5,"title":"A \"quoted\" title","pin":true})"#
+        );
+    }
+
+    #[test]
+    fn test_tx_link_args_empty() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .tx_link_args("Refresh", "refresh", &TxArgs::new(&env))
+            .build();
+        assert_output_eq(&output, "[Refresh](tx:refresh)");
+    }
+
+    #[test]
+    fn test_tx_link_to_args() {
+        let env = Env::default();
+        let args = TxArgs::new(&env).add_u32("id", 123);
+        let output = MarkdownBuilder::new(&env)
+            .tx_link_to_args("Flag Post", "content", "flag_reply", &args)
+            .build();
+        assert_eq!(
+            to_str(&output),
+            r#"[Flag Post](tx:@content:flag_reply {"id":123})"#
+        );
+    }
+
+    #[test]
+    fn test_render_link_id() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .render_link_id("View", "/task/", 42)
+            .build();
+        assert_output_eq(&output, "[View](render:/task/42)");
+    }
+
+    #[test]
+    fn test_render_link_id_with_text() {
+        let env = Env::default();
+        let title = String::from_str(&env, "My Task");
+        let output = MarkdownBuilder::new(&env)
+            .render_link_id_with_text(&title, "/task/", 7)
+            .build();
+        assert_output_eq(&output, "[My Task](render:/task/7)");
+    }
+
+    #[test]
+    fn test_render_link_string() {
+        let env = Env::default();
+        let name = String::from_str(&env, "hello world (1)");
+        let output = MarkdownBuilder::new(&env)
+            .render_link_string("Profile", "/u/", &name)
+            .build();
+        assert_output_eq(&output, "[Profile](render:/u/hello%20world%20%281%29)");
+    }
+
+    #[test]
+    fn test_render_link_string_with_text() {
+        let env = Env::default();
+        let name = String::from_str(&env, "alice");
+        let output = MarkdownBuilder::new(&env)
+            .render_link_string_with_text(&name, "/u/", &name)
+            .build();
+        assert_output_eq(&output, "[alice](render:/u/alice)");
+    }
+
+    #[test]
+    fn test_each_renders_task_list() {
+        let env = Env::default();
+        let mut tasks: Vec<String> = Vec::new(&env);
+        tasks.push_back(String::from_str(&env, "Write docs"));
+        tasks.push_back(String::from_str(&env, "Ship release"));
+        let empty: Vec<String> = Vec::new(&env);
+
+        let output = MarkdownBuilder::new(&env)
+            .h2("Tasks")
+            .each(&tasks, |b, task| b.list_item_string(&task))
+            .when(tasks.is_empty(), |b| b.paragraph("No tasks"))
+            .list_end()
+            .each(&empty, |b, task| b.list_item_string(&task))
+            .build();
+        assert_output_eq(&output, "## Tasks\n\n- Write docs\n- Ship release\n\n");
+    }
+
+    #[test]
+    fn test_when_and_when_some() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .when(true, |b| b.text("yes "))
+            .when(false, |b| b.text("no "))
+            .when_some(Some(5u32), |b, n| b.number(n))
+            .when_some(None::<u32>, |b, n| b.number(n))
+            .build();
+        assert_output_eq(&output, "yes 5");
+    }
+
+    #[test]
+    fn test_introspection() {
+        let env = Env::default();
+        let empty = MarkdownBuilder::new(&env);
+        assert!(empty.is_empty());
+        assert_eq!(empty.approx_len(), 0);
+        assert_eq!(empty.part_count(), 0);
+
+        let builder = MarkdownBuilder::new(&env)
+            .h1("Title")
+            .paragraph("Some text")
+            .text_string(&String::from_str(&env, "dynamic"))
+            .number(12345)
+            .render_link_id("View", "/task/", 42)
+            .tip("Careful");
+        assert!(!builder.is_empty());
+        assert!(builder.part_count() > 6);
+        let approx = builder.approx_len();
+        assert_eq!(approx, builder.build().len());
+    }
+
+    #[test]
+    fn test_inline_string_formatting() {
+        let env = Env::default();
+        let name = String::from_str(&env, "alice");
+        let output = MarkdownBuilder::new(&env)
+            .bold_string(&name)
+            .text(" ")
+            .italic_string(&name)
+            .text(" ")
+            .code_string(&name)
+            .text(" ")
+            .strikethrough_string(&name)
+            .build();
+        assert_output_eq(&output, "**alice** *alice* `alice` ~~alice~~");
+    }
+
+    #[test]
+    fn test_inline_string_formatting_empty() {
+        let env = Env::default();
+        let empty = String::from_str(&env, "");
+        let output = MarkdownBuilder::new(&env)
+            .bold_string(&empty)
+            .italic_string(&empty)
+            .code_string(&empty)
+            .strikethrough_string(&empty)
+            .build();
+        assert_output_eq(&output, "******``~~~~");
+    }
+
+    #[test]
+    fn test_link_string() {
+        let env = Env::default();
+        let text = String::from_str(&env, "Alice's page");
+        let output = MarkdownBuilder::new(&env)
+            .link_string(&text, "https://example.com")
+            .text(" ")
+            .render_link_string_text(&text, "/users/alice")
+            .build();
+        assert_output_eq(
+            &output,
+            "[Alice's page](https://example.com) [Alice's page](render:/users/alice)",
+        );
+
+        let empty = String::from_str(&env, "");
+        let output = MarkdownBuilder::new(&env)
+            .render_link_string_text(&empty, "/")
+            .build();
+        assert_output_eq(&output, "[](render:/)");
+    }
+
+    #[test]
+    fn test_build_string() {
+        let env = Env::default();
+        let s = MarkdownBuilder::new(&env)
+            .h1("Café ☕")
+            .text("日本語")
+            .build_string();
+        assert_eq!(s, String::from_str(&env, "# Café ☕\n\n日本語"));
+
+        let empty = MarkdownBuilder::new(&env).build_string();
+        assert_eq!(empty, String::from_str(&env, ""));
+    }
+
+    #[test]
+    fn test_pagination_first_page() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .pagination("/items", 1, 50)
+            .build();
+        assert_output_eq(
+            &output,
+            "**1** [2](render:/items/2) [3](render:/items/3) … [50](render:/items/50) \
+             [Next »](render:/items/2)\n\n",
+        );
+    }
+
+    #[test]
+    fn test_pagination_last_page() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .pagination("/items", 50, 50)
+            .build();
+        assert_output_eq(
+            &output,
+            "[« Prev](render:/items/49) [1](render:/items/1) … [48](render:/items/48) \
+             [49](render:/items/49) **50**\n\n",
+        );
+    }
+
+    #[test]
+    fn test_pagination_middle_page() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .pagination("/items", 25, 50)
+            .build();
+        assert_output_eq(
+            &output,
+            "[« Prev](render:/items/24) [1](render:/items/1) … [23](render:/items/23) \
+             [24](render:/items/24) **25** [26](render:/items/26) [27](render:/items/27) … \
+             [50](render:/items/50) [Next »](render:/items/26)\n\n",
+        );
+    }
+
+    #[test]
+    fn test_pagination_small_and_empty() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env).pagination("/p", 3, 4).build();
+        assert_output_eq(
+            &output,
+            "[« Prev](render:/p/2) [1](render:/p/1) [2](render:/p/2) **3** [4](render:/p/4) \
+             [Next »](render:/p/4)\n\n",
+        );
+
+        let single = MarkdownBuilder::new(&env).pagination("/p", 1, 1).build();
+        assert_output_eq(&single, "**1**\n\n");
+
+        let empty = MarkdownBuilder::new(&env).pagination("/p", 1, 0).build();
+        assert_eq!(empty.len(), 0);
+    }
+
+    #[test]
+    fn test_each_budgeted() {
+        let env = Env::default();
+        let mut items = Vec::new(&env);
+        for i in 0..100u32 {
+            items.push_back(i);
+        }
+        let budget = 500;
+        let (builder, rendered) =
+            MarkdownBuilder::new(&env)
+                .h2("Items")
+                .each_budgeted(&items, budget, |b, i| b.text("- item ").number(i).newline());
+        assert!(rendered > 0 && rendered < 100);
+        assert!(builder.approx_len() <= budget);
+
+        let output = builder
+            .continuation("items", rendered, Some(items.len()))
+            .build();
+        let text = to_str(&output);
+        assert!(text.contains(&alloc::format!("- item {}\n", rendered - 1)));
+        assert!(!text.contains(&alloc::format!("- item {}\n", rendered)));
+        assert!(text.ends_with(&alloc::format!(
+            "{{{{continue collection=\"items\" from={} total=100}}}}",
+            rendered
+        )));
+    }
+
+    #[test]
+    fn test_each_budgeted_fits_and_exact() {
+        let env = Env::default();
+        let items = Vec::from_array(&env, [1u32, 2, 3]);
+        let (builder, rendered) =
+            MarkdownBuilder::new(&env).each_budgeted(&items, 1000, |b, i| b.number(i));
+        assert_eq!(rendered, 3);
+        assert_eq!(to_str(&builder.build()), "123");
+
+        // An item that lands exactly on the budget is kept.
+        let (builder, rendered) =
+            MarkdownBuilder::new(&env).each_budgeted(&items, 2, |b, i| b.number(i));
+        assert_eq!(rendered, 2);
+        assert_eq!(to_str(&builder.build()), "12");
+
+        let (builder, rendered) =
+            MarkdownBuilder::new(&env)
+                .text("header")
+                .each_budgeted(&items, 3, |b, i| b.number(i));
+        assert_eq!(rendered, 0);
+        assert_eq!(to_str(&builder.build()), "header");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_each_budgeted_buffered() {
+        let env = Env::default();
+        let items = Vec::from_array(&env, [1u32, 2, 3]);
+        let (builder, rendered) = MarkdownBuilder::new_buffered(&env)
+            .text("> ")
+            .each_budgeted(&items, 4, |b, i| b.number(i));
+        assert_eq!(rendered, 2);
+        assert_eq!(to_str(&builder.text("!").build()), "> 12!");
+    }
+
+    #[cfg(feature = "router")]
+    #[test]
+    fn test_pagination_for() {
+        let env = Env::default();
+        let p = Pagination::new(47, 10, 3);
+        let output = MarkdownBuilder::new(&env)
+            .pagination_for("/items", &p)
+            .continue_page_for("items", &p)
+            .build();
+        let expected = MarkdownBuilder::new(&env)
+            .pagination("/items", 3, 5)
+            .continue_page("items", 3, 10, 47)
+            .build();
+        assert_eq!(output, expected);
+
+        let empty = MarkdownBuilder::new(&env)
+            .pagination_for("/items", &Pagination::new(0, 10, 1))
+            .build();
+        assert_eq!(empty.len(), 0);
+    }
+
+    #[test]
+    fn test_kv_block() {
+        use soroban_sdk::testutils::Address as _;
+
+        let env = Env::default();
+        let owner = Address::generate(&env);
+        let owner_str = to_str(&address_to_bytes(&env, &owner));
+        let output = MarkdownBuilder::new(&env)
+            .kv_block_start()
+            .kv_address("Owner", &owner)
+            .kv_string("Created", &String::from_str(&env, "2024-01-02"))
+            .kv_u32("Votes", 12)
+            .kv("Status", "Open")
+            .kv_block_end()
+            .build();
+        let expected = alloc::format!(
+            "<div class=\"kv\">\n**Owner:** {}\n\n**Created:** 2024-01-02\n\n\
+             **Votes:** 12\n\n**Status:** Open\n\n</div>\n",
+            owner_str
+        );
+        assert_eq!(to_str(&output), expected);
+    }
+
+    #[test]
+    fn test_kv_i128_and_bool() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .kv_i128("Balance", -1_000_000_000_000)
+            .kv_bool("Active", true)
+            .build();
+        assert_output_eq(
+            &output,
+            "**Balance:** -1000000000000\n\n**Active:** true\n\n",
+        );
+    }
+
+    #[test]
+    fn test_html_comment() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .html_comment("cache: 60s")
+            .build();
+        assert_output_eq(&output, "<!-- cache: 60s -->\n");
+    }
+
+    #[test]
+    fn test_html_comment_injection() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .html_comment("x --> <script>alert(1)</script> --!> y")
+            .build();
+        assert_output_eq(
+            &output,
+            "<!-- x --&gt; <script>alert(1)</script> --!&gt; y -->\n",
+        );
+
+        // A lone `>` that can't close the comment is left alone.
+        let output = MarkdownBuilder::new(&env)
+            .html_comment("a > b -> c")
+            .build();
+        assert_output_eq(&output, "<!-- a > b -> c -->\n");
+    }
+
+    #[test]
+    fn test_marker() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .marker("section", "comments")
+            .marker("k-->", "v-->")
+            .build();
+        assert_output_eq(
+            &output,
+            "<!-- render:section=comments -->\n<!-- render:k--&gt;=v--&gt; -->\n",
+        );
+    }
+
+    #[test]
+    fn test_alert_lines() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .alert_lines("WARNING", &["Line one", "Line two", "Line three"])
+            .build();
+        let s = to_str(&output);
+        assert_eq!(s, "> [!WARNING]\n> Line one\n> Line two\n> Line three\n\n");
+        assert!(
+            s.lines()
+                .filter(|l| !l.is_empty())
+                .all(|l| l.starts_with("> "))
+        );
+    }
+
+    #[test]
+    fn test_alert_string() {
+        let env = Env::default();
+        let body = String::from_str(&env, "Line one\nLine two\nLine three");
+        let output = MarkdownBuilder::new(&env)
+            .alert_string("NOTE", &body)
+            .build();
+        assert_output_eq(
+            &output,
+            "> [!NOTE]\n> Line one\n> Line two\n> Line three\n\n",
+        );
+    }
+
+    #[test]
+    fn test_alert_incremental() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .alert_start("TIP")
+            .alert_line("Line one")
+            .alert_line("Line two")
+            .alert_line("Line three")
+            .alert_end()
+            .paragraph("After")
+            .build();
+        assert_output_eq(
+            &output,
+            "> [!TIP]\n> Line one\n> Line two\n> Line three\n\nAfter\n\n",
+        );
+    }
+
+    #[test]
+    fn test_blockquote_multiline() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .blockquote_multiline("First paragraph\n\nSecond paragraph\nstill second")
+            .build();
+        assert_output_eq(
+            &output,
+            "> First paragraph\n>\n> Second paragraph\n> still second\n\n",
+        );
+    }
+
+    #[test]
+    fn test_blockquote_string() {
+        let env = Env::default();
+        let reply = String::from_str(&env, "Agreed.\n\nShip it.");
+        let output = MarkdownBuilder::new(&env).blockquote_string(&reply).build();
+        assert_output_eq(&output, "> Agreed.\n>\n> Ship it.\n\n");
+
+        let empty = MarkdownBuilder::new(&env)
+            .blockquote_string(&String::from_str(&env, ""))
+            .build();
+        assert_output_eq(&empty, ">\n\n");
+    }
+
+    #[test]
+    fn test_blockquote_nested() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .blockquote_nested(2, "Quoted quote\n\nmore")
+            .build();
+        assert_output_eq(&output, ">> Quoted quote\n>>\n>> more\n\n");
+
+        let output = MarkdownBuilder::new(&env).blockquote_nested(0, "x").build();
+        assert_output_eq(&output, "> x\n\n");
+    }
+
+    #[test]
+    fn test_relative_time() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .text("Posted ")
+            .relative_time(10_000, 10_000 - 300)
+            .build();
+        assert_output_eq(&output, "Posted 5 minutes ago");
+    }
+
+    #[test]
+    fn test_timestamp() {
+        let env = Env::default();
+        let output = MarkdownBuilder::new(&env)
+            .text("Created ")
+            .timestamp(1_704_153_600)
+            .build();
+        assert_output_eq(&output, "Created 2024-01-02 00:00:00 UTC");
+    }
+
+    #[test]
+    fn test_preview() {
+        let env = Env::default();
+        let body = String::from_str(&env, "First line\r\nSecond\n\nThird line is long");
+        let output = MarkdownBuilder::new(&env).preview(&body, 24).build();
+        assert_output_eq(&output, "First line Second Thir…");
+
+        let short = String::from_str(&env, "Fits");
+        let output = MarkdownBuilder::new(&env).preview(&short, 140).build();
+        assert_output_eq(&output, "Fits");
+
+        // The cut lands inside "é"; no partial character is emitted.
+        let accented = String::from_str(&env, "Résumé");
+        let output = MarkdownBuilder::new(&env).preview(&accented, 2).build();
+        assert_output_eq(&output, "R…");
+    }
+
+    #[test]
+    fn test_error_page() {
+        let env = Env::default();
+        let output =
+            MarkdownBuilder::error_page(&env, 500, "Server Error", "Try again later.", "/home");
+        assert_output_eq(
+            &output,
+            "# 500 Server Error\n\n> [!WARNING]\n> Try again later.\n\n[Go back](render:/home)\n",
+        );
+    }
+
+    #[test]
+    fn test_error_page_multiline_message() {
+        let env = Env::default();
+        let output = MarkdownBuilder::error_page(&env, 503, "Paused", "Line one.\nLine two.", "/");
+        assert_output_eq(
+            &output,
+            "# 503 Paused\n\n> [!WARNING]\n> Line one.\n> Line two.\n\n[Go back](render:/)\n",
+        );
+    }
+
+    #[test]
+    fn test_not_found_and_unauthorized() {
+        let env = Env::default();
+        let output = to_str(&not_found(&env, "/tasks"));
+        assert!(output.starts_with("# 404 Not Found\n"));
+        assert!(output.contains("> [!WARNING]\n"));
+        assert!(output.ends_with("[Go back](render:/tasks)\n"));
+
+        let output = to_str(&unauthorized(&env, "/"));
+        assert!(output.starts_with("# 401 Unauthorized\n"));
+        assert!(output.ends_with("[Go back](render:/)\n"));
     }
 }
@@ -41,6 +41,10 @@ macro_rules! render_v1 {
 
 /// Declare supported render formats.
 ///
+/// Accepts any combination of `markdown`, `json`, and `css`, in any order.
+/// The value is always written in the order `markdown,json,css`, so the
+/// same set of formats produces the same metadata.
+///
 /// # Examples
 ///
 /// ```rust,ignore
@@ -49,87 +53,213 @@ macro_rules! render_v1 {
 /// // Markdown only
 /// render_formats!(markdown);
 ///
-/// // JSON only
-/// render_formats!(json);
-///
 /// // Both formats
 /// render_formats!(markdown, json);
+///
+/// // A theme contract that only serves CSS
+/// render_formats!(css);
 /// ```
 #[macro_export]
 macro_rules! render_formats {
-    (markdown) => {
+    ($($formats:tt)*) => {
+        $crate::__render_formats_emit!(@munch [no no no] ; $($formats)*);
+    };
+}
+
+/// Internal helper macro that collects `render_formats!` arguments into
+/// `[markdown json css]` flags and emits the canonical value. Not intended
+/// for direct use.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __render_formats_emit {
+    (@munch [$md:ident $js:ident $css:ident] ;) => {
+        $crate::__render_formats_emit!(@emit $md $js $css);
+    };
+    (@munch [$md:ident $js:ident $css:ident] ; markdown $(, $($rest:tt)*)?) => {
+        $crate::__render_formats_emit!(@munch [yes $js $css] ; $($($rest)*)?);
+    };
+    (@munch [$md:ident $js:ident $css:ident] ; json $(, $($rest:tt)*)?) => {
+        $crate::__render_formats_emit!(@munch [$md yes $css] ; $($($rest)*)?);
+    };
+    (@munch [$md:ident $js:ident $css:ident] ; css $(, $($rest:tt)*)?) => {
+        $crate::__render_formats_emit!(@munch [$md $js yes] ; $($($rest)*)?);
+    };
+    (@munch $flags:tt ; $other:tt $($rest:tt)*) => {
+        ::core::compile_error!(::core::concat!(
+            "render_formats!: unknown format `",
+            ::core::stringify!($other),
+            "`; expected `markdown`, `json`, or `css`"
+        ));
+    };
+
+    (@emit yes no no) => {
         soroban_sdk::contractmeta!(key = "render_formats", val = "markdown");
     };
-    (json) => {
+    (@emit no yes no) => {
         soroban_sdk::contractmeta!(key = "render_formats", val = "json");
     };
-    // Both orderings produce the same normalized output
-    (markdown, json) => {
-        soroban_sdk::contractmeta!(key = "render_formats", val = "markdown,json");
+    (@emit no no yes) => {
+        soroban_sdk::contractmeta!(key = "render_formats", val = "css");
     };
-    (json, markdown) => {
+    (@emit yes yes no) => {
         soroban_sdk::contractmeta!(key = "render_formats", val = "markdown,json");
     };
+    (@emit yes no yes) => {
+        soroban_sdk::contractmeta!(key = "render_formats", val = "markdown,css");
+    };
+    (@emit no yes yes) => {
+        soroban_sdk::contractmeta!(key = "render_formats", val = "json,css");
+    };
+    (@emit yes yes yes) => {
+        soroban_sdk::contractmeta!(key = "render_formats", val = "markdown,json,css");
+    };
+    (@emit no no no) => {
+        ::core::compile_error!("render_formats!: at least one format is required");
+    };
 }
 
-/// Internal helper macro that emits metadata after arguments are parsed.
-/// Not intended for direct use.
+/// Internal helper macro that parses `soroban_render!` options and emits
+/// the metadata. Not intended for direct use.
+///
+/// Options are consumed one at a time into the accumulator
+/// `[markdown json styles] [theme] [registry] [title] [description] [icon]`,
+/// where each flag is `yes` or `no` and each value slot holds at most one
+/// expression, so options may appear in any order and repeated formats
+/// are harmless.
 #[doc(hidden)]
 #[macro_export]
 macro_rules! __soroban_render_emit {
-    // Single format without options
-    (@format $fmt:ident) => {
+    // All options consumed: emit the metadata
+    (@munch [$md:ident $js:ident $st:ident]
+        [$($theme:expr)?] [$($registry:expr)?] [$($title:expr)?]
+        [$($description:expr)?] [$($icon:expr)?] ;) => {
         $crate::render_v1!();
-        $crate::render_formats!($fmt);
+        $crate::__soroban_render_emit!(@formats $md $js $st);
+        $crate::__soroban_render_emit!(@styles $st);
+        $($crate::render_theme!($theme);)?
+        $($crate::render_registry!($registry);)?
+        $($crate::render_title!($title);)?
+        $($crate::render_description!($description);)?
+        $($crate::render_icon!($icon);)?
     };
-    // Single format with styles only
-    (@format $fmt:ident @styles) => {
-        $crate::render_v1!();
-        $crate::render_formats!($fmt);
-        $crate::render_has_styles!();
+
+    // Flags
+    (@munch [$md:ident $js:ident $st:ident]
+        $theme:tt $registry:tt $title:tt $description:tt $icon:tt ;
+        markdown $(, $($rest:tt)*)?) => {
+        $crate::__soroban_render_emit!(
+            @munch [yes $js $st] $theme $registry $title $description $icon ; $($($rest)*)?
+        );
     };
-    // Single format with theme only
-    (@format $fmt:ident @theme $theme:expr) => {
-        $crate::render_v1!();
-        $crate::render_formats!($fmt);
-        $crate::render_theme!($theme);
+    (@munch [$md:ident $js:ident $st:ident]
+        $theme:tt $registry:tt $title:tt $description:tt $icon:tt ;
+        json $(, $($rest:tt)*)?) => {
+        $crate::__soroban_render_emit!(
+            @munch [$md yes $st] $theme $registry $title $description $icon ; $($($rest)*)?
+        );
     };
-    // Single format with both styles and theme
-    (@format $fmt:ident @styles @theme $theme:expr) => {
-        $crate::render_v1!();
-        $crate::render_formats!($fmt);
-        $crate::render_has_styles!();
-        $crate::render_theme!($theme);
+    (@munch [$md:ident $js:ident $st:ident]
+        $theme:tt $registry:tt $title:tt $description:tt $icon:tt ;
+        styles $(, $($rest:tt)*)?) => {
+        $crate::__soroban_render_emit!(
+            @munch [$md $js yes] $theme $registry $title $description $icon ; $($($rest)*)?
+        );
     };
-    // Dual format without options
-    (@formats $fmt1:ident $fmt2:ident) => {
-        $crate::render_v1!();
-        $crate::render_formats!($fmt1, $fmt2);
+
+    // Values, each allowed once
+    (@munch $flags:tt [] $registry:tt $title:tt $description:tt $icon:tt ;
+        theme = $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__soroban_render_emit!(
+            @munch $flags [$value] $registry $title $description $icon ; $($($rest)*)?
+        );
     };
-    // Dual format with styles only
-    (@formats $fmt1:ident $fmt2:ident @styles) => {
-        $crate::render_v1!();
-        $crate::render_formats!($fmt1, $fmt2);
-        $crate::render_has_styles!();
+    (@munch $flags:tt $theme:tt [] $title:tt $description:tt $icon:tt ;
+        registry = $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__soroban_render_emit!(
+            @munch $flags $theme [$value] $title $description $icon ; $($($rest)*)?
+        );
     };
-    // Dual format with theme only
-    (@formats $fmt1:ident $fmt2:ident @theme $theme:expr) => {
-        $crate::render_v1!();
-        $crate::render_formats!($fmt1, $fmt2);
-        $crate::render_theme!($theme);
+    (@munch $flags:tt $theme:tt $registry:tt [] $description:tt $icon:tt ;
+        title = $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__soroban_render_emit!(
+            @munch $flags $theme $registry [$value] $description $icon ; $($($rest)*)?
+        );
     };
-    // Dual format with both styles and theme
-    (@formats $fmt1:ident $fmt2:ident @styles @theme $theme:expr) => {
-        $crate::render_v1!();
-        $crate::render_formats!($fmt1, $fmt2);
+    (@munch $flags:tt $theme:tt $registry:tt $title:tt [] $icon:tt ;
+        description = $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__soroban_render_emit!(
+            @munch $flags $theme $registry $title [$value] $icon ; $($($rest)*)?
+        );
+    };
+    (@munch $flags:tt $theme:tt $registry:tt $title:tt $description:tt [] ;
+        icon = $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__soroban_render_emit!(
+            @munch $flags $theme $registry $title $description [$value] ; $($($rest)*)?
+        );
+    };
+    (@munch $flags:tt $theme:tt $registry:tt $title:tt $description:tt $icon:tt ;
+        theme = $($rest:tt)*) => {
+        ::core::compile_error!("soroban_render!: `theme` given more than once");
+    };
+    (@munch $flags:tt $theme:tt $registry:tt $title:tt $description:tt $icon:tt ;
+        registry = $($rest:tt)*) => {
+        ::core::compile_error!("soroban_render!: `registry` given more than once");
+    };
+    (@munch $flags:tt $theme:tt $registry:tt $title:tt $description:tt $icon:tt ;
+        title = $($rest:tt)*) => {
+        ::core::compile_error!("soroban_render!: `title` given more than once");
+    };
+    (@munch $flags:tt $theme:tt $registry:tt $title:tt $description:tt $icon:tt ;
+        description = $($rest:tt)*) => {
+        ::core::compile_error!("soroban_render!: `description` given more than once");
+    };
+    (@munch $flags:tt $theme:tt $registry:tt $title:tt $description:tt $icon:tt ;
+        icon = $($rest:tt)*) => {
+        ::core::compile_error!("soroban_render!: `icon` given more than once");
+    };
+
+    (@munch $flags:tt $theme:tt $registry:tt $title:tt $description:tt $icon:tt ;
+        $other:tt $($rest:tt)*) => {
+        ::core::compile_error!(::core::concat!(
+            "soroban_render!: unknown option `",
+            ::core::stringify!($other),
+            "`; expected `markdown`, `json`, `styles`, or one of ",
+            "`theme`, `registry`, `title`, `description`, `icon` = \"...\""
+        ));
+    };
+
+    // Normalized format list; a styles-only contract serves CSS
+    (@formats yes no $st:ident) => {
+        $crate::render_formats!(markdown);
+    };
+    (@formats no yes $st:ident) => {
+        $crate::render_formats!(json);
+    };
+    (@formats yes yes $st:ident) => {
+        $crate::render_formats!(markdown, json);
+    };
+    (@formats no no yes) => {
+        $crate::render_formats!(css);
+    };
+    (@formats no no no) => {
+        ::core::compile_error!(
+            "soroban_render!: at least one of `markdown`, `json`, or `styles` is required"
+        );
+    };
+
+    (@styles yes) => {
         $crate::render_has_styles!();
-        $crate::render_theme!($theme);
     };
+    (@styles no) => {};
 }
 
 /// Declare full Soroban Render support with format specification.
 ///
-/// This is a convenience macro that combines `render_v1!()` and `render_formats!()`.
+/// This is a convenience macro that combines `render_v1!()`,
+/// `render_formats!()`, and optionally `render_has_styles!()`,
+/// `render_theme!()`, `render_registry!()`, `render_title!()`,
+/// `render_description!()`, and `render_icon!()`. Options may be given in any order; at least one
+/// format is required.
 ///
 /// # Examples
 ///
@@ -144,35 +274,23 @@ macro_rules! __soroban_render_emit {
 ///
 /// // Both formats
 /// soroban_render!(markdown, json);
+///
+/// // Styles only, e.g. a theme contract
+/// soroban_render!(styles);
+///
+/// // Options in any order
+/// soroban_render!(styles, theme = "CABCD123...", json, markdown);
+///
+/// // Part of a multi-contract app
+/// soroban_render!(markdown, registry = "CREGISTRY...", theme = "CABCD123...");
+///
+/// // With directory listing details
+/// soroban_render!(markdown, title = "My Forum", description = "A message board");
 /// ```
 #[macro_export]
 macro_rules! soroban_render {
-    // Single format patterns
-    ($fmt:ident) => {
-        $crate::__soroban_render_emit!(@format $fmt);
-    };
-    ($fmt:ident, styles) => {
-        $crate::__soroban_render_emit!(@format $fmt @styles);
-    };
-    ($fmt:ident, theme = $theme:expr) => {
-        $crate::__soroban_render_emit!(@format $fmt @theme $theme);
-    };
-    ($fmt:ident, styles, theme = $theme:expr) => {
-        $crate::__soroban_render_emit!(@format $fmt @styles @theme $theme);
-    };
-
-    // Dual format patterns
-    ($fmt1:ident, $fmt2:ident) => {
-        $crate::__soroban_render_emit!(@formats $fmt1 $fmt2);
-    };
-    ($fmt1:ident, $fmt2:ident, styles) => {
-        $crate::__soroban_render_emit!(@formats $fmt1 $fmt2 @styles);
-    };
-    ($fmt1:ident, $fmt2:ident, theme = $theme:expr) => {
-        $crate::__soroban_render_emit!(@formats $fmt1 $fmt2 @theme $theme);
-    };
-    ($fmt1:ident, $fmt2:ident, styles, theme = $theme:expr) => {
-        $crate::__soroban_render_emit!(@formats $fmt1 $fmt2 @styles @theme $theme);
+    ($($options:tt)*) => {
+        $crate::__soroban_render_emit!(@munch [no no no] [] [] [] [] [] ; $($options)*);
     };
 }
 
@@ -195,6 +313,26 @@ macro_rules! render_theme {
     };
 }
 
+/// Declare the registry contract of a multi-contract app.
+///
+/// Viewers read this from any contract in the app to find the registry
+/// that resolves `@alias` links and includes. The registry should expose
+/// `get_contract_by_alias`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use soroban_render_sdk::render_registry;
+///
+/// render_registry!("CREGISTRY..."); // Contract ID of the registry
+/// ```
+#[macro_export]
+macro_rules! render_registry {
+    ($contract_id:expr) => {
+        soroban_sdk::contractmeta!(key = "render_registry", val = $contract_id);
+    };
+}
+
 /// Declare that this contract provides styles.
 ///
 /// This signals to viewers that the contract has a `styles()` function
@@ -213,3 +351,179 @@ macro_rules! render_has_styles {
         soroban_sdk::contractmeta!(key = "render_styles", val = "true");
     };
 }
+
+/// Declare a human-readable name for the contract.
+///
+/// Viewers use this when listing render-capable contracts in a directory.
+/// Expands to `contractmeta!(key = "render_title", val = ...)`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use soroban_render_sdk::render_title;
+///
+/// render_title!("My Forum");
+/// ```
+#[macro_export]
+macro_rules! render_title {
+    ($title:expr) => {
+        soroban_sdk::contractmeta!(key = "render_title", val = $title);
+    };
+}
+
+/// Declare a short description of the contract.
+///
+/// Shown alongside the title in viewer directories.
+/// Expands to `contractmeta!(key = "render_description", val = ...)`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use soroban_render_sdk::render_description;
+///
+/// render_description!("A decentralized message board");
+/// ```
+#[macro_export]
+macro_rules! render_description {
+    ($description:expr) => {
+        soroban_sdk::contractmeta!(key = "render_description", val = $description);
+    };
+}
+
+/// Declare an icon for the contract.
+///
+/// The value is a render path, such as `"/icon"`, or a URL. Expands to
+/// `contractmeta!(key = "render_icon", val = ...)`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use soroban_render_sdk::render_icon;
+///
+/// render_icon!("/icon");
+/// ```
+#[macro_export]
+macro_rules! render_icon {
+    ($icon:expr) => {
+        soroban_sdk::contractmeta!(key = "render_icon", val = $icon);
+    };
+}
+
+/// Advertise the paths a contract renders.
+///
+/// Viewers can use the list to prefetch pages or build navigation. Each
+/// route must start with `/` and may not contain a comma; anything else
+/// fails to compile. The routes are joined with commas into the
+/// `render_routes` metadata entry.
+///
+/// Routes may be string literals or `&str` constants, so the same consts
+/// can feed both this macro and the `Router` (see the `router` module).
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use soroban_render_sdk::render_routes;
+///
+/// render_routes!("/", "/tasks", "/task/{id}");
+/// // Emits render_routes = "/,/tasks,/task/{id}"
+/// ```
+#[macro_export]
+macro_rules! render_routes {
+    ($($route:expr),+ $(,)?) => {
+        #[doc(hidden)]
+        #[cfg_attr(target_family = "wasm", link_section = "contractmetav0")]
+        static __CONTRACT_KEY_72656e6465725f726f75746573: [u8; $crate::__render_routes_len(
+            &[$($route),+],
+        )] = $crate::__render_routes_xdr(&[$($route),+]);
+    };
+}
+
+/// Metadata key written by `render_routes!`.
+const ROUTES_KEY: &[u8] = b"render_routes";
+
+/// Length of `len` bytes padded to a 4-byte XDR boundary.
+const fn xdr_padded(len: usize) -> usize {
+    len.div_ceil(4) * 4
+}
+
+/// Length of the routes joined with commas, checking each route.
+const fn joined_routes_len(routes: &[&str]) -> usize {
+    assert!(
+        !routes.is_empty(),
+        "render_routes! needs at least one route"
+    );
+    let mut len = 0;
+    let mut i = 0;
+    while i < routes.len() {
+        let route = routes[i].as_bytes();
+        assert!(
+            !route.is_empty() && route[0] == b'/',
+            "render_routes!: every route must start with `/`"
+        );
+        let mut j = 0;
+        while j < route.len() {
+            assert!(
+                route[j] != b',',
+                "render_routes!: routes may not contain `,`"
+            );
+            j += 1;
+        }
+        if i > 0 {
+            len += 1;
+        }
+        len += route.len();
+        i += 1;
+    }
+    len
+}
+
+/// Size of the XDR-encoded `render_routes` metadata entry.
+#[doc(hidden)]
+pub const fn __render_routes_len(routes: &[&str]) -> usize {
+    // Union discriminant, then the key and value as length-prefixed strings
+    4 + 4 + xdr_padded(ROUTES_KEY.len()) + 4 + xdr_padded(joined_routes_len(routes))
+}
+
+/// Write `len` as a big-endian XDR length at `pos`, returning the next position.
+const fn write_xdr_len<const N: usize>(out: &mut [u8; N], pos: usize, len: usize) -> usize {
+    let bytes = (len as u32).to_be_bytes();
+    let mut i = 0;
+    while i < 4 {
+        out[pos + i] = bytes[i];
+        i += 1;
+    }
+    pos + 4
+}
+
+/// XDR-encode the `render_routes` metadata entry, as `contractmeta!` would.
+#[doc(hidden)]
+pub const fn __render_routes_xdr<const N: usize>(routes: &[&str]) -> [u8; N] {
+    let mut out = [0u8; N];
+    // SC_META_V0 discriminant is zero, so the first four bytes stay zero
+    let mut pos = write_xdr_len(&mut out, 4, ROUTES_KEY.len());
+    let mut i = 0;
+    while i < ROUTES_KEY.len() {
+        out[pos + i] = ROUTES_KEY[i];
+        i += 1;
+    }
+    pos += xdr_padded(ROUTES_KEY.len());
+
+    let value_len = joined_routes_len(routes);
+    pos = write_xdr_len(&mut out, pos, value_len);
+    let mut r = 0;
+    while r < routes.len() {
+        if r > 0 {
+            out[pos] = b',';
+            pos += 1;
+        }
+        let route = routes[r].as_bytes();
+        let mut j = 0;
+        while j < route.len() {
+            out[pos] = route[j];
+            pos += 1;
+            j += 1;
+        }
+        r += 1;
+    }
+    out
+}
@@ -0,0 +1,216 @@
+//! Page arithmetic for list views.
+//!
+//! `Pagination` turns a collection size, a page size, and a requested page
+//! into the index range to render and the facts a pager needs, so list
+//! views don't each redo the offset math.
+//!
+//! # Indexing
+//!
+//! Pages are 1-based, matching `MarkdownBuilder::pagination` and
+//! `continue_page`. Item indices are 0-based.
+//!
+//! - Page 0 is treated as page 1, so an unset query parameter that defaults
+//!   to 0 shows the first page.
+//! - A page past the last one is kept as requested and selects an empty
+//!   range. Call `clamp()` to move it onto the last page instead.
+//! - A `per_page` of 0 is treated as 1.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! let page = req.get_query_param_u32(b"page").unwrap_or(1);
+//! let pages = Pagination::new(items.len(), 10, page).clamp();
+//!
+//! let mut b = MarkdownBuilder::new(&env);
+//! for i in pages.start()..pages.end_exclusive() {
+//!     b = b.list_item_string(&items.get_unchecked(i));
+//! }
+//! b.pagination_for("/items", &pages).build()
+//! ```
+
+/// Offset and limit bookkeeping for one page of a collection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pagination {
+    total: u32,
+    per_page: u32,
+    page: u32,
+}
+
+impl Pagination {
+    /// Describe `page` (1-based) of a collection of `total` items.
+    pub const fn new(total: u32, per_page: u32, page: u32) -> Self {
+        Self {
+            total,
+            per_page: if per_page == 0 { 1 } else { per_page },
+            page: if page == 0 { 1 } else { page },
+        }
+    }
+
+    /// Move the page into range.
+    ///
+    /// Pages past the end become the last page. An empty collection has no
+    /// pages, so it clamps to page 1 with an empty range.
+    pub const fn clamp(self) -> Self {
+        let last = self.total_pages();
+        let page = if last == 0 {
+            1
+        } else if self.page > last {
+            last
+        } else {
+            self.page
+        };
+        Self { page, ..self }
+    }
+
+    /// The current page (1-based).
+    pub const fn page(&self) -> u32 {
+        self.page
+    }
+
+    /// Items per page.
+    pub const fn per_page(&self) -> u32 {
+        self.per_page
+    }
+
+    /// Number of items in the collection.
+    pub const fn total(&self) -> u32 {
+        self.total
+    }
+
+    /// Number of pages; 0 for an empty collection.
+    pub const fn total_pages(&self) -> u32 {
+        self.total.div_ceil(self.per_page)
+    }
+
+    /// Index of the first item on the page.
+    ///
+    /// Never exceeds `total`, so out-of-range pages give an empty range.
+    pub const fn start(&self) -> u32 {
+        let start = (self.page - 1).saturating_mul(self.per_page);
+        if start > self.total {
+            self.total
+        } else {
+            start
+        }
+    }
+
+    /// Index one past the last item on the page.
+    pub const fn end_exclusive(&self) -> u32 {
+        let end = self.start().saturating_add(self.per_page);
+        if end > self.total { self.total } else { end }
+    }
+
+    /// Whether a later page has items.
+    pub const fn has_next(&self) -> bool {
+        self.page < self.total_pages()
+    }
+
+    /// Whether there is an earlier page to go back to.
+    ///
+    /// True on any page after the first, including pages past the end.
+    pub const fn has_prev(&self) -> bool {
+        self.page > 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_collection() {
+        let p = Pagination::new(0, 10, 1);
+        assert_eq!(p.total_pages(), 0);
+        assert_eq!((p.start(), p.end_exclusive()), (0, 0));
+        assert!(!p.has_next());
+        assert!(!p.has_prev());
+        assert_eq!(p.clamp().page(), 1);
+
+        let p = Pagination::new(0, 10, 3);
+        assert_eq!((p.start(), p.end_exclusive()), (0, 0));
+        assert!(p.has_prev());
+        assert_eq!(p.clamp(), Pagination::new(0, 10, 1));
+    }
+
+    #[test]
+    fn test_exact_multiple_of_per_page() {
+        let p = Pagination::new(30, 10, 3);
+        assert_eq!(p.total_pages(), 3);
+        assert_eq!((p.start(), p.end_exclusive()), (20, 30));
+        assert!(!p.has_next());
+        assert!(p.has_prev());
+
+        let p = Pagination::new(30, 10, 2);
+        assert_eq!((p.start(), p.end_exclusive()), (10, 20));
+        assert!(p.has_next());
+
+        let p = Pagination::new(30, 10, 4);
+        assert_eq!((p.start(), p.end_exclusive()), (30, 30));
+        assert!(!p.has_next());
+        assert_eq!(p.clamp().page(), 3);
+    }
+
+    #[test]
+    fn test_partial_last_page() {
+        let p = Pagination::new(47, 10, 5);
+        assert_eq!(p.total_pages(), 5);
+        assert_eq!((p.start(), p.end_exclusive()), (40, 47));
+        assert!(!p.has_next());
+
+        let p = Pagination::new(47, 10, 1);
+        assert_eq!((p.start(), p.end_exclusive()), (0, 10));
+        assert!(p.has_next());
+        assert!(!p.has_prev());
+    }
+
+    #[test]
+    fn test_fewer_items_than_per_page() {
+        let p = Pagination::new(3, 10, 1);
+        assert_eq!(p.total_pages(), 1);
+        assert_eq!((p.start(), p.end_exclusive()), (0, 3));
+        assert!(!p.has_next());
+        assert!(!p.has_prev());
+    }
+
+    #[test]
+    fn test_page_zero_is_first_page() {
+        let p = Pagination::new(25, 10, 0);
+        assert_eq!(p.page(), 1);
+        assert_eq!(p, Pagination::new(25, 10, 1));
+        assert_eq!((p.start(), p.end_exclusive()), (0, 10));
+        assert!(!p.has_prev());
+    }
+
+    #[test]
+    fn test_out_of_range_page() {
+        let p = Pagination::new(25, 10, 9);
+        assert_eq!(p.page(), 9);
+        assert_eq!((p.start(), p.end_exclusive()), (25, 25));
+        assert!(!p.has_next());
+        assert!(p.has_prev());
+
+        let p = p.clamp();
+        assert_eq!(p.page(), 3);
+        assert_eq!((p.start(), p.end_exclusive()), (20, 25));
+
+        let p = Pagination::new(25, 10, u32::MAX);
+        assert_eq!((p.start(), p.end_exclusive()), (25, 25));
+        assert_eq!(p.clamp().page(), 3);
+    }
+
+    #[test]
+    fn test_zero_per_page() {
+        let p = Pagination::new(3, 0, 2);
+        assert_eq!(p.per_page(), 1);
+        assert_eq!(p.total_pages(), 3);
+        assert_eq!((p.start(), p.end_exclusive()), (1, 2));
+    }
+
+    #[test]
+    fn test_large_values_do_not_overflow() {
+        let p = Pagination::new(u32::MAX, u32::MAX, 2);
+        assert_eq!(p.total_pages(), 1);
+        assert_eq!((p.start(), p.end_exclusive()), (u32::MAX, u32::MAX));
+        assert_eq!(p.clamp().page(), 1);
+    }
+}
@@ -6,23 +6,49 @@
 //! use soroban_render_sdk::prelude::*;
 //! ```
 
+// Re-export transaction argument builder
+pub use crate::args::TxArgs;
+
+// Re-export chunked content storage
+pub use crate::chunks::{ChunkKey, ChunkedContent};
+
 // Re-export bytes utilities
 pub use crate::bytes::{
+    // Buffered output
+    BytesWriter,
     // Address and Symbol utilities
     address_to_bytes,
-    // Decimal Bytes to number
+    // Search and replace
+    bytes_contains,
+    // Hashing and comparison
+    bytes_eq,
+    bytes_find,
+    bytes_hash_64,
+    bytes_replace_all,
+    // Split and trim
+    bytes_split,
     bytes_to_i32,
     bytes_to_i64,
     bytes_to_i128,
     bytes_to_i256,
+    // Decimal Bytes to number
+    // Case conversion and slugs
+    bytes_to_lower,
+    bytes_to_string,
     bytes_to_u32,
     bytes_to_u64,
     bytes_to_u128,
     bytes_to_u256,
+    bytes_to_upper,
+    bytes_trim,
     // Core utilities
     concat_bytes,
     escape_json_bytes,
     escape_json_string,
+    // Time formatting
+    format_date,
+    format_relative_time,
+    format_timestamp,
     // Hex Bytes to number
     hex_to_i32,
     hex_to_i64,
@@ -39,9 +65,13 @@ pub use crate::bytes::{
     i64_to_bytes,
     i64_to_hex,
     i128_to_bytes,
+    i128_to_bytes_grouped,
     i128_to_hex,
     i256_to_bytes,
     i256_to_hex,
+    // URL encoding
+    percent_encode,
+    slugify,
     // &str convenience wrappers
     str_to_i32,
     str_to_i64,
@@ -62,6 +92,9 @@ pub use crate::bytes::{
     string_to_u128,
     string_to_u256,
     symbol_to_bytes,
+    // Truncation
+    truncate_bytes,
+    truncate_string,
     u32_to_bytes,
     u32_to_hex,
     u64_to_bytes,
@@ -77,25 +110,42 @@ pub use crate::{render_formats, render_has_styles, render_theme, render_v1, soro
 
 // Re-export markdown builder (when feature enabled)
 #[cfg(feature = "markdown")]
+pub use crate::component::Renderable;
+#[cfg(any(feature = "markdown", feature = "json"))]
+pub use crate::component::{Footer, PageHeader};
+#[cfg(feature = "markdown")]
 pub use crate::markdown::MarkdownBuilder;
+#[cfg(feature = "markdown")]
+pub use crate::render_trait::{RenderField, RenderFields};
 
 // Re-export JSON builder (when feature enabled)
 #[cfg(feature = "json")]
+pub use crate::component::JsonRenderable;
+#[cfg(feature = "json")]
 pub use crate::json::{FormBuilder, JsonDocument, TaskBuilder};
 
 // Re-export router (when feature enabled)
 #[cfg(feature = "router")]
+pub use crate::paginate::Pagination;
+#[cfg(feature = "router")]
+pub use crate::render_entry;
+#[cfg(feature = "router")]
 pub use crate::router::{
-    Request, Router, RouterResult, parse_id, path_eq, path_starts_with, path_suffix, path_to_bytes,
+    RenderFormat, Request, RouteHandler, RouteTable, Router, RouterResult, parse_id, path_eq,
+    path_starts_with, path_suffix, path_to_bytes, split_format,
 };
 
 // Re-export style builder (when feature enabled)
 #[cfg(feature = "styles")]
-pub use crate::styles::StyleBuilder;
+pub use crate::styles::{ColorTransform, StyleBuilder, darken_hex, lighten_hex, with_alpha};
 
 // Re-export registry (when feature enabled)
+#[cfg(all(feature = "registry", feature = "router"))]
+pub use crate::registry::RenderTarget;
 #[cfg(feature = "registry")]
-pub use crate::registry::{BaseRegistry, ContractRegistry, RegistryKey};
+pub use crate::registry::{
+    BaseRegistry, ContractRegistry, RegistryEntry, RegistryError, RegistryKey,
+};
 
 // Re-export Bytes from soroban_sdk for convenience
 pub use soroban_sdk::Bytes;
@@ -0,0 +1,181 @@
+//! Render structs as markdown key-value blocks.
+//!
+//! `RenderFields` turns a struct into one `**label:** value` line per field,
+//! which is handy for admin and debug pages. The `render_fields!` macro
+//! writes the impl, using each field's name as its label.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use soroban_render_sdk::prelude::*;
+//! use soroban_render_sdk::render_fields;
+//!
+//! #[contracttype]
+//! pub struct Task {
+//!     pub id: u32,
+//!     pub title: String,
+//!     pub owner: Address,
+//! }
+//!
+//! render_fields!(Task { id, title, owner });
+//!
+//! MarkdownBuilder::new(&env)
+//!     .kv_block_start()
+//!     .fields(&task)
+//!     .kv_block_end()
+//!     .build()
+//! // **id:** 7
+//! // **title:** Write docs
+//! // **owner:** GABC...
+//! ```
+//!
+//! # Limits
+//!
+//! Every listed field must implement `RenderField`, which covers `u32`,
+//! `i128`, `bool`, `String`, and `Address`. Nested structs, `Option`, and
+//! collections are not supported; render those fields by hand.
+
+use crate::markdown::MarkdownBuilder;
+use soroban_sdk::{Address, String};
+
+/// A value that can be rendered as a labelled key-value line.
+pub trait RenderField {
+    /// Append `**label:** value` to the builder.
+    fn render_field<'a>(&self, b: MarkdownBuilder<'a>, label: &str) -> MarkdownBuilder<'a>;
+}
+
+impl RenderField for u32 {
+    fn render_field<'a>(&self, b: MarkdownBuilder<'a>, label: &str) -> MarkdownBuilder<'a> {
+        b.kv_u32(label, *self)
+    }
+}
+
+impl RenderField for i128 {
+    fn render_field<'a>(&self, b: MarkdownBuilder<'a>, label: &str) -> MarkdownBuilder<'a> {
+        b.kv_i128(label, *self)
+    }
+}
+
+impl RenderField for bool {
+    fn render_field<'a>(&self, b: MarkdownBuilder<'a>, label: &str) -> MarkdownBuilder<'a> {
+        b.kv_bool(label, *self)
+    }
+}
+
+impl RenderField for String {
+    fn render_field<'a>(&self, b: MarkdownBuilder<'a>, label: &str) -> MarkdownBuilder<'a> {
+        b.kv_string(label, self)
+    }
+}
+
+impl RenderField for Address {
+    fn render_field<'a>(&self, b: MarkdownBuilder<'a>, label: &str) -> MarkdownBuilder<'a> {
+        b.kv_address(label, self)
+    }
+}
+
+/// A struct that renders its fields as key-value lines.
+///
+/// Usually implemented with `render_fields!`.
+pub trait RenderFields {
+    /// Append one key-value line per field to the builder.
+    fn render_fields<'a>(&self, b: MarkdownBuilder<'a>) -> MarkdownBuilder<'a>;
+}
+
+impl<'a> MarkdownBuilder<'a> {
+    /// Add a key-value line for each field of `value`.
+    ///
+    /// Wrap in `kv_block_start()`/`kv_block_end()` for styling.
+    pub fn fields(self, value: &impl RenderFields) -> Self {
+        value.render_fields(self)
+    }
+}
+
+/// Implement `RenderFields` for a struct, labelling each field by name.
+///
+/// Fields are rendered in the order listed, and fields that are left out
+/// are skipped.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// render_fields!(Task { id, title, owner });
+/// ```
+#[macro_export]
+macro_rules! render_fields {
+    ($ty:ty { $($field:ident),+ $(,)? }) => {
+        impl $crate::render_trait::RenderFields for $ty {
+            fn render_fields<'a>(
+                &self,
+                b: $crate::markdown::MarkdownBuilder<'a>,
+            ) -> $crate::markdown::MarkdownBuilder<'a> {
+                $(let b = $crate::render_trait::RenderField::render_field(
+                    &self.$field,
+                    b,
+                    ::core::stringify!($field),
+                );)+
+                b
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bytes::address_to_bytes;
+    use crate::testutils::to_str;
+    use soroban_sdk::{Env, contracttype, testutils::Address as _};
+
+    extern crate alloc;
+
+    #[contracttype]
+    pub struct Task {
+        pub id: u32,
+        pub title: String,
+        pub owner: Address,
+        pub done: bool,
+    }
+
+    render_fields!(Task { id, title, owner });
+
+    #[test]
+    fn test_render_fields() {
+        let env = Env::default();
+        let owner = Address::generate(&env);
+        let task = Task {
+            id: 7,
+            title: String::from_str(&env, "Write docs"),
+            owner: owner.clone(),
+            done: false,
+        };
+        let output = MarkdownBuilder::new(&env)
+            .kv_block_start()
+            .fields(&task)
+            .kv_block_end()
+            .build();
+        let expected = alloc::format!(
+            "<div class=\"kv\">\n**id:** 7\n\n**title:** Write docs\n\n**owner:** {}\n\n</div>\n",
+            to_str(&address_to_bytes(&env, &owner))
+        );
+        assert_eq!(to_str(&output), expected);
+    }
+
+    struct Balance {
+        amount: i128,
+        frozen: bool,
+    }
+
+    render_fields!(Balance { frozen, amount });
+
+    #[test]
+    fn test_render_fields_order_and_types() {
+        let env = Env::default();
+        let balance = Balance {
+            amount: -250,
+            frozen: true,
+        };
+        let output = balance.render_fields(MarkdownBuilder::new(&env)).build();
+        assert_eq!(to_str(&output), "**frozen:** true\n\n**amount:** -250\n\n");
+    }
+}
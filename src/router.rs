@@ -8,6 +8,78 @@
 //! - Static segments: `/tasks` - exact match
 //! - Named parameters: `/users/{id}` - captures segment as variable
 //! - Wildcards: `/files/*` - captures remaining path
+//! - Named wildcards: `/files/{path*}` - captures remaining path as `path`
+//! - Mid-pattern wildcards: `/docs/*/edit` - captures segments between the
+//!   leading and trailing parts of the pattern
+//!
+//! A pattern may contain at most one wildcard. Wildcards match zero or more
+//! segments: `/files/*` matches `/files` with an empty capture, and `/*`
+//! matches `/`.
+//!
+//! # Mounting
+//!
+//! Routes sharing a common prefix can be grouped with `mount`/`or_mount`.
+//! The prefix is stripped and the remaining path is dispatched to a nested
+//! router. Parameters captured by the prefix remain available in the inner
+//! `Request`.
+//!
+//! ```rust,ignore
+//! Router::new(&env, path)
+//!     .handle(b"/", |_| render_home(&env))
+//!     .or_mount(b"/b/{board}", |r| {
+//!         r.handle(b"/", |req| render_board(&env, req.get_var_u32(b"board")))
+//!             .or_handle(b"/t/{thread}", |req| {
+//!                 let board = req.get_var_u32(b"board").unwrap_or(0);
+//!                 let thread = req.get_var_u32(b"thread").unwrap_or(0);
+//!                 render_thread(&env, board, thread)
+//!             })
+//!             .or_default(|_| render_not_found(&env))
+//!     })
+//!     .or_default(|_| render_home(&env))
+//! ```
+//!
+//! # Format Negotiation
+//!
+//! Calling `negotiate_format()` on a router strips a trailing `.json`, `.md`,
+//! or `.css` extension before matching and exposes it through
+//! `Request::format()`, so one route can serve several output formats.
+//!
+//! # Route Tables
+//!
+//! For contracts with many routes, `RouteTable` dispatches over a constant
+//! slice of `(pattern, fn)` pairs instead of a long `or_handle` chain.
+//!
+//! ```rust,ignore
+//! const ROUTES: &[(&[u8], RouteHandler<Bytes>)] = &[
+//!     (b"/", render_home),
+//!     (b"/task/{id}", render_task),
+//! ];
+//!
+//! RouteTable::new(ROUTES, render_not_found).dispatch(&env, path)
+//! ```
+//!
+//! # Advertising Routes
+//!
+//! `render_routes!` publishes a contract's paths as metadata. Declaring each
+//! pattern once as a `&str` const keeps the metadata and the router in sync:
+//!
+//! ```rust,ignore
+//! const HOME: &str = "/";
+//! const TASK: &str = "/task/{id}";
+//!
+//! soroban_render_sdk::render_routes!(HOME, TASK);
+//!
+//! Router::new(&env, path)
+//!     .handle(HOME.as_bytes(), |_| render_home(&env))
+//!     .or_handle(TASK.as_bytes(), |req| render_task(&env, req.get_var_u32(b"id").unwrap_or(0)))
+//!     .or_default(|_| render_home(&env))
+//! ```
+//!
+//! # Guards
+//!
+//! `handle_if`/`or_handle_if` take a guard closure that inspects the
+//! `Request`. If the guard rejects it, the route is treated as unmatched and
+//! routing continues with the next route or the default.
 //!
 //! # Example
 //!
@@ -25,8 +97,8 @@
 //! }
 //! ```
 
-use crate::bytes::string_to_bytes;
-use soroban_sdk::{Bytes, Env, String};
+use crate::bytes::{bytes_to_string, string_to_bytes};
+use soroban_sdk::{Address, Bytes, Env, String, Symbol, Vec};
 
 // ============================================================================
 // Path Utilities
@@ -45,23 +117,15 @@ pub fn path_to_bytes(env: &Env, path: &Option<String>) -> Bytes {
 /// For `/create?community=5`, returns (`/create`, Some(`community=5`)).
 /// For `/create`, returns (`/create`, None).
 pub fn split_path_and_query(env: &Env, full_path: &Bytes) -> (Bytes, Option<Bytes>) {
-    let mut path = Bytes::new(env);
-    let mut query = Bytes::new(env);
-    let mut in_query = false;
-
-    for i in 0..full_path.len() {
-        if let Some(b) = full_path.get(i) {
-            if b == b'?' && !in_query {
-                in_query = true;
-                continue; // Skip the '?' itself
-            }
-            if in_query {
-                query.push_back(b);
-            } else {
-                path.push_back(b);
-            }
+    // Slice at the first '?' instead of rebuilding each half byte by byte.
+    let len = full_path.len();
+    let (mut path, query) = match full_path.iter().position(|b| b == b'?') {
+        Some(q) => {
+            let q = q as u32;
+            (full_path.slice(0..q), full_path.slice(q + 1..len))
         }
-    }
+        None => (full_path.clone(), Bytes::new(env)),
+    };
 
     // Default to "/" if path is empty
     if path.is_empty() {
@@ -72,6 +136,59 @@ pub fn split_path_and_query(env: &Env, full_path: &Bytes) -> (Bytes, Option<Byte
     (path, query_opt)
 }
 
+/// Output format requested through a path extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RenderFormat {
+    /// `.md` extension.
+    Markdown,
+    /// `.json` extension.
+    Json,
+    /// `.css` extension.
+    Css,
+    /// No recognized extension.
+    Unknown,
+}
+
+/// Split a trailing format extension off a path.
+///
+/// Only the last segment is examined, so dots in earlier segments are left
+/// alone. Unrecognized extensions are not stripped.
+///
+/// - `/tasks.json` returns (`/tasks`, `Json`)
+/// - `/.md` returns (`/`, `Markdown`)
+/// - `/v1.2/tasks` returns (`/v1.2/tasks`, `Unknown`)
+pub fn split_format(env: &Env, path: &Bytes) -> (Bytes, RenderFormat) {
+    let mut dot = None;
+    for (i, b) in path.iter().enumerate() {
+        match b {
+            b'/' => dot = None,
+            b'.' => dot = Some(i as u32),
+            _ => {}
+        }
+    }
+
+    let Some(dot) = dot else {
+        return (path.clone(), RenderFormat::Unknown);
+    };
+
+    let ext = path.slice(dot + 1..path.len());
+    let format = if bytes_eq_slice(&ext, b"json") {
+        RenderFormat::Json
+    } else if bytes_eq_slice(&ext, b"md") {
+        RenderFormat::Markdown
+    } else if bytes_eq_slice(&ext, b"css") {
+        RenderFormat::Css
+    } else {
+        return (path.clone(), RenderFormat::Unknown);
+    };
+
+    let stripped = path.slice(0..dot);
+    if stripped.is_empty() {
+        return (Bytes::from_slice(env, b"/"), format);
+    }
+    (stripped, format)
+}
+
 /// Check if a path exactly equals a route pattern.
 ///
 /// Only works for simple static routes without parameters.
@@ -108,18 +225,13 @@ pub fn path_suffix(env: &Env, path: &Bytes, prefix: &[u8]) -> Bytes {
     if path.len() <= prefix_len {
         return Bytes::new(env);
     }
-    let mut result = Bytes::new(env);
-    for i in prefix_len..path.len() {
-        if let Some(b) = path.get(i) {
-            result.push_back(b);
-        }
-    }
-    result
+    path.slice(prefix_len..path.len())
 }
 
 /// Parse a numeric ID from a path with a given prefix.
 ///
 /// For example, given path "/task/123" and prefix "/task/", returns Some(123).
+/// Returns None if the ID does not fit in a u32.
 pub fn parse_id(path: &Bytes, prefix: &[u8]) -> Option<u32> {
     let prefix_len = prefix.len() as u32;
 
@@ -141,7 +253,7 @@ pub fn parse_id(path: &Bytes, prefix: &[u8]) -> Option<u32> {
         if let Some(b) = path.get(i) {
             if b.is_ascii_digit() {
                 has_digit = true;
-                result = result * 10 + (b - b'0') as u32;
+                result = result.checked_mul(10)?.checked_add((b - b'0') as u32)?;
             } else if b == b'/' {
                 // Stop at next path segment
                 break;
@@ -162,22 +274,23 @@ pub fn parse_id(path: &Bytes, prefix: &[u8]) -> Option<u32> {
 /// A request context containing path information and the matched pattern.
 ///
 /// Used to extract path parameters and query parameters within route handlers.
+///
+/// The pattern and path are split into segments once, when the request is
+/// created, so repeated `get_var` calls only walk the cached segments.
 pub struct Request<'a> {
     env: &'a Env,
     path: Bytes,
     query: Option<Bytes>,
-    handler_pattern: &'a [u8],
+    viewer: Option<Address>,
+    format: RenderFormat,
+    pattern_segments: Vec<Bytes>,
+    path_segments: Vec<Bytes>,
 }
 
 impl<'a> Request<'a> {
     /// Create a new request.
     pub fn new(env: &'a Env, path: Bytes, handler_pattern: &'a [u8]) -> Self {
-        Self {
-            env,
-            path,
-            query: None,
-            handler_pattern,
-        }
+        Self::with_query(env, path, None, handler_pattern)
     }
 
     /// Create a new request with query string.
@@ -187,19 +300,41 @@ impl<'a> Request<'a> {
         query: Option<Bytes>,
         handler_pattern: &'a [u8],
     ) -> Self {
+        let pattern_segments = split_path(env, handler_pattern);
+        let path_segments = split_path_bytes(env, &path);
         Self {
             env,
             path,
             query,
-            handler_pattern,
+            viewer: None,
+            format: RenderFormat::Unknown,
+            pattern_segments,
+            path_segments,
         }
     }
 
     /// Get the path (without query string).
+    ///
+    /// Inside a mounted router this is the path relative to the mount prefix.
     pub fn path(&self) -> &Bytes {
         &self.path
     }
 
+    /// Get the viewer address, if the router was created with one.
+    ///
+    /// See `Router::new_with_viewer()`.
+    pub fn viewer(&self) -> Option<Address> {
+        self.viewer.clone()
+    }
+
+    /// Get the format requested by the path extension.
+    ///
+    /// Returns `RenderFormat::Unknown` unless the router was created with
+    /// `Router::negotiate_format()` and the path had a recognized extension.
+    pub fn format(&self) -> RenderFormat {
+        self.format
+    }
+
     /// Get the raw query string (everything after `?`).
     ///
     /// For path `/create?community=5&foo=bar`, returns Some(`community=5&foo=bar`).
@@ -216,37 +351,34 @@ impl<'a> Request<'a> {
     pub fn get_query_param(&self, key: &[u8]) -> Option<Bytes> {
         let query = self.query.as_ref()?;
 
-        // Parse key=value pairs separated by &
-        let mut current_key = Bytes::new(self.env);
-        let mut current_value = Bytes::new(self.env);
-        let mut in_value = false;
-
-        for i in 0..query.len() {
-            if let Some(b) = query.get(i) {
-                if b == b'=' && !in_value {
-                    in_value = true;
-                } else if b == b'&' {
-                    // Check if current key matches
-                    if bytes_eq_slice(&current_key, key) {
-                        return Some(current_value);
-                    }
-                    // Reset for next pair
-                    current_key = Bytes::new(self.env);
-                    current_value = Bytes::new(self.env);
-                    in_value = false;
-                } else if in_value {
-                    current_value.push_back(b);
-                } else {
-                    current_key.push_back(b);
+        // Parse key=value pairs separated by &, slicing each part out of
+        // the query rather than copying it byte by byte.
+        let len = query.len();
+        let mut key_start = 0u32;
+        let mut value_start: Option<u32> = None;
+
+        for i in 0..=len {
+            let b = if i < len {
+                query.get_unchecked(i)
+            } else {
+                b'&'
+            };
+            if b == b'=' && value_start.is_none() {
+                value_start = Some(i + 1);
+            } else if b == b'&' {
+                let key_end = value_start.map_or(i, |v| v - 1);
+                if bytes_eq_slice(&query.slice(key_start..key_end), key) {
+                    let value = match value_start {
+                        Some(v) => query.slice(v..i),
+                        None => Bytes::new(self.env),
+                    };
+                    return Some(value);
                 }
+                key_start = i + 1;
+                value_start = None;
             }
         }
 
-        // Check final pair
-        if bytes_eq_slice(&current_key, key) {
-            return Some(current_value);
-        }
-
         None
     }
 
@@ -268,54 +400,34 @@ impl<'a> Request<'a> {
     ///
     /// For pattern `/users/{id}` and path `/users/123`,
     /// `get_var(b"id")` returns `Some(Bytes("123"))`.
+    ///
+    /// Parameters captured by a mount prefix are also available.
     pub fn get_var(&self, key: &[u8]) -> Option<Bytes> {
-        // Split handler pattern and path into segments
-        let pattern_segments = split_path(self.env, self.handler_pattern);
-        let path_segments = split_path_bytes(self.env, &self.path);
+        let pattern_len = self.pattern_segments.len();
+        let path_len = self.path_segments.len();
+        let wildcard = wildcard_index(&self.pattern_segments);
 
         // Iterate through pattern segments looking for {key}
-        for (path_idx, pattern_seg) in pattern_segments.iter().enumerate() {
-            let path_idx = path_idx as u32;
-            if path_idx >= path_segments.len() {
-                break;
-            }
-
-            // Check if this is a parameter segment
-            if pattern_seg.len() > 2
-                && pattern_seg.get(0) == Some(b'{')
-                && pattern_seg.get(pattern_seg.len() - 1) == Some(b'}')
-            {
-                // Extract parameter name
-                let mut param_name = Bytes::new(self.env);
-                for i in 1..pattern_seg.len() - 1 {
-                    if let Some(b) = pattern_seg.get(i) {
-                        param_name.push_back(b);
-                    }
-                }
-
-                // Check if this matches the requested key
-                if bytes_eq_slice(&param_name, key)
-                    && let Some(path_seg) = path_segments.get(path_idx)
-                {
-                    return Some(path_seg);
+        for (i, pattern_seg) in self.pattern_segments.iter().enumerate() {
+            let i = i as u32;
+
+            // Wildcards answer to `*` and to their name, if they have one
+            if wildcard == Some(i) {
+                let named = pattern_seg.len() > 1
+                    && bytes_eq_slice(&pattern_seg.slice(1..pattern_seg.len() - 2), key);
+                if key != b"*" && !named {
+                    continue;
                 }
+                let end = path_len.checked_sub(pattern_len - i - 1)?;
+                return Some(join_segments(self.env, &self.path_segments, i, end));
             }
 
-            // Check for wildcard
-            if pattern_seg.len() == 1 && pattern_seg.get(0) == Some(b'*') {
-                // Return remaining path
-                let mut result = Bytes::new(self.env);
-                for i in path_idx..path_segments.len() {
-                    if let Some(seg) = path_segments.get(i) {
-                        if i > path_idx {
-                            result.push_back(b'/');
-                        }
-                        result.append(&seg);
-                    }
-                }
-                if bytes_eq_slice(&Bytes::from_slice(self.env, b"*"), key) {
-                    return Some(result);
-                }
+            // Check if this is a parameter segment matching the requested key
+            if is_param_segment(&pattern_seg)
+                && bytes_eq_slice(&pattern_seg.slice(1..pattern_seg.len() - 1), key)
+            {
+                let idx = aligned_index(i, wildcard, pattern_len, path_len)?;
+                return self.path_segments.get(idx);
             }
         }
 
@@ -323,12 +435,40 @@ impl<'a> Request<'a> {
     }
 
     /// Get a path parameter as a u32.
+    ///
+    /// Returns None if the value is not a number or does not fit in a u32.
     pub fn get_var_u32(&self, key: &[u8]) -> Option<u32> {
         let bytes = self.get_var(key)?;
         parse_bytes_as_u32(&bytes)
     }
 
-    /// Get the wildcard match (everything after *).
+    /// Get a path parameter as a u64.
+    pub fn get_var_u64(&self, key: &[u8]) -> Option<u64> {
+        let bytes = self.get_var(key)?;
+        parse_bytes_as_u64(&bytes)
+    }
+
+    /// Get a path parameter as a `String`.
+    ///
+    /// Useful when the parameter is a storage key held as `soroban_sdk::String`.
+    pub fn get_var_string(&self, key: &[u8]) -> Option<String> {
+        let bytes = self.get_var(key)?;
+        Some(bytes_to_string(self.env, &bytes))
+    }
+
+    /// Get a path parameter as a `Symbol`.
+    ///
+    /// Returns None if the value is longer than 32 characters or contains
+    /// characters outside `[a-zA-Z0-9_]`.
+    pub fn get_var_symbol(&self, key: &[u8]) -> Option<Symbol> {
+        let bytes = self.get_var(key)?;
+        parse_bytes_as_symbol(self.env, &bytes)
+    }
+
+    /// Get the wildcard match (the segments matched by `*` or `{name*}`).
+    ///
+    /// Returns an empty Bytes when the wildcard matched zero segments, and
+    /// None when the pattern has no wildcard.
     pub fn get_wildcard(&self) -> Option<Bytes> {
         self.get_var(b"*")
     }
@@ -347,6 +487,10 @@ pub struct Router<'a> {
     env: &'a Env,
     path: Bytes,
     query: Option<Bytes>,
+    viewer: Option<Address>,
+    format: RenderFormat,
+    mount_pattern: Bytes,
+    mount_path: Bytes,
 }
 
 impl<'a> Router<'a> {
@@ -356,11 +500,25 @@ impl<'a> Router<'a> {
     /// pattern matching. Use `Request::get_query_param()` to access them.
     pub fn new(env: &'a Env, path: Option<String>) -> Self {
         let full_path = path_to_bytes(env, &path);
-        let (path_only, query) = split_path_and_query(env, &full_path);
+        Self::from_bytes(env, full_path)
+    }
+
+    /// Create a new router that carries the viewer address.
+    ///
+    /// The viewer is available to every handler via `Request::viewer()`,
+    /// so handlers don't need to capture it separately.
+    ///
+    /// ```rust,ignore
+    /// pub fn render(env: Env, path: Option<String>, viewer: Option<Address>) -> Bytes {
+    ///     Router::new_with_viewer(&env, path, viewer)
+    ///         .handle(b"/profile", |req| render_profile(&env, req.viewer()))
+    ///         .or_default(|_| render_home(&env))
+    /// }
+    /// ```
+    pub fn new_with_viewer(env: &'a Env, path: Option<String>, viewer: Option<Address>) -> Self {
         Self {
-            env,
-            path: path_only,
-            query,
+            viewer,
+            ..Self::new(env, path)
         }
     }
 
@@ -373,6 +531,33 @@ impl<'a> Router<'a> {
             env,
             path: path_only,
             query,
+            viewer: None,
+            format: RenderFormat::Unknown,
+            mount_pattern: Bytes::new(env),
+            mount_path: Bytes::new(env),
+        }
+    }
+
+    /// Strip a trailing `.json`, `.md`, or `.css` extension before matching.
+    ///
+    /// The detected format is available to handlers via `Request::format()`,
+    /// so `/tasks` and `/tasks.json` can share one route.
+    ///
+    /// ```rust,ignore
+    /// Router::new(&env, path)
+    ///     .negotiate_format()
+    ///     .handle(b"/tasks", |req| match req.format() {
+    ///         RenderFormat::Json => render_tasks_json(&env),
+    ///         _ => render_tasks_markdown(&env),
+    ///     })
+    ///     .or_default(|_| render_home(&env))
+    /// ```
+    pub fn negotiate_format(self) -> Self {
+        let (path, format) = split_format(self.env, &self.path);
+        Self {
+            path,
+            format,
+            ..self
         }
     }
 
@@ -381,21 +566,48 @@ impl<'a> Router<'a> {
     where
         F: FnOnce(Request) -> T,
     {
-        if pattern_matches(self.env, &self.path, pattern) {
-            let req = Request::with_query(self.env, self.path.clone(), self.query.clone(), pattern);
-            RouterResult {
-                env: self.env,
-                path: self.path,
-                query: self.query,
-                result: Some(handler(req)),
-            }
-        } else {
-            RouterResult {
-                env: self.env,
-                path: self.path,
-                query: self.query,
-                result: None,
-            }
+        self.into_result().or_handle(pattern, handler)
+    }
+
+    /// Handle a route pattern only if `guard` accepts the request.
+    ///
+    /// See `RouterResult::or_handle_if()`.
+    pub fn handle_if<G, F, T>(self, pattern: &'a [u8], guard: G, handler: F) -> RouterResult<'a, T>
+    where
+        G: FnOnce(&Request) -> bool,
+        F: FnOnce(Request) -> T,
+    {
+        self.into_result().or_handle_if(pattern, guard, handler)
+    }
+
+    /// Mount a nested router under a path prefix. Returns a RouterResult for chaining.
+    ///
+    /// If the path starts with `prefix`, the prefix is stripped and `mount`
+    /// receives a router over the remaining path (`/` when nothing remains).
+    /// Named parameters in the prefix stay accessible via `Request::get_var()`
+    /// in the nested router's handlers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the prefix contains a wildcard.
+    pub fn mount<F, T>(self, prefix: &'a [u8], mount: F) -> RouterResult<'a, T>
+    where
+        F: FnOnce(Router<'a>) -> T,
+    {
+        self.into_result().or_mount(prefix, mount)
+    }
+
+    /// Convert into an unmatched RouterResult.
+    fn into_result<T>(self) -> RouterResult<'a, T> {
+        RouterResult {
+            env: self.env,
+            path: self.path,
+            query: self.query,
+            viewer: self.viewer,
+            format: self.format,
+            mount_pattern: self.mount_pattern,
+            mount_path: self.mount_path,
+            result: None,
         }
     }
 }
@@ -405,6 +617,10 @@ pub struct RouterResult<'a, T> {
     env: &'a Env,
     path: Bytes,
     query: Option<Bytes>,
+    viewer: Option<Address>,
+    format: RenderFormat,
+    mount_pattern: Bytes,
+    mount_path: Bytes,
     result: Option<T>,
 }
 
@@ -414,20 +630,79 @@ impl<'a, T> RouterResult<'a, T> {
     where
         F: FnOnce(Request) -> T,
     {
+        self.or_handle_if(pattern, |_| true, handler)
+    }
+
+    /// Try another route if no match yet, but only if `guard` accepts the request.
+    ///
+    /// When the guard returns false the route is treated as unmatched, so
+    /// later routes or the default handler get a chance to handle the path.
+    ///
+    /// ```rust,ignore
+    /// Router::new(&env, path)
+    ///     .handle_if(b"/admin", |req| is_admin(&env, req.viewer()), |_| render_admin(&env))
+    ///     .or_handle(b"/admin", |_| render_not_authorized(&env))
+    ///     .or_default(|_| render_home(&env))
+    /// ```
+    pub fn or_handle_if<G, F>(self, pattern: &'a [u8], guard: G, handler: F) -> Self
+    where
+        G: FnOnce(&Request) -> bool,
+        F: FnOnce(Request) -> T,
+    {
+        if self.result.is_some() {
+            return self;
+        }
+
+        if !pattern_matches(self.env, &self.path, pattern) {
+            return self;
+        }
+
+        let req = self.request(pattern);
+        if !guard(&req) {
+            return self;
+        }
+        let result = Some(handler(req));
+        RouterResult { result, ..self }
+    }
+
+    /// Try a nested router under a path prefix if no match yet.
+    ///
+    /// See `Router::mount()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the prefix contains a wildcard.
+    pub fn or_mount<F>(self, prefix: &'a [u8], mount: F) -> Self
+    where
+        F: FnOnce(Router<'a>) -> T,
+    {
+        if prefix.contains(&b'*') {
+            panic!("Wildcards are not allowed in mount prefixes");
+        }
+
         if self.result.is_some() {
             return self;
         }
 
-        if pattern_matches(self.env, &self.path, pattern) {
-            let req = Request::with_query(self.env, self.path.clone(), self.query.clone(), pattern);
-            RouterResult {
-                env: self.env,
-                path: self.path,
-                query: self.query,
-                result: Some(handler(req)),
+        match strip_prefix_pattern(self.env, &self.path, prefix) {
+            Some((consumed, rest)) => {
+                let mut mount_pattern = self.mount_pattern.clone();
+                mount_pattern.append(&Bytes::from_slice(self.env, prefix));
+                let mut mount_path = self.mount_path.clone();
+                mount_path.append(&consumed);
+                let router = Router {
+                    env: self.env,
+                    path: rest,
+                    query: self.query.clone(),
+                    viewer: self.viewer.clone(),
+                    format: self.format,
+                    mount_pattern,
+                    mount_path,
+                };
+                let result = Some(mount(router));
+                RouterResult { result, ..self }
             }
-        } else {
-            self
+            None => self,
         }
     }
 
@@ -439,11 +714,89 @@ impl<'a, T> RouterResult<'a, T> {
         match self.result {
             Some(r) => r,
             None => {
-                let req = Request::with_query(self.env, self.path, self.query, b"");
+                let req = self.request(b"");
                 handler(req)
             }
         }
     }
+
+    /// Build the Request for a matched pattern, including any mount prefix.
+    fn request(&self, pattern: &'a [u8]) -> Request<'a> {
+        let mut pattern_segments = split_path_bytes(self.env, &self.mount_pattern);
+        pattern_segments.append(&split_path(self.env, pattern));
+        let mut path_segments = split_path_bytes(self.env, &self.mount_path);
+        path_segments.append(&split_path_bytes(self.env, &self.path));
+        Request {
+            env: self.env,
+            path: self.path.clone(),
+            query: self.query.clone(),
+            viewer: self.viewer.clone(),
+            format: self.format,
+            pattern_segments,
+            path_segments,
+        }
+    }
+}
+
+// ============================================================================
+// Route Table
+// ============================================================================
+
+/// A route handler stored as a plain function pointer.
+pub type RouteHandler<T> = fn(&Env, Request) -> T;
+
+/// A declarative table of routes dispatched with first-match-wins semantics.
+///
+/// Unlike the closure-chained `Router`, routes are `(pattern, fn)` pairs held
+/// in a slice, so tables can be declared as constants, split across modules,
+/// and dispatched without generating a nested closure type per route.
+///
+/// ```rust,ignore
+/// const ROUTES: &[(&[u8], RouteHandler<Bytes>)] = &[
+///     (b"/", render_home),
+///     (b"/task/{id}", render_task),
+/// ];
+///
+/// fn render_task(env: &Env, req: Request) -> Bytes {
+///     let id = req.get_var_u32(b"id").unwrap_or(0);
+///     // ...
+/// }
+///
+/// pub fn render(env: Env, path: Option<String>, _viewer: Option<Address>) -> Bytes {
+///     RouteTable::new(ROUTES, render_not_found).dispatch(&env, path)
+/// }
+/// ```
+pub struct RouteTable<'a, T> {
+    routes: &'a [(&'a [u8], RouteHandler<T>)],
+    fallback: RouteHandler<T>,
+}
+
+impl<'a, T> RouteTable<'a, T> {
+    /// Create a route table with a fallback for unmatched paths.
+    pub const fn new(routes: &'a [(&'a [u8], RouteHandler<T>)], fallback: RouteHandler<T>) -> Self {
+        Self { routes, fallback }
+    }
+
+    /// Dispatch an optional path to the first matching route.
+    pub fn dispatch(&self, env: &Env, path: Option<String>) -> T {
+        self.dispatch_router(Router::new(env, path))
+    }
+
+    /// Dispatch using an existing router.
+    ///
+    /// Use this to combine a table with `Router::new_with_viewer()` or
+    /// `Router::negotiate_format()`.
+    pub fn dispatch_router<'r>(&self, router: Router<'r>) -> T
+    where
+        'a: 'r,
+    {
+        let env = router.env;
+        let mut result = router.into_result();
+        for (pattern, handler) in self.routes.iter() {
+            result = result.or_handle(pattern, |req| handler(env, req));
+        }
+        result.or_default(|req| (self.fallback)(env, req))
+    }
 }
 
 // ============================================================================
@@ -455,87 +808,152 @@ impl<'a, T> RouterResult<'a, T> {
 /// Supports:
 /// - Static segments: `/tasks`
 /// - Named parameters: `/task/{id}`
-/// - Wildcards: `/files/*`
+/// - Wildcards: `/files/*`, `/files/{path*}`, `/docs/*/edit`
 fn pattern_matches(env: &Env, path: &Bytes, pattern: &[u8]) -> bool {
     let pattern_segments = split_path(env, pattern);
     let path_segments = split_path_bytes(env, path);
+    let pattern_len = pattern_segments.len();
+    let path_len = path_segments.len();
+    let wildcard = wildcard_index(&pattern_segments);
 
-    // Check for wildcard
-    let has_wildcard = pattern.contains(&b'*');
-
-    // If no wildcard, lengths must match
-    if !has_wildcard && pattern_segments.len() != path_segments.len() {
-        return false;
-    }
-
-    // If has wildcard, path must have at least as many segments (minus wildcard)
-    if has_wildcard && path_segments.len() < pattern_segments.len() - 1 {
-        return false;
+    match wildcard {
+        // If no wildcard, lengths must match
+        None if pattern_len != path_len => return false,
+        // If has wildcard, path needs every non-wildcard segment; the wildcard
+        // itself may match zero segments, so `/*` matches `/`
+        Some(_) if path_len < pattern_len.saturating_sub(1) => return false,
+        _ => {}
     }
 
-    // Match each pattern segment
+    // Match each non-wildcard segment against its aligned path segment
     for (i, pattern_seg) in pattern_segments.iter().enumerate() {
-        // Wildcard matches rest
-        if pattern_seg.len() == 1 && pattern_seg.get(0) == Some(b'*') {
-            return true;
-        }
-
-        // Get corresponding path segment
-        if i as u32 >= path_segments.len() {
-            return false;
+        let i = i as u32;
+        if wildcard == Some(i) {
+            continue;
         }
-        let path_seg = match path_segments.get(i as u32) {
+        let path_seg = match aligned_index(i, wildcard, pattern_len, path_len)
+            .and_then(|idx| path_segments.get(idx))
+        {
             Some(s) => s,
             None => return false,
         };
 
-        // Parameter matches any segment
-        if pattern_seg.len() > 2
-            && pattern_seg.get(0) == Some(b'{')
-            && pattern_seg.get(pattern_seg.len() - 1) == Some(b'}')
-        {
-            continue;
-        }
-
-        // Static segment must match exactly
-        if pattern_seg.len() != path_seg.len() {
+        // Parameter matches any segment; static segment must match exactly
+        if !is_param_segment(&pattern_seg) && pattern_seg != path_seg {
             return false;
         }
-        for j in 0..pattern_seg.len() {
-            if pattern_seg.get(j) != path_seg.get(j) {
-                return false;
+    }
+
+    true
+}
+
+/// Check whether a pattern segment is a wildcard: `*` or `{name*}`.
+fn is_wildcard_segment(seg: &Bytes) -> bool {
+    if seg.len() == 1 {
+        return seg.get(0) == Some(b'*');
+    }
+    seg.len() > 3 && is_param_segment(seg) && seg.get(seg.len() - 2) == Some(b'*')
+}
+
+/// Find the index of the wildcard segment in a pattern, if any.
+fn wildcard_index(pattern_segments: &Vec<Bytes>) -> Option<u32> {
+    pattern_segments
+        .iter()
+        .position(|seg| is_wildcard_segment(&seg))
+        .map(|i| i as u32)
+}
+
+/// Map a pattern segment index to the path segment it matches.
+///
+/// Segments before the wildcard align from the start of the path; segments
+/// after it align from the end. Returns None if the path is too short.
+fn aligned_index(i: u32, wildcard: Option<u32>, pattern_len: u32, path_len: u32) -> Option<u32> {
+    match wildcard {
+        Some(w) if i > w => path_len.checked_sub(pattern_len - i),
+        _ => Some(i),
+    }
+}
+
+/// Join path segments `start..end` with `/`.
+fn join_segments(env: &Env, segments: &Vec<Bytes>, start: u32, end: u32) -> Bytes {
+    let mut result = Bytes::new(env);
+    for i in start..end {
+        if let Some(seg) = segments.get(i) {
+            if i > start {
+                result.push_back(b'/');
             }
+            result.append(&seg);
         }
     }
+    result
+}
 
-    true
+/// Match the leading segments of a path against a prefix pattern.
+///
+/// Returns `(consumed, rest)` where `consumed` is the part of the path matched
+/// by the prefix and `rest` is the remaining path (`/` if nothing remains).
+fn strip_prefix_pattern(env: &Env, path: &Bytes, prefix: &[u8]) -> Option<(Bytes, Bytes)> {
+    let prefix_segments = split_path(env, prefix);
+    let path_segments = split_path_bytes(env, path);
+
+    if path_segments.len() < prefix_segments.len() {
+        return None;
+    }
+
+    let mut consumed = Bytes::new(env);
+    for (i, prefix_seg) in prefix_segments.iter().enumerate() {
+        let path_seg = path_segments.get(i as u32)?;
+        if !is_param_segment(&prefix_seg) && prefix_seg != path_seg {
+            return None;
+        }
+        consumed.push_back(b'/');
+        consumed.append(&path_seg);
+    }
+
+    let mut rest = Bytes::new(env);
+    for i in prefix_segments.len()..path_segments.len() {
+        if let Some(seg) = path_segments.get(i) {
+            rest.push_back(b'/');
+            rest.append(&seg);
+        }
+    }
+    if rest.is_empty() {
+        rest.push_back(b'/');
+    }
+
+    Some((consumed, rest))
+}
+
+/// Check whether a pattern segment is a named parameter like `{id}`.
+fn is_param_segment(seg: &Bytes) -> bool {
+    seg.len() > 2 && seg.get(0) == Some(b'{') && seg.get(seg.len() - 1) == Some(b'}')
 }
 
 /// Split a path pattern (byte slice) into segments.
-fn split_path(env: &Env, path: &[u8]) -> soroban_sdk::Vec<Bytes> {
+fn split_path(env: &Env, path: &[u8]) -> Vec<Bytes> {
     split_path_bytes(env, &Bytes::from_slice(env, path))
 }
 
 /// Split a path (Bytes) into segments.
-fn split_path_bytes(env: &Env, path: &Bytes) -> soroban_sdk::Vec<Bytes> {
-    let mut segments = soroban_sdk::Vec::new(env);
-    let mut current = Bytes::new(env);
-
-    for i in 0..path.len() {
-        if let Some(b) = path.get(i) {
-            if b == b'/' {
-                if !current.is_empty() {
-                    segments.push_back(current);
-                    current = Bytes::new(env);
-                }
-            } else {
-                current.push_back(b);
+///
+/// Segments are taken as `slice` ranges of the input rather than rebuilt
+/// byte by byte.
+fn split_path_bytes(env: &Env, path: &Bytes) -> Vec<Bytes> {
+    let mut segments = Vec::new(env);
+    let mut start = 0u32;
+
+    for (i, b) in path.iter().enumerate() {
+        let i = i as u32;
+        if b == b'/' {
+            if i > start {
+                segments.push_back(path.slice(start..i));
             }
+            start = i + 1;
         }
     }
 
-    if !current.is_empty() {
-        segments.push_back(current);
+    if path.len() > start {
+        segments.push_back(path.slice(start..path.len()));
     }
 
     segments
@@ -554,49 +972,71 @@ fn bytes_eq_slice(bytes: &Bytes, slice: &[u8]) -> bool {
     true
 }
 
-/// Parse Bytes as an unsigned integer.
-fn parse_bytes_as_uint<T>(bytes: &Bytes) -> Option<T>
-where
-    T: From<u8> + core::ops::Mul<Output = T> + core::ops::Add<Output = T> + Copy,
-{
+/// Parse Bytes as an unsigned integer, rejecting values that overflow `T`.
+fn parse_bytes_as_uint<T: TryFrom<u64>>(bytes: &Bytes) -> Option<T> {
     if bytes.is_empty() {
         return None;
     }
 
-    let ten = T::from(10);
-    let mut result = T::from(0);
-    for i in 0..bytes.len() {
-        if let Some(b) = bytes.get(i) {
-            if b.is_ascii_digit() {
-                result = result * ten + T::from(b - b'0');
-            } else {
-                return None;
-            }
+    let mut result: u64 = 0;
+    for b in bytes.iter() {
+        if !b.is_ascii_digit() {
+            return None;
         }
+        result = result.checked_mul(10)?.checked_add((b - b'0') as u64)?;
+    }
+    T::try_from(result).ok()
+}
+
+/// Maximum length of a Soroban symbol.
+const MAX_SYMBOL_LEN: usize = 32;
+
+/// Parse bytes as a Symbol, validating length and charset instead of panicking.
+fn parse_bytes_as_symbol(env: &Env, bytes: &Bytes) -> Option<Symbol> {
+    let len = bytes.len() as usize;
+    if len > MAX_SYMBOL_LEN {
+        return None;
     }
-    Some(result)
+
+    let mut buf = [0u8; MAX_SYMBOL_LEN];
+    bytes.copy_into_slice(&mut buf[..len]);
+    if !buf[..len]
+        .iter()
+        .all(|b| b.is_ascii_alphanumeric() || *b == b'_')
+    {
+        return None;
+    }
+
+    // Charset was validated above, so this is always valid UTF-8
+    let s = core::str::from_utf8(&buf[..len]).ok()?;
+    Some(Symbol::new(env, s))
 }
 
 /// Parse Bytes as a u32.
-fn parse_bytes_as_u32(bytes: &Bytes) -> Option<u32> {
+///
+/// Returns None for empty input, non-digit characters, or values above `u32::MAX`.
+pub fn parse_bytes_as_u32(bytes: &Bytes) -> Option<u32> {
     parse_bytes_as_uint(bytes)
 }
 
 /// Parse Bytes as a u64.
-fn parse_bytes_as_u64(bytes: &Bytes) -> Option<u64> {
+///
+/// Returns None for empty input, non-digit characters, or values above `u64::MAX`.
+pub fn parse_bytes_as_u64(bytes: &Bytes) -> Option<u64> {
     parse_bytes_as_uint(bytes)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::testutils::assert_output_eq;
+    use soroban_sdk::testutils::Address as _;
 
     #[test]
     fn test_path_to_bytes_none() {
         let env = Env::default();
         let bytes = path_to_bytes(&env, &None);
-        assert_eq!(bytes.len(), 1);
-        assert_eq!(bytes.get(0), Some(b'/'));
+        assert_output_eq(&bytes, "/");
     }
 
     #[test]
@@ -604,7 +1044,7 @@ mod tests {
         let env = Env::default();
         let s = String::from_str(&env, "/tasks");
         let bytes = path_to_bytes(&env, &Some(s));
-        assert_eq!(bytes.len(), 6);
+        assert_output_eq(&bytes, "/tasks");
     }
 
     #[test]
@@ -630,7 +1070,7 @@ mod tests {
         let env = Env::default();
         let path = Bytes::from_slice(&env, b"/tasks/123");
         let suffix = path_suffix(&env, &path, b"/tasks/");
-        assert_eq!(suffix.len(), 3);
+        assert_output_eq(&suffix, "123");
     }
 
     #[test]
@@ -647,6 +1087,48 @@ mod tests {
         assert_eq!(parse_id(&path, b"/task/"), Some(0));
     }
 
+    #[test]
+    fn test_parse_id_overflow() {
+        let env = Env::default();
+        let max = Bytes::from_slice(&env, b"/task/4294967295");
+        assert_eq!(parse_id(&max, b"/task/"), Some(u32::MAX));
+        let over = Bytes::from_slice(&env, b"/task/4294967296");
+        assert_eq!(parse_id(&over, b"/task/"), None);
+        let long = Bytes::from_slice(&env, b"/task/99999999999999999999999999");
+        assert_eq!(parse_id(&long, b"/task/"), None);
+    }
+
+    #[test]
+    fn test_parse_bytes_as_u32_overflow() {
+        let env = Env::default();
+        let parse = |s: &[u8]| parse_bytes_as_u32(&Bytes::from_slice(&env, s));
+        assert_eq!(parse(b"4294967295"), Some(u32::MAX));
+        assert_eq!(parse(b"4294967296"), None);
+        assert_eq!(parse(b"99999999999"), None);
+        assert_eq!(parse(b"123456789012345678901234567890"), None);
+        assert_eq!(parse(b""), None);
+        assert_eq!(parse(b"12a"), None);
+    }
+
+    #[test]
+    fn test_parse_bytes_as_u64() {
+        let env = Env::default();
+        let parse = |s: &[u8]| parse_bytes_as_u64(&Bytes::from_slice(&env, s));
+        assert_eq!(parse(b"4294967296"), Some(4294967296));
+        assert_eq!(parse(b"18446744073709551615"), Some(u64::MAX));
+        assert_eq!(parse(b"18446744073709551616"), None);
+        assert_eq!(parse(b"123456789012345678901234567890"), None);
+    }
+
+    #[test]
+    fn test_get_var_u32_overflow() {
+        let env = Env::default();
+        let path = Bytes::from_slice(&env, b"/task/99999999999");
+        let req = Request::new(&env, path, b"/task/{id}");
+        assert_eq!(req.get_var_u32(b"id"), None);
+        assert_eq!(req.get_var_u64(b"id"), Some(99999999999));
+    }
+
     #[test]
     fn test_parse_id_no_match() {
         let env = Env::default();
@@ -691,10 +1173,7 @@ mod tests {
         let req = Request::new(&env, path, b"/users/{id}/posts");
         let id = req.get_var(b"id");
         assert!(id.is_some());
-        let id_bytes = id.unwrap();
-        assert_eq!(id_bytes.len(), 2);
-        assert_eq!(id_bytes.get(0), Some(b'4'));
-        assert_eq!(id_bytes.get(1), Some(b'2'));
+        assert_output_eq(&id.unwrap(), "42");
     }
 
     #[test]
@@ -705,6 +1184,219 @@ mod tests {
         assert_eq!(req.get_var_u32(b"id"), Some(123));
     }
 
+    #[test]
+    fn test_request_get_var_deep_path() {
+        let env = Env::default();
+        let path = Bytes::from_slice(&env, b"/org/7/team/42/member/alice/posts/9");
+        let req = Request::new(
+            &env,
+            path,
+            b"/org/{org}/team/{team}/member/{member}/posts/{post}",
+        );
+        assert_eq!(req.get_var_u32(b"org"), Some(7));
+        assert_eq!(req.get_var_u32(b"team"), Some(42));
+        assert_eq!(
+            req.get_var(b"member"),
+            Some(Bytes::from_slice(&env, b"alice"))
+        );
+        assert_eq!(req.get_var_u32(b"post"), Some(9));
+        assert_eq!(req.get_var(b"missing"), None);
+        // Repeated lookups return the same value
+        assert_eq!(req.get_var_u32(b"team"), Some(42));
+    }
+
+    #[test]
+    fn test_split_path_bytes() {
+        let env = Env::default();
+        let segments = split_path_bytes(&env, &Bytes::from_slice(&env, b"//a/bc//def/"));
+        assert_eq!(segments.len(), 3);
+        assert_eq!(segments.get(0), Some(Bytes::from_slice(&env, b"a")));
+        assert_eq!(segments.get(1), Some(Bytes::from_slice(&env, b"bc")));
+        assert_eq!(segments.get(2), Some(Bytes::from_slice(&env, b"def")));
+        assert_eq!(
+            split_path_bytes(&env, &Bytes::from_slice(&env, b"/")).len(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_request_get_var_string() {
+        let env = Env::default();
+        let path = Bytes::from_slice(&env, b"/profile/alice-smith");
+        let req = Request::new(&env, path, b"/profile/{name}");
+        assert_eq!(
+            req.get_var_string(b"name"),
+            Some(String::from_str(&env, "alice-smith"))
+        );
+        assert_eq!(req.get_var_string(b"missing"), None);
+    }
+
+    #[test]
+    fn test_request_get_var_symbol() {
+        let env = Env::default();
+        let path = Bytes::from_slice(&env, b"/config/max_items");
+        let req = Request::new(&env, path, b"/config/{key}");
+        assert_eq!(
+            req.get_var_symbol(b"key"),
+            Some(Symbol::new(&env, "max_items"))
+        );
+
+        // Longer than a short symbol but within the 32-char limit
+        let path = Bytes::from_slice(&env, b"/config/a_much_longer_symbol_name");
+        let req = Request::new(&env, path, b"/config/{key}");
+        assert_eq!(
+            req.get_var_symbol(b"key"),
+            Some(Symbol::new(&env, "a_much_longer_symbol_name"))
+        );
+    }
+
+    #[test]
+    fn test_request_get_var_symbol_invalid() {
+        let env = Env::default();
+
+        // Invalid charset
+        let path = Bytes::from_slice(&env, b"/config/not-valid");
+        let req = Request::new(&env, path, b"/config/{key}");
+        assert_eq!(req.get_var_symbol(b"key"), None);
+
+        // Too long (33 chars)
+        let path = Bytes::from_slice(&env, b"/config/abcdefghijklmnopqrstuvwxyz0123456");
+        let req = Request::new(&env, path, b"/config/{key}");
+        assert_eq!(req.get_var_symbol(b"key"), None);
+
+        // Missing
+        assert_eq!(req.get_var_symbol(b"other"), None);
+    }
+
+    #[test]
+    fn test_named_wildcard() {
+        let env = Env::default();
+        let path = Bytes::from_slice(&env, b"/files/docs/readme.md");
+        assert!(pattern_matches(&env, &path, b"/files/{path*}"));
+        let req = Request::new(&env, path, b"/files/{path*}");
+        assert_eq!(
+            req.get_var(b"path"),
+            Some(Bytes::from_slice(&env, b"docs/readme.md"))
+        );
+        // The anonymous wildcard key still works
+        assert_eq!(
+            req.get_wildcard(),
+            Some(Bytes::from_slice(&env, b"docs/readme.md"))
+        );
+    }
+
+    #[test]
+    fn test_named_wildcard_single_segment() {
+        let env = Env::default();
+        let path = Bytes::from_slice(&env, b"/files/readme.md");
+        let req = Request::new(&env, path, b"/files/{path*}");
+        assert_eq!(
+            req.get_var(b"path"),
+            Some(Bytes::from_slice(&env, b"readme.md"))
+        );
+    }
+
+    #[test]
+    fn test_named_wildcard_empty_rest() {
+        let env = Env::default();
+        let path = Bytes::from_slice(&env, b"/files");
+        assert!(pattern_matches(&env, &path, b"/files/{path*}"));
+        let req = Request::new(&env, path, b"/files/{path*}");
+        assert_eq!(req.get_var(b"path"), Some(Bytes::new(&env)));
+    }
+
+    #[test]
+    fn test_mid_pattern_wildcard() {
+        let env = Env::default();
+        let path = Bytes::from_slice(&env, b"/docs/guide/intro/edit");
+        assert!(pattern_matches(&env, &path, b"/docs/*/edit"));
+        assert!(!pattern_matches(&env, &path, b"/docs/*/view"));
+        assert!(!pattern_matches(
+            &env,
+            &Bytes::from_slice(&env, b"/other/guide/edit"),
+            b"/docs/*/edit"
+        ));
+
+        let req = Request::new(&env, path, b"/docs/*/edit");
+        assert_eq!(
+            req.get_wildcard(),
+            Some(Bytes::from_slice(&env, b"guide/intro"))
+        );
+    }
+
+    #[test]
+    fn test_mid_pattern_wildcard_with_params() {
+        let env = Env::default();
+        let path = Bytes::from_slice(&env, b"/b/3/a/b/c/rev/7");
+        let pattern = b"/b/{board}/{page*}/rev/{rev}";
+        assert!(pattern_matches(&env, &path, pattern));
+        let req = Request::new(&env, path, pattern);
+        assert_eq!(req.get_var_u32(b"board"), Some(3));
+        assert_eq!(
+            req.get_var(b"page"),
+            Some(Bytes::from_slice(&env, b"a/b/c"))
+        );
+        assert_eq!(req.get_var_u32(b"rev"), Some(7));
+    }
+
+    #[test]
+    fn test_mid_pattern_wildcard_empty() {
+        let env = Env::default();
+        let path = Bytes::from_slice(&env, b"/docs/edit");
+        assert!(pattern_matches(&env, &path, b"/docs/*/edit"));
+        assert!(!pattern_matches(
+            &env,
+            &Bytes::from_slice(&env, b"/docs"),
+            b"/docs/*/edit"
+        ));
+        let req = Request::new(&env, path, b"/docs/*/edit");
+        assert_eq!(req.get_wildcard(), Some(Bytes::new(&env)));
+    }
+
+    #[test]
+    fn test_wildcard_only_pattern_matches_root() {
+        let env = Env::default();
+        let root = Bytes::from_slice(&env, b"/");
+        assert!(pattern_matches(&env, &root, b"/*"));
+        assert!(pattern_matches(&env, &root, b"*"));
+        let req = Request::new(&env, root, b"/*");
+        assert_eq!(req.get_wildcard(), Some(Bytes::new(&env)));
+
+        let path = Bytes::from_slice(&env, b"/a/b");
+        assert!(pattern_matches(&env, &path, b"/*"));
+        let req = Request::new(&env, path, b"/*");
+        assert_eq!(req.get_wildcard(), Some(Bytes::from_slice(&env, b"a/b")));
+    }
+
+    #[test]
+    fn test_wildcard_matches_bare_prefix() {
+        let env = Env::default();
+        let path = Bytes::from_slice(&env, b"/files");
+        assert!(pattern_matches(&env, &path, b"/files/*"));
+        let req = Request::new(&env, path, b"/files/*");
+        assert_eq!(req.get_wildcard(), Some(Bytes::new(&env)));
+
+        // A shorter path still doesn't match
+        let root = Bytes::from_slice(&env, b"/");
+        assert!(!pattern_matches(&env, &root, b"/files/*"));
+    }
+
+    #[test]
+    fn test_wildcard_matches_nested_rest() {
+        let env = Env::default();
+        let path = Bytes::from_slice(&env, b"/files/a/b");
+        assert!(pattern_matches(&env, &path, b"/files/*"));
+        let req = Request::new(&env, path, b"/files/*");
+        assert_eq!(req.get_wildcard(), Some(Bytes::from_slice(&env, b"a/b")));
+    }
+
+    #[test]
+    fn test_get_wildcard_none_without_wildcard() {
+        let env = Env::default();
+        let req = Request::new(&env, Bytes::from_slice(&env, b"/files"), b"/files");
+        assert_eq!(req.get_wildcard(), None);
+    }
+
     #[test]
     fn test_request_get_wildcard() {
         let env = Env::default();
@@ -761,7 +1453,7 @@ mod tests {
         let env = Env::default();
         let full = Bytes::from_slice(&env, b"/create");
         let (path, query) = split_path_and_query(&env, &full);
-        assert_eq!(path.len(), 7);
+        assert_output_eq(&path, "/create");
         assert!(query.is_none());
     }
 
@@ -770,14 +1462,9 @@ mod tests {
         let env = Env::default();
         let full = Bytes::from_slice(&env, b"/create?community=5");
         let (path, query) = split_path_and_query(&env, &full);
-        // Path should be "/create"
-        assert_eq!(path.len(), 7);
-        assert_eq!(path.get(0), Some(b'/'));
-        assert_eq!(path.get(6), Some(b'e'));
-        // Query should be "community=5"
+        assert_output_eq(&path, "/create");
         assert!(query.is_some());
-        let q = query.unwrap();
-        assert_eq!(q.len(), 11);
+        assert_output_eq(&query.unwrap(), "community=5");
     }
 
     #[test]
@@ -785,11 +1472,9 @@ mod tests {
         let env = Env::default();
         let full = Bytes::from_slice(&env, b"/search?q=hello&page=2&sort=date");
         let (path, query) = split_path_and_query(&env, &full);
-        assert_eq!(path.len(), 7); // "/search"
+        assert_output_eq(&path, "/search");
         assert!(query.is_some());
-        let q = query.unwrap();
-        // "q=hello&page=2&sort=date" = 24 chars
-        assert_eq!(q.len(), 24);
+        assert_output_eq(&query.unwrap(), "q=hello&page=2&sort=date");
     }
 
     #[test]
@@ -892,7 +1577,7 @@ mod tests {
         // Path with trailing ? but no query params
         let full = Bytes::from_slice(&env, b"/create?");
         let (path, query) = split_path_and_query(&env, &full);
-        assert_eq!(path.len(), 7); // "/create"
+        assert_output_eq(&path, "/create");
         assert!(query.is_none()); // Empty query should be None
     }
 
@@ -1031,6 +1716,108 @@ mod tests {
         assert_eq!(result, 18);
     }
 
+    // ========================================================================
+    // Mount Tests
+    // ========================================================================
+
+    #[test]
+    fn test_mount_strips_prefix() {
+        let env = Env::default();
+        let result = Router::new(&env, Some(String::from_str(&env, "/admin/users")))
+            .handle(b"/", |_| 1u32)
+            .or_mount(b"/admin", |r| {
+                r.handle(b"/", |_| 2u32)
+                    .or_handle(b"/users", |_| 3u32)
+                    .or_default(|_| 4u32)
+            })
+            .or_default(|_| 0u32);
+        assert_eq!(result, 3);
+    }
+
+    #[test]
+    fn test_mount_bare_prefix_is_root() {
+        let env = Env::default();
+        let result = Router::new(&env, Some(String::from_str(&env, "/admin")))
+            .mount(b"/admin", |r| {
+                r.handle(b"/", |req| req.path().len()).or_default(|_| 99u32)
+            })
+            .or_default(|_| 0u32);
+        assert_eq!(result, 1); // Inner path is "/"
+    }
+
+    #[test]
+    fn test_mount_no_match_falls_through() {
+        let env = Env::default();
+        let result = Router::new(&env, Some(String::from_str(&env, "/about")))
+            .mount(b"/admin", |r| r.handle(b"/", |_| 1u32).or_default(|_| 2u32))
+            .or_handle(b"/about", |_| 3u32)
+            .or_default(|_| 0u32);
+        assert_eq!(result, 3);
+    }
+
+    #[test]
+    fn test_mount_param_in_prefix() {
+        let env = Env::default();
+        let result = Router::new(&env, Some(String::from_str(&env, "/b/7")))
+            .mount(b"/b/{board}", |r| {
+                r.handle(b"/", |req| req.get_var_u32(b"board").unwrap_or(0))
+                    .or_default(|_| 0u32)
+            })
+            .or_default(|_| 0u32);
+        assert_eq!(result, 7);
+    }
+
+    #[test]
+    fn test_mount_two_levels_with_params() {
+        let env = Env::default();
+        let result = Router::new(&env, Some(String::from_str(&env, "/b/5/t/12/r/3?sort=new")))
+            .handle(b"/", |_| 0u32)
+            .or_mount(b"/b/{board}", |r| {
+                r.handle(b"/", |_| 1u32)
+                    .or_mount(b"/t/{thread}", |r| {
+                        r.handle(b"/", |_| 2u32)
+                            .or_handle(b"/r/{reply}", |req| {
+                                let board = req.get_var_u32(b"board").unwrap_or(0);
+                                let thread = req.get_var_u32(b"thread").unwrap_or(0);
+                                let reply = req.get_var_u32(b"reply").unwrap_or(0);
+                                let has_sort = req.get_query_param(b"sort").is_some();
+                                if has_sort {
+                                    board * 10000 + thread * 100 + reply
+                                } else {
+                                    0
+                                }
+                            })
+                            .or_default(|_| 3u32)
+                    })
+                    .or_default(|_| 4u32)
+            })
+            .or_default(|_| 5u32);
+        assert_eq!(result, 51203);
+    }
+
+    #[test]
+    fn test_mount_default_sees_prefix_params() {
+        let env = Env::default();
+        let result = Router::new(&env, Some(String::from_str(&env, "/b/9/unknown")))
+            .mount(b"/b/{board}", |r| {
+                r.handle(b"/", |_| 0u32)
+                    .or_default(|req| req.get_var_u32(b"board").unwrap_or(0))
+            })
+            .or_default(|_| 0u32);
+        assert_eq!(result, 9);
+    }
+
+    #[test]
+    #[should_panic(expected = "Wildcards are not allowed in mount prefixes")]
+    fn test_mount_rejects_wildcard_prefix() {
+        let env = Env::default();
+        Router::new(&env, Some(String::from_str(&env, "/files/a")))
+            .mount(b"/files/*", |r| {
+                r.handle(b"/", |_| 1u32).or_default(|_| 0u32)
+            })
+            .or_default(|_| 0u32);
+    }
+
     #[test]
     fn test_markdown_with_dynamic_number() {
         // Integration test: markdown with bytes utilities
@@ -1044,13 +1831,263 @@ mod tests {
             .raw(u32_to_bytes(&env, count))
             .build();
 
-        // Verify the output contains both parts
-        assert!(output.len() > 7);
-        // First 7 chars should be "Count: "
-        assert_eq!(output.get(0), Some(b'C'));
-        assert_eq!(output.get(6), Some(b' '));
-        // Next chars should be "42"
-        assert_eq!(output.get(7), Some(b'4'));
-        assert_eq!(output.get(8), Some(b'2'));
+        assert_output_eq(&output, "Count: 42");
+    }
+
+    #[test]
+    fn test_or_handle_if_same_pattern_different_guards() {
+        let env = Env::default();
+        let route = |p: &str| {
+            Router::new(&env, Some(String::from_str(&env, p)))
+                .handle_if(
+                    b"/admin/{id}",
+                    |req| req.get_var_u32(b"id") == Some(1),
+                    |_| 1,
+                )
+                .or_handle_if(
+                    b"/admin/{id}",
+                    |req| req.get_query_param(b"token").is_some(),
+                    |_| 2,
+                )
+                .or_handle(b"/admin/{id}", |_| 3)
+                .or_default(|_| 0)
+        };
+        assert_eq!(route("/admin/1"), 1);
+        assert_eq!(route("/admin/2?token=abc"), 2);
+        assert_eq!(route("/admin/2"), 3);
+        assert_eq!(route("/other"), 0);
+    }
+
+    #[test]
+    fn test_or_handle_if_guard_failure_falls_to_default() {
+        let env = Env::default();
+        let result = Router::new(&env, Some(String::from_str(&env, "/admin")))
+            .handle_if(b"/admin", |_| false, |_| 1)
+            .or_default(|req| {
+                assert_eq!(req.path(), &Bytes::from_slice(&env, b"/admin"));
+                2
+            });
+        assert_eq!(result, 2);
+    }
+
+    #[test]
+    fn test_or_handle_if_guard_not_called_without_match() {
+        let env = Env::default();
+        let result = Router::new(&env, Some(String::from_str(&env, "/home")))
+            .handle_if(b"/admin", |_| panic!("guard should not run"), |_| 1)
+            .or_default(|_| 2);
+        assert_eq!(result, 2);
+    }
+
+    #[test]
+    fn test_viewer_in_or_handle() {
+        let env = Env::default();
+        let viewer = Address::generate(&env);
+        let seen = Router::new_with_viewer(
+            &env,
+            Some(String::from_str(&env, "/profile")),
+            Some(viewer.clone()),
+        )
+        .handle(b"/", |_| None)
+        .or_handle(b"/profile", |req| req.viewer())
+        .or_default(|_| None);
+        assert_eq!(seen, Some(viewer));
+    }
+
+    #[test]
+    fn test_viewer_in_or_default() {
+        let env = Env::default();
+        let viewer = Address::generate(&env);
+        let seen = Router::new_with_viewer(
+            &env,
+            Some(String::from_str(&env, "/missing")),
+            Some(viewer.clone()),
+        )
+        .handle(b"/", |_| None)
+        .or_default(|req| req.viewer());
+        assert_eq!(seen, Some(viewer));
+    }
+
+    #[test]
+    fn test_viewer_in_mounted_router() {
+        let env = Env::default();
+        let viewer = Address::generate(&env);
+        let seen = Router::new_with_viewer(
+            &env,
+            Some(String::from_str(&env, "/admin/users")),
+            Some(viewer.clone()),
+        )
+        .mount(b"/admin", |r| {
+            r.handle(b"/users", |req| req.viewer()).or_default(|_| None)
+        })
+        .or_default(|_| None);
+        assert_eq!(seen, Some(viewer));
+    }
+
+    #[test]
+    fn test_viewer_none_by_default() {
+        let env = Env::default();
+        let seen = Router::new(&env, Some(String::from_str(&env, "/")))
+            .handle(b"/", |req| req.viewer())
+            .or_default(|_| None);
+        assert_eq!(seen, None);
+        let req = Request::new(&env, Bytes::from_slice(&env, b"/"), b"/");
+        assert_eq!(req.viewer(), None);
+    }
+
+    #[test]
+    fn test_split_format_no_extension() {
+        let env = Env::default();
+        let path = Bytes::from_slice(&env, b"/tasks");
+        assert_eq!(split_format(&env, &path), (path, RenderFormat::Unknown));
+    }
+
+    #[test]
+    fn test_split_format_extensions() {
+        let env = Env::default();
+        let cases: [(&[u8], &[u8], RenderFormat); 4] = [
+            (b"/tasks.json", b"/tasks", RenderFormat::Json),
+            (b"/tasks.md", b"/tasks", RenderFormat::Markdown),
+            (b"/theme.css", b"/theme", RenderFormat::Css),
+            (b"/task/5.json", b"/task/5", RenderFormat::Json),
+        ];
+        for (input, path, format) in cases {
+            let (p, f) = split_format(&env, &Bytes::from_slice(&env, input));
+            assert_eq!(p, Bytes::from_slice(&env, path));
+            assert_eq!(f, format);
+        }
+    }
+
+    #[test]
+    fn test_split_format_root() {
+        let env = Env::default();
+        let (path, format) = split_format(&env, &Bytes::from_slice(&env, b"/.json"));
+        assert_eq!(path, Bytes::from_slice(&env, b"/"));
+        assert_eq!(format, RenderFormat::Json);
+
+        let root = Bytes::from_slice(&env, b"/");
+        assert_eq!(split_format(&env, &root), (root, RenderFormat::Unknown));
+    }
+
+    #[test]
+    fn test_split_format_dots_in_middle_segments() {
+        let env = Env::default();
+        let path = Bytes::from_slice(&env, b"/v1.json/tasks");
+        assert_eq!(
+            split_format(&env, &path),
+            (path.clone(), RenderFormat::Unknown)
+        );
+
+        let (p, f) = split_format(&env, &Bytes::from_slice(&env, b"/v1.2/file.name.md"));
+        assert_eq!(p, Bytes::from_slice(&env, b"/v1.2/file.name"));
+        assert_eq!(f, RenderFormat::Markdown);
+
+        // Unrecognized extensions are left in place
+        let path = Bytes::from_slice(&env, b"/files/notes.txt");
+        assert_eq!(
+            split_format(&env, &path),
+            (path.clone(), RenderFormat::Unknown)
+        );
+    }
+
+    #[test]
+    fn test_negotiate_format() {
+        let env = Env::default();
+        let route = |p: &str| {
+            Router::new(&env, Some(String::from_str(&env, p)))
+                .negotiate_format()
+                .handle(b"/tasks/{id}", |req| (req.get_var_u32(b"id"), req.format()))
+                .or_default(|req| (None, req.format()))
+        };
+        assert_eq!(route("/tasks/3"), (Some(3), RenderFormat::Unknown));
+        assert_eq!(route("/tasks/3.json?x=1"), (Some(3), RenderFormat::Json));
+        assert_eq!(route("/.md"), (None, RenderFormat::Markdown));
+    }
+
+    #[test]
+    fn test_format_unknown_without_negotiation() {
+        let env = Env::default();
+        let format = Router::new(&env, Some(String::from_str(&env, "/files/readme.md")))
+            .handle(b"/files/*", |req| {
+                assert_eq!(
+                    req.get_wildcard(),
+                    Some(Bytes::from_slice(&env, b"readme.md"))
+                );
+                req.format()
+            })
+            .or_default(|_| panic!("should match"));
+        assert_eq!(format, RenderFormat::Unknown);
+    }
+
+    fn route_home(_env: &Env, _req: Request) -> u32 {
+        1
+    }
+
+    fn route_new_task(_env: &Env, _req: Request) -> u32 {
+        2
+    }
+
+    fn route_task(_env: &Env, req: Request) -> u32 {
+        100 + req.get_var_u32(b"id").unwrap_or(0)
+    }
+
+    fn route_not_found(_env: &Env, _req: Request) -> u32 {
+        0
+    }
+
+    const TEST_ROUTES: &[(&[u8], RouteHandler<u32>)] = &[
+        (b"/", route_home),
+        (b"/task/new", route_new_task),
+        (b"/task/{id}", route_task),
+        (b"/task/{other}", route_home),
+    ];
+
+    #[test]
+    fn test_route_table_priority() {
+        let env = Env::default();
+        let table = RouteTable::new(TEST_ROUTES, route_not_found);
+        assert_eq!(table.dispatch(&env, None), 1);
+        // Earlier static route wins over the later param route
+        assert_eq!(
+            table.dispatch(&env, Some(String::from_str(&env, "/task/new"))),
+            2
+        );
+    }
+
+    #[test]
+    fn test_route_table_params() {
+        let env = Env::default();
+        let table = RouteTable::new(TEST_ROUTES, route_not_found);
+        assert_eq!(
+            table.dispatch(&env, Some(String::from_str(&env, "/task/7"))),
+            107
+        );
+        assert_eq!(
+            table.dispatch(&env, Some(String::from_str(&env, "/task/7?tab=info"))),
+            107
+        );
+    }
+
+    #[test]
+    fn test_route_table_fallback() {
+        let env = Env::default();
+        let table = RouteTable::new(TEST_ROUTES, route_not_found);
+        assert_eq!(
+            table.dispatch(&env, Some(String::from_str(&env, "/missing"))),
+            0
+        );
+        assert_eq!(
+            RouteTable::new(&[], route_not_found).dispatch(&env, None),
+            0
+        );
+    }
+
+    #[test]
+    fn test_route_table_with_router() {
+        let env = Env::default();
+        let table = RouteTable::new(TEST_ROUTES, route_not_found);
+        let router =
+            Router::new(&env, Some(String::from_str(&env, "/task/9.json"))).negotiate_format();
+        assert_eq!(table.dispatch_router(router), 109);
     }
 }
@@ -0,0 +1,200 @@
+//! Output accumulator shared by the builders.
+//!
+//! By default builders collect output as a `Vec<Bytes>` of host objects and
+//! concatenate them at `build()`. With the `alloc` feature, builders created
+//! through `new_buffered` instead append into a single heap buffer and create
+//! one `Bytes` at the end, avoiding a host object per fragment.
+//!
+//! Test builds with `alloc` give `Sink::new` the buffered backend when
+//! `SOROBAN_RENDER_SINK=buffered` is set, so the builder tests can be run
+//! once against each backend.
+
+use crate::bytes::concat_bytes;
+use soroban_sdk::{Bytes, Env, Vec};
+
+/// Accumulator for builder output.
+///
+/// Tracks the total length and number of fragments as they are pushed, so
+/// builders can report their size without concatenating.
+pub(crate) struct Sink {
+    backend: Backend,
+    len: u32,
+    count: u32,
+}
+
+/// A position in a `Sink`, used to discard output written after it.
+#[cfg(feature = "markdown")]
+#[derive(Clone, Copy)]
+pub(crate) struct Mark {
+    len: u32,
+    count: u32,
+}
+
+/// Storage backend for a `Sink`.
+enum Backend {
+    /// One host `Bytes` object per fragment.
+    Parts(Vec<Bytes>),
+    /// A single growable guest buffer.
+    #[cfg(feature = "alloc")]
+    Buffer(alloc::vec::Vec<u8>),
+}
+
+impl Sink {
+    /// Create the default sink for a builder.
+    pub(crate) fn new(env: &Env) -> Self {
+        #[cfg(all(test, feature = "alloc"))]
+        if test_sink_is_buffered() {
+            return Self::buffered();
+        }
+        Self::parts(env)
+    }
+
+    /// Create a sink backed by a `Vec<Bytes>` of parts.
+    pub(crate) fn parts(env: &Env) -> Self {
+        Self::with_backend(Backend::Parts(Vec::new(env)))
+    }
+
+    /// Create a sink backed by a heap buffer.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn buffered() -> Self {
+        Self::with_backend(Backend::Buffer(alloc::vec::Vec::new()))
+    }
+
+    fn with_backend(backend: Backend) -> Self {
+        Self {
+            backend,
+            len: 0,
+            count: 0,
+        }
+    }
+
+    /// Append an existing Bytes value.
+    pub(crate) fn push_back(&mut self, bytes: Bytes) {
+        self.len += bytes.len();
+        self.count += 1;
+        match &mut self.backend {
+            Backend::Parts(parts) => parts.push_back(bytes),
+            #[cfg(feature = "alloc")]
+            Backend::Buffer(buf) => {
+                let start = buf.len();
+                buf.resize(start + bytes.len() as usize, 0);
+                bytes.copy_into_slice(&mut buf[start..]);
+            }
+        }
+    }
+
+    /// Append a byte slice.
+    pub(crate) fn push_slice(&mut self, env: &Env, bytes: &[u8]) {
+        self.len += bytes.len() as u32;
+        self.count += 1;
+        match &mut self.backend {
+            Backend::Parts(parts) => parts.push_back(Bytes::from_slice(env, bytes)),
+            #[cfg(feature = "alloc")]
+            Backend::Buffer(buf) => buf.extend_from_slice(bytes),
+        }
+    }
+
+    /// Total number of bytes pushed so far.
+    pub(crate) fn len(&self) -> u32 {
+        self.len
+    }
+
+    /// Number of fragments pushed so far.
+    pub(crate) fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Record the current position.
+    #[cfg(feature = "markdown")]
+    pub(crate) fn mark(&self) -> Mark {
+        Mark {
+            len: self.len,
+            count: self.count,
+        }
+    }
+
+    /// Discard everything pushed since `mark` was taken.
+    #[cfg(feature = "markdown")]
+    pub(crate) fn rewind(&mut self, mark: Mark) {
+        match &mut self.backend {
+            Backend::Parts(parts) => *parts = parts.slice(0..mark.count),
+            #[cfg(feature = "alloc")]
+            Backend::Buffer(buf) => buf.truncate(mark.len as usize),
+        }
+        self.len = mark.len;
+        self.count = mark.count;
+    }
+
+    /// Produce the accumulated output as a single Bytes value.
+    pub(crate) fn build(&self, env: &Env) -> Bytes {
+        match &self.backend {
+            Backend::Parts(parts) => concat_bytes(env, parts),
+            #[cfg(feature = "alloc")]
+            Backend::Buffer(buf) => Bytes::from_slice(env, buf),
+        }
+    }
+}
+
+/// Whether `SOROBAN_RENDER_SINK=buffered` selects the buffered backend.
+#[cfg(all(test, feature = "alloc"))]
+fn test_sink_is_buffered() -> bool {
+    extern crate std;
+    std::env::var("SOROBAN_RENDER_SINK").is_ok_and(|backend| backend == "buffered")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parts_sink() {
+        let env = Env::default();
+        let mut sink = Sink::parts(&env);
+        sink.push_slice(&env, b"Hello, ");
+        sink.push_back(Bytes::from_slice(&env, b"World!"));
+        assert_eq!(sink.build(&env), Bytes::from_slice(&env, b"Hello, World!"));
+        assert_eq!(sink.len(), 13);
+        assert_eq!(sink.count(), 2);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_buffered_sink() {
+        let env = Env::default();
+        let mut sink = Sink::buffered();
+        sink.push_slice(&env, b"Hello, ");
+        sink.push_back(Bytes::from_slice(&env, b"World!"));
+        sink.push_back(Bytes::new(&env));
+        assert_eq!(sink.build(&env), Bytes::from_slice(&env, b"Hello, World!"));
+        assert_eq!(sink.len(), 13);
+        assert_eq!(sink.count(), 3);
+    }
+
+    /// Push, rewind, and push again, checking only the rewound part is lost.
+    #[cfg(feature = "markdown")]
+    fn check_rewind(env: &Env, mut sink: Sink) {
+        sink.push_slice(env, b"keep ");
+        let mark = sink.mark();
+        sink.push_slice(env, b"drop");
+        sink.push_back(Bytes::from_slice(env, b" this"));
+        sink.rewind(mark);
+        sink.push_slice(env, b"end");
+        assert_eq!(sink.build(env), Bytes::from_slice(env, b"keep end"));
+        assert_eq!(sink.len(), 8);
+        assert_eq!(sink.count(), 2);
+    }
+
+    #[cfg(feature = "markdown")]
+    #[test]
+    fn test_parts_rewind() {
+        let env = Env::default();
+        check_rewind(&env, Sink::parts(&env));
+    }
+
+    #[cfg(all(feature = "markdown", feature = "alloc"))]
+    #[test]
+    fn test_buffered_rewind() {
+        let env = Env::default();
+        check_rewind(&env, Sink::buffered());
+    }
+}
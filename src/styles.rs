@@ -16,55 +16,428 @@
 //!     .build();
 //! ```
 
-use crate::bytes::concat_bytes;
-use soroban_sdk::{Bytes, Env, Vec};
+use crate::bytes::{BytesWriter, bytes_to_string, for_each_string_chunk, u32_to_bytes};
+use crate::sink::Sink;
+use soroban_sdk::{Bytes, Env, IntoVal, String, TryFromVal, Val, Vec};
+
+/// Default spacing scale in pixels, indexed by utility suffix (`.mt-0` ..
+/// `.mt-8`).
+pub const DEFAULT_SPACING_SCALE: [u32; 9] = [0, 4, 8, 12, 16, 20, 24, 32, 40];
+
+/// Default spacing utility prefixes as `(class prefix, CSS property)`.
+pub const DEFAULT_SPACING_PREFIXES: [(&str, &str); 11] = [
+    ("m", "margin"),
+    ("mt", "margin-top"),
+    ("mr", "margin-right"),
+    ("mb", "margin-bottom"),
+    ("ml", "margin-left"),
+    ("p", "padding"),
+    ("pt", "padding-top"),
+    ("pr", "padding-right"),
+    ("pb", "padding-bottom"),
+    ("pl", "padding-left"),
+    ("gap", "gap"),
+];
+
+/// Layout and typography utilities emitted by `utilities_layout`.
+const LAYOUT_UTILITIES: [(&str, &str); 16] = [
+    (".flex", "display: flex;"),
+    (".flex-col", "flex-direction: column;"),
+    (".flex-wrap", "flex-wrap: wrap;"),
+    (".items-center", "align-items: center;"),
+    (".justify-center", "justify-content: center;"),
+    (".justify-between", "justify-content: space-between;"),
+    (".grid", "display: grid;"),
+    (".block", "display: block;"),
+    (".hidden", "display: none;"),
+    (".w-full", "width: 100%;"),
+    (".text-sm", "font-size: 0.875rem;"),
+    (".text-base", "font-size: 1rem;"),
+    (".text-lg", "font-size: 1.125rem;"),
+    (".text-xl", "font-size: 1.25rem;"),
+    (".text-center", "text-align: center;"),
+    (".font-bold", "font-weight: bold;"),
+];
+
+/// A block opened by a `*_start` method and closed by the matching `*_end`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Block {
+    RootVars,
+    Rule,
+    AtRule,
+    Keyframes,
+    /// A `scope_start` scope, which emits no braces of its own.
+    Scope,
+}
+
+impl Block {
+    /// All blocks, indexed by their discriminant.
+    const ALL: [Block; 5] = [
+        Block::RootVars,
+        Block::Rule,
+        Block::AtRule,
+        Block::Keyframes,
+        Block::Scope,
+    ];
+
+    fn from_u32(value: u32) -> Self {
+        Self::ALL[value as usize]
+    }
+
+    /// Name used in panic messages.
+    fn name(self) -> &'static str {
+        match self {
+            Block::RootVars => "root_vars",
+            Block::Rule => "rule",
+            Block::AtRule => "media/supports/container",
+            Block::Keyframes => "keyframes",
+            Block::Scope => "scope",
+        }
+    }
+}
 
 /// A builder for constructing CSS stylesheets.
 ///
 /// Uses the `Vec<Bytes>` accumulator pattern internally for efficient
-/// string building in Soroban's no_std environment.
+/// string building in Soroban's no_std environment. With the `alloc`
+/// feature, `new_buffered` accumulates into a single heap buffer instead.
+///
+/// Blocks must be closed in reverse order of opening. Closing the wrong kind
+/// of block, or adding a property or keyframe outside its block, panics.
+/// Blocks still open at `build()` panic in debug builds and are closed
+/// automatically in release builds.
 pub struct StyleBuilder<'a> {
     env: &'a Env,
-    parts: Vec<Bytes>,
+    parts: Sink,
+    /// Fully resolved selector entries of the open scopes, innermost last.
+    scopes: Vec<Vec<Bytes>>,
+    /// Bytes emitted by `import_url`, which must precede everything else.
+    imports_len: u32,
+    /// Omit indentation, newlines, padding, and comments.
+    minify: bool,
+    /// Open blocks as `Block` discriminants, innermost last.
+    open_blocks: Vec<u32>,
+    /// `.namespace ` prefix added to every selector, set by `namespace`.
+    namespace: Option<Bytes>,
 }
 
 impl<'a> StyleBuilder<'a> {
     /// Create a new StyleBuilder.
     pub fn new(env: &'a Env) -> Self {
+        Self::with_sink(env, Sink::new(env))
+    }
+
+    /// Create a new StyleBuilder that accumulates into a heap buffer.
+    ///
+    /// Requires the `alloc` feature and an allocator.
+    #[cfg(feature = "alloc")]
+    pub fn new_buffered(env: &'a Env) -> Self {
+        Self::with_sink(env, Sink::buffered())
+    }
+
+    /// Create a builder writing to the given sink.
+    fn with_sink(env: &'a Env, parts: Sink) -> Self {
         Self {
             env,
-            parts: Vec::new(env),
+            parts,
+            scopes: Vec::new(env),
+            imports_len: 0,
+            minify: false,
+            open_blocks: Vec::new(env),
+            namespace: None,
+        }
+    }
+
+    /// Emit minified CSS.
+    ///
+    /// Builder methods skip the indentation, newlines, and padding they
+    /// normally add, and `comment()` and `newline()` emit nothing. Values
+    /// passed in by the caller, such as rule properties, are kept as given.
+    ///
+    /// Call before adding any output; doing otherwise panics in debug
+    /// builds.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// StyleBuilder::new(&env)
+    ///     .minify()
+    ///     .rule("h1", "margin: 0;")
+    ///     .build_minified()
+    /// // Output: h1{margin: 0;}
+    /// ```
+    pub fn minify(mut self) -> Self {
+        self.require_no_output("minify");
+        self.minify = true;
+        self
+    }
+
+    /// Nest every selector under a class.
+    ///
+    /// Every selector written by `rule`, `rule_start`, `scope_start`, the
+    /// pseudo-class helpers, and the utility helpers is prefixed with
+    /// `.namespace `, so the sheet cannot collide with styles merged from
+    /// another contract. Each entry of a selector list is prefixed, while
+    /// `:root` selectors, media conditions, and keyframe names are left as
+    /// given.
+    ///
+    /// Call before adding any output; doing otherwise panics in debug
+    /// builds.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// StyleBuilder::new(&env)
+    ///     .namespace("blog")
+    ///     .rule("h1, h2", "margin: 0;")
+    ///     .build()
+    /// // Output: .blog h1, .blog h2 { margin: 0; }
+    /// ```
+    pub fn namespace(mut self, namespace: &str) -> Self {
+        self.require_no_output("namespace");
+        let mut prefix = Bytes::from_slice(self.env, b".");
+        prefix.extend_from_slice(namespace.as_bytes());
+        prefix.push_back(b' ');
+        self.namespace = Some(prefix);
+        self
+    }
+
+    /// Panic in debug builds if output has already been written.
+    fn require_no_output(&self, method: &str) {
+        if cfg!(debug_assertions) && self.parts.len() > 0 {
+            panic!("{} must be called before adding any output", method);
         }
     }
 
+    // ========================================================================
+    // Themes
+    // ========================================================================
+
+    /// Create a builder pre-filled with the default theme.
+    ///
+    /// Emits these `:root` variables, a dark-mode block that overrides the
+    /// colors, and base rules for `body`, `h1`-`h3`, `a`, `button`, form
+    /// inputs, and tables that use them:
+    ///
+    /// | Variable | Default |
+    /// |----------|---------|
+    /// | `--primary` | `#0066cc` |
+    /// | `--bg` | `#ffffff` |
+    /// | `--fg` | `#1f2328` |
+    /// | `--muted` | `#656d76` |
+    /// | `--border` | `#d0d7de` |
+    /// | `--radius` | `6px` |
+    /// | `--space-1` .. `--space-5` | `0.25rem`, `0.5rem`, `1rem`, `1.5rem`, `2rem` |
+    /// | `--font` | system UI font stack |
+    ///
+    /// The builder is returned so rules appended afterwards override the
+    /// theme by source order.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// StyleBuilder::default_theme(&env)
+    ///     .rule(".proposal", "border: 1px solid var(--border);")
+    ///     .build()
+    /// ```
+    pub fn default_theme(env: &'a Env) -> Self {
+        Self::default_theme_with(env, "#0066cc", "#ffffff")
+    }
+
+    /// Create a builder with the default theme using a custom primary and
+    /// background color.
+    ///
+    /// The dark-mode block still replaces `--bg` for viewers that prefer a
+    /// dark color scheme; append a `dark_mode_start()` override to change it.
+    pub fn default_theme_with(env: &'a Env, primary: &str, bg: &str) -> Self {
+        Self::new(env)
+            .root_vars_start()
+            .var("primary", primary)
+            .var("bg", bg)
+            .var("fg", "#1f2328")
+            .var("muted", "#656d76")
+            .var("border", "#d0d7de")
+            .var("radius", "6px")
+            .var("space-1", "0.25rem")
+            .var("space-2", "0.5rem")
+            .var("space-3", "1rem")
+            .var("space-4", "1.5rem")
+            .var("space-5", "2rem")
+            .var("font", "system-ui, -apple-system, \"Segoe UI\", sans-serif")
+            .root_vars_end()
+            .rule(
+                "body",
+                "margin: 0; padding: var(--space-4); background: var(--bg); color: var(--fg); font-family: var(--font); line-height: 1.5;",
+            )
+            .rule(
+                "h1, h2, h3",
+                "margin: var(--space-4) 0 var(--space-2); line-height: 1.25;",
+            )
+            .rule("h1", "font-size: 2rem;")
+            .rule("h2", "font-size: 1.5rem;")
+            .rule("h3", "font-size: 1.25rem;")
+            .rule("a", "color: var(--primary); text-decoration: none;")
+            .rule("a:hover", "text-decoration: underline;")
+            .rule(
+                "button",
+                "padding: var(--space-2) var(--space-3); border: none; border-radius: var(--radius); background: var(--primary); color: #ffffff; cursor: pointer;",
+            )
+            .rule(
+                "input, textarea, select",
+                "padding: var(--space-2); border: 1px solid var(--border); border-radius: var(--radius); background: var(--bg); color: var(--fg); font: inherit;",
+            )
+            .rule("table", "width: 100%; border-collapse: collapse;")
+            .rule(
+                "th, td",
+                "padding: var(--space-2); border-bottom: 1px solid var(--border); text-align: left;",
+            )
+            .dark_mode_start()
+            .rule(
+                ":root",
+                "--bg: #0d1117; --fg: #e6edf3; --muted: #8d96a0; --border: #30363d;",
+            )
+            .media_end()
+    }
+
     // ========================================================================
     // Private Helpers
     // ========================================================================
 
     /// Push a byte slice to parts.
     fn push(&mut self, bytes: &[u8]) {
-        self.parts.push_back(Bytes::from_slice(self.env, bytes));
+        self.parts.push_slice(self.env, bytes);
+    }
+
+    /// Push `pretty` normally, or `compact` when minifying.
+    fn pad(&mut self, pretty: &[u8], compact: &[u8]) {
+        let bytes = if self.minify { compact } else { pretty };
+        if !bytes.is_empty() {
+            self.push(bytes);
+        }
     }
 
     /// Push a string to parts.
     fn push_str(&mut self, s: &str) {
-        self.parts
-            .push_back(Bytes::from_slice(self.env, s.as_bytes()));
+        self.parts.push_slice(self.env, s.as_bytes());
+    }
+
+    /// Push each selector in a comma-separated list followed by `suffix`,
+    /// prefixed with the namespace if the builder is scoped.
+    ///
+    /// `a, button` with `:hover` becomes `a:hover, button:hover`.
+    fn push_selector_suffixed(&mut self, selector: &str, suffix: &[u8]) {
+        for (i, item) in selector.split(',').enumerate() {
+            let item = item.trim();
+            if i > 0 {
+                self.pad(b", ", b",");
+            }
+            self.push_namespace(item);
+            self.push_str(item);
+            if !suffix.is_empty() {
+                self.push(suffix);
+            }
+        }
+    }
+
+    /// Push the namespace prefix for one selector if the builder is scoped.
+    ///
+    /// `:root` selectors are left global.
+    fn push_namespace(&mut self, item: &str) {
+        if let Some(prefix) = &self.namespace
+            && !item.starts_with(":root")
+        {
+            self.parts.push_back(prefix.clone());
+        }
+    }
+
+    /// Push a rule selector, namespaced if the builder is scoped.
+    fn push_selector(&mut self, selector: &str) {
+        if self.namespace.is_some() {
+            self.push_selector_suffixed(selector, b"");
+        } else {
+            self.push_str(selector);
+        }
+    }
+
+    /// Add an indented property line with an already built value.
+    fn indented_property_bytes(&mut self, prefix: &[u8], name: &str, value: Bytes) {
+        self.pad(b"  ", b"");
+        self.push(prefix);
+        self.push_str(name);
+        self.pad(b": ", b":");
+        self.parts.push_back(value);
+        self.pad(b";\n", b";");
     }
 
     /// Add an indented property line: `  prefix{name}: value;\n`
     fn indented_property(&mut self, prefix: &[u8], name: &str, value: &str) {
-        self.push(b"  ");
+        self.pad(b"  ", b"");
         self.push(prefix);
         self.push_str(name);
-        self.push(b": ");
+        self.pad(b": ", b":");
         self.push_str(value);
-        self.push(b";\n");
+        self.pad(b";\n", b";");
+    }
+
+    /// Push a u32 as decimal digits.
+    fn push_u32(&mut self, value: u32) {
+        self.parts.push_back(u32_to_bytes(self.env, value));
+    }
+
+    /// Add an indented numeric property line: `  prefix{name}: {value}{unit};\n`
+    fn indented_number(&mut self, prefix: &[u8], name: &str, value: u32, unit: &[u8]) {
+        self.pad(b"  ", b"");
+        self.push(prefix);
+        self.push_str(name);
+        self.pad(b": ", b":");
+        self.push_u32(value);
+        self.push(unit);
+        self.pad(b";\n", b";");
     }
 
     /// Close a block with `}\n`.
     fn close_block(&mut self) {
-        self.push(b"}\n");
+        self.pad(b"}\n", b"}");
+    }
+
+    /// Emit the end of `block`; a scope only drops its selectors.
+    fn close(&mut self, block: Block) {
+        if block == Block::Scope {
+            self.scopes.pop_back();
+        } else {
+            self.close_block();
+        }
+    }
+
+    /// Record a newly opened block.
+    fn open_block(&mut self, block: Block) {
+        self.open_blocks.push_back(block as u32);
+    }
+
+    /// Close `block`, which must be the innermost open block.
+    fn end_block(&mut self, block: Block, method: &str) {
+        match self.open_blocks.pop_back() {
+            Some(top) if top == block as u32 => self.close(block),
+            Some(top) => panic!(
+                "{} called while a {} block is open",
+                method,
+                Block::from_u32(top).name()
+            ),
+            None => panic!("{} called with no open block", method),
+        }
+    }
+
+    /// Panic unless the innermost open block is one of `blocks`.
+    fn require_open(&self, blocks: &[Block], method: &str) {
+        let open = self.open_blocks.last().map(Block::from_u32);
+        if !open.is_some_and(|open| blocks.contains(&open)) {
+            panic!("{} called outside a {} block", method, blocks[0].name());
+        }
+    }
+
+    /// Panic unless a rule or :root block is open, for declarations.
+    fn require_declaration_block(&self, method: &str) {
+        self.require_open(&[Block::Rule, Block::RootVars], method);
     }
 
     // ========================================================================
@@ -82,11 +455,26 @@ impl<'a> StyleBuilder<'a> {
     /// // Output: :root { --primary: #0066cc; }
     /// ```
     pub fn root_var(mut self, name: &str, value: &str) -> Self {
-        self.push(b":root { --");
+        self.pad(b":root { --", b":root{--");
         self.push_str(name);
-        self.push(b": ");
+        self.pad(b": ", b":");
         self.push_str(value);
-        self.push(b"; }\n");
+        self.pad(b"; }\n", b";}");
+        self
+    }
+
+    /// Add a single CSS variable in its own :root block from a stored String.
+    ///
+    /// Creates: `:root { --name: value; }`
+    ///
+    /// The value is sanitized with the same rules as `prop_string`.
+    pub fn root_var_string(mut self, name: &str, value: &String) -> Self {
+        let value = sanitized_value(self.env, value);
+        self.pad(b":root { --", b":root{--");
+        self.push_str(name);
+        self.pad(b": ", b":");
+        self.parts.push_back(value);
+        self.pad(b"; }\n", b";}");
         self
     }
 
@@ -96,7 +484,8 @@ impl<'a> StyleBuilder<'a> {
     ///
     /// Use with `.var()` and `.root_vars_end()`.
     pub fn root_vars_start(mut self) -> Self {
-        self.push(b":root {\n");
+        self.pad(b":root {\n", b":root{");
+        self.open_block(Block::RootVars);
         self
     }
 
@@ -106,15 +495,77 @@ impl<'a> StyleBuilder<'a> {
     ///
     /// Must be used between `.root_vars_start()` and `.root_vars_end()`.
     pub fn var(mut self, name: &str, value: &str) -> Self {
+        self.require_declaration_block("var");
         self.indented_property(b"--", name, value);
         self
     }
 
+    /// Add a CSS variable from a stored String within a :root block.
+    ///
+    /// Creates: `  --name: value;`, sanitized as in `prop_string`.
+    pub fn var_string(mut self, name: &str, value: &String) -> Self {
+        self.require_declaration_block("var_string");
+        let value = sanitized_value(self.env, value);
+        self.indented_property_bytes(b"--", name, value);
+        self
+    }
+
+    /// Add a pixel CSS variable within a :root block.
+    ///
+    /// Creates: `  --name: Npx;`
+    pub fn var_px(mut self, name: &str, value: u32) -> Self {
+        self.require_declaration_block("var_px");
+        self.indented_number(b"--", name, value, b"px");
+        self
+    }
+
+    /// Add a CSS variable derived from a base hex color.
+    ///
+    /// Creates: `  --name: #rrggbb;` (or `#rrggbbaa` for `ColorTransform::Alpha`)
+    ///
+    /// Must be used between `.root_vars_start()` and `.root_vars_end()`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// .root_vars_start()
+    ///     .var("primary", "#0066cc")
+    ///     .var_derived("primary-hover", "#0066cc", ColorTransform::Darken(15))
+    ///     .var_derived("primary-faint", "#0066cc", ColorTransform::Alpha(20))
+    /// .root_vars_end()
+    /// ```
+    pub fn var_derived(mut self, name: &str, base_hex: &str, transform: ColorTransform) -> Self {
+        self.require_declaration_block("var_derived");
+        self.pad(b"  --", b"--");
+        self.push_str(name);
+        self.pad(b": ", b":");
+        self.parts.push_back(transform.apply(self.env, base_hex));
+        self.pad(b";\n", b";");
+        self
+    }
+
+    /// Add a single :root variable derived from a base hex color.
+    ///
+    /// Creates: `:root { --name: #rrggbb; }`
+    pub fn root_var_derived(
+        mut self,
+        name: &str,
+        base_hex: &str,
+        transform: ColorTransform,
+    ) -> Self {
+        self.pad(b":root { --", b":root{--");
+        self.push_str(name);
+        self.pad(b": ", b":");
+        self.parts.push_back(transform.apply(self.env, base_hex));
+        self.pad(b"; }\n", b";}");
+        self
+    }
+
     /// End a :root block.
     ///
     /// Creates: `}`
     pub fn root_vars_end(mut self) -> Self {
-        self.close_block();
+        self.end_block(Block::RootVars, "root_vars_end");
         self
     }
 
@@ -133,10 +584,10 @@ impl<'a> StyleBuilder<'a> {
     /// // Output: h1 { color: blue; font-size: 2rem; }
     /// ```
     pub fn rule(mut self, selector: &str, properties: &str) -> Self {
-        self.push_str(selector);
-        self.push(b" { ");
+        self.push_selector(selector);
+        self.pad(b" { ", b"{");
         self.push_str(properties);
-        self.push(b" }\n");
+        self.pad(b" }\n", b"}");
         self
     }
 
@@ -146,8 +597,9 @@ impl<'a> StyleBuilder<'a> {
     ///
     /// Use with `.prop()` and `.rule_end()`.
     pub fn rule_start(mut self, selector: &str) -> Self {
-        self.push_str(selector);
-        self.push(b" {\n");
+        self.push_selector(selector);
+        self.pad(b" {\n", b"{");
+        self.open_block(Block::Rule);
         self
     }
 
@@ -157,15 +609,256 @@ impl<'a> StyleBuilder<'a> {
     ///
     /// Must be used between `.rule_start()` and `.rule_end()`.
     pub fn prop(mut self, property: &str, value: &str) -> Self {
+        self.require_declaration_block("prop");
         self.indented_property(b"", property, value);
         self
     }
 
+    /// Add a property from a stored String within a rule block.
+    ///
+    /// Creates: `  property: value;`
+    ///
+    /// Meant for values an admin can set, such as theme colors. Semicolons,
+    /// braces, angle brackets, line breaks, quotes, and backslashes are
+    /// stripped from the value, and comment markers are broken up, so it
+    /// cannot end the declaration, the rule it sits in, or an enclosing
+    /// `<style>` element, or comment out the rest of the sheet.
+    pub fn prop_string(mut self, property: &str, value: &String) -> Self {
+        self.require_declaration_block("prop_string");
+        let value = sanitized_value(self.env, value);
+        self.indented_property_bytes(b"", property, value);
+        self
+    }
+
+    /// Add a pixel property within a rule block.
+    ///
+    /// Creates: `  property: Npx;`
+    pub fn prop_px(mut self, property: &str, value: u32) -> Self {
+        self.require_declaration_block("prop_px");
+        self.indented_number(b"", property, value, b"px");
+        self
+    }
+
+    /// Add a percentage property within a rule block.
+    ///
+    /// Creates: `  property: N%;`
+    pub fn prop_percent(mut self, property: &str, value: u32) -> Self {
+        self.require_declaration_block("prop_percent");
+        self.indented_number(b"", property, value, b"%");
+        self
+    }
+
+    /// Add a rem property given in tenths of a rem.
+    ///
+    /// Creates: `  property: 1.5rem;` for 15, or `  property: 2rem;` for 20.
+    pub fn prop_rem_tenths(mut self, property: &str, tenths: u32) -> Self {
+        self.require_declaration_block("prop_rem_tenths");
+        self.pad(b"  ", b"");
+        self.push_str(property);
+        self.pad(b": ", b":");
+        self.push_u32(tenths / 10);
+        if !tenths.is_multiple_of(10) {
+            self.push(b".");
+            self.push_u32(tenths % 10);
+        }
+        self.pad(b"rem;\n", b"rem;");
+        self
+    }
+
+    /// Add a unitless numeric property within a rule block.
+    ///
+    /// Creates: `  property: N;` (e.g. `z-index`, `font-weight`)
+    pub fn prop_u32(mut self, property: &str, value: u32) -> Self {
+        self.require_declaration_block("prop_u32");
+        self.indented_number(b"", property, value, b"");
+        self
+    }
+
     /// End a rule block.
     ///
     /// Creates: `}`
     pub fn rule_end(mut self) -> Self {
-        self.close_block();
+        self.end_block(Block::Rule, "rule_end");
+        self
+    }
+
+    // ========================================================================
+    // Scoped (Nested) Rules
+    // ========================================================================
+
+    /// Resolve each entry of a selector list against the innermost open scope.
+    ///
+    /// Every entry is paired with every parent entry. Each `&` is replaced
+    /// by the parent; without one the parent is prepended as a descendant
+    /// combinator. Outside a scope the entries are only namespaced.
+    fn scoped_entries(&self, selector: &str) -> Vec<Bytes> {
+        let mut entries = Vec::new(self.env);
+        let Some(parents) = self.scopes.last() else {
+            for item in selector.split(',') {
+                let item = item.trim();
+                let mut entry = match &self.namespace {
+                    Some(prefix) if !item.starts_with(":root") => prefix.clone(),
+                    _ => Bytes::new(self.env),
+                };
+                entry.extend_from_slice(item.as_bytes());
+                entries.push_back(entry);
+            }
+            return entries;
+        };
+        for parent in parents.iter() {
+            for item in selector.split(',') {
+                let item = item.trim();
+                let mut entry = Bytes::new(self.env);
+                if item.contains('&') {
+                    for (i, piece) in item.split('&').enumerate() {
+                        if i > 0 {
+                            entry.append(&parent);
+                        }
+                        entry.extend_from_slice(piece.as_bytes());
+                    }
+                } else {
+                    entry.append(&parent);
+                    entry.push_back(b' ');
+                    entry.extend_from_slice(item.as_bytes());
+                }
+                entries.push_back(entry);
+            }
+        }
+        entries
+    }
+
+    /// Open a scope whose rules are nested under `parent_selector`.
+    ///
+    /// Scopes compose: a scope opened inside another resolves against it,
+    /// so `&` and descendant selectors work at every level. Nothing is
+    /// emitted until `scoped_rule` is called. Each entry of a selector list
+    /// is resolved separately, so `.a, .b` scopes rules under both.
+    ///
+    /// The scope is a block like any other: blocks opened inside it must be
+    /// closed before `scope_end`, and `build()` reports a scope left open.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// .scope_start(".reply")
+    ///     .scoped_rule(".author", "font-weight: bold;")
+    ///     .scoped_rule("&:hover", "background: #f5f5f5;")
+    /// .scope_end()
+    /// // Output:
+    /// // .reply .author { font-weight: bold; }
+    /// // .reply:hover { background: #f5f5f5; }
+    /// ```
+    pub fn scope_start(mut self, parent_selector: &str) -> Self {
+        let entries = self.scoped_entries(parent_selector);
+        self.scopes.push_back(entries);
+        self.open_block(Block::Scope);
+        self
+    }
+
+    /// Add a rule whose selector is resolved against the open scope.
+    ///
+    /// Creates: `parent child { properties }`, or `parent:hover { ... }`
+    /// for a child of `&:hover`. Outside a scope this behaves like `rule`.
+    pub fn scoped_rule(mut self, child_selector: &str, properties: &str) -> Self {
+        let entries = self.scoped_entries(child_selector);
+        for (i, entry) in entries.iter().enumerate() {
+            if i > 0 {
+                self.pad(b", ", b",");
+            }
+            self.parts.push_back(entry);
+        }
+        self.pad(b" { ", b"{");
+        self.push_str(properties);
+        self.pad(b" }\n", b"}");
+        self
+    }
+
+    /// Close the innermost scope.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the innermost open block is not a scope.
+    pub fn scope_end(mut self) -> Self {
+        self.end_block(Block::Scope, "scope_end");
+        self
+    }
+
+    // ========================================================================
+    // Pseudo-classes and Pseudo-elements
+    // ========================================================================
+
+    /// Add a rule for each selector in `selector` followed by a pseudo suffix.
+    fn pseudo_rule(&mut self, selector: &str, suffix: &[u8], properties: &str) {
+        self.push_selector_suffixed(selector, suffix);
+        self.pad(b" { ", b"{");
+        self.push_str(properties);
+        self.pad(b" }\n", b"}");
+    }
+
+    /// Add a `:hover` rule.
+    ///
+    /// Creates: `selector:hover { properties }`
+    ///
+    /// Like every pseudo-class helper, the suffix is added to each entry of
+    /// a selector list: `a, button` becomes `a:hover, button:hover`.
+    pub fn hover(mut self, selector: &str, properties: &str) -> Self {
+        self.pseudo_rule(selector, b":hover", properties);
+        self
+    }
+
+    /// Start a multi-line `:hover` rule block.
+    ///
+    /// Creates: `selector:hover {`
+    ///
+    /// Use with `.prop()` and `.rule_end()`.
+    pub fn hover_start(mut self, selector: &str) -> Self {
+        self.push_selector_suffixed(selector, b":hover");
+        self.pad(b" {\n", b"{");
+        self.open_block(Block::Rule);
+        self
+    }
+
+    /// Add a `:focus` rule.
+    ///
+    /// Creates: `selector:focus { properties }`
+    pub fn focus(mut self, selector: &str, properties: &str) -> Self {
+        self.pseudo_rule(selector, b":focus", properties);
+        self
+    }
+
+    /// Add an `:active` rule.
+    ///
+    /// Creates: `selector:active { properties }`
+    pub fn active(mut self, selector: &str, properties: &str) -> Self {
+        self.pseudo_rule(selector, b":active", properties);
+        self
+    }
+
+    /// Add a `::before` pseudo-element rule.
+    ///
+    /// Creates: `selector::before { properties }`
+    pub fn before(mut self, selector: &str, properties: &str) -> Self {
+        self.pseudo_rule(selector, b"::before", properties);
+        self
+    }
+
+    /// Add an `::after` pseudo-element rule.
+    ///
+    /// Creates: `selector::after { properties }`
+    pub fn after(mut self, selector: &str, properties: &str) -> Self {
+        self.pseudo_rule(selector, b"::after", properties);
+        self
+    }
+
+    /// Add an `:nth-child(n)` rule.
+    ///
+    /// Creates: `selector:nth-child(n) { properties }`
+    pub fn nth_child(mut self, selector: &str, n: u32, properties: &str) -> Self {
+        let (suffix, len) = nth_child_suffix(n);
+        self.push_selector_suffixed(selector, &suffix[..len]);
+        self.pad(b" { ", b"{");
+        self.push_str(properties);
+        self.pad(b" }\n", b"}");
         self
     }
 
@@ -189,7 +882,8 @@ impl<'a> StyleBuilder<'a> {
     pub fn media_start(mut self, condition: &str) -> Self {
         self.push(b"@media ");
         self.push_str(condition);
-        self.push(b" {\n");
+        self.pad(b" {\n", b"{");
+        self.open_block(Block::AtRule);
         self
     }
 
@@ -197,7 +891,7 @@ impl<'a> StyleBuilder<'a> {
     ///
     /// Creates: `}`
     pub fn media_end(mut self) -> Self {
-        self.close_block();
+        self.end_block(Block::AtRule, "media_end");
         self
     }
 
@@ -222,9 +916,9 @@ impl<'a> StyleBuilder<'a> {
     /// Creates: `@media (min-width: Npx) {`
     pub fn breakpoint_min(mut self, min_width: u32) -> Self {
         self.push(b"@media (min-width: ");
-        self.parts
-            .push_back(crate::bytes::u32_to_bytes(self.env, min_width));
-        self.push(b"px) {\n");
+        self.parts.push_back(u32_to_bytes(self.env, min_width));
+        self.pad(b"px) {\n", b"px){");
+        self.open_block(Block::AtRule);
         self
     }
 
@@ -233,71 +927,582 @@ impl<'a> StyleBuilder<'a> {
     /// Creates: `@media (max-width: Npx) {`
     pub fn breakpoint_max(mut self, max_width: u32) -> Self {
         self.push(b"@media (max-width: ");
-        self.parts
-            .push_back(crate::bytes::u32_to_bytes(self.env, max_width));
-        self.push(b"px) {\n");
+        self.parts.push_back(u32_to_bytes(self.env, max_width));
+        self.pad(b"px) {\n", b"px){");
+        self.open_block(Block::AtRule);
         self
     }
 
+    /// Start a print media query block.
+    ///
+    /// Creates: `@media print {`
+    pub fn print_start(self) -> Self {
+        self.media_start("print")
+    }
+
+    /// Start a block for viewers that prefer reduced motion.
+    ///
+    /// Creates: `@media (prefers-reduced-motion: reduce) {`
+    pub fn reduced_motion_start(self) -> Self {
+        self.media_start("(prefers-reduced-motion: reduce)")
+    }
+
     // ========================================================================
-    // Utilities
+    // Feature and Container Queries
     // ========================================================================
 
-    /// Add raw CSS string.
+    /// Start a feature query block.
     ///
-    /// Useful for complex selectors or CSS that doesn't fit the builder pattern.
-    pub fn raw(mut self, css: &str) -> Self {
-        self.push_str(css);
+    /// Creates: `@supports condition {`
+    ///
+    /// Close with `.block_end()` (or `.media_end()`).
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// .supports_start("(display: grid)")
+    ///     .rule(".cards", "display: grid;")
+    /// .block_end()
+    /// ```
+    pub fn supports_start(mut self, condition: &str) -> Self {
+        self.push(b"@supports ");
+        self.push_str(condition);
+        self.pad(b" {\n", b"{");
+        self.open_block(Block::AtRule);
         self
     }
 
-    /// Add a CSS comment.
+    /// Start a container query block.
     ///
-    /// Creates: `/* text */`
-    pub fn comment(mut self, text: &str) -> Self {
-        self.push(b"/* ");
-        self.push_str(text);
-        self.push(b" */\n");
+    /// Creates: `@container condition {`
+    ///
+    /// The condition may name a container, e.g. `"sidebar (min-width: 400px)"`.
+    pub fn container_query_start(mut self, condition: &str) -> Self {
+        self.push(b"@container ");
+        self.push_str(condition);
+        self.pad(b" {\n", b"{");
+        self.open_block(Block::AtRule);
         self
     }
 
-    /// Add a newline for formatting.
-    pub fn newline(mut self) -> Self {
-        self.push(b"\n");
+    /// End any at-rule block (`@media`, `@supports`, `@container`).
+    ///
+    /// Creates: `}`
+    pub fn block_end(mut self) -> Self {
+        self.end_block(Block::AtRule, "block_end");
         self
     }
 
     // ========================================================================
-    // Build
+    // Fonts and Imports
     // ========================================================================
 
-    /// Build the final CSS Bytes output.
-    pub fn build(self) -> Bytes {
-        concat_bytes(self.env, &self.parts)
-    }
+    /// Import an external stylesheet, such as a web font's CSS.
+    ///
+    /// Creates: `@import url("url");`
+    ///
+    /// # Panics
+    ///
+    /// CSS ignores `@import` after any other rule, so this panics if
+    /// anything other than another import has already been emitted.
+    pub fn import_url(mut self, url: &str) -> Self {
+        if self.parts.len() != self.imports_len {
+            panic!("import_url must be called before any other rules");
+        }
+        self.push(b"@import url(\"");
+        self.push_str(url);
+        self.pad(b"\");\n", b"\");");
+        self.imports_len = self.parts.len();
+        self
+    }
+
+    /// Declare a custom font served as WOFF2.
+    ///
+    /// Creates:
+    /// ```css
+    /// @font-face {
+    ///   font-family: "family";
+    ///   src: url("src_url") format("woff2");
+    ///   font-weight: weight;
+    ///   font-style: style;
+    /// }
+    /// ```
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// .font_face("Inter", "https://example.com/inter.woff2", "400", "normal")
+    /// .rule("body", "font-family: \"Inter\", sans-serif;")
+    /// ```
+    pub fn font_face(mut self, family: &str, src_url: &str, weight: &str, style: &str) -> Self {
+        self.push(b"@font-face");
+        self.pad(b" {\n  ", b"{");
+        self.push(b"font-family:");
+        self.pad(b" ", b"");
+        self.push(b"\"");
+        self.push_str(family);
+        self.push(b"\";");
+        self.pad(b"\n  ", b"");
+        self.push(b"src:");
+        self.pad(b" ", b"");
+        self.push(b"url(\"");
+        self.push_str(src_url);
+        self.push(b"\") format(\"woff2\");");
+        self.pad(b"\n", b"");
+        self.indented_property(b"", "font-weight", weight);
+        self.indented_property(b"", "font-style", style);
+        self.close_block();
+        self
+    }
+
+    // ========================================================================
+    // Animations
+    // ========================================================================
+
+    /// Start a `@keyframes` block.
+    ///
+    /// Creates: `@keyframes name {`
+    ///
+    /// Use with `.keyframe()` and `.keyframes_end()`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// .keyframes_start("spin")
+    ///     .keyframe("from", "transform: rotate(0deg);")
+    ///     .keyframe("to", "transform: rotate(360deg);")
+    /// .keyframes_end()
+    /// .animation(".spinner", "spin", 800, "linear", "infinite")
+    /// ```
+    pub fn keyframes_start(mut self, name: &str) -> Self {
+        self.push(b"@keyframes ");
+        self.push_str(name);
+        self.pad(b" {\n", b"{");
+        self.open_block(Block::Keyframes);
+        self
+    }
+
+    /// Add a keyframe within a `@keyframes` block.
+    ///
+    /// Creates: `  stop { properties }`
+    ///
+    /// The stop is a percentage such as `"50%"`, or `"from"` / `"to"`.
+    pub fn keyframe(mut self, stop: &str, properties: &str) -> Self {
+        self.require_open(&[Block::Keyframes], "keyframe");
+        self.pad(b"  ", b"");
+        self.push_str(stop);
+        self.pad(b" { ", b"{");
+        self.push_str(properties);
+        self.pad(b" }\n", b"}");
+        self
+    }
+
+    /// End a `@keyframes` block.
+    ///
+    /// Creates: `}`
+    pub fn keyframes_end(mut self) -> Self {
+        self.end_block(Block::Keyframes, "keyframes_end");
+        self
+    }
+
+    /// Add a rule applying a named animation to a selector.
+    ///
+    /// Creates: `selector { animation: name 500ms timing iteration; }`
+    ///
+    /// The duration is written in milliseconds so no precision is lost.
+    pub fn animation(
+        mut self,
+        selector: &str,
+        name: &str,
+        duration_ms: u32,
+        timing: &str,
+        iteration: &str,
+    ) -> Self {
+        self.push_selector(selector);
+        self.pad(b" { animation: ", b"{animation:");
+        self.push_str(name);
+        self.push(b" ");
+        self.parts.push_back(u32_to_bytes(self.env, duration_ms));
+        self.push(b"ms ");
+        self.push_str(timing);
+        self.push(b" ");
+        self.push_str(iteration);
+        self.pad(b"; }\n", b";}");
+        self
+    }
+
+    // ========================================================================
+    // Utility Classes
+    // ========================================================================
+
+    /// Emit margin, padding, and gap utility classes for a spacing scale.
+    ///
+    /// Creates one rule per prefix and scale step, such as
+    /// `.mt-3 { margin-top: 12px; }`, using `DEFAULT_SPACING_PREFIXES`.
+    /// The class suffix is the index into `scale_px`; pass
+    /// `&DEFAULT_SPACING_SCALE` for a 4px-based scale.
+    pub fn utilities_spacing(self, scale_px: &[u32]) -> Self {
+        self.utilities_spacing_with(scale_px, &DEFAULT_SPACING_PREFIXES)
+    }
+
+    /// Emit spacing utilities with custom `(class prefix, property)` pairs.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// .utilities_spacing_with(&[0, 8, 16], &[("space", "margin-bottom")])
+    /// // .space-0 { margin-bottom: 0px; } .space-1 { margin-bottom: 8px; } ...
+    /// ```
+    pub fn utilities_spacing_with(mut self, scale_px: &[u32], prefixes: &[(&str, &str)]) -> Self {
+        for (prefix, property) in prefixes {
+            for (step, px) in scale_px.iter().enumerate() {
+                self.push_namespace(prefix);
+                self.push(b".");
+                self.push_str(prefix);
+                self.push(b"-");
+                self.parts.push_back(u32_to_bytes(self.env, step as u32));
+                self.pad(b" { ", b"{");
+                self.push_str(property);
+                self.pad(b": ", b":");
+                self.parts.push_back(u32_to_bytes(self.env, *px));
+                self.pad(b"px; }\n", b"px;}");
+            }
+        }
+        self
+    }
+
+    /// Emit a fixed set of flex, grid, display, and text utility classes.
+    ///
+    /// Includes `.flex`, `.flex-col`, `.items-center`, `.justify-between`,
+    /// `.grid`, `.hidden`, `.text-sm` .. `.text-xl`, and `.font-bold`, among
+    /// others.
+    pub fn utilities_layout(self) -> Self {
+        let mut builder = self;
+        for (selector, properties) in LAYOUT_UTILITIES {
+            builder = builder.rule(selector, properties);
+        }
+        builder
+    }
+
+    // ========================================================================
+    // Utilities
+    // ========================================================================
+
+    /// Add raw CSS string.
+    ///
+    /// Useful for complex selectors or CSS that doesn't fit the builder pattern.
+    pub fn raw(mut self, css: &str) -> Self {
+        self.push_str(css);
+        self
+    }
+
+    /// Add a CSS comment.
+    ///
+    /// Creates: `/* text */`
+    pub fn comment(mut self, text: &str) -> Self {
+        if self.minify {
+            return self;
+        }
+        self.push(b"/* ");
+        self.push_str(text);
+        self.push(b" */\n");
+        self
+    }
+
+    /// Add a newline for formatting.
+    pub fn newline(mut self) -> Self {
+        self.pad(b"\n", b"");
+        self
+    }
+
+    // ========================================================================
+    // Introspection
+    // ========================================================================
+
+    /// Number of bytes `build()` would currently return.
+    ///
+    /// Tracked as content is added, so this is O(1).
+    pub fn approx_len(&self) -> u32 {
+        self.parts.len()
+    }
+
+    /// Check whether nothing has been added yet.
+    pub fn is_empty(&self) -> bool {
+        self.parts.len() == 0
+    }
+
+    /// Number of fragments accumulated so far.
+    pub fn part_count(&self) -> u32 {
+        self.parts.count()
+    }
+
+    // ========================================================================
+    // Combinators
+    // ========================================================================
+
+    /// Apply `f` only when `cond` is true.
+    pub fn when<F>(self, cond: bool, f: F) -> Self
+    where
+        F: FnOnce(Self) -> Self,
+    {
+        if cond { f(self) } else { self }
+    }
+
+    /// Apply `f` with the contained value when `opt` is `Some`.
+    pub fn when_some<T, F>(self, opt: Option<T>, f: F) -> Self
+    where
+        F: FnOnce(Self, T) -> Self,
+    {
+        match opt {
+            Some(value) => f(self, value),
+            None => self,
+        }
+    }
+
+    /// Apply `f` for each item in a soroban_sdk::Vec, in order.
+    pub fn each<T, F>(self, items: &Vec<T>, mut f: F) -> Self
+    where
+        T: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone,
+        F: FnMut(Self, T) -> Self,
+    {
+        let mut builder = self;
+        for item in items.iter() {
+            builder = f(builder, item);
+        }
+        builder
+    }
+
+    // ========================================================================
+    // Build
+    // ========================================================================
+
+    /// Build the final CSS Bytes output.
+    ///
+    /// Blocks left open panic in debug builds and are closed in release
+    /// builds.
+    pub fn build(mut self) -> Bytes {
+        if cfg!(debug_assertions)
+            && let Some(top) = self.open_blocks.last()
+        {
+            panic!(
+                "StyleBuilder built with an unclosed {} block",
+                Block::from_u32(top).name()
+            );
+        }
+        self.close_open_blocks();
+        self.parts.build(self.env)
+    }
+
+    /// Close every open block, innermost first.
+    fn close_open_blocks(&mut self) {
+        while let Some(top) = self.open_blocks.pop_back() {
+            self.close(Block::from_u32(top));
+        }
+    }
+
+    /// Build the final CSS output of a builder set up with `minify`.
+    ///
+    /// Panics in debug builds if `minify` was not called; release builds
+    /// return the output as written.
+    pub fn build_minified(self) -> Bytes {
+        if cfg!(debug_assertions) && !self.minify {
+            panic!("build_minified called without minify");
+        }
+        self.build()
+    }
+
+    /// Build the final CSS output as a String.
+    ///
+    /// Stylesheets over 16KB are replaced with a placeholder.
+    pub fn build_string(self) -> String {
+        let env = self.env;
+        bytes_to_string(env, &self.build())
+    }
+}
+
+/// Format `:nth-child(n)` into a stack buffer, returning it and its length.
+fn nth_child_suffix(n: u32) -> ([u8; 22], usize) {
+    let mut buf = [0u8; 22];
+    buf[..11].copy_from_slice(b":nth-child(");
+    let mut digits = [0u8; 10];
+    let mut count = 0;
+    let mut n = n;
+    loop {
+        digits[count] = b'0' + (n % 10) as u8;
+        count += 1;
+        n /= 10;
+        if n == 0 {
+            break;
+        }
+    }
+    let mut len = 11;
+    for &d in digits[..count].iter().rev() {
+        buf[len] = d;
+        len += 1;
+    }
+    buf[len] = b')';
+    (buf, len + 1)
+}
+
+/// Copy a stored String, dropping bytes that could escape a declaration.
+///
+/// Removes `;`, `{`, `}`, `<`, `>`, line breaks, quotes, and backslashes,
+/// the angle brackets so a value can't close a `<style>` element, and breaks
+/// up comment markers by dropping a `*` after `/` and a `/` after `*`.
+/// Checking against the last kept byte catches markers that only form once
+/// other bytes are removed, such as `/;*`.
+fn sanitized_value(env: &Env, value: &String) -> Bytes {
+    let mut out = BytesWriter::new(env);
+    let mut prev = 0u8;
+    for_each_string_chunk(env, value, |chunk| {
+        for &b in chunk {
+            let dropped = matches!(
+                b,
+                b';' | b'{' | b'}' | b'<' | b'>' | b'\n' | b'\r' | b'"' | b'\'' | b'\\'
+            ) || (b == b'*' && prev == b'/')
+                || (b == b'/' && prev == b'*');
+            if !dropped {
+                out.write_byte(b);
+                prev = b;
+            }
+        }
+    });
+    out.into_bytes()
+}
+
+// ============================================================================
+// Color Helpers
+// ============================================================================
+
+/// A transformation applied to a base color by `var_derived`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorTransform {
+    /// Mix toward white by the given percent (see `lighten_hex`).
+    Lighten(u32),
+    /// Mix toward black by the given percent (see `darken_hex`).
+    Darken(u32),
+    /// Set the opacity to the given percent (see `with_alpha`).
+    Alpha(u32),
+}
+
+impl ColorTransform {
+    /// Apply the transformation to a hex color.
+    pub fn apply(self, env: &Env, hex: &str) -> Bytes {
+        match self {
+            ColorTransform::Lighten(percent) => lighten_hex(env, hex, percent),
+            ColorTransform::Darken(percent) => darken_hex(env, hex, percent),
+            ColorTransform::Alpha(percent) => with_alpha(env, hex, percent),
+        }
+    }
+}
+
+/// Parse `#RRGGBB` or `#RGB` (the `#` is optional) into channels.
+fn parse_hex_color(hex: &str) -> Option<[u8; 3]> {
+    let digits = hex.as_bytes().strip_prefix(b"#").unwrap_or(hex.as_bytes());
+    let mut rgb = [0u8; 3];
+    match digits.len() {
+        6 => {
+            for (i, channel) in rgb.iter_mut().enumerate() {
+                *channel = hex_value(digits[i * 2])? << 4 | hex_value(digits[i * 2 + 1])?;
+            }
+        }
+        3 => {
+            for (i, channel) in rgb.iter_mut().enumerate() {
+                *channel = hex_value(digits[i])? * 0x11;
+            }
+        }
+        _ => return None,
+    }
+    Some(rgb)
+}
+
+fn hex_value(digit: u8) -> Option<u8> {
+    match digit {
+        b'0'..=b'9' => Some(digit - b'0'),
+        b'a'..=b'f' => Some(digit - b'a' + 10),
+        b'A'..=b'F' => Some(digit - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Write `#` followed by each channel as two lowercase hex digits.
+fn hex_color_bytes(env: &Env, channels: &[u8]) -> Bytes {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+    let mut buf = [0u8; 9];
+    buf[0] = b'#';
+    for (i, &channel) in channels.iter().enumerate() {
+        buf[1 + i * 2] = HEX[(channel >> 4) as usize];
+        buf[2 + i * 2] = HEX[(channel & 0x0f) as usize];
+    }
+    Bytes::from_slice(env, &buf[..1 + channels.len() * 2])
+}
+
+/// Map each channel of a parsed color, or return the input unchanged if it
+/// isn't a valid hex color.
+fn map_channels(env: &Env, hex: &str, f: impl Fn(u32) -> u32) -> Bytes {
+    match parse_hex_color(hex) {
+        Some(rgb) => {
+            let mapped = rgb.map(|c| f(c as u32).min(255) as u8);
+            hex_color_bytes(env, &mapped)
+        }
+        None => Bytes::from_slice(env, hex.as_bytes()),
+    }
+}
+
+/// Lighten a hex color by mixing it toward white.
+///
+/// Each channel moves `percent`% of the way to 255; `percent` is clamped to
+/// 100. Accepts `#RRGGBB` or `#RGB` and returns `#rrggbb`. Input that isn't
+/// a hex color is returned unchanged.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// lighten_hex(&env, "#0066cc", 20) // "#3384d6"
+/// ```
+pub fn lighten_hex(env: &Env, hex: &str, percent: u32) -> Bytes {
+    let percent = percent.min(100);
+    map_channels(env, hex, |c| c + (255 - c) * percent / 100)
+}
+
+/// Darken a hex color by mixing it toward black.
+///
+/// Each channel moves `percent`% of the way to 0, with the same input and
+/// output rules as `lighten_hex`.
+pub fn darken_hex(env: &Env, hex: &str, percent: u32) -> Bytes {
+    let percent = percent.min(100);
+    map_channels(env, hex, |c| c - c * percent / 100)
+}
+
+/// Add an alpha channel to a hex color.
+///
+/// Returns `#rrggbbaa` with the alpha byte set to `alpha_percent`% opacity
+/// (clamped to 100, rounded to the nearest step). Input that isn't a hex
+/// color is returned unchanged.
+pub fn with_alpha(env: &Env, hex: &str, alpha_percent: u32) -> Bytes {
+    match parse_hex_color(hex) {
+        Some([r, g, b]) => {
+            let alpha = ((alpha_percent.min(100) * 255 + 50) / 100) as u8;
+            hex_color_bytes(env, &[r, g, b, alpha])
+        }
+        None => Bytes::from_slice(env, hex.as_bytes()),
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::testutils::to_str;
     extern crate alloc;
 
     use super::*;
 
-    fn bytes_to_string(bytes: &Bytes) -> alloc::string::String {
-        let mut s = alloc::string::String::new();
-        for i in 0..bytes.len() {
-            s.push(bytes.get(i).unwrap() as char);
-        }
-        s
-    }
-
     #[test]
     fn test_root_var() {
         let env = Env::default();
         let output = StyleBuilder::new(&env)
             .root_var("primary", "#0066cc")
             .build();
-        let css = bytes_to_string(&output);
+        let css = to_str(&output);
         assert_eq!(css, ":root { --primary: #0066cc; }\n");
     }
 
@@ -310,7 +1515,7 @@ mod tests {
             .var("bg", "#ffffff")
             .root_vars_end()
             .build();
-        let css = bytes_to_string(&output);
+        let css = to_str(&output);
         assert!(css.contains(":root {\n"));
         assert!(css.contains("  --primary: #0066cc;\n"));
         assert!(css.contains("  --bg: #ffffff;\n"));
@@ -321,7 +1526,7 @@ mod tests {
     fn test_rule() {
         let env = Env::default();
         let output = StyleBuilder::new(&env).rule("h1", "color: blue;").build();
-        let css = bytes_to_string(&output);
+        let css = to_str(&output);
         assert_eq!(css, "h1 { color: blue; }\n");
     }
 
@@ -334,7 +1539,7 @@ mod tests {
             .prop("font-size", "2rem")
             .rule_end()
             .build();
-        let css = bytes_to_string(&output);
+        let css = to_str(&output);
         assert!(css.contains("h1 {\n"));
         assert!(css.contains("  color: blue;\n"));
         assert!(css.contains("  font-size: 2rem;\n"));
@@ -350,7 +1555,7 @@ mod tests {
             .rule_end()
             .media_end()
             .build();
-        let css = bytes_to_string(&output);
+        let css = to_str(&output);
         assert!(css.contains("@media (prefers-color-scheme: dark)"));
         assert!(css.contains("--bg: #1a1a1a;"));
     }
@@ -363,7 +1568,7 @@ mod tests {
             .rule(":root", "--bg: #ffffff;")
             .media_end()
             .build();
-        let css = bytes_to_string(&output);
+        let css = to_str(&output);
         assert!(css.contains("@media (prefers-color-scheme: light)"));
         assert!(css.contains("--bg: #ffffff;"));
     }
@@ -376,7 +1581,7 @@ mod tests {
             .rule("h1", "font-size: 2rem;")
             .media_end()
             .build();
-        let css = bytes_to_string(&output);
+        let css = to_str(&output);
         assert!(css.contains("@media (min-width: 768px)"));
         assert!(css.contains("font-size: 2rem;"));
     }
@@ -389,7 +1594,7 @@ mod tests {
             .rule("h1", "font-size: 1.5rem;")
             .media_end()
             .build();
-        let css = bytes_to_string(&output);
+        let css = to_str(&output);
         assert!(css.contains("@media (max-width: 767px)"));
         assert!(css.contains("font-size: 1.5rem;"));
     }
@@ -398,7 +1603,7 @@ mod tests {
     fn test_comment() {
         let env = Env::default();
         let output = StyleBuilder::new(&env).comment("Theme styles").build();
-        let css = bytes_to_string(&output);
+        let css = to_str(&output);
         assert_eq!(css, "/* Theme styles */\n");
     }
 
@@ -410,7 +1615,7 @@ mod tests {
             .newline()
             .comment("Section 2")
             .build();
-        let css = bytes_to_string(&output);
+        let css = to_str(&output);
         assert!(css.contains("*/\n\n/*"));
     }
 
@@ -419,7 +1624,7 @@ mod tests {
         let env = Env::default();
         let raw_css = ".complex > .selector:hover { opacity: 0.8; }";
         let output = StyleBuilder::new(&env).raw(raw_css).build();
-        let css = bytes_to_string(&output);
+        let css = to_str(&output);
         assert_eq!(css, raw_css);
     }
 
@@ -433,7 +1638,7 @@ mod tests {
             .root_vars_end()
             .rule("h1", "color: var(--primary);")
             .build();
-        let css = bytes_to_string(&output);
+        let css = to_str(&output);
         assert!(css.starts_with("/* Base theme */\n"));
         assert!(css.contains("--primary: #0066cc;"));
         assert!(css.contains("h1 { color: var(--primary); }"));
@@ -454,7 +1659,7 @@ mod tests {
             .rule_end()
             .media_end()
             .build();
-        let css = bytes_to_string(&output);
+        let css = to_str(&output);
 
         // Verify structure
         assert!(css.contains(":root {\n  --primary: #0066cc;"));
@@ -462,4 +1667,909 @@ mod tests {
         assert!(css.contains("@media (prefers-color-scheme: dark)"));
         assert!(css.contains("--bg: #1a1a1a;"));
     }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_buffered_budget() {
+        let env = Env::default();
+        // 75 rules of 4 fragments each = 300 parts
+        let build = |mut builder: StyleBuilder| {
+            for _ in 0..75 {
+                builder = builder.rule("a", "color: red;");
+            }
+            builder.build()
+        };
+
+        let mut budget = env.cost_estimate().budget();
+        budget.reset_unlimited();
+        let from_parts = build(StyleBuilder::with_sink(&env, Sink::parts(&env)));
+        let parts_cpu = budget.cpu_instruction_cost();
+
+        budget.reset_unlimited();
+        let from_buffer = build(StyleBuilder::new_buffered(&env));
+        let buffered_cpu = budget.cpu_instruction_cost();
+
+        assert_eq!(from_parts, from_buffer);
+        assert!(buffered_cpu < parts_cpu);
+    }
+
+    #[test]
+    fn test_combinators() {
+        let env = Env::default();
+        let mut sizes: Vec<u32> = Vec::new(&env);
+        sizes.push_back(600);
+        sizes.push_back(900);
+
+        let output = StyleBuilder::new(&env)
+            .when(true, |b| b.rule("a", "color: red;"))
+            .when_some(None::<&str>, |b, css| b.raw(css))
+            .each(&sizes, |b, px| b.breakpoint_min(px).media_end())
+            .build();
+        assert_eq!(
+            to_str(&output),
+            "a { color: red; }\n@media (min-width: 600px) {\n}\n@media (min-width: 900px) {\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_introspection() {
+        let env = Env::default();
+        assert!(StyleBuilder::new(&env).is_empty());
+
+        let builder = StyleBuilder::new(&env)
+            .root_var("primary", "#0066cc")
+            .rule("a", "color: var(--primary);")
+            .breakpoint_min(768)
+            .media_end();
+        assert!(!builder.is_empty());
+        assert!(builder.part_count() > 0);
+        let approx = builder.approx_len();
+        assert_eq!(approx, builder.build().len());
+    }
+
+    #[test]
+    fn test_build_string() {
+        let env = Env::default();
+        let s = StyleBuilder::new(&env)
+            .rule(".quote::before", "content: \"“\";")
+            .build_string();
+        assert_eq!(
+            s,
+            String::from_str(&env, ".quote::before { content: \"“\"; }\n")
+        );
+
+        let empty = StyleBuilder::new(&env).build_string();
+        assert_eq!(empty, String::from_str(&env, ""));
+    }
+
+    #[test]
+    fn test_keyframes_spin() {
+        let env = Env::default();
+        let output = StyleBuilder::new(&env)
+            .keyframes_start("spin")
+            .keyframe("from", "transform: rotate(0deg);")
+            .keyframe("to", "transform: rotate(360deg);")
+            .keyframes_end()
+            .animation(".spinner", "spin", 500, "linear", "infinite")
+            .build();
+        assert_eq!(
+            to_str(&output),
+            "@keyframes spin {\n  from { transform: rotate(0deg); }\n  to { transform: rotate(360deg); }\n}\n.spinner { animation: spin 500ms linear infinite; }\n"
+        );
+    }
+
+    #[test]
+    fn test_keyframes_percentages() {
+        let env = Env::default();
+        let output = StyleBuilder::new(&env)
+            .keyframes_start("fade")
+            .keyframe("0%", "opacity: 0;")
+            .keyframe("50%", "opacity: 0.5;")
+            .keyframe("100%", "opacity: 1;")
+            .keyframes_end()
+            .animation(".toast", "fade", 1500, "ease-in", "1")
+            .build();
+        let css = to_str(&output);
+        assert!(css.contains("  50% { opacity: 0.5; }\n"));
+        assert!(css.ends_with(".toast { animation: fade 1500ms ease-in 1; }\n"));
+    }
+
+    #[test]
+    fn test_pseudo_helpers() {
+        let env = Env::default();
+        let output = StyleBuilder::new(&env)
+            .hover("a", "color: red;")
+            .focus("input", "outline: none;")
+            .active(".btn", "opacity: 0.8;")
+            .before(".quote", "content: '\"';")
+            .after(".clearfix", "clear: both;")
+            .nth_child("tr", 2, "background: #eee;")
+            .build();
+        assert_eq!(
+            to_str(&output),
+            "a:hover { color: red; }\n\
+             input:focus { outline: none; }\n\
+             .btn:active { opacity: 0.8; }\n\
+             .quote::before { content: '\"'; }\n\
+             .clearfix::after { clear: both; }\n\
+             tr:nth-child(2) { background: #eee; }\n"
+        );
+    }
+
+    #[test]
+    fn test_hover_start_block() {
+        let env = Env::default();
+        let output = StyleBuilder::new(&env)
+            .hover_start(".card")
+            .prop("box-shadow", "0 2px 4px #0003")
+            .rule_end()
+            .build();
+        assert_eq!(
+            to_str(&output),
+            ".card:hover {\n  box-shadow: 0 2px 4px #0003;\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_scoped_rules() {
+        let env = Env::default();
+        let output = StyleBuilder::new(&env)
+            .scope_start(".reply")
+            .scoped_rule(".author", "font-weight: bold;")
+            .scoped_rule("&:hover", "background: #f5f5f5;")
+            .scope_start(".meta")
+            .scoped_rule(".timestamp", "color: gray;")
+            .scoped_rule("&.pinned", "color: gold;")
+            .scope_end()
+            .scoped_rule("& + &", "margin-top: 1rem;")
+            .scope_end()
+            .scoped_rule("p", "margin: 0;")
+            .build();
+        assert_eq!(
+            to_str(&output),
+            ".reply .author { font-weight: bold; }\n\
+             .reply:hover { background: #f5f5f5; }\n\
+             .reply .meta .timestamp { color: gray; }\n\
+             .reply .meta.pinned { color: gold; }\n\
+             .reply + .reply { margin-top: 1rem; }\n\
+             p { margin: 0; }\n"
+        );
+    }
+
+    #[test]
+    fn test_scope_with_ampersand_parent() {
+        let env = Env::default();
+        let output = StyleBuilder::new(&env)
+            .scope_start(".btn")
+            .scope_start("&:hover")
+            .scoped_rule(".icon", "opacity: 1;")
+            .scope_end()
+            .scope_end()
+            .build();
+        assert_eq!(to_str(&output), ".btn:hover .icon { opacity: 1; }\n");
+    }
+
+    #[test]
+    fn test_scope_resolves_each_list_entry() {
+        let env = Env::default();
+        let output = StyleBuilder::new(&env)
+            .scope_start(".reply")
+            .scoped_rule("a, b", "color: red;")
+            .scope_end()
+            .scope_start(".post, .comment")
+            .scoped_rule("&:hover, .meta", "opacity: 1;")
+            .scope_end()
+            .build();
+        assert_eq!(
+            to_str(&output),
+            ".reply a, .reply b { color: red; }\n\
+             .post:hover, .post .meta, .comment:hover, .comment .meta { opacity: 1; }\n"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "scope_end called with no open block")]
+    fn test_scope_end_without_start_panics() {
+        let env = Env::default();
+        StyleBuilder::new(&env).scope_end();
+    }
+
+    #[test]
+    #[should_panic(expected = "scope_end called while a rule block is open")]
+    fn test_scope_end_inside_rule_panics() {
+        let env = Env::default();
+        StyleBuilder::new(&env)
+            .scope_start(".reply")
+            .rule_start("p")
+            .scope_end();
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "StyleBuilder built with an unclosed scope block")]
+    fn test_build_with_unclosed_scope_panics() {
+        let env = Env::default();
+        StyleBuilder::new(&env)
+            .scope_start(".reply")
+            .scoped_rule("p", "margin: 0;")
+            .build();
+    }
+
+    #[test]
+    fn test_close_open_blocks_drops_scopes() {
+        let env = Env::default();
+        let mut builder = StyleBuilder::new(&env)
+            .scope_start(".reply")
+            .breakpoint_max(600)
+            .scoped_rule("p", "margin: 0;");
+        builder.close_open_blocks();
+        assert_eq!(
+            to_str(&builder.scoped_rule("p", "margin: 1rem;").build()),
+            "@media (max-width: 600px) {\n.reply p { margin: 0; }\n}\np { margin: 1rem; }\n"
+        );
+    }
+
+    #[test]
+    fn test_default_theme() {
+        let env = Env::default();
+        let css = to_str(&StyleBuilder::default_theme(&env).build());
+        for var in [
+            "--primary: #0066cc;",
+            "--bg: #ffffff;",
+            "--fg:",
+            "--border:",
+            "--radius:",
+            "--space-1:",
+            "--space-5:",
+        ] {
+            assert!(css.contains(var), "missing {var}");
+        }
+        for selector in ["body {", "h1 {", "h3 {", "a {", "button {", "table {"] {
+            assert!(css.contains(selector), "missing {selector}");
+        }
+        assert!(css.contains("@media (prefers-color-scheme: dark) {\n:root { --bg: #0d1117;"));
+        assert!(css.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_default_theme_with_and_overrides() {
+        let env = Env::default();
+        let css = to_str(
+            &StyleBuilder::default_theme_with(&env, "#7b2cbf", "#fafafa")
+                .rule("button", "background: black;")
+                .build(),
+        );
+        assert!(css.contains("  --primary: #7b2cbf;\n"));
+        assert!(css.contains("  --bg: #fafafa;\n"));
+        assert!(!css.contains("#0066cc"));
+        let theme_rule = css.find("button { padding").unwrap();
+        let override_rule = css.find("button { background: black; }").unwrap();
+        assert!(override_rule > theme_rule);
+        assert!(override_rule > css.find("prefers-color-scheme: dark").unwrap());
+    }
+
+    #[test]
+    fn test_lighten_and_darken_hex() {
+        let env = Env::default();
+        assert_eq!(to_str(&lighten_hex(&env, "#0066cc", 20)), "#3384d6");
+        assert_eq!(to_str(&darken_hex(&env, "#0066CC", 50)), "#003366");
+        assert_eq!(to_str(&lighten_hex(&env, "#0066cc", 0)), "#0066cc");
+    }
+
+    #[test]
+    fn test_hex_clamping() {
+        let env = Env::default();
+        assert_eq!(to_str(&lighten_hex(&env, "#123456", 100)), "#ffffff");
+        assert_eq!(to_str(&lighten_hex(&env, "#123456", 250)), "#ffffff");
+        assert_eq!(to_str(&darken_hex(&env, "#abcdef", 100)), "#000000");
+        assert_eq!(to_str(&darken_hex(&env, "#abcdef", 999)), "#000000");
+        assert_eq!(to_str(&lighten_hex(&env, "#ffffff", 40)), "#ffffff");
+        assert_eq!(to_str(&darken_hex(&env, "#000000", 40)), "#000000");
+    }
+
+    #[test]
+    fn test_short_hex_and_invalid_input() {
+        let env = Env::default();
+        assert_eq!(to_str(&darken_hex(&env, "#fa0", 0)), "#ffaa00");
+        assert_eq!(to_str(&darken_hex(&env, "f0c", 50)), "#800066");
+        assert_eq!(to_str(&lighten_hex(&env, "red", 10)), "red");
+        assert_eq!(to_str(&lighten_hex(&env, "#12345g", 10)), "#12345g");
+        assert_eq!(to_str(&with_alpha(&env, "#1234", 50)), "#1234");
+    }
+
+    #[test]
+    fn test_with_alpha() {
+        let env = Env::default();
+        assert_eq!(to_str(&with_alpha(&env, "#0066cc", 100)), "#0066ccff");
+        assert_eq!(to_str(&with_alpha(&env, "#0066cc", 50)), "#0066cc80");
+        assert_eq!(to_str(&with_alpha(&env, "#abc", 0)), "#aabbcc00");
+        assert_eq!(to_str(&with_alpha(&env, "#abc", 150)), "#aabbccff");
+    }
+
+    #[test]
+    fn test_var_derived() {
+        let env = Env::default();
+        let output = StyleBuilder::new(&env)
+            .root_vars_start()
+            .var("primary", "#0066cc")
+            .var_derived("primary-hover", "#0066cc", ColorTransform::Darken(50))
+            .var_derived("primary-faint", "#0066cc", ColorTransform::Alpha(50))
+            .root_vars_end()
+            .root_var_derived("primary-light", "#0066cc", ColorTransform::Lighten(20))
+            .build();
+        assert_eq!(
+            to_str(&output),
+            ":root {\n  --primary: #0066cc;\n  --primary-hover: #003366;\n  --primary-faint: #0066cc80;\n}\n:root { --primary-light: #3384d6; }\n"
+        );
+    }
+
+    #[test]
+    fn test_utilities_spacing() {
+        let env = Env::default();
+        let css = to_str(
+            &StyleBuilder::new(&env)
+                .utilities_spacing(&DEFAULT_SPACING_SCALE)
+                .build(),
+        );
+        assert_eq!(
+            css.lines().count(),
+            DEFAULT_SPACING_SCALE.len() * DEFAULT_SPACING_PREFIXES.len()
+        );
+        assert!(css.contains(".mt-3 { margin-top: 12px; }\n"));
+        assert!(css.contains(".p-0 { padding: 0px; }\n"));
+        assert!(css.contains(".gap-8 { gap: 40px; }\n"));
+    }
+
+    #[test]
+    fn test_utilities_spacing_custom_prefixes() {
+        let env = Env::default();
+        let css = to_str(
+            &StyleBuilder::new(&env)
+                .utilities_spacing_with(&[0, 6], &[("space", "margin-bottom")])
+                .build(),
+        );
+        assert_eq!(
+            css,
+            ".space-0 { margin-bottom: 0px; }\n.space-1 { margin-bottom: 6px; }\n"
+        );
+    }
+
+    #[test]
+    fn test_scoped_utilities() {
+        let env = Env::default();
+        let css = to_str(
+            &StyleBuilder::new(&env)
+                .namespace("ns")
+                .utilities_spacing_with(&[0, 8], &[("mt", "margin-top")])
+                .utilities_layout()
+                .build(),
+        );
+        assert!(css.starts_with(
+            ".ns .mt-0 { margin-top: 0px; }\n.ns .mt-1 { margin-top: 8px; }\n.ns .flex { display: flex; }\n"
+        ));
+        assert!(!css.contains("\n.mt-"));
+    }
+
+    #[test]
+    fn test_utilities_layout() {
+        let env = Env::default();
+        let css = to_str(&StyleBuilder::new(&env).utilities_layout().build());
+        assert_eq!(css.lines().count(), 16);
+        assert!(css.starts_with(".flex { display: flex; }\n"));
+        assert!(css.contains(".text-lg { font-size: 1.125rem; }\n"));
+    }
+
+    #[test]
+    fn test_numeric_props() {
+        let env = Env::default();
+        let output = StyleBuilder::new(&env)
+            .root_vars_start()
+            .var_px("radius", 6)
+            .root_vars_end()
+            .rule_start(".card")
+            .prop_px("margin-top", 12)
+            .prop_percent("width", 50)
+            .prop_rem_tenths("font-size", 15)
+            .prop_rem_tenths("line-height", 20)
+            .prop_rem_tenths("letter-spacing", 1)
+            .prop_u32("z-index", 10)
+            .rule_end()
+            .build();
+        assert_eq!(
+            to_str(&output),
+            ":root {\n  --radius: 6px;\n}\n\
+             .card {\n  margin-top: 12px;\n  width: 50%;\n  font-size: 1.5rem;\n  line-height: 2rem;\n  letter-spacing: 0.1rem;\n  z-index: 10;\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_at_rule_openers() {
+        let env = Env::default();
+        let css = to_str(
+            &StyleBuilder::new(&env)
+                .supports_start("(display: grid)")
+                .rule(".cards", "display: grid;")
+                .block_end()
+                .container_query_start("sidebar (min-width: 400px)")
+                .rule(".card", "flex-direction: row;")
+                .block_end()
+                .print_start()
+                .rule("nav", "display: none;")
+                .media_end()
+                .reduced_motion_start()
+                .rule("*", "animation: none;")
+                .block_end()
+                .build(),
+        );
+        assert_eq!(
+            css,
+            "@supports (display: grid) {\n.cards { display: grid; }\n}\n\
+             @container sidebar (min-width: 400px) {\n.card { flex-direction: row; }\n}\n\
+             @media print {\nnav { display: none; }\n}\n\
+             @media (prefers-reduced-motion: reduce) {\n* { animation: none; }\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_supports_nested_in_dark_mode() {
+        let env = Env::default();
+        let css = to_str(
+            &StyleBuilder::new(&env)
+                .dark_mode_start()
+                .supports_start("(backdrop-filter: blur(4px))")
+                .rule(".modal", "backdrop-filter: blur(4px);")
+                .block_end()
+                .media_end()
+                .build(),
+        );
+        assert_eq!(
+            css,
+            "@media (prefers-color-scheme: dark) {\n@supports (backdrop-filter: blur(4px)) {\n.modal { backdrop-filter: blur(4px); }\n}\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_font_face_and_imports() {
+        let env = Env::default();
+        let css = to_str(
+            &StyleBuilder::new(&env)
+                .import_url("https://fonts.example/inter.css")
+                .import_url("https://fonts.example/mono.css")
+                .font_face(
+                    "Inter",
+                    "https://fonts.example/inter.woff2",
+                    "400",
+                    "normal",
+                )
+                .rule("body", "font-family: \"Inter\", sans-serif;")
+                .build(),
+        );
+        assert_eq!(
+            css,
+            "@import url(\"https://fonts.example/inter.css\");\n\
+             @import url(\"https://fonts.example/mono.css\");\n\
+             @font-face {\n  font-family: \"Inter\";\n  src: url(\"https://fonts.example/inter.woff2\") format(\"woff2\");\n  font-weight: 400;\n  font-style: normal;\n}\n\
+             body { font-family: \"Inter\", sans-serif; }\n"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "import_url must be called before any other rules")]
+    fn test_import_after_rule_panics() {
+        let env = Env::default();
+        StyleBuilder::new(&env)
+            .rule("body", "margin: 0;")
+            .import_url("https://fonts.example/inter.css");
+    }
+
+    #[test]
+    #[should_panic(expected = "import_url must be called before any other rules")]
+    fn test_import_after_font_face_panics() {
+        let env = Env::default();
+        StyleBuilder::new(&env)
+            .import_url("https://fonts.example/a.css")
+            .font_face("A", "a.woff2", "400", "normal")
+            .import_url("https://fonts.example/b.css");
+    }
+
+    /// Exercise most builder methods so minified and pretty output can be
+    /// compared.
+    fn sample_styles(builder: StyleBuilder) -> StyleBuilder {
+        builder
+            .import_url("https://fonts.example/a.css")
+            .comment("Theme")
+            .root_vars_start()
+            .var("primary", "#0066cc")
+            .var_px("radius", 6)
+            .root_vars_end()
+            .root_var("bg", "#fff")
+            .rule("h1", "color: var(--primary);")
+            .rule_start(".card")
+            .prop("border", "1px solid #ccc")
+            .prop_rem_tenths("padding", 15)
+            .rule_end()
+            .hover_start(".card")
+            .prop_px("margin-top", 2)
+            .rule_end()
+            .nth_child("tr", 2, "background: #eee;")
+            .newline()
+            .breakpoint_max(600)
+            .rule("h1", "font-size: 1.5rem;")
+            .media_end()
+            .font_face("Inter", "inter.woff2", "400", "normal")
+            .keyframes_start("spin")
+            .keyframe("to", "transform: rotate(360deg);")
+            .keyframes_end()
+            .animation(".spinner", "spin", 500, "linear", "infinite")
+            .utilities_spacing_with(&[0, 4], &[("mt", "margin-top")])
+    }
+
+    #[test]
+    fn test_minified_builder() {
+        let env = Env::default();
+        let pretty = to_str(&sample_styles(StyleBuilder::new(&env)).build());
+        let minified = to_str(&sample_styles(StyleBuilder::new(&env).minify()).build());
+        assert!(!minified.contains('\n'));
+        assert!(!minified.contains("Theme"));
+        assert!(minified.len() < pretty.len());
+        assert!(minified.starts_with(
+            "@import url(\"https://fonts.example/a.css\");:root{--primary:#0066cc;--radius:6px;}:root{--bg:#fff;}h1{color: var(--primary);}"
+        ));
+        assert!(minified.contains(".card{border:1px solid #ccc;padding:1.5rem;}"));
+        assert!(minified.contains("@media (max-width: 600px){h1{font-size: 1.5rem;}}"));
+        assert!(minified.contains(
+            "@font-face{font-family:\"Inter\";src:url(\"inter.woff2\") format(\"woff2\");font-weight:400;font-style:normal;}"
+        ));
+        assert!(minified.contains("@keyframes spin{to{transform: rotate(360deg);}}"));
+        assert!(minified.ends_with(".spinner{animation:spin 500ms linear infinite;}.mt-0{margin-top:0px;}.mt-1{margin-top:4px;}"));
+    }
+
+    #[test]
+    fn test_build_minified() {
+        let env = Env::default();
+        let direct = to_str(&sample_styles(StyleBuilder::new(&env).minify()).build());
+        let minified = to_str(&sample_styles(StyleBuilder::new(&env).minify()).build_minified());
+        assert_eq!(direct, minified);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "build_minified called without minify")]
+    fn test_build_minified_without_minify_panics() {
+        let env = Env::default();
+        StyleBuilder::new(&env)
+            .rule("h1", "margin: 0;")
+            .build_minified();
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "minify must be called before adding any output")]
+    fn test_minify_after_output_panics() {
+        let env = Env::default();
+        StyleBuilder::new(&env).rule("h1", "margin: 0;").minify();
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "namespace must be called before adding any output")]
+    fn test_namespace_after_output_panics() {
+        let env = Env::default();
+        StyleBuilder::new(&env)
+            .rule("h1", "margin: 0;")
+            .namespace("ns");
+    }
+
+    #[test]
+    fn test_minify_and_namespace_combine() {
+        let env = Env::default();
+        let css = to_str(
+            &StyleBuilder::new(&env)
+                .minify()
+                .namespace("ns")
+                .rule("h1, h2", "margin: 0;")
+                .hover("a", "opacity: 0.8;")
+                .build_minified(),
+        );
+        assert_eq!(css, ".ns h1,.ns h2{margin: 0;}.ns a:hover{opacity: 0.8;}");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_buffered_minified_namespace() {
+        let env = Env::default();
+        let build = |builder: StyleBuilder| {
+            builder
+                .minify()
+                .namespace("ns")
+                .rule_start(".card")
+                .prop("padding", "1rem")
+                .rule_end()
+                .build()
+        };
+        let buffered = build(StyleBuilder::new_buffered(&env));
+        assert_eq!(buffered, build(StyleBuilder::new(&env)));
+        assert_eq!(to_str(&buffered), ".ns .card{padding:1rem;}");
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "StyleBuilder built with an unclosed media/supports/container block")]
+    fn test_build_with_unclosed_media_panics() {
+        let env = Env::default();
+        StyleBuilder::new(&env)
+            .dark_mode_start()
+            .rule("body", "background: #000;")
+            .build();
+    }
+
+    #[test]
+    #[cfg(not(debug_assertions))]
+    fn test_build_closes_unclosed_blocks() {
+        let env = Env::default();
+        let css = to_str(
+            &StyleBuilder::new(&env)
+                .dark_mode_start()
+                .rule_start("body")
+                .prop("color", "#fff")
+                .build(),
+        );
+        assert!(css.ends_with("  color: #fff;\n}\n}\n"));
+    }
+
+    #[test]
+    fn test_close_open_blocks() {
+        let env = Env::default();
+        let mut builder = StyleBuilder::new(&env)
+            .breakpoint_max(600)
+            .rule_start("h1")
+            .prop("margin", "0");
+        builder.close_open_blocks();
+        assert_eq!(
+            to_str(&builder.build()),
+            "@media (max-width: 600px) {\nh1 {\n  margin: 0;\n}\n}\n"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "rule_end called with no open block")]
+    fn test_rule_end_without_start_panics() {
+        let env = Env::default();
+        StyleBuilder::new(&env).rule("h1", "margin: 0;").rule_end();
+    }
+
+    #[test]
+    #[should_panic(expected = "media_end called while a rule block is open")]
+    fn test_mismatched_end_panics() {
+        let env = Env::default();
+        StyleBuilder::new(&env)
+            .media_start("print")
+            .rule_start("nav")
+            .media_end();
+    }
+
+    #[test]
+    #[should_panic(expected = "prop called outside a rule block")]
+    fn test_prop_outside_block_panics() {
+        let env = Env::default();
+        StyleBuilder::new(&env).prop("color", "red");
+    }
+
+    #[test]
+    #[should_panic(expected = "prop_px called outside a rule block")]
+    fn test_prop_directly_in_media_panics() {
+        let env = Env::default();
+        StyleBuilder::new(&env).print_start().prop_px("margin", 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "keyframe called outside a keyframes block")]
+    fn test_keyframe_outside_keyframes_panics() {
+        let env = Env::default();
+        StyleBuilder::new(&env).keyframe("to", "opacity: 1;");
+    }
+
+    #[test]
+    fn test_declarations_in_root_vars_and_rules() {
+        let env = Env::default();
+        let css = to_str(
+            &StyleBuilder::new(&env)
+                .root_vars_start()
+                .prop("color-scheme", "light dark")
+                .root_vars_end()
+                .rule_start(".dark")
+                .var("bg", "#000")
+                .rule_end()
+                .build(),
+        );
+        assert_eq!(
+            css,
+            ":root {\n  color-scheme: light dark;\n}\n.dark {\n  --bg: #000;\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_scoped_prefixes_selector_lists() {
+        let env = Env::default();
+        let css = to_str(
+            &StyleBuilder::new(&env)
+                .namespace("ns")
+                .rule("h1, h2", "margin: 0;")
+                .rule_start(".card")
+                .prop("padding", "1rem")
+                .rule_end()
+                .hover("a,button", "opacity: 0.8;")
+                .build(),
+        );
+        assert_eq!(
+            css,
+            ".ns h1, .ns h2 { margin: 0; }\n\
+             .ns .card {\n  padding: 1rem;\n}\n\
+             .ns a:hover, .ns button:hover { opacity: 0.8; }\n"
+        );
+    }
+
+    #[test]
+    fn test_pseudo_helpers_suffix_each_list_entry() {
+        let env = Env::default();
+        let css = to_str(
+            &StyleBuilder::new(&env)
+                .hover("a, button", "opacity: 0.8;")
+                .focus("input,select", "outline: none;")
+                .before("h1, h2", "content: '#';")
+                .nth_child("tr, li", 12, "color: gray;")
+                .hover_start(".card, .tile")
+                .prop("color", "red")
+                .rule_end()
+                .build(),
+        );
+        assert_eq!(
+            css,
+            "a:hover, button:hover { opacity: 0.8; }\n\
+             input:focus, select:focus { outline: none; }\n\
+             h1::before, h2::before { content: '#'; }\n\
+             tr:nth-child(12), li:nth-child(12) { color: gray; }\n\
+             .card:hover, .tile:hover {\n  color: red;\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_pseudo_helpers_suffix_each_list_entry_minified() {
+        let env = Env::default();
+        let css = to_str(
+            &StyleBuilder::new(&env)
+                .minify()
+                .active("a, button", "opacity: 1;")
+                .nth_child("li", 0, "margin: 0;")
+                .build(),
+        );
+        assert_eq!(
+            css,
+            "a:active,button:active{opacity: 1;}li:nth-child(0){margin: 0;}"
+        );
+    }
+
+    #[test]
+    fn test_scoped_leaves_root_media_and_keyframes() {
+        let env = Env::default();
+        let css = to_str(
+            &StyleBuilder::new(&env)
+                .namespace("ns")
+                .root_var("primary", "#0066cc")
+                .rule(":root", "color-scheme: light;")
+                .media_start("(max-width: 600px)")
+                .rule("p", "font-size: 14px;")
+                .media_end()
+                .keyframes_start("fade")
+                .keyframe("to", "opacity: 1;")
+                .keyframes_end()
+                .build(),
+        );
+        assert_eq!(
+            css,
+            ":root { --primary: #0066cc; }\n\
+             :root { color-scheme: light; }\n\
+             @media (max-width: 600px) {\n.ns p { font-size: 14px; }\n}\n\
+             @keyframes fade {\n  to { opacity: 1; }\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_scoped_applies_to_scope_start() {
+        let env = Env::default();
+        let css = to_str(
+            &StyleBuilder::new(&env)
+                .namespace("ns")
+                .scope_start(".reply")
+                .scoped_rule(".author", "font-weight: bold;")
+                .scope_end()
+                .build(),
+        );
+        assert_eq!(css, ".ns .reply .author { font-weight: bold; }\n");
+    }
+
+    #[test]
+    fn test_string_values() {
+        let env = Env::default();
+        let color = String::from_str(&env, "#0066cc");
+        let css = to_str(
+            &StyleBuilder::new(&env)
+                .root_var_string("primary", &color)
+                .root_vars_start()
+                .var_string("accent", &color)
+                .root_vars_end()
+                .rule_start("a")
+                .prop_string("color", &color)
+                .rule_end()
+                .build(),
+        );
+        assert_eq!(
+            css,
+            ":root { --primary: #0066cc; }\n\
+             :root {\n  --accent: #0066cc;\n}\n\
+             a {\n  color: #0066cc;\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_string_values_are_sanitized() {
+        let env = Env::default();
+        let evil = String::from_str(&env, "red} body{display:none");
+        let css = to_str(
+            &StyleBuilder::new(&env)
+                .root_var_string("primary", &evil)
+                .rule_start("a")
+                .prop_string("color", &String::from_str(&env, "red;\nbackground: url(x)"))
+                .prop_string(
+                    "border-color",
+                    &String::from_str(&env, "red</style><script>alert(1)</script>"),
+                )
+                .rule_end()
+                .build(),
+        );
+        assert_eq!(
+            css,
+            ":root { --primary: red bodydisplay:none; }\n\
+             a {\n  color: redbackground: url(x);\n  \
+             border-color: red/stylescriptalert(1)/script;\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_string_values_break_comments_quotes_and_escapes() {
+        let env = Env::default();
+        let css = to_str(
+            &StyleBuilder::new(&env)
+                .minify()
+                .rule_start("a")
+                .prop_string("color", &String::from_str(&env, "red /*"))
+                .prop_string("border", &String::from_str(&env, "*/ 1px"))
+                .prop_string("margin", &String::from_str(&env, "/;*x"))
+                .prop_string("font", &String::from_str(&env, "\"Inter\\\" 'x"))
+                .prop_string("width", &String::from_str(&env, "calc(100% / 2 * 3)"))
+                .rule_end()
+                .build(),
+        );
+        assert_eq!(
+            css,
+            "a{color:red /;border:* 1px;margin:/x;font:Inter x;width:calc(100% / 2 * 3);}"
+        );
+    }
+
+    #[test]
+    fn test_long_string_value_is_sanitized() {
+        let env = Env::default();
+        let mut raw = alloc::string::String::new();
+        for _ in 0..1500 {
+            raw.push_str("ab;");
+        }
+        let css = to_str(
+            &StyleBuilder::new(&env)
+                .minify()
+                .root_var_string("x", &String::from_str(&env, &raw))
+                .build(),
+        );
+        assert_eq!(css.len(), ":root{--x:;}".len() + 3000);
+        assert!(!css[10..css.len() - 2].contains(';'));
+    }
 }
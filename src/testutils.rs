@@ -0,0 +1,170 @@
+//! Assertions for testing rendered output.
+//!
+//! Available with the `testutils` feature, for use from a contract's tests:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! soroban-render-sdk = { version = "...", features = ["testutils"] }
+//! ```
+//!
+//! ```rust,ignore
+//! use soroban_render_sdk::testutils::{assert_contains, assert_output_eq};
+//!
+//! let output = client.render(&None, &None);
+//! assert_output_eq(&output, "# Hello\n\n");
+//! assert_contains(&output, "# Hello");
+//! ```
+//!
+//! On failure the panic message shows the rendered output, and for
+//! `assert_output_eq` a line diff against the expected text.
+
+use alloc::string::String;
+use core::fmt::Write;
+use soroban_sdk::Bytes;
+
+/// Copy `bytes` into a `String`.
+///
+/// Invalid UTF-8 is replaced with U+FFFD rather than panicking, so a broken
+/// output still shows up in a failing assertion.
+pub fn to_str(bytes: &Bytes) -> String {
+    let mut buf = alloc::vec![0u8; bytes.len() as usize];
+    bytes.copy_into_slice(&mut buf);
+    String::from_utf8_lossy(&buf).into_owned()
+}
+
+/// Assert that `output` is exactly `expected`.
+///
+/// # Panics
+///
+/// Panics with a line diff when they differ:
+///
+/// ```text
+/// rendered output does not match (first difference on line 2)
+/// --- expected
+/// +++ actual
+///  # Title
+/// -Hello
+/// +Goodbye
+/// ```
+#[track_caller]
+pub fn assert_output_eq(output: &Bytes, expected: &str) {
+    let actual = to_str(output);
+    if actual != expected {
+        panic!("{}", diff(expected, &actual));
+    }
+}
+
+/// Assert that `output` contains `needle`.
+///
+/// # Panics
+///
+/// Panics showing the needle and the full output when it is missing.
+#[track_caller]
+pub fn assert_contains(output: &Bytes, needle: &str) {
+    let actual = to_str(output);
+    if !actual.contains(needle) {
+        panic!("rendered output does not contain {needle:?}\n--- output\n{actual}");
+    }
+}
+
+/// Format a line diff between `expected` and `actual`.
+///
+/// Lines shared at the start and end are printed as context, and the lines
+/// between them as removed (`-`) and added (`+`).
+fn diff(expected: &str, actual: &str) -> String {
+    let expected: alloc::vec::Vec<&str> = expected.split('\n').collect();
+    let actual: alloc::vec::Vec<&str> = actual.split('\n').collect();
+    let prefix = expected
+        .iter()
+        .zip(&actual)
+        .take_while(|(e, a)| e == a)
+        .count();
+    let suffix = expected[prefix..]
+        .iter()
+        .rev()
+        .zip(actual[prefix..].iter().rev())
+        .take_while(|(e, a)| e == a)
+        .count();
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "rendered output does not match (first difference on line {})",
+        prefix + 1
+    );
+    out.push_str("--- expected\n+++ actual\n");
+    for line in &expected[..prefix] {
+        let _ = writeln!(out, " {line}");
+    }
+    for line in &expected[prefix..expected.len() - suffix] {
+        let _ = writeln!(out, "-{line}");
+    }
+    for line in &actual[prefix..actual.len() - suffix] {
+        let _ = writeln!(out, "+{line}");
+    }
+    for line in &expected[expected.len() - suffix..] {
+        let _ = writeln!(out, " {line}");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::Env;
+
+    #[test]
+    fn test_to_str() {
+        let env = Env::default();
+        assert_eq!(
+            to_str(&Bytes::from_slice(&env, "Café ☕".as_bytes())),
+            "Café ☕"
+        );
+        assert_eq!(to_str(&Bytes::from_slice(&env, b"a\xffb")), "a\u{fffd}b");
+        assert_eq!(to_str(&Bytes::new(&env)), "");
+    }
+
+    #[test]
+    fn test_assert_output_eq_passes() {
+        let env = Env::default();
+        assert_output_eq(&Bytes::from_slice(&env, b"# Hi\n\n"), "# Hi\n\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "first difference on line 2")]
+    fn test_assert_output_eq_fails() {
+        let env = Env::default();
+        assert_output_eq(
+            &Bytes::from_slice(&env, b"# Title\nGoodbye\n"),
+            "# Title\nHello\n",
+        );
+    }
+
+    #[test]
+    fn test_assert_contains_passes() {
+        let env = Env::default();
+        assert_contains(&Bytes::from_slice(&env, b"# Title\n\nBody"), "Body");
+    }
+
+    #[test]
+    #[should_panic(expected = "does not contain \"Missing\"")]
+    fn test_assert_contains_fails() {
+        let env = Env::default();
+        assert_contains(&Bytes::from_slice(&env, b"# Title"), "Missing");
+    }
+
+    #[test]
+    fn test_diff() {
+        assert_eq!(
+            diff("# Title\nHello\n\nEnd", "# Title\nGoodbye\nAgain\n\nEnd"),
+            "rendered output does not match (first difference on line 2)\n\
+             --- expected\n+++ actual\n \
+             # Title\n-Hello\n+Goodbye\n+Again\n \n End\n"
+        );
+        assert_eq!(
+            diff("a", "ab"),
+            "rendered output does not match (first difference on line 1)\n\
+             --- expected\n+++ actual\n-a\n+ab\n"
+        );
+    }
+}
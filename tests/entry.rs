@@ -0,0 +1,99 @@
+//! Integration test for `render_entry!`.
+//!
+//! The contract below has no hand-written `render` function; the macro
+//! generates it, and the test calls it through the generated client.
+
+use soroban_render_sdk::prelude::*;
+use soroban_sdk::{Address, Bytes, Env, String, contract, testutils::Address as _};
+
+soroban_render!(markdown);
+
+#[contract]
+pub struct TaskContract;
+
+render_entry! {
+    TaskContract;
+    "/" => home,
+    "/task/{id}" => task_page,
+    _ => not_found,
+}
+
+fn home(env: &Env, _req: Request, viewer: Option<Address>) -> Bytes {
+    MarkdownBuilder::new(env)
+        .h1("Tasks")
+        .when(viewer.is_some(), |b| b.paragraph("Welcome back."))
+        .build()
+}
+
+fn task_page(env: &Env, req: Request, viewer: Option<Address>) -> Bytes {
+    let id = req.get_var_u32(b"id").unwrap_or(0);
+    MarkdownBuilder::new(env)
+        .h1("Task")
+        .kv_u32("id", id)
+        .when_some(viewer, |b, v| b.kv_address("viewer", &v))
+        .build()
+}
+
+fn not_found(env: &Env, req: Request, _viewer: Option<Address>) -> Bytes {
+    MarkdownBuilder::new(env)
+        .h1("Not Found")
+        .raw(req.path().clone())
+        .build()
+}
+
+fn setup(env: &Env) -> TaskContractClient<'_> {
+    let id = env.register(TaskContract, ());
+    TaskContractClient::new(env, &id)
+}
+
+fn path(env: &Env, p: &str) -> Option<String> {
+    Some(String::from_str(env, p))
+}
+
+#[test]
+fn test_root_route() {
+    let env = Env::default();
+    let client = setup(&env);
+    let expected = MarkdownBuilder::new(&env).h1("Tasks").build();
+    assert_eq!(client.render(&None, &None), expected);
+    assert_eq!(client.render(&path(&env, "/"), &None), expected);
+}
+
+#[test]
+fn test_handler_receives_viewer() {
+    let env = Env::default();
+    let client = setup(&env);
+    let viewer = Address::generate(&env);
+    let expected = MarkdownBuilder::new(&env)
+        .h1("Tasks")
+        .paragraph("Welcome back.")
+        .build();
+    assert_eq!(client.render(&None, &Some(viewer)), expected);
+}
+
+#[test]
+fn test_param_extraction() {
+    let env = Env::default();
+    let client = setup(&env);
+    let viewer = Address::generate(&env);
+    let expected = MarkdownBuilder::new(&env)
+        .h1("Task")
+        .kv_u32("id", 42)
+        .kv_address("viewer", &viewer)
+        .build();
+    assert_eq!(
+        client.render(&path(&env, "/task/42?tab=notes"), &Some(viewer)),
+        expected
+    );
+}
+
+#[test]
+fn test_fallback() {
+    let env = Env::default();
+    let client = setup(&env);
+    let expected = MarkdownBuilder::new(&env)
+        .h1("Not Found")
+        .raw(Bytes::from_slice(&env, b"/missing"))
+        .build();
+    assert_eq!(client.render(&path(&env, "/missing"), &None), expected);
+}
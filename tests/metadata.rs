@@ -0,0 +1,309 @@
+//! Integration tests for the contract metadata macros.
+//!
+//! `contractmeta!` stores each entry in a static named after its key, which
+//! is what ends up in the wasm `contractmetav0` section. Each case lives in
+//! its own module and decodes its entries back from those statics.
+
+use soroban_sdk::xdr::{Limits, ReadXdr, ScMetaEntry};
+
+/// Decode a metadata entry into its key and value.
+fn decode(xdr: &[u8]) -> (String, String) {
+    let ScMetaEntry::ScMetaV0(entry) = ScMetaEntry::from_xdr(xdr, Limits::none()).unwrap();
+    (entry.key.to_string(), entry.val.to_string())
+}
+
+/// The `render` entry.
+macro_rules! render_meta {
+    () => {
+        super::decode(&__CONTRACT_KEY_72656e646572)
+    };
+}
+
+/// The `render_formats` entry.
+macro_rules! formats_meta {
+    () => {
+        super::decode(&__CONTRACT_KEY_72656e6465725f666f726d617473)
+    };
+}
+
+/// The `render_styles` entry.
+macro_rules! styles_meta {
+    () => {
+        super::decode(&__CONTRACT_KEY_72656e6465725f7374796c6573)
+    };
+}
+
+/// The `render_theme` entry.
+macro_rules! theme_meta {
+    () => {
+        super::decode(&__CONTRACT_KEY_72656e6465725f7468656d65)
+    };
+}
+
+/// The `render_registry` entry.
+macro_rules! registry_meta {
+    () => {
+        super::decode(&__CONTRACT_KEY_72656e6465725f7265676973747279)
+    };
+}
+
+/// The `render_title` entry.
+macro_rules! title_meta {
+    () => {
+        super::decode(&__CONTRACT_KEY_72656e6465725f7469746c65)
+    };
+}
+
+/// The `render_description` entry.
+macro_rules! description_meta {
+    () => {
+        super::decode(&__CONTRACT_KEY_72656e6465725f6465736372697074696f6e)
+    };
+}
+
+/// The `render_icon` entry.
+macro_rules! icon_meta {
+    () => {
+        super::decode(&__CONTRACT_KEY_72656e6465725f69636f6e)
+    };
+}
+
+fn pair(key: &str, val: &str) -> (String, String) {
+    (key.to_string(), val.to_string())
+}
+
+mod markdown_only {
+    soroban_render_sdk::soroban_render!(markdown);
+
+    #[test]
+    fn test_markdown_only() {
+        assert_eq!(render_meta!(), super::pair("render", "v1"));
+        assert_eq!(formats_meta!(), super::pair("render_formats", "markdown"));
+    }
+}
+
+mod styles_only {
+    soroban_render_sdk::soroban_render!(styles);
+
+    #[test]
+    fn test_styles_only() {
+        assert_eq!(formats_meta!(), super::pair("render_formats", "css"));
+        assert_eq!(styles_meta!(), super::pair("render_styles", "true"));
+    }
+}
+
+mod styles_before_format {
+    soroban_render_sdk::soroban_render!(styles, markdown);
+
+    #[test]
+    fn test_styles_before_format() {
+        assert_eq!(formats_meta!(), super::pair("render_formats", "markdown"));
+        assert_eq!(styles_meta!(), super::pair("render_styles", "true"));
+    }
+}
+
+mod theme_between_options {
+    soroban_render_sdk::soroban_render!(markdown, theme = "CTHEME", styles);
+
+    #[test]
+    fn test_theme_between_options() {
+        assert_eq!(formats_meta!(), super::pair("render_formats", "markdown"));
+        assert_eq!(styles_meta!(), super::pair("render_styles", "true"));
+        assert_eq!(theme_meta!(), super::pair("render_theme", "CTHEME"));
+    }
+}
+
+mod json_before_markdown {
+    soroban_render_sdk::soroban_render!(json, markdown);
+
+    #[test]
+    fn test_json_before_markdown() {
+        assert_eq!(
+            formats_meta!(),
+            super::pair("render_formats", "markdown,json")
+        );
+    }
+}
+
+mod repeated_formats {
+    soroban_render_sdk::soroban_render!(json, markdown, json,);
+
+    #[test]
+    fn test_repeated_formats() {
+        assert_eq!(
+            formats_meta!(),
+            super::pair("render_formats", "markdown,json")
+        );
+    }
+}
+
+mod theme_first {
+    soroban_render_sdk::soroban_render!(theme = "CTHEME", styles, json);
+
+    #[test]
+    fn test_theme_first() {
+        assert_eq!(formats_meta!(), super::pair("render_formats", "json"));
+        assert_eq!(styles_meta!(), super::pair("render_styles", "true"));
+        assert_eq!(theme_meta!(), super::pair("render_theme", "CTHEME"));
+    }
+}
+
+mod directory_options {
+    soroban_render_sdk::soroban_render!(
+        title = "My Forum",
+        markdown,
+        description = "A decentralized message board",
+        icon = "/icon",
+    );
+
+    #[test]
+    fn test_directory_options() {
+        assert_eq!(formats_meta!(), super::pair("render_formats", "markdown"));
+        assert_eq!(title_meta!(), super::pair("render_title", "My Forum"));
+        assert_eq!(
+            description_meta!(),
+            super::pair("render_description", "A decentralized message board")
+        );
+        assert_eq!(icon_meta!(), super::pair("render_icon", "/icon"));
+    }
+}
+
+mod directory_contract {
+    use soroban_sdk::{Bytes, Env, String, contract, contractimpl};
+
+    soroban_render_sdk::soroban_render!(json);
+    soroban_render_sdk::render_title!("Forum");
+    soroban_render_sdk::render_description!("Threads and replies");
+    soroban_render_sdk::render_icon!("/icon");
+
+    #[contract]
+    pub struct Forum;
+
+    #[contractimpl]
+    impl Forum {
+        pub fn render(env: Env, _path: Option<String>) -> Bytes {
+            Bytes::from_slice(&env, b"{}")
+        }
+    }
+
+    #[test]
+    fn test_directory_contract() {
+        let env = Env::default();
+        let client = ForumClient::new(&env, &env.register(Forum, ()));
+        assert_eq!(client.render(&None), Bytes::from_slice(&env, b"{}"));
+
+        assert_eq!(render_meta!(), super::pair("render", "v1"));
+        assert_eq!(formats_meta!(), super::pair("render_formats", "json"));
+        assert_eq!(title_meta!(), super::pair("render_title", "Forum"));
+        assert_eq!(
+            description_meta!(),
+            super::pair("render_description", "Threads and replies")
+        );
+        assert_eq!(icon_meta!(), super::pair("render_icon", "/icon"));
+    }
+}
+
+mod registry_and_theme {
+    soroban_render_sdk::soroban_render!(markdown, registry = "CREGISTRY", theme = "CTHEME");
+
+    #[test]
+    fn test_registry_and_theme() {
+        assert_eq!(
+            registry_meta!(),
+            super::pair("render_registry", "CREGISTRY")
+        );
+        assert_eq!(theme_meta!(), super::pair("render_theme", "CTHEME"));
+    }
+}
+
+mod registry_macro {
+    soroban_render_sdk::render_registry!("CREGISTRY");
+
+    #[test]
+    fn test_registry_macro() {
+        assert_eq!(
+            registry_meta!(),
+            super::pair("render_registry", "CREGISTRY")
+        );
+    }
+}
+
+/// The `render_routes` entry.
+macro_rules! routes_meta {
+    () => {
+        super::decode(&__CONTRACT_KEY_72656e6465725f726f75746573)
+    };
+}
+
+mod single_route {
+    soroban_render_sdk::render_routes!("/");
+
+    #[test]
+    fn test_single_route() {
+        assert_eq!(routes_meta!(), super::pair("render_routes", "/"));
+    }
+}
+
+mod five_routes {
+    soroban_render_sdk::render_routes!("/", "/tasks", "/task/{id}", "/about", "/files/*",);
+
+    #[test]
+    fn test_five_routes() {
+        assert_eq!(
+            routes_meta!(),
+            super::pair("render_routes", "/,/tasks,/task/{id},/about,/files/*")
+        );
+    }
+}
+
+mod shared_route_consts {
+    const HOME: &str = "/";
+    const TASK: &str = "/task/{id}";
+
+    soroban_render_sdk::render_routes!(HOME, TASK);
+
+    #[test]
+    fn test_shared_route_consts() {
+        assert_eq!(routes_meta!(), super::pair("render_routes", "/,/task/{id}"));
+    }
+}
+
+mod css_only {
+    soroban_render_sdk::render_formats!(css);
+
+    #[test]
+    fn test_css_only() {
+        assert_eq!(formats_meta!(), super::pair("render_formats", "css"));
+    }
+}
+
+mod markdown_and_css {
+    soroban_render_sdk::render_formats!(css, markdown);
+
+    #[test]
+    fn test_markdown_and_css() {
+        assert_eq!(
+            formats_meta!(),
+            super::pair("render_formats", "markdown,css")
+        );
+    }
+}
+
+mod all_formats {
+    soroban_render_sdk::render_formats!(css, json, markdown, css);
+
+    #[test]
+    fn test_all_formats() {
+        assert_eq!(
+            formats_meta!(),
+            super::pair("render_formats", "markdown,json,css")
+        );
+    }
+}
+
+#[test]
+fn test_ui_cases() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+    cases.pass("tests/ui/pass/*.rs");
+}
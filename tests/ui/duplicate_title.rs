@@ -0,0 +1,3 @@
+soroban_render_sdk::soroban_render!(markdown, title = "A", title = "B");
+
+fn main() {}
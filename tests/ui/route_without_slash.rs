@@ -0,0 +1,3 @@
+soroban_render_sdk::render_routes!("/", "tasks");
+
+fn main() {}
@@ -0,0 +1,3 @@
+soroban_render_sdk::render_formats!(markdown, html);
+
+fn main() {}